@@ -0,0 +1,68 @@
+// implement Life for a new storage type and run it through the library
+use rlife::analysis::{self, Behavior};
+use rlife::prelude::*;
+use rlife::stats;
+use std::collections::HashSet;
+
+// bounded grid that only stores its live cells
+#[derive(Clone)]
+struct SetGrid {
+    width: usize,
+    height: usize,
+    alive: HashSet<(usize, usize)>,
+}
+
+impl Life for SetGrid {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        if is_alive {
+            self.alive.insert((x, y));
+        } else {
+            self.alive.remove(&(x, y));
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.alive.contains(&(x, y))
+    }
+}
+
+fn main() {
+    let mut grid = SetGrid {
+        width: 32,
+        height: 32,
+        alive: HashSet::new(),
+    };
+    // a glider
+    [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+        .iter()
+        .for_each(|&(x, y)| grid.set_cell(x, y, true));
+
+    // the default next_generation works for any storage
+    let mut simulation = Simulation::from_life(grid.clone());
+    simulation.step_by(20);
+    println!(
+        "generation {}: {} cells at {:?}",
+        simulation.generation(),
+        simulation.life().alive.len(),
+        stats::bounding_box(simulation.life())
+    );
+
+    // analysis functions accept any implementation too
+    match analysis::classify(&grid, Rule::default(), 8) {
+        Behavior::Ship { period, dx, dy } => {
+            println!("glider moves ({}, {}) every {} generations", dx, dy, period)
+        }
+        behavior => println!("unexpected behavior: {}", behavior),
+    }
+}
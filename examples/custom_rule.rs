@@ -0,0 +1,36 @@
+// HighLife (B36/S23) stepped by wrapping a grid in RuleLife, the stepping loop stays the
+// default one
+use rlife::prelude::*;
+use rlife::stats;
+
+fn main() {
+    // the HighLife replicator copies itself, which Conway's rule doesn't do
+    let mut grid = VecGrid::new(64, 64);
+    [
+        (2, 0),
+        (3, 0),
+        (4, 0),
+        (1, 1),
+        (4, 1),
+        (0, 2),
+        (4, 2),
+        (0, 3),
+        (3, 3),
+        (0, 4),
+        (1, 4),
+        (2, 4),
+    ]
+    .iter()
+    .for_each(|&(x, y)| grid.set_cell(x + 30, y + 30, true));
+
+    let highlife: Rule = "B36/S23".parse().expect("Invalid rule");
+    let mut simulation = Simulation::from_life(RuleLife::new(grid, highlife));
+    for _ in 0..4 {
+        simulation.step_by(12);
+        println!(
+            "generation {}: population {}",
+            simulation.generation(),
+            stats::population(simulation.life())
+        );
+    }
+}
@@ -0,0 +1,39 @@
+// run an R-pentomino without a window, printing statistics as it evolves
+use rlife::formats::rle;
+use rlife::prelude::*;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let r_pentomino = rle::parse("x = 3, y = 3\nb2o$2o$bo!")?;
+
+    // leave plenty of room around the pattern for it to grow
+    let mut board = Board::new(240, 180);
+    board.place(&r_pentomino.grid, 118, 88);
+
+    // kill anything reaching the outermost cells so debris doesn't pile up against the edges
+    board.add_hook(|grid, _generation| {
+        let (width, height) = (grid.width(), grid.height());
+        for x in 0..width {
+            grid.set_cell(x, 0, false);
+            grid.set_cell(x, height - 1, false);
+        }
+        for y in 0..height {
+            grid.set_cell(0, y, false);
+            grid.set_cell(width - 1, y, false);
+        }
+    });
+
+    while board.generation() < 500 {
+        board.step_by(50);
+        let extent = board
+            .bounding_box()
+            .map(|bbox| format!("{}x{}", bbox.width, bbox.height))
+            .unwrap_or_else(|| "empty".to_string());
+        println!(
+            "generation {:>3}: population {:>3}, extent {}",
+            board.generation(),
+            board.population(),
+            extent
+        );
+    }
+    Ok(())
+}
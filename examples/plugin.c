@@ -0,0 +1,54 @@
+// plugin seeding soups in vertical bands, running HighLife (B36/S23) on the left half of
+// the board and Conway's rule on the right, and reporting the population of each half
+// every 10 generations, build and use it with
+//
+//     cc -shared -fPIC -o libbands.so examples/plugin.c
+//     rlife run --plugin ./libbands.so
+//     rlife sweep --sample 8 --plugin ./libbands.so
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+
+uint32_t rlife_plugin_abi(void) { return 1; }
+
+const char *rlife_plugin_name(void) { return "bands"; }
+
+void rlife_plugin_seed(size_t width, size_t height, uint64_t seed, uint8_t *cells) {
+    uint64_t state = seed | 1;
+    for (size_t y = 0; y < height; y++) {
+        for (size_t x = 0; x < width; x++) {
+            // xorshift, live cells only in every other band of 4 columns
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            cells[y * width + x] = (x / 4) % 2 == 0 && state % 2 == 0;
+        }
+    }
+}
+
+// the rule doesn't know the board width, so the halves are split at a fixed column
+uint8_t rlife_plugin_rule(uint8_t alive, uint8_t neighbors, size_t x, size_t y) {
+    (void)y;
+    if (alive) {
+        return neighbors == 2 || neighbors == 3;
+    }
+    return neighbors == 3 || (x < 32 && neighbors == 6);
+}
+
+const char *rlife_plugin_analyze(uint64_t generation, size_t width, size_t height,
+                                 const uint8_t *cells) {
+    static char report[64];
+    if (generation % 10 != 0) {
+        return NULL;
+    }
+    size_t left = 0, right = 0;
+    for (size_t y = 0; y < height; y++) {
+        for (size_t x = 0; x < width; x++) {
+            if (cells[y * width + x]) {
+                *(x < width / 2 ? &left : &right) += 1;
+            }
+        }
+    }
+    snprintf(report, sizeof report, "left %zu, right %zu", left, right);
+    return report;
+}
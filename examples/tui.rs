@@ -0,0 +1,97 @@
+// render a simulation in the terminal, two cells per character using half blocks, keys
+// typed followed by Enter act like in the window, space pausing and q quitting
+use rlife::formats::rle;
+use rlife::input::{Action, InputEvent, Key, Keymap};
+use rlife::prelude::*;
+use std::io::{BufRead, Write};
+use std::sync::mpsc;
+use std::time::Duration;
+
+// size of the board, the terminal needs WIDTH columns and HEIGHT / 2 rows
+const WIDTH: usize = 80;
+const HEIGHT: usize = 48;
+
+// key of a typed character, the terminal only passes on whole lines
+fn key(c: char) -> InputEvent {
+    let key = match c {
+        ' ' => Key::Space,
+        '\t' => Key::Tab,
+        c => Key::Char(c.to_ascii_lowercase()),
+    };
+    InputEvent {
+        shift: c.is_ascii_uppercase(),
+        ..InputEvent::pressed(key)
+    }
+}
+
+// character for a vertical pair of cells
+fn block(top: bool, bottom: bool) -> char {
+    match (top, bottom) {
+        (true, true) => '█',
+        (true, false) => '▀',
+        (false, true) => '▄',
+        (false, false) => ' ',
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Gosper glider gun
+    let gun = rle::parse(
+        "x = 36, y = 9\n\
+         24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b\n\
+         obo$10bo5bo7bo$11bo3bo$12b2o!",
+    )?;
+    let mut board = Board::new(WIDTH, HEIGHT);
+    board.place(&gun.grid, 2, 2);
+
+    // the same bindings as the window, with a key for quitting since Escape doesn't reach
+    // line buffered input
+    let mut keymap = Keymap::default();
+    keymap.bind(Key::Char('q'), false, Action::Quit);
+    let (keys_tx, keys_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+            for c in line.chars() {
+                let _ = keys_tx.send(key(c));
+            }
+        }
+    });
+    let mut paused = false;
+
+    let mut stdout = std::io::stdout();
+    // clear screen and hide cursor
+    write!(stdout, "\x1b[2J\x1b[?25l")?;
+    while board.generation() < 300 {
+        for action in keys_rx.try_iter().filter_map(|event| keymap.action(&event)) {
+            match action {
+                Action::TogglePause => paused = !paused,
+                Action::Quit => {
+                    write!(stdout, "\x1b[?25h")?;
+                    return Ok(());
+                }
+                _ => (),
+            }
+        }
+
+        let mut frame = String::from("\x1b[H");
+        for y in (0..HEIGHT).step_by(2) {
+            frame.extend((0..WIDTH).map(|x| block(board.is_alive(x, y), board.is_alive(x, y + 1))));
+            frame.push('\n');
+        }
+        frame.push_str(&format!(
+            "generation {} population {}\n",
+            board.generation(),
+            board.population()
+        ));
+        stdout.write_all(frame.as_bytes())?;
+        stdout.flush()?;
+
+        if !paused {
+            board.step();
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    // show cursor again
+    write!(stdout, "\x1b[?25h")?;
+    Ok(())
+}
@@ -0,0 +1,154 @@
+// analysis of patterns: long-term behavior and equivalence
+use crate::stats::live_cells;
+use crate::transform::{self, Transform};
+use crate::{Life, Rule, SparseLife, VecGrid};
+use std::collections::BTreeSet;
+use std::fmt;
+
+// long-term behavior of a pattern left on an otherwise empty plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Behavior {
+    /// every cell is dead at the given generation
+    Dies { generation: usize },
+
+    /// pattern doesn't change
+    StillLife,
+
+    /// pattern returns to its starting state in place
+    Oscillator { period: usize },
+
+    /// pattern returns to its starting state displaced by (dx, dy)
+    Ship { period: usize, dx: isize, dy: isize },
+
+    /// none of the above within the generations searched
+    Unknown,
+}
+
+impl fmt::Display for Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Behavior::Dies { generation } => write!(f, "dies at generation {}", generation),
+            Behavior::StillLife => write!(f, "still life"),
+            Behavior::Oscillator { period } => write!(f, "oscillator with period {}", period),
+            Behavior::Ship { period, dx, dy } => {
+                // speed in the usual fraction-of-c notation
+                let distance = dx.unsigned_abs().max(dy.unsigned_abs());
+                let divisor = gcd(distance, period);
+                let (distance, period_c) = (distance / divisor, period / divisor);
+                let speed = match (distance, period_c) {
+                    (1, 1) => "c".to_string(),
+                    (1, p) => format!("c/{}", p),
+                    (d, 1) => format!("{}c", d),
+                    (d, p) => format!("{}c/{}", d, p),
+                };
+                write!(
+                    f,
+                    "ship with period {} moving ({}, {}) per period ({})",
+                    period, dx, dy, speed
+                )
+            }
+            Behavior::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// classify a pattern by simulating it under a rule for up to max_period generations,
+/// patterns of B0 rules, which fill the plane around them, are unknown
+pub fn classify(life: &impl Life, rule: Rule, max_period: usize) -> Behavior {
+    if rule.is_born(0) {
+        return Behavior::Unknown;
+    }
+    // stepped on the unbounded plane visiting only live cells and their neighbors, which
+    // small patterns searched for long periods, like those of enumerate, spend little time on
+    let mut sparse = SparseLife::with_rule(rule);
+    live_cells(life).for_each(|(x, y)| sparse.set_cell(x as i64, y as i64, true));
+    let start = match normalized(&sparse) {
+        Some(start) => start,
+        None => return Behavior::Dies { generation: 0 },
+    };
+    for generation in 1..=max_period {
+        sparse.step();
+        match normalized(&sparse) {
+            None => return Behavior::Dies { generation },
+            Some((cells, origin)) if cells == start.0 => {
+                let dx = (origin.0 - start.1 .0) as isize;
+                let dy = (origin.1 - start.1 .1) as isize;
+                return match (generation, dx, dy) {
+                    (1, 0, 0) => Behavior::StillLife,
+                    (period, 0, 0) => Behavior::Oscillator { period },
+                    (period, dx, dy) => Behavior::Ship { period, dx, dy },
+                };
+            }
+            _ => (),
+        }
+    }
+    Behavior::Unknown
+}
+
+/// symmetry mapping pattern a onto pattern b up to translation, None if there is none
+pub fn symmetry_between(a: &impl Life, b: &impl Life) -> Option<Transform> {
+    let (a, b) = (transform::trimmed(a), transform::trimmed(b));
+    Transform::ALL
+        .iter()
+        .copied()
+        .find(|&t| transform::transformed(&a, t) == b)
+}
+
+/// representative of a pattern shared by all of its translations, rotations and reflections
+pub fn canonical(life: &impl Life) -> VecGrid {
+    let trimmed = transform::trimmed(life);
+    Transform::ALL
+        .iter()
+        .map(|&t| transform::transformed(&trimmed, t))
+        .min()
+        .unwrap_or(trimmed)
+}
+
+/// largest number of cells in the box of enumerate, 2^cells patterns are visited
+pub const MAX_ENUMERATED_CELLS: usize = 25;
+
+/// every pattern fitting in a width by height box, each once up to translation, rotation and
+/// reflection, as canonical forms in ascending order, without the empty pattern
+pub fn enumerate(width: usize, height: usize) -> Vec<VecGrid> {
+    let cells = width * height;
+    assert!(
+        cells <= MAX_ENUMERATED_CELLS,
+        "can't enumerate boxes of more than {} cells",
+        MAX_ENUMERATED_CELLS
+    );
+    if cells == 0 {
+        return Vec::new();
+    }
+    // bits of the first row and column, patterns missing either are translations of others
+    let top_row = (1u64 << width) - 1;
+    let left_column = (0..height).fold(0u64, |column, y| column | 1 << (y * width));
+    let mut patterns = BTreeSet::new();
+    let mut grid = VecGrid::new(width, height);
+    for bits in (1u64..1 << cells).filter(|bits| bits & top_row != 0 && bits & left_column != 0) {
+        for i in 0..cells {
+            grid.set_cell(i % width, i / width, (bits >> i) & 1 == 1);
+        }
+        patterns.insert(canonical(&grid));
+    }
+    patterns.into_iter().collect()
+}
+
+// live cells relative to the bounding box origin in row order, along with that origin
+type Normalized = (Vec<(i64, i64)>, (i64, i64));
+
+// translation independent form of a pattern, None if it is empty
+fn normalized(sparse: &SparseLife) -> Option<Normalized> {
+    let (x, y, _, _) = sparse.bounds()?;
+    let mut cells: Vec<_> = sparse.cells().map(|(cx, cy)| (cx - x, cy - y)).collect();
+    cells.sort_unstable_by_key(|&(cx, cy)| (cy, cx));
+    Some((cells, (x, y)))
+}
+
+// greatest common divisor, used to reduce speeds
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a.max(1)
+    } else {
+        gcd(b, a % b)
+    }
+}
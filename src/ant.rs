@@ -0,0 +1,250 @@
+// Langton's Ant and its generalizations: ants walking a grid of colored cells, each turning
+// the way the rule gives for the color of its cell, moving that cell on to the next color and
+// stepping forward, rules being written a turn per color like RL for Langton's own ant or LLRR
+// for one growing symmetric patterns
+//
+// the grid wraps around its edges so that ants never walk off it
+use crate::multistate::{MultiStateLife, StateGrid};
+use crate::Steppable;
+use std::fmt;
+use std::str::FromStr;
+
+// way an ant turns on a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+    /// no turn, N
+    None,
+    /// turning around, U
+    Back,
+}
+
+// direction an ant faces, north being up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Heading {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Heading {
+    const ALL: [Heading; 4] = [Heading::North, Heading::East, Heading::South, Heading::West];
+
+    /// heading after a turn
+    pub fn turn(self, turn: Turn) -> Self {
+        let quarters = match turn {
+            Turn::None => 0,
+            Turn::Right => 1,
+            Turn::Back => 2,
+            Turn::Left => 3,
+        };
+        Self::ALL[(self as usize + quarters) % 4]
+    }
+
+    /// columns and rows moved by a step forward
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Heading::North => (0, -1),
+            Heading::East => (1, 0),
+            Heading::South => (0, 1),
+            Heading::West => (-1, 0),
+        }
+    }
+}
+
+// position and heading of an ant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ant {
+    pub x: usize,
+    pub y: usize,
+    pub heading: Heading,
+}
+
+// turn on every color of cell, the number of turns being the number of colors
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AntRule {
+    turns: Vec<Turn>,
+}
+
+impl AntRule {
+    /// Langton's Ant, which builds a highway after some 10000 steps of chaos
+    pub const LANGTON: &'static str = "RL";
+
+    /// turn on cells of a color
+    pub fn turn(&self, color: u8) -> Turn {
+        self.turns[color as usize % self.turns.len()]
+    }
+
+    /// number of colors cells go through
+    pub fn colors(&self) -> u8 {
+        self.turns.len() as u8
+    }
+}
+
+impl Default for AntRule {
+    fn default() -> Self {
+        Self {
+            turns: vec![Turn::Right, Turn::Left],
+        }
+    }
+}
+
+impl fmt::Display for AntRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for turn in &self.turns {
+            let c = match turn {
+                Turn::Left => 'L',
+                Turn::Right => 'R',
+                Turn::None => 'N',
+                Turn::Back => 'U',
+            };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+// reason an ant rule couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAntRuleError(String);
+
+impl fmt::Display for ParseAntRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid ant rule '{}', expected 2 to 255 turns of L, R, N or U like RL or LLRR",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseAntRuleError {}
+
+impl FromStr for AntRule {
+    type Err = ParseAntRuleError;
+
+    /// parse a turn per color, case insensitive
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseAntRuleError(s.to_string());
+        let turns = s
+            .trim()
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                'L' => Ok(Turn::Left),
+                'R' => Ok(Turn::Right),
+                'N' => Ok(Turn::None),
+                'U' => Ok(Turn::Back),
+                _ => Err(error()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match turns.len() {
+            2..=255 => Ok(Self { turns }),
+            _ => Err(error()),
+        }
+    }
+}
+
+// grid of colored cells the ants walk on, colors being the states of the grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AntWorld {
+    pub grid: StateGrid,
+    pub ants: Vec<Ant>,
+    pub rule: AntRule,
+    generation: u64,
+}
+
+impl AntWorld {
+    /// grid of cells of the first color without ants
+    pub fn new(width: usize, height: usize, rule: AntRule) -> Self {
+        Self {
+            grid: StateGrid::new(width, height),
+            ants: Vec::new(),
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// put an ant on the grid, cells outside of it wrapping around
+    pub fn add_ant(&mut self, x: usize, y: usize, heading: Heading) {
+        let (x, y) = (x % self.grid.width().max(1), y % self.grid.height().max(1));
+        self.ants.push(Ant { x, y, heading });
+    }
+
+    /// number of steps taken by every ant so far
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// move every ant a step, in the order they were added: turn, color the cell on and step
+    /// forward
+    pub fn step(&mut self) {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        if width == 0 || height == 0 {
+            return;
+        }
+        for ant in &mut self.ants {
+            let color = self.grid.get(ant.x, ant.y);
+            ant.heading = ant.heading.turn(self.rule.turn(color));
+            self.grid
+                .set(ant.x, ant.y, (color + 1) % self.rule.colors());
+            let (dx, dy) = ant.heading.offset();
+            ant.x = (ant.x as isize + dx).rem_euclid(width as isize) as usize;
+            ant.y = (ant.y as isize + dy).rem_euclid(height as isize) as usize;
+        }
+        self.generation += 1;
+    }
+
+    /// move every ant a number of steps
+    pub fn step_by(&mut self, steps: u64) {
+        (0..steps).for_each(|_| self.step());
+    }
+}
+
+impl MultiStateLife for AntWorld {
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn states(&self) -> u8 {
+        self.rule.colors()
+    }
+
+    fn state(&self, x: usize, y: usize) -> u8 {
+        self.grid.get(x, y)
+    }
+
+    fn set_state(&mut self, x: usize, y: usize, state: u8) {
+        self.grid.set(x, y, state.min(self.rule.colors() - 1))
+    }
+
+    fn wraps(&self) -> bool {
+        true
+    }
+
+    /// color of the cell once every ant on it moved on, the ants themselves only moving
+    /// with step
+    fn next_state(&self, x: usize, y: usize) -> u8 {
+        let ants = self
+            .ants
+            .iter()
+            .filter(|ant| (ant.x, ant.y) == (x, y))
+            .count();
+        ((self.state(x, y) as usize + ants) % self.rule.colors() as usize) as u8
+    }
+}
+
+impl Steppable for AntWorld {
+    fn step(&mut self) {
+        AntWorld::step(self)
+    }
+
+    fn generation(&self) -> u64 {
+        AntWorld::generation(self)
+    }
+}
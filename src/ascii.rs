@@ -0,0 +1,175 @@
+// text pictures of boards, a character per cell and a line per row like
+//
+//     .#.
+//     ..#
+//     ###
+//
+// for tests, debugging and piping boards to a terminal, the grids of the crate display
+// themselves this way with the default characters
+use crate::{BitGrid, Life, LifeGrid, TorusGrid, VecGrid};
+use std::fmt;
+
+// reason a picture couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// line and column of the offending character, counted from 1
+    pub line: usize,
+    pub column: usize,
+    pub found: char,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: unexpected character '{}'",
+            self.line, self.column, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// characters drawn for live and dead cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ascii {
+    pub alive: char,
+    pub dead: char,
+}
+
+impl Default for Ascii {
+    /// # for live cells and . for dead ones
+    fn default() -> Self {
+        Self {
+            alive: '#',
+            dead: '.',
+        }
+    }
+}
+
+impl Ascii {
+    /// characters for live and dead cells
+    pub fn new(alive: char, dead: char) -> Self {
+        Self { alive, dead }
+    }
+
+    /// picture of a board, for formatting with {}
+    pub fn picture<'a, L: Life>(&self, life: &'a L) -> Picture<'a, L> {
+        Picture { life, ascii: *self }
+    }
+
+    /// picture of a board as a string, every row ending in a newline
+    pub fn draw(&self, life: &impl Life) -> String {
+        self.picture(life).to_string()
+    }
+
+    /// board of a picture, as wide as its longest line, shorter lines are dead past their end
+    pub fn parse(&self, text: &str) -> Result<VecGrid, ParseError> {
+        let rows = self.rows(text)?;
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut grid = VecGrid::new(width, rows.len());
+        set_cells(&mut grid, &rows);
+        Ok(grid)
+    }
+
+    /// clear a board and set the live cells of a picture from its top left corner, cells of
+    /// the picture past the edges are left out
+    pub fn parse_into(&self, text: &str, life: &mut impl Life) -> Result<(), ParseError> {
+        let rows = self.rows(text)?;
+        for y in 0..life.height() {
+            for x in 0..life.width() {
+                life.set_cell(x, y, false);
+            }
+        }
+        set_cells(life, &rows);
+        Ok(())
+    }
+
+    // states of the cells of every line
+    fn rows(&self, text: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+        text.lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| match c {
+                        c if c == self.alive => Ok(true),
+                        c if c == self.dead => Ok(false),
+                        found => Err(ParseError {
+                            line: y + 1,
+                            column: x + 1,
+                            found,
+                        }),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+// set the live cells of rows that fit on a board
+fn set_cells(life: &mut impl Life, rows: &[Vec<bool>]) {
+    for (y, row) in rows.iter().enumerate().take(life.height()) {
+        for (x, _) in row.iter().enumerate().filter(|(_, &alive)| alive) {
+            if x < life.width() {
+                life.set_cell(x, y, true);
+            }
+        }
+    }
+}
+
+/// picture of a board with the default characters
+pub fn to_ascii(life: &impl Life) -> String {
+    Ascii::default().draw(life)
+}
+
+/// board of a picture with the default characters
+pub fn from_ascii(text: &str) -> Result<VecGrid, ParseError> {
+    Ascii::default().parse(text)
+}
+
+// board formatted as a picture
+pub struct Picture<'a, L> {
+    life: &'a L,
+    ascii: Ascii,
+}
+
+impl<L: Life> fmt::Display for Picture<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.life.height() {
+            for x in 0..self.life.width() {
+                let c = match self.life.is_alive(x, y) {
+                    true => self.ascii.alive,
+                    false => self.ascii.dead,
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for VecGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ascii::default().picture(self).fmt(f)
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ascii::default().picture(self).fmt(f)
+    }
+}
+
+impl<const W: usize, const H: usize> fmt::Display for LifeGrid<W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ascii::default().picture(self).fmt(f)
+    }
+}
+
+impl<const W: usize, const H: usize> fmt::Display for TorusGrid<W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ascii::default().picture(self).fmt(f)
+    }
+}
@@ -0,0 +1,102 @@
+// files sessions are saved to every so often, so that hours of building aren't lost to a
+// crash or a stray Escape, and the autosaves such runs leave behind, told apart from those
+// already dealt with by being newer than the file the session was saved to by hand
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+// name of the autosave file in the state directory, used when there's no session file
+const NAME: &str = "autosave.rle";
+
+// autosave file and when it's next due
+#[derive(Debug, Clone)]
+pub struct Autosave {
+    path: PathBuf,
+    every: Duration,
+    last: Instant,
+}
+
+impl Autosave {
+    /// autosave to a file every so often, the first autosave being due a period from now
+    pub fn new(path: PathBuf, every: Duration, now: Instant) -> Self {
+        Self {
+            path,
+            every,
+            last: now,
+        }
+    }
+
+    /// file autosaved to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// whether it's time to autosave again
+    pub fn is_due(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.last) >= self.every
+    }
+
+    /// write the contents of the autosave file, the next autosave is due a period from now
+    /// even when writing fails so that failures aren't retried on every frame
+    pub fn save(&mut self, contents: &str, now: Instant) -> io::Result<()> {
+        self.last = now;
+        write(&self.path, contents)
+    }
+}
+
+/// write a file, making its directory first as the state directory may not exist yet
+pub fn write(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
+
+/// file sessions are autosaved to, next to the session file if there is one, else in the
+/// user's state directory, the temporary one when it can't be found
+pub fn path(session: Option<&Path>) -> PathBuf {
+    if let Some(session) = session {
+        let mut name = session.file_name().unwrap_or_default().to_os_string();
+        name.push(".autosave");
+        return session.with_file_name(name);
+    }
+    let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    let state = var("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".local/state")))
+        .or_else(|| var("LOCALAPPDATA").map(PathBuf::from))
+        .unwrap_or_else(std::env::temp_dir);
+    state.join("rlife").join(NAME)
+}
+
+// autosave left behind by an earlier run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recovered {
+    /// contents of the autosave file
+    pub contents: String,
+    /// time since it was written
+    pub age: Duration,
+}
+
+/// autosave left behind by an earlier run, None when there is none or the session file was
+/// saved since, so that a session saved on exit isn't offered back
+pub fn recover(path: &Path, session: Option<&Path>) -> io::Result<Option<Recovered>> {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified());
+    let saved = match modified(path) {
+        Ok(saved) => saved,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    if let Some(newer) = session.and_then(|session| modified(session).ok()) {
+        if newer >= saved {
+            return Ok(None);
+        }
+    }
+    Ok(Some(Recovered {
+        contents: std::fs::read_to_string(path)?,
+        age: SystemTime::now().duration_since(saved).unwrap_or_default(),
+    }))
+}
@@ -0,0 +1,147 @@
+// grid packing 64 cells into every word, stepped a word at a time by adding up the neighbors
+// of 64 cells at once with bitwise adders
+use crate::rule::Rule;
+use crate::Life;
+
+// grid of runtime size stored as rows of words, the leftmost cell of a word in its lowest bit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+impl BitGrid {
+    /// create an empty grid of the given size
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            words_per_row,
+            data: vec![0; words_per_row * height],
+        }
+    }
+
+    /// words of a row, the bits past the right edge are always clear
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    // bits of the cells past the right edge in the last word of a row cleared
+    fn mask(&self, i: usize, word: u64) -> u64 {
+        match self.width % 64 {
+            bits if bits != 0 && i + 1 == self.words_per_row => word & ((1 << bits) - 1),
+            _ => word,
+        }
+    }
+
+    // word of a row, dead outside the grid
+    fn word(&self, y: Option<usize>, i: usize) -> u64 {
+        match y {
+            Some(y) if y < self.height && i < self.words_per_row => {
+                self.data[y * self.words_per_row + i]
+            }
+            _ => 0,
+        }
+    }
+
+    /// calculate the next generation under a rule into other, 64 cells at a time
+    pub fn next_generation_with(&self, rule: Rule, other: &mut impl Life) {
+        for y in 0..self.height {
+            for i in 0..self.words_per_row {
+                let [above, here, below] = [y.checked_sub(1), Some(y), Some(y + 1)];
+                // neighbors of the cells in a word: the words above and below along with all
+                // three shifted by a cell either way, carrying over from the adjacent words
+                let mut counts = [0; 4];
+                for (y, with_middle) in [(above, true), (here, false), (below, true)] {
+                    let word = self.word(y, i);
+                    let west = word << 1 | self.word(y, i.wrapping_sub(1)) >> 63;
+                    let east = word >> 1 | self.word(y, i + 1) << 63;
+                    add(&mut counts, west);
+                    add(&mut counts, east);
+                    if with_middle {
+                        add(&mut counts, word);
+                    }
+                }
+                let alive = self.word(here, i);
+                let next = (0..=8).fold(0, |next, neighbors| {
+                    let states = match (rule.is_born(neighbors), rule.survives(neighbors)) {
+                        (true, true) => !0,
+                        (true, false) => !alive,
+                        (false, true) => alive,
+                        (false, false) => 0,
+                    };
+                    next | states & equals(&counts, neighbors)
+                });
+                other.set_packed(i * 64, y, self.mask(i, next));
+            }
+        }
+    }
+}
+
+// add a bit of every cell to the four bit counts of the cells, one bit of the counts per word
+fn add(counts: &mut [u64; 4], mut carry: u64) {
+    for count in counts.iter_mut() {
+        (*count, carry) = (*count ^ carry, *count & carry);
+    }
+}
+
+// cells whose count equals a number
+fn equals(counts: &[u64; 4], number: usize) -> u64 {
+    counts
+        .iter()
+        .enumerate()
+        .fold(!0, |equal, (bit, &count)| match (number >> bit) & 1 {
+            1 => equal & count,
+            _ => equal & !count,
+        })
+}
+
+impl Life for BitGrid {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        if x < self.width && y < self.height {
+            let word = &mut self.data[y * self.words_per_row + x / 64];
+            match is_alive {
+                true => *word |= 1 << (x % 64),
+                false => *word &= !(1 << (x % 64)),
+            }
+        }
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && (self.word(Some(y), x / 64) >> (x % 64)) & 1 == 1
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.data.capacity() * std::mem::size_of::<u64>()
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn iter_rows_packed(&self) -> impl Iterator<Item = Vec<u64>> + '_ {
+        (0..self.height).map(|y| self.row(y).to_vec())
+    }
+
+    fn set_packed(&mut self, x: usize, y: usize, word: u64) {
+        if x.is_multiple_of(64) && x < self.width && y < self.height {
+            let i = x / 64;
+            self.data[y * self.words_per_row + i] = self.mask(i, word);
+        } else {
+            (x..self.width.min(x.saturating_add(64)))
+                .for_each(|cx| self.set_cell(cx, y, (word >> (cx - x)) & 1 == 1));
+        }
+    }
+
+    /// steps 64 cells at a time, see next_generation_with
+    fn next_generation(&self, other: &mut impl Life) {
+        self.next_generation_with(self.rule(), other)
+    }
+}
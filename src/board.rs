@@ -0,0 +1,157 @@
+// high level board combining a grid, its simulation and pattern files
+use crate::edit::{self, Symmetry};
+use crate::explain::Explanation;
+#[cfg(feature = "io")]
+use crate::formats::{self, Pattern};
+use crate::stats::{self, BoundingBox};
+use crate::transform::Transform;
+use crate::{Emitter, Life, Rule, RuleLife, Simulation, VecGrid};
+#[cfg(feature = "io")]
+use std::path::Path;
+
+// board of runtime size that can be stepped under its rule, queried, edited and saved
+pub struct Board {
+    simulation: Simulation<RuleLife<VecGrid>>,
+    symmetry: Symmetry,
+}
+
+impl Board {
+    /// create an empty board
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            simulation: Simulation::from_life(RuleLife::new(
+                VecGrid::new(width, height),
+                Rule::default(),
+            )),
+            symmetry: Symmetry::None,
+        }
+    }
+
+    /// create a board holding a pattern, sized to the pattern and stepped under its rule,
+    /// failing when the rule isn't in B/S notation
+    #[cfg(feature = "io")]
+    pub fn from_pattern(pattern: Pattern) -> Result<Self, formats::Error> {
+        let rule = match pattern.rule {
+            Some(rule) => rule.parse()?,
+            None => Rule::default(),
+        };
+        Ok(Self {
+            simulation: Simulation::from_life(RuleLife::new(pattern.grid, rule)),
+            symmetry: Symmetry::None,
+        })
+    }
+
+    /// load a board from a pattern file
+    #[cfg(feature = "io")]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, formats::Error> {
+        formats::load(path).and_then(Self::from_pattern)
+    }
+
+    /// save the board to a pattern file, choosing the format from its extension
+    #[cfg(feature = "io")]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), formats::Error> {
+        formats::save(path, &self.to_pattern())
+    }
+
+    /// copy of the board as a pattern
+    #[cfg(feature = "io")]
+    pub fn to_pattern(&self) -> Pattern {
+        Pattern {
+            grid: self.grid().clone(),
+            rule: Some(self.rule().to_string()),
+        }
+    }
+
+    /// copy the live cells of a pattern onto the board with its top left corner at (x, y)
+    pub fn place(&mut self, pattern: &impl Life, x: usize, y: usize) {
+        self.simulation
+            .life_mut()
+            .blit(pattern, x, y, Transform::Identity);
+    }
+
+    /// calculate the next generation
+    pub fn step(&mut self) {
+        self.simulation.step();
+    }
+
+    /// calculate the given number of generations
+    pub fn step_by(&mut self, generations: u64) {
+        self.simulation.step_by(generations);
+    }
+
+    /// advance the generation by a single phase of counting, deciding and committing
+    pub fn step_phase(&mut self) -> &Explanation {
+        self.simulation.step_phase()
+    }
+
+    /// add a transform run on every new generation after the rule is applied
+    pub fn add_hook(&mut self, mut hook: impl FnMut(&mut VecGrid, u64) + Send + 'static) {
+        self.simulation
+            .add_hook(move |life, generation| hook(&mut life.life, generation));
+    }
+
+    /// add an emitter stamping a pattern every period generations
+    pub fn add_emitter(&mut self, emitter: Emitter) {
+        self.simulation.add_emitter(emitter);
+    }
+
+    /// number of generations calculated so far
+    pub fn generation(&self) -> u64 {
+        self.simulation.generation()
+    }
+
+    /// rule the board is stepped under
+    pub fn rule(&self) -> Rule {
+        self.simulation.life().rule
+    }
+
+    /// width of the board
+    pub fn width(&self) -> usize {
+        self.grid().width()
+    }
+
+    /// height of the board
+    pub fn height(&self) -> usize {
+        self.grid().height()
+    }
+
+    /// state of cell
+    pub fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.grid().is_alive(x, y)
+    }
+
+    /// set state of cell, along with its mirror images under the symmetry of the board
+    pub fn set(&mut self, x: usize, y: usize, is_alive: bool) {
+        edit::set_mirrored(self.simulation.life_mut(), x, y, is_alive, self.symmetry);
+    }
+
+    /// symmetry edits made with set are mirrored by
+    pub fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    /// mirror later edits made with set by a symmetry
+    pub fn set_symmetry(&mut self, symmetry: Symmetry) {
+        self.symmetry = symmetry;
+    }
+
+    /// number of live cells
+    pub fn population(&self) -> usize {
+        stats::population(self.grid())
+    }
+
+    /// bytes taken by the grids of the board
+    pub fn memory_usage(&self) -> usize {
+        self.simulation.memory_usage()
+    }
+
+    /// smallest rectangle containing every live cell
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        stats::bounding_box(self.grid())
+    }
+
+    /// underlying grid, for use with the rest of the library
+    pub fn grid(&self) -> &VecGrid {
+        &self.simulation.life().life
+    }
+}
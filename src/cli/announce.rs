@@ -0,0 +1,103 @@
+// announcements of what a run or the GUI is doing, as plain sentences on stdout that screen
+// readers can follow, and spoken by a command like espeak when one is given
+use super::messages::{self, Message};
+use log::warn;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+// time between announcements of the generation and population
+const STATUS_INTERVAL: Duration = Duration::from_secs(10);
+
+// announcer of actions as they happen and of the state every so often
+pub struct Announcer {
+    enabled: bool,
+    speak: Option<String>,
+    speaking: Option<Child>,
+    announced: Option<Instant>,
+    last_action: Option<String>,
+}
+
+impl Announcer {
+    /// announcer writing to stdout when enabled, each announcement also given as the only
+    /// argument to the speak command
+    pub fn new(enabled: bool, speak: Option<String>) -> Self {
+        Self {
+            enabled: enabled || speak.is_some(),
+            speak,
+            speaking: None,
+            announced: None,
+            last_action: None,
+        }
+    }
+
+    /// announce something that just happened, it's remembered as the last action even when
+    /// announcements are off
+    pub fn action(&mut self, action: impl Into<String>) {
+        let action = action.into();
+        self.say(&action, true);
+        self.last_action = Some(action);
+    }
+
+    /// announce the generation and population, at most once per interval and skipped while
+    /// the last announcement is still being spoken
+    pub fn status(&mut self, generation: u64, population: usize, paused: bool) {
+        if self
+            .announced
+            .is_some_and(|announced| announced.elapsed() < STATUS_INTERVAL)
+        {
+            return;
+        }
+        self.announced = Some(Instant::now());
+        let mut status = messages::format(Message::Status, &[&generation, &population]);
+        if paused {
+            status += &format!(", {}", messages::text(Message::Paused));
+        }
+        self.say(&status, false);
+    }
+
+    /// last action announced, if any
+    #[cfg(feature = "gui")]
+    pub fn last_action(&self) -> Option<&str> {
+        self.last_action.as_deref()
+    }
+
+    // write an announcement and speak it, waiting for the previous one to be spoken first
+    // unless it can be skipped
+    fn say(&mut self, text: &str, wait: bool) {
+        if !self.enabled {
+            return;
+        }
+        let speaking = self
+            .speaking
+            .as_mut()
+            .is_some_and(|child| matches!(child.try_wait(), Ok(None)));
+        if speaking && !wait {
+            return;
+        }
+        println!("{}", text);
+        let _ = std::io::stdout().flush();
+        let command = match &self.speak {
+            Some(command) => command,
+            None => return,
+        };
+        if let Some(mut child) = self.speaking.take() {
+            let _ = child.wait();
+        }
+        match Command::new(command)
+            .arg(text)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => self.speaking = Some(child),
+            Err(err) => {
+                warn!(
+                    "couldn't run {}: {}, announcements won't be spoken",
+                    command, err
+                );
+                self.speak = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// processing of whole directories of pattern files
+use super::{flag_value, parallel_map, DEFAULT_MAX_PERIOD};
+use rlife::analysis::{self, Behavior};
+use rlife::formats::{self, Pattern};
+use rlife::report::{Table, Value};
+use rlife::stats;
+use rlife::Life;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+// operation applied to every file by batch
+#[derive(Clone, Copy, PartialEq)]
+enum BatchOp {
+    /// count files by behavior
+    Census,
+    /// one row of info output per file
+    Info,
+    /// rewrite files trimmed and in canonical orientation
+    Normalize,
+}
+
+// process every pattern file of a directory in parallel and print a combined report
+pub fn batch(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut dir = None;
+    let mut op = BatchOp::Info;
+    let mut json = false;
+    let mut output = None;
+    let mut max_period = DEFAULT_MAX_PERIOD;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--op" => {
+                op = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "census" => BatchOp::Census,
+                    "info" => BatchOp::Info,
+                    "normalize" => BatchOp::Normalize,
+                    _ => return Err("--op must be census, info or normalize".into()),
+                }
+            }
+            "--format" => {
+                json = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err("--format must be csv or json".into()),
+                }
+            }
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            "--max-period" => max_period = flag_value(arg, args.next())?,
+            _ if dir.is_none() => dir = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let dir = dir.ok_or("batch requires a directory")?;
+
+    let mut files = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    files.retain(|path| path.is_file() && formats::is_supported(path));
+    files.sort();
+
+    let table = match op {
+        BatchOp::Census => {
+            // aggregate the class of every file into counts
+            let mut counts: Vec<(String, usize)> = Vec::new();
+            for class in parallel_map(&files, |path| census_class(path, max_period)) {
+                match counts.iter_mut().find(|(c, _)| *c == class) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((class, 1)),
+                }
+            }
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            let mut table = Table::new(&["class", "count"]);
+            counts
+                .into_iter()
+                .for_each(|(class, count)| table.push(vec![class.into(), count.into()]));
+            table
+        }
+        BatchOp::Info | BatchOp::Normalize => {
+            let rows = parallel_map(&files, |path| {
+                batch_file(path, op == BatchOp::Normalize, max_period)
+            });
+            let columns: &[&str] = match op {
+                BatchOp::Info => &["file", "width", "height", "population", "rule", "behavior"],
+                _ => &["file", "changed"],
+            };
+            let mut table = Table::new(&[columns, &["error"]].concat());
+            for (path, row) in files.iter().zip(rows) {
+                let file = Value::from(path.display().to_string());
+                table.push(match row {
+                    Ok(values) => [vec![file], values, vec!["".into()]].concat(),
+                    Err(err) => [
+                        vec![file],
+                        vec!["".into(); columns.len() - 1],
+                        vec![err.into()],
+                    ]
+                    .concat(),
+                });
+            }
+            table
+        }
+    };
+
+    let report = if json {
+        table.to_json()
+    } else {
+        table.to_csv()
+    };
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+// behavior class of a file under its rule for census, "error" if it can't be read
+fn census_class(path: &Path, max_period: usize) -> String {
+    let pattern = match formats::load(path) {
+        Ok(pattern) => pattern,
+        Err(_) => return "error".to_string(),
+    };
+    let rule = match pattern.parsed_rule() {
+        Ok(rule) => rule,
+        Err(_) => return "error".to_string(),
+    };
+    match analysis::classify(&pattern.grid, rule, max_period) {
+        Behavior::Dies { .. } => "dies".to_string(),
+        Behavior::StillLife => "still life".to_string(),
+        Behavior::Oscillator { period } => format!("oscillator p{}", period),
+        Behavior::Ship { period, .. } => format!("ship p{}", period),
+        Behavior::Unknown => "unknown".to_string(),
+    }
+}
+
+// apply the info or normalize operation to one file, yielding the row values after the file name
+fn batch_file(path: &Path, normalize: bool, max_period: usize) -> Result<Vec<Value>, String> {
+    let pattern = formats::load(path).map_err(|err| err.to_string())?;
+    let grid = &pattern.grid;
+    Ok(match normalize {
+        false => {
+            let rule = pattern.parsed_rule().map_err(|err| err.to_string())?;
+            vec![
+                grid.width().into(),
+                grid.height().into(),
+                stats::population(grid).into(),
+                pattern
+                    .rule
+                    .as_deref()
+                    .unwrap_or(rlife::DEFAULT_RULE)
+                    .into(),
+                analysis::classify(grid, rule, max_period)
+                    .to_string()
+                    .into(),
+            ]
+        }
+        true => {
+            let canonical = analysis::canonical(grid);
+            let changed = canonical != *grid;
+            if changed {
+                let pattern = Pattern {
+                    grid: canonical,
+                    rule: pattern.rule,
+                };
+                formats::save(path, &pattern).map_err(|err| err.to_string())?;
+            }
+            vec![(if changed { "yes" } else { "no" }).into()]
+        }
+    })
+}
@@ -0,0 +1,399 @@
+// commands controlling a headless run while it is going, read from stdin or a control socket
+use rlife::formats::rle;
+use rlife::ownership::{Ownership, NO_OWNER};
+use rlife::{Life, VecGrid};
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::sync::mpsc;
+
+// command accepted during a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// stop calculating generations until resumed
+    Pause,
+    Resume,
+    /// calculate the given number of generations, then pause
+    Step(u64),
+    /// save the current generation to a pattern file
+    Dump(String),
+    /// report the generation and population
+    Status,
+    /// stop the run, writing outputs like when it completes
+    Quit,
+    /// bring a cell to life for the client sending the command, or kill it
+    Set(usize, usize),
+    Clear(usize, usize),
+    /// report the live cells of every client on a shared board
+    Scores,
+    /// copy of the current generation and its owners for attached viewers, only sent by
+    /// the control socket
+    Snapshot,
+}
+
+impl Command {
+    /// parse a command line like "step 100", "dump out.rle" or "set 3 4"
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let cell = |x: &str, y: &str| {
+            x.parse()
+                .ok()
+                .zip(y.parse().ok())
+                .ok_or_else(|| format!("invalid cell '{} {}'", x, y))
+        };
+        let (command, arguments) = match words[..] {
+            ["pause", ..] => (Command::Pause, 0),
+            ["resume", ..] => (Command::Resume, 0),
+            ["step", n, ..] => (
+                Command::Step(
+                    n.parse()
+                        .map_err(|_| format!("invalid generation count '{}'", n))?,
+                ),
+                1,
+            ),
+            ["step"] => (Command::Step(1), 0),
+            ["dump", path, ..] => (Command::Dump(path.to_string()), 1),
+            ["status", ..] => (Command::Status, 0),
+            ["quit", ..] => (Command::Quit, 0),
+            ["set", x, y, ..] => (cell(x, y).map(|(x, y)| Command::Set(x, y))?, 2),
+            ["clear", x, y, ..] => (cell(x, y).map(|(x, y)| Command::Clear(x, y))?, 2),
+            ["scores", ..] => (Command::Scores, 0),
+            _ => {
+                return Err(format!(
+                    "unknown command '{}', expected pause, resume, step [N], dump FILE, status, \
+                     quit, set X Y, clear X Y or scores",
+                    line.trim()
+                ))
+            }
+        };
+        match words.get(arguments + 1) {
+            Some(extra) => Err(format!("unexpected '{}' after command", extra)),
+            None => Ok(command),
+        }
+    }
+}
+
+// where the reply to a command goes
+enum Reply {
+    Stderr,
+    Stream(Box<dyn Write + Send>),
+    Snapshot(mpsc::Sender<Snapshot>),
+}
+
+// copy of a generation sent to the control socket, owners are empty when not tracked
+struct Snapshot {
+    generation: u64,
+    grid: VecGrid,
+    owners: Vec<u8>,
+}
+
+// command along with where its reply goes
+pub struct Request {
+    pub command: Command,
+    /// sender of the command, connections to the control socket are numbered from 1 and
+    /// stdin is NO_OWNER
+    pub client: u8,
+    reply: Reply,
+}
+
+impl Request {
+    /// answer the request with one line, on stderr when it came from stdin, the reply is
+    /// written before this returns so that it isn't lost when the run ends right after
+    pub fn reply(&mut self, message: String) {
+        match &mut self.reply {
+            Reply::Stream(stream) => {
+                let _ = writeln!(stream, "{}", message);
+            }
+            Reply::Stderr | Reply::Snapshot(_) => eprintln!("{}", message),
+        }
+    }
+
+    /// answer a snapshot request with the current generation and its owners, if tracked
+    pub fn snapshot(&self, generation: u64, life: &VecGrid, ownership: Option<&Ownership>) {
+        if let Reply::Snapshot(snapshot) = &self.reply {
+            let _ = snapshot.send(Snapshot {
+                generation,
+                grid: life.clone(),
+                owners: ownership.map_or_else(Vec::new, |o| o.owners().to_vec()),
+            });
+        }
+    }
+}
+
+// board state sent to attached viewers, as a whole or as the cells changed since the last
+// one, along with the owners of cells on shared boards, as (x, y, owner)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Update {
+    /// every cell, and the owner of every owned cell
+    Frame {
+        generation: u64,
+        grid: VecGrid,
+        owners: Vec<(usize, usize, u8)>,
+    },
+    /// cells that were toggled, and the cells whose owner changed
+    Changes {
+        generation: u64,
+        cells: Vec<(usize, usize)>,
+        owners: Vec<(usize, usize, u8)>,
+    },
+}
+
+impl Update {
+    /// update bringing a viewer showing previous, if any, to the given generation, owners
+    /// are row by row and empty when they aren't tracked
+    pub fn between(
+        previous: Option<(&VecGrid, &[u8])>,
+        generation: u64,
+        grid: &VecGrid,
+        owners: &[u8],
+    ) -> Self {
+        let (width, height) = (grid.width(), grid.height());
+        let owner = |owners: &[u8], x: usize, y: usize| {
+            owners.get(y * width + x).copied().unwrap_or(NO_OWNER)
+        };
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)));
+        match previous {
+            Some((previous, previous_owners))
+                if (previous.width(), previous.height()) == (width, height) =>
+            {
+                Update::Changes {
+                    generation,
+                    cells: cells
+                        .clone()
+                        .filter(|&(x, y)| previous.is_alive(x, y) != grid.is_alive(x, y))
+                        .collect(),
+                    owners: cells
+                        .map(|(x, y)| (x, y, owner(owners, x, y)))
+                        .filter(|&(x, y, o)| owner(previous_owners, x, y) != o)
+                        .collect(),
+                }
+            }
+            _ => Update::Frame {
+                generation,
+                grid: grid.clone(),
+                owners: cells
+                    .map(|(x, y)| (x, y, owner(owners, x, y)))
+                    .filter(|&(_, _, o)| o != NO_OWNER)
+                    .collect(),
+            },
+        }
+    }
+
+    /// parse an update line as written by Display
+    #[cfg(feature = "gui")]
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let error = || format!("invalid update '{}'", line.trim());
+        let cell = |cell: &str| {
+            cell.split_once(',')
+                .and_then(|(x, y)| x.parse().ok().zip(y.parse().ok()))
+                .ok_or_else(error)
+        };
+        // the owners follow the word "owners" as x,y:owner
+        let (line, owned) = line.split_once(" owners ").unwrap_or((line, ""));
+        let owners = owned
+            .split_whitespace()
+            .map(|owned| {
+                let (xy, owner) = owned.split_once(':').ok_or_else(error)?;
+                let (x, y) = cell(xy)?;
+                Ok((x, y, owner.parse().map_err(|_| error())?))
+            })
+            .collect::<Result<_, String>>()?;
+        let mut words = line.split_whitespace();
+        let (kind, generation) = (words.next(), words.next().and_then(|g| g.parse().ok()));
+        match (kind, generation) {
+            (Some("frame"), Some(generation)) => {
+                let size = words.next().ok_or_else(error)?;
+                let (width, height) = size.split_once('x').ok_or_else(error)?;
+                let body = words.next().unwrap_or("!");
+                let pattern = rle::parse(&format!("x = {}, y = {}\n{}", width, height, body))
+                    .map_err(|err| err.to_string())?;
+                Ok(Update::Frame {
+                    generation,
+                    grid: pattern.grid,
+                    owners,
+                })
+            }
+            (Some("changes"), Some(generation)) => {
+                let cells = words.map(cell).collect::<Result<_, _>>()?;
+                Ok(Update::Changes {
+                    generation,
+                    cells,
+                    owners,
+                })
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for Update {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let owners = match self {
+            Update::Frame {
+                generation,
+                grid,
+                owners,
+            } => {
+                // the RLE body without its header and line breaks
+                let rle = rle::write(grid, rlife::DEFAULT_RULE);
+                let body: String = rle.lines().skip(1).collect();
+                write!(
+                    f,
+                    "frame {} {}x{} {}",
+                    generation,
+                    grid.width(),
+                    grid.height(),
+                    body
+                )?;
+                owners
+            }
+            Update::Changes {
+                generation,
+                cells,
+                owners,
+            } => {
+                write!(f, "changes {}", generation)?;
+                cells
+                    .iter()
+                    .try_for_each(|(x, y)| write!(f, " {},{}", x, y))?;
+                owners
+            }
+        };
+        if !owners.is_empty() {
+            write!(f, " owners")?;
+        }
+        owners
+            .iter()
+            .try_for_each(|(x, y, owner)| write!(f, " {},{}:{}", x, y, owner))
+    }
+}
+
+/// read commands from stdin on a thread of their own, the thread drops its sender at the end
+/// of input
+pub fn stdin(requests: mpsc::Sender<Request>) {
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => line,
+                Err(_) => return,
+            };
+            match Command::parse(&line) {
+                Ok(command) => {
+                    let request = Request {
+                        command,
+                        client: NO_OWNER,
+                        reply: Reply::Stderr,
+                    };
+                    if requests.send(request).is_err() {
+                        return;
+                    }
+                }
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    });
+}
+
+// listening control socket, the socket file is removed when it is dropped
+#[cfg(unix)]
+pub struct Socket {
+    path: std::path::PathBuf,
+}
+
+#[cfg(unix)]
+impl Drop for Socket {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// accept connections on a Unix socket, each sending one command per line and getting one
+/// reply line per command, "frame" replies with the whole board and "changes" with the cells
+/// changed since the last frame or changes of the connection, connections are numbered so
+/// that the cells they set are owned by them
+#[cfg(unix)]
+pub fn socket(path: &str, requests: mpsc::Sender<Request>) -> std::io::Result<Socket> {
+    use std::io::BufReader;
+    use std::os::unix::net::UnixListener;
+
+    let listener = UnixListener::bind(path)?;
+    std::thread::spawn(move || {
+        let mut clients = (1..=u8::MAX).cycle();
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            let requests = requests.clone();
+            let client = clients.next().unwrap_or(NO_OWNER);
+            // one thread per connection, so a slow client doesn't hold up the others
+            std::thread::spawn(move || {
+                let mut writer = match stream.try_clone() {
+                    Ok(writer) => writer,
+                    Err(_) => return,
+                };
+                // last board and owners sent to this connection, changes are relative to them
+                let mut shown: Option<(VecGrid, Vec<u8>)> = None;
+                for line in BufReader::new(stream).lines() {
+                    let line = match line {
+                        Ok(line) if line.trim().is_empty() => continue,
+                        Ok(line) => line,
+                        Err(_) => return,
+                    };
+                    // board updates are worked out here from a copy of the board
+                    if let command @ ("frame" | "changes") = line.trim() {
+                        let (tx, rx) = mpsc::channel();
+                        let request = Request {
+                            command: Command::Snapshot,
+                            client,
+                            reply: Reply::Snapshot(tx),
+                        };
+                        let update = match requests.send(request).ok().and_then(|_| rx.recv().ok())
+                        {
+                            Some(snapshot) => {
+                                let previous = shown
+                                    .as_ref()
+                                    .filter(|_| command == "changes")
+                                    .map(|(grid, owners)| (grid, owners.as_slice()));
+                                let update = Update::between(
+                                    previous,
+                                    snapshot.generation,
+                                    &snapshot.grid,
+                                    &snapshot.owners,
+                                );
+                                shown = Some((snapshot.grid, snapshot.owners));
+                                update.to_string()
+                            }
+                            None => "run finished".to_string(),
+                        };
+                        if writeln!(writer, "{}", update).is_err() {
+                            return;
+                        }
+                        continue;
+                    }
+
+                    // the run replies to other commands itself
+                    let error = match (Command::parse(&line), writer.try_clone()) {
+                        (Ok(command), Ok(reply)) => {
+                            let request = Request {
+                                command,
+                                client,
+                                reply: Reply::Stream(Box::new(reply)),
+                            };
+                            match requests.send(request) {
+                                Ok(()) => continue,
+                                Err(_) => "run finished".to_string(),
+                            }
+                        }
+                        (Err(err), _) => err,
+                        (_, Err(err)) => err.to_string(),
+                    };
+                    if writeln!(writer, "{}", error).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    Ok(Socket { path: path.into() })
+}
@@ -0,0 +1,96 @@
+// damage spreading experiments: how far flipping one cell changes a board over time
+use super::{flag_value, parallel_map, size_value};
+use rlife::damage::{self, DamageCurve};
+use rlife::formats;
+use rlife::random::Rng;
+use rlife::report::Table;
+use rlife::rule::{Rule, RuleLife};
+use rlife::{Life, VecGrid};
+use std::error::Error;
+
+// trials averaged unless --trials is given
+const DEFAULT_TRIALS: usize = 32;
+
+// flip a random cell of a pattern, or of a new random soup for every trial without one, and
+// report the mean, variance and survival of the damage at every generation
+pub fn damage(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut path = None;
+    let (mut width, mut height) = (64, 64);
+    let mut density = 0.35;
+    let mut seed = 1;
+    let mut generations = 200;
+    let mut trials = DEFAULT_TRIALS;
+    let mut rule = None;
+    let mut json = false;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => (width, height) = size_value(arg, args.next())?,
+            "--density" => density = flag_value(arg, args.next())?,
+            "--seed" => seed = flag_value(arg, args.next())?,
+            "--generations" => generations = flag_value(arg, args.next())?,
+            "--trials" => trials = flag_value(arg, args.next())?,
+            "--rule" => rule = Some(flag_value::<Rule>(arg, args.next())?),
+            "--format" => {
+                json = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err("--format must be csv or json".into()),
+                }
+            }
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+
+    // the rule of the pattern file unless --rule is given
+    let pattern = path.map(formats::load).transpose()?;
+    let rule = match (rule, pattern.as_ref().and_then(|p| p.rule.as_deref())) {
+        (Some(rule), _) => rule,
+        (None, Some(rule)) => rule.parse()?,
+        (None, None) => Rule::default(),
+    };
+    // a seed per trial drawn up front, so the trials don't depend on the threads
+    let mut rng = Rng::new(seed);
+    let seeds: Vec<u64> = (0..trials).map(|_| rng.next_u64()).collect();
+    let distances = parallel_map(&seeds, |&seed| {
+        let mut rng = Rng::new(seed);
+        let board = match &pattern {
+            Some(pattern) => pattern.grid.clone(),
+            None => {
+                let mut soup = VecGrid::new(width, height);
+                soup.fill_random(density, &mut rng);
+                soup
+            }
+        };
+        damage::spread_random(&RuleLife::new(board, rule), generations, &mut rng)
+    });
+    let curve = DamageCurve::new(&distances);
+
+    let mut table = Table::new(&["generation", "mean_distance", "variance", "surviving"]);
+    for (generation, ((&mean, &variance), &surviving)) in curve
+        .mean
+        .iter()
+        .zip(&curve.variance)
+        .zip(&curve.surviving)
+        .enumerate()
+    {
+        table.push(vec![
+            generation.into(),
+            mean.into(),
+            variance.into(),
+            surviving.into(),
+        ]);
+    }
+    let report = match json {
+        true => table.to_json(),
+        false => table.to_csv(),
+    };
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
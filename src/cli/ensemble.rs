@@ -0,0 +1,83 @@
+// ensembles of random soups run with the same settings and their own seeds
+use super::{flag_value, size_value};
+use rlife::ensemble::{Curve, Ensemble};
+use rlife::report::Table;
+use rlife::rule::{Rule, RuleLife};
+use rlife::{Life, VecGrid};
+use std::error::Error;
+
+// runs of an ensemble unless --runs is given
+const DEFAULT_RUNS: usize = 64;
+
+// run soups of the same size, density and rule from different seeds on every core, and
+// report the mean, variance and survival of their populations at every generation
+pub fn ensemble(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut width, mut height) = (64, 64);
+    let mut density = 0.35;
+    let mut rule = Rule::default();
+    let mut ensemble = Ensemble {
+        runs: DEFAULT_RUNS,
+        generations: 500,
+        seed: 1,
+        threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let mut every = 1;
+    let mut json = false;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => ensemble.runs = flag_value(arg, args.next())?,
+            "--size" => (width, height) = size_value(arg, args.next())?,
+            "--density" => density = flag_value(arg, args.next())?,
+            "--rule" => rule = flag_value(arg, args.next())?,
+            "--seed" => ensemble.seed = flag_value(arg, args.next())?,
+            "--generations" => ensemble.generations = flag_value(arg, args.next())?,
+            "--threads" => ensemble.threads = flag_value(arg, args.next())?,
+            "--every" => every = flag_value::<usize>(arg, args.next())?.max(1),
+            "--format" => {
+                json = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err("--format must be csv or json".into()),
+                }
+            }
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+
+    let populations = ensemble.populations(|rng| {
+        let mut soup = VecGrid::new(width, height);
+        soup.fill_random(density, rng);
+        RuleLife::new(soup, rule)
+    });
+    let curve = Curve::new(&populations);
+
+    let cells = (width * height).max(1) as f64;
+    let mut table = Table::new(&[
+        "generation",
+        "mean_population",
+        "variance",
+        "mean_density",
+        "surviving",
+    ]);
+    for generation in (0..curve.mean.len()).step_by(every) {
+        table.push(vec![
+            generation.into(),
+            curve.mean[generation].into(),
+            curve.variance[generation].into(),
+            (curve.mean[generation] / cells).into(),
+            curve.surviving[generation].into(),
+        ]);
+    }
+    let report = match json {
+        true => table.to_json(),
+        false => table.to_csv(),
+    };
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+// catalog of every small pattern and what becomes of it
+use super::{flag_value, parallel_map, size_value, DEFAULT_MAX_PERIOD};
+use rlife::analysis::{self, Behavior};
+use rlife::formats::rle;
+use rlife::report::Table;
+use rlife::{stats, Life, Rule};
+use std::error::Error;
+
+// enumerate the patterns of a box up to symmetry, classify them in parallel and write a
+// table of them with their class, period and displacement per period
+pub fn enumerate(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut size = None;
+    let mut max_period = DEFAULT_MAX_PERIOD;
+    let mut json = false;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-period" => max_period = flag_value(arg, args.next())?,
+            "--format" => {
+                json = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err("--format must be csv or json".into()),
+                }
+            }
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ if size.is_none() => size = Some(size_value("enumerate", Some(arg))?),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let (width, height) = size.ok_or("enumerate requires a box size WxH")?;
+    if width * height > analysis::MAX_ENUMERATED_CELLS {
+        return Err(format!(
+            "boxes can have at most {} cells",
+            analysis::MAX_ENUMERATED_CELLS
+        )
+        .into());
+    }
+
+    let patterns = analysis::enumerate(width, height);
+    let behaviors = parallel_map(&patterns, |pattern| {
+        analysis::classify(pattern, Rule::default(), max_period)
+    });
+    let mut table = Table::new(&[
+        "pattern",
+        "width",
+        "height",
+        "population",
+        "class",
+        "period",
+        "dx",
+        "dy",
+    ]);
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for (pattern, behavior) in patterns.iter().zip(behaviors) {
+        let (class, period, dx, dy) = match behavior {
+            Behavior::Dies { .. } => ("dies", 0, 0, 0),
+            Behavior::StillLife => ("still life", 1, 0, 0),
+            Behavior::Oscillator { period } => ("oscillator", period, 0, 0),
+            Behavior::Ship { period, dx, dy } => ("ship", period, dx, dy),
+            Behavior::Unknown => ("unknown", 0, 0, 0),
+        };
+        match counts.iter_mut().find(|(c, _)| *c == class) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((class, 1)),
+        }
+        table.push(vec![
+            body(pattern).into(),
+            pattern.width().into(),
+            pattern.height().into(),
+            stats::population(pattern).into(),
+            class.into(),
+            period.into(),
+            (dx as i64).into(),
+            (dy as i64).into(),
+        ]);
+    }
+    let counts: Vec<String> = counts
+        .iter()
+        .map(|(class, count)| format!("{} {}", count, class))
+        .collect();
+    eprintln!(
+        "{} distinct patterns in {}x{}: {}",
+        patterns.len(),
+        width,
+        height,
+        counts.join(", ")
+    );
+
+    let report = match json {
+        true => table.to_json(),
+        false => table.to_csv(),
+    };
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+// RLE body of a pattern on one line, without the header
+fn body(pattern: &impl Life) -> String {
+    rle::write(pattern, rlife::DEFAULT_RULE)
+        .lines()
+        .skip(1)
+        .collect()
+}
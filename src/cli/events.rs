@@ -0,0 +1,44 @@
+// events of a headless run, logged for people or as JSON lines for programs
+use log::info;
+use rlife::report::{self, Value};
+
+// where events of a run are reported
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventLog {
+    /// one JSON object per line on stdout instead of env_logger lines
+    pub json: bool,
+}
+
+impl EventLog {
+    /// report an event with its fields, the event name is the "event" field in JSON
+    pub fn emit(&self, event: &str, fields: &[(&str, Value)]) {
+        if self.json {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, value)| {
+                    format!(
+                        "{}: {}",
+                        report::json_string(name),
+                        report::json_value(value)
+                    )
+                })
+                .collect();
+            println!(
+                "{{\"event\": {}{}{}}}",
+                report::json_string(event),
+                if fields.is_empty() { "" } else { ", " },
+                fields.join(", ")
+            );
+        } else {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, value)| match value {
+                    Value::Int(v) => format!("{}={}", name, v),
+                    Value::Float(v) => format!("{}={}", name, v),
+                    Value::Text(v) => format!("{}={}", name, v),
+                })
+                .collect();
+            info!("{}: {}", event, fields.join(" "));
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// frames of a pattern drawn headlessly and hashed, to check renderers against golden outputs
+use super::flag_value;
+use rlife::formats::{self, png};
+use rlife::hex::{HexLife, HexRule};
+use rlife::ltl::{LtlLife, LtlRule};
+use rlife::margolus::{MargolusLife, MargolusRule};
+use rlife::render::{self, Palette, Viewport};
+use rlife::rule::RuleLife;
+use rlife::{Life, Simulation, Steppable};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+// generations drawn unless --generations is given
+const DEFAULT_GENERATIONS: u64 = 100;
+
+// draw every generation of a pattern, printing a line of generation and frame hash for each,
+// comparing them with a file of such lines with --check and saving the frames with --png
+pub fn frames(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut path = None;
+    let mut generations = DEFAULT_GENERATIONS;
+    let (mut check, mut png_dir) = (None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--generations" => generations = flag_value(arg, args.next())?,
+            "--check" => check = Some(flag_value::<String>(arg, args.next())?),
+            "--png" => png_dir = Some(flag_value::<PathBuf>(arg, args.next())?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let path = path.ok_or("frames requires a pattern file")?;
+    let expected = match &check {
+        Some(check) => Some(golden(&std::fs::read_to_string(check)?)?),
+        None => None,
+    };
+    if let Some(dir) = &png_dir {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    // patterns of hex rules like B2/S34H are stepped and drawn as hexagons, those of Larger
+    // than Life rules like R5,C0,M1,S34..58,B34..45,NM stepped with their radius and those
+    // of Margolus rules like MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15 block by block
+    let pattern = formats::load(path)?;
+    let rule = pattern.rule.as_deref().unwrap_or(rlife::DEFAULT_RULE);
+    let hashes = match rule.parse::<HexRule>() {
+        Ok(hex) => draw(
+            Simulation::from_life(HexLife::new(pattern.grid, hex)),
+            generations,
+            png_dir.as_deref(),
+            |simulation, frame| {
+                let life = simulation.life();
+                let (width, height) = render::hex_frame_size(life);
+                frame.resize(width * height, 0);
+                render::render_hex_to_buffer(life, frame, &Palette::default());
+                Ok(width)
+            },
+        )?,
+        Err(_) => match rule.parse::<LtlRule>() {
+            Ok(ltl) => square(
+                Simulation::from_life(LtlLife::new(pattern.grid, ltl)),
+                generations,
+                png_dir.as_deref(),
+            )?,
+            Err(_) => match rule.parse::<MargolusRule>() {
+                Ok(margolus) => {
+                    let viewport = Viewport::whole(&pattern.grid);
+                    draw(
+                        MargolusLife::new(pattern.grid, margolus),
+                        generations,
+                        png_dir.as_deref(),
+                        |margolus, frame| {
+                            frame.resize(viewport.width * viewport.height, 0);
+                            let palette = Palette::default();
+                            render::render_to_buffer(&margolus.life, frame, &palette, viewport);
+                            Ok(viewport.width)
+                        },
+                    )?
+                }
+                Err(_) => square(
+                    Simulation::from_life(RuleLife::new(pattern.grid, rule.parse()?)),
+                    generations,
+                    png_dir.as_deref(),
+                )?,
+            },
+        },
+    };
+
+    if let (Some(check), Some(expected)) = (check, expected) {
+        for (&(generation, hash), &(_, want)) in hashes.iter().zip(&expected) {
+            if hash != want {
+                return Err(format!(
+                    "generation {}: frame hash {:016x}, expected {:016x} from {}",
+                    generation, hash, want, check
+                )
+                .into());
+            }
+        }
+        if hashes.len() != expected.len() {
+            return Err(format!(
+                "{} frames drawn, {} has {}",
+                hashes.len(),
+                check,
+                expected.len()
+            )
+            .into());
+        }
+        eprintln!("{} frames match {}", hashes.len(), check);
+    }
+    Ok(())
+}
+
+// step a simulation drawn as square cells, checking that drawing each frame from the previous
+// one with render_changes agrees with a full redraw
+fn square<L: Life + Clone>(
+    simulation: Simulation<L>,
+    generations: u64,
+    png_dir: Option<&Path>,
+) -> Result<Vec<(u64, u64)>, Box<dyn Error>> {
+    let viewport = Viewport::whole(simulation.life());
+    let palette = Palette::default();
+    let mut changes = vec![0; viewport.width * viewport.height];
+    let mut drawn: Option<L> = None;
+    draw(simulation, generations, png_dir, |simulation, frame| {
+        let life = simulation.life();
+        frame.resize(viewport.width * viewport.height, 0);
+        render::render_to_buffer(life, frame, &palette, viewport);
+        match &drawn {
+            Some(previous) => {
+                render::render_changes(previous, life, &mut changes, &palette, viewport)
+            }
+            None => changes.copy_from_slice(frame),
+        }
+        if changes != *frame {
+            return Err("partial redraw differs".into());
+        }
+        drawn = Some(life.clone());
+        Ok(viewport.width)
+    })
+}
+
+// step a universe, drawing every generation with a function giving the width of the frame,
+// and print and return the generation and hash of each frame
+fn draw<S: Steppable>(
+    mut universe: S,
+    generations: u64,
+    png_dir: Option<&Path>,
+    mut render: impl FnMut(&S, &mut Vec<u32>) -> Result<usize, Box<dyn Error>>,
+) -> Result<Vec<(u64, u64)>, Box<dyn Error>> {
+    let mut frame = Vec::new();
+    let mut hashes = Vec::new();
+    for generation in 0..=generations {
+        let width = render(&universe, &mut frame)
+            .map_err(|err| format!("generation {}: {}", generation, err))?;
+        let hash = render::frame_hash(&frame);
+        println!("{} {:016x}", generation, hash);
+        hashes.push((generation, hash));
+        if let Some(dir) = png_dir {
+            let file = dir.join(format!("frame-{:05}.png", generation));
+            std::fs::write(file, png::write_frame(&frame, width))?;
+        }
+        universe.step();
+    }
+    Ok(hashes)
+}
+
+// generations and hashes of a file in the output format of frames, in order from 0
+fn golden(contents: &str) -> Result<Vec<(u64, u64)>, Box<dyn Error>> {
+    let mut frames = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let frame = line
+            .split_once(' ')
+            .and_then(|(generation, hash)| {
+                let hash = u64::from_str_radix(hash.trim(), 16).ok()?;
+                Some((generation.parse().ok()?, hash))
+            })
+            .filter(|&(generation, _)| generation == frames.len() as u64)
+            .ok_or_else(|| format!("line {}: expected GENERATION HASH, got '{}'", i + 1, line))?;
+        frames.push(frame);
+    }
+    Ok(frames)
+}
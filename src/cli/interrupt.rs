@@ -0,0 +1,32 @@
+// Ctrl+C handling for headless runs: the first one asks the run to stop cleanly,
+// a second one exits immediately
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// number of interrupts received so far
+static INTERRUPTS: AtomicUsize = AtomicUsize::new(0);
+
+/// start counting interrupts instead of being killed by the first one
+#[cfg(unix)]
+pub fn install() {
+    extern "C" fn handle(_: libc::c_int) {
+        // only async signal safe calls are allowed here
+        if INTERRUPTS.fetch_add(1, Ordering::SeqCst) > 0 {
+            unsafe { libc::_exit(130) };
+        }
+    }
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            handle as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+}
+
+/// without signals Ctrl+C keeps its default behavior
+#[cfg(not(unix))]
+pub fn install() {}
+
+/// whether an interrupt was received
+pub fn interrupted() -> bool {
+    INTERRUPTS.load(Ordering::SeqCst) > 0
+}
@@ -0,0 +1,129 @@
+// experiment manifests recording everything needed to repeat a headless run exactly
+use std::error::Error;
+use std::fmt;
+
+// grid engine runs are calculated with, the only one so far
+pub const BACKEND: &str = "vecgrid";
+
+// settings of a headless run, written as key = value lines
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    /// version of rlife that made the run
+    pub version: String,
+    /// pattern file simulated, None for a random soup
+    pub pattern: Option<String>,
+    /// seed and density of the random soup
+    pub seed: u64,
+    pub density: f64,
+    pub rule: String,
+    /// board size, None to use the size of the pattern
+    pub size: Option<(usize, usize)>,
+    pub generations: u64,
+    pub backend: String,
+    pub threads: usize,
+    pub verify: bool,
+    /// whether the run stops early once the board dies out or settles into a cycle
+    pub until_settled: bool,
+    /// emitters as given to --emit
+    pub emitters: Vec<String>,
+    /// plugins as given to --plugin
+    pub plugins: Vec<String>,
+    /// rule zones as given to --zone
+    pub zones: Vec<String>,
+    /// shape file of the walls, if any
+    pub obstacles: Option<String>,
+}
+
+impl Manifest {
+    /// parse a manifest, unknown keys are errors so that typos don't go unnoticed
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut manifest = Self {
+            version: String::new(),
+            pattern: None,
+            seed: 0,
+            density: 0.0,
+            rule: rlife::DEFAULT_RULE.to_string(),
+            size: None,
+            generations: 0,
+            backend: BACKEND.to_string(),
+            threads: 1,
+            verify: false,
+            until_settled: false,
+            emitters: Vec::new(),
+            plugins: Vec::new(),
+            zones: Vec::new(),
+            obstacles: None,
+        };
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: &str| format!("manifest line {}: {}", number + 1, message);
+            let (key, value) = line
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim().to_string()))
+                .ok_or_else(|| error("expected key = value"))?;
+            let invalid = || error(&format!("invalid value for {}", key));
+            match key {
+                "version" => manifest.version = value,
+                "pattern" => manifest.pattern = Some(value),
+                "seed" => manifest.seed = value.parse().map_err(|_| invalid())?,
+                "density" => manifest.density = value.parse().map_err(|_| invalid())?,
+                "rule" => manifest.rule = value,
+                "size" => {
+                    let size = super::size_value(key, Some(&value)).map_err(|_| invalid())?;
+                    manifest.size = Some(size);
+                }
+                "generations" => manifest.generations = value.parse().map_err(|_| invalid())?,
+                "backend" => manifest.backend = value,
+                "threads" => manifest.threads = value.parse().map_err(|_| invalid())?,
+                "verify" => manifest.verify = value.parse().map_err(|_| invalid())?,
+                "until-settled" => manifest.until_settled = value.parse().map_err(|_| invalid())?,
+                "emit" => manifest.emitters.push(value),
+                "plugin" => manifest.plugins.push(value),
+                "zone" => manifest.zones.push(value),
+                "obstacles" => manifest.obstacles = Some(value),
+                _ => return Err(error(&format!("unknown key '{}'", key)).into()),
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# rlife experiment manifest, repeat with rlife run --from-manifest"
+        )?;
+        writeln!(f, "version = {}", self.version)?;
+        if let Some(pattern) = &self.pattern {
+            writeln!(f, "pattern = {}", pattern)?;
+        }
+        writeln!(f, "seed = {}", self.seed)?;
+        writeln!(f, "density = {}", self.density)?;
+        writeln!(f, "rule = {}", self.rule)?;
+        if let Some((width, height)) = self.size {
+            writeln!(f, "size = {}x{}", width, height)?;
+        }
+        writeln!(f, "generations = {}", self.generations)?;
+        writeln!(f, "backend = {}", self.backend)?;
+        writeln!(f, "threads = {}", self.threads)?;
+        writeln!(f, "verify = {}", self.verify)?;
+        writeln!(f, "until-settled = {}", self.until_settled)?;
+        self.emitters
+            .iter()
+            .try_for_each(|emitter| writeln!(f, "emit = {}", emitter))?;
+        self.plugins
+            .iter()
+            .try_for_each(|plugin| writeln!(f, "plugin = {}", plugin))?;
+        self.zones
+            .iter()
+            .try_for_each(|zone| writeln!(f, "zone = {}", zone))?;
+        if let Some(obstacles) = &self.obstacles {
+            writeln!(f, "obstacles = {}", obstacles)?;
+        }
+        Ok(())
+    }
+}
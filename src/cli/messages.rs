@@ -0,0 +1,186 @@
+// catalog of the text shown to users, so that translations only need a catalog of their
+// own, the locale is taken from RLIFE_LANG or else LANG and messages missing from a
+// translation fall back to English
+use std::fmt;
+use std::sync::OnceLock;
+
+// text shown to users, placeholders are written {} and filled in order, most of it is only
+// shown by the GUI
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    /// usage summary of every subcommand
+    Usage,
+    /// name of the GUI window
+    WindowTitle,
+    /// generation and population in titles and announcements
+    Status,
+    /// added to the status while paused
+    Paused,
+    /// end of every window title
+    EscToExit,
+    /// time-lapse speed in the title
+    TimeLapse,
+    /// bytes taken by the board and its history in the title
+    Memory,
+    /// macro being recorded and its number of edits
+    RecordingMacro,
+    /// macro replayed and its orientation
+    SelectedMacro,
+    /// last announced action
+    LastAction,
+    /// position in a tour and the prompt of the step
+    TutorialStep,
+    /// how the expectations of the previous step went
+    TutorialObserved,
+    /// previous step showing everything expected
+    AsExpected,
+    /// expectations the previous step missed
+    Expected,
+    /// size of the blocks shown by attach on big boards
+    CellsPerPixel,
+    /// top left cell of the view of attach
+    ViewAt,
+    /// attach following the live cells
+    Following,
+    /// populations of socket clients
+    Clients,
+    /// war round, placing player, cells left and scores
+    WarPlacing,
+    /// war round, generations left, live cells and scores
+    WarRunning,
+    /// war winner and scores
+    WarWinner,
+    /// war ending in a tie
+    WarTie,
+    /// transition demo waiting for the first ensemble, of the given number of soups
+    TransitionRunning,
+    /// transition demo density done last with its final density, lifespan and survivors
+    TransitionPoint,
+    /// WireWorld generation and electrons running
+    WireWorldStatus,
+    /// steps taken by the ants, their number and rule
+    AntStatus,
+    /// title of the dialog choosing a pattern file to open
+    #[cfg_attr(not(feature = "dialogs"), allow(dead_code))]
+    OpenPattern,
+    /// title of the dialog choosing the file a session is saved in
+    #[cfg_attr(not(feature = "dialogs"), allow(dead_code))]
+    SaveSession,
+}
+
+/// text of a message in the current locale
+pub fn text(message: Message) -> &'static str {
+    let translated = match locale() {
+        "es" => spanish(message),
+        _ => None,
+    };
+    translated.unwrap_or_else(|| english(message))
+}
+
+/// text of a message in the current locale with its placeholders filled in order, left
+/// over placeholders stay empty
+pub fn format(message: Message, args: &[&dyn fmt::Display]) -> String {
+    let mut parts = text(message).split("{}");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    let mut args = args.iter();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out += &arg.to_string();
+        }
+        out += part;
+    }
+    out
+}
+
+// language code of the locale, like "es" for es_ES.UTF-8, read once
+fn locale() -> &'static str {
+    static LOCALE: OnceLock<String> = OnceLock::new();
+    LOCALE.get_or_init(|| {
+        let locale = std::env::var("RLIFE_LANG")
+            .or_else(|_| std::env::var("LANG"))
+            .unwrap_or_default();
+        locale
+            .split(['_', '.', '-'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+    })
+}
+
+// the English text of every message
+fn english(message: Message) -> &'static str {
+    match message {
+        Message::Usage => super::USAGE,
+        Message::WindowTitle => "Conway's Game of Life",
+        Message::Status => "generation {}, population {}",
+        Message::Paused => "paused",
+        Message::EscToExit => "ESC to exit",
+        Message::TimeLapse => "time-lapse every {} generations",
+        Message::Memory => "memory {}",
+        Message::RecordingMacro => "recording macro, {} edits",
+        Message::SelectedMacro => "macro {} by {}",
+        Message::LastAction => "last: {}",
+        Message::TutorialStep => "step {}/{}: {}",
+        Message::TutorialObserved => "last step: {}",
+        Message::AsExpected => "as expected",
+        Message::Expected => "expected {}",
+        Message::CellsPerPixel => "{}x{} cells per pixel",
+        Message::ViewAt => "view at {},{}",
+        Message::Following => "following",
+        Message::Clients => "clients {}",
+        Message::WarPlacing => {
+            "round {}/{}, player {} places {} more cells (Enter ends the turn), scores {} to {}"
+        }
+        Message::WarRunning => "round {}/{}, {} generations left, alive {} to {}, scores {} to {}",
+        Message::WarWinner => "game over, player {} wins {} to {}",
+        Message::WarTie => "game over, tied at {}",
+        Message::TransitionRunning => "running ensembles of {} soups",
+        Message::TransitionPoint => {
+            "initial density {}%, final density {}, lifespan {}, {}% surviving"
+        }
+        Message::WireWorldStatus => "generation {}, {} electrons",
+        Message::AntStatus => "step {}, {} ants, rule {}",
+        Message::OpenPattern => "Open pattern",
+        Message::SaveSession => "Save session",
+    }
+}
+
+// Spanish translation, the usage summary is left in English
+fn spanish(message: Message) -> Option<&'static str> {
+    Some(match message {
+        Message::WindowTitle => "Juego de la vida de Conway",
+        Message::Status => "generación {}, población {}",
+        Message::Paused => "en pausa",
+        Message::EscToExit => "ESC para salir",
+        Message::TimeLapse => "cámara rápida cada {} generaciones",
+        Message::Memory => "memoria {}",
+        Message::RecordingMacro => "grabando macro, {} ediciones",
+        Message::SelectedMacro => "macro {} con {}",
+        Message::LastAction => "último: {}",
+        Message::TutorialStep => "paso {}/{}: {}",
+        Message::TutorialObserved => "paso anterior: {}",
+        Message::AsExpected => "como se esperaba",
+        Message::Expected => "se esperaba {}",
+        Message::CellsPerPixel => "{}x{} células por píxel",
+        Message::ViewAt => "vista en {},{}",
+        Message::Following => "siguiendo",
+        Message::Clients => "clientes {}",
+        Message::WarPlacing => {
+            "ronda {}/{}, el jugador {} coloca {} células más (Enter termina el turno), \
+             puntos {} a {}"
+        }
+        Message::WarRunning => "ronda {}/{}, quedan {} generaciones, vivas {} a {}, puntos {} a {}",
+        Message::WarWinner => "fin de la partida, el jugador {} gana {} a {}",
+        Message::WarTie => "fin de la partida, empate a {}",
+        Message::TransitionRunning => "simulando conjuntos de {} sopas",
+        Message::TransitionPoint => {
+            "densidad inicial {}%, densidad final {}, vida {}, {}% sobreviven"
+        }
+        Message::WireWorldStatus => "generación {}, {} electrones",
+        Message::AntStatus => "paso {}, {} hormigas, regla {}",
+        Message::OpenPattern => "Abrir patrón",
+        Message::SaveSession => "Guardar sesión",
+        Message::Usage => return None,
+    })
+}
@@ -0,0 +1,371 @@
+// command line subcommands that run without the GUI
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+pub(crate) mod announce;
+mod batch;
+pub(crate) mod control;
+mod damage;
+mod ensemble;
+mod enumerate;
+mod events;
+mod frames;
+mod interrupt;
+mod manifest;
+pub(crate) mod messages;
+mod pattern;
+mod plugin;
+pub(crate) mod priority;
+mod progress;
+mod puzzle;
+mod run;
+mod scan;
+mod search;
+mod series;
+mod snapshot;
+mod sonify;
+mod spectrum;
+mod states;
+mod sweep;
+
+// generations searched for periodic behavior unless --max-period is given
+const DEFAULT_MAX_PERIOD: usize = 64;
+
+/// usage summary printed on bad invocations
+pub const USAGE: &str = "usage:
+    rlife [--art] [--reseed-after SECONDS] [--time-lapse N] [--snap N] [--session FILE]
+          [--tutorial | --lesson FILE] [--announce] [--speak COMMAND] [--keys FILE]
+          [--pattern FILE] [--seed N] [--sim-nice N] [--sim-cpus LIST]
+          [--frame-drop latest|never|N] [--autosave SECONDS] [--restore]
+          [--guard none|shift|confirm]
+                                            open the GUI, when built with the gui feature,
+                                            --pattern starts with an RLE, plaintext or Life
+                                            1.05/1.06 file, like those of the LifeWiki and
+                                            older collections, in the middle of the board,
+                                            --art starts in the ambient art mode,
+                                            --reseed-after replaces settled boards with a
+                                            random soup, made from --seed if given like those
+                                            of F, --time-lapse simulates at full speed
+                                            drawing every Nth generation, --snap sets the
+                                            spacing of the snap grid and the board,
+                                            annotations, bookmarks and macros are kept in the
+                                            --session file, saved on exit and with S, or
+                                            in a file chosen with Shift+S when built with
+                                            the dialogs feature, which also opens a pattern
+                                            with Shift+O, the session is also autosaved
+                                            every minute or --autosave seconds, 0 turning it
+                                            off, on exit and on a crash, next to the session
+                                            file or in the state directory, and offered back
+                                            on the next launch, or restored with --restore,
+                                            --tutorial takes a guided tour of the rules and
+                                            the GUI and --lesson steps through the boards,
+                                            questions and expected observations of a lesson,
+                                            the title shows the generation, population,
+                                            memory taken by the board and its history and
+                                            last action, which --announce and --speak also
+                                            give as for rlife run, and --keys rebinds keys
+                                            with lines like 'p pause' or 'shift+1 bookmark-1'
+                                            or unbinds them with lines like 'escape none',
+                                            --guard sets what it takes to quit, start over
+                                            from a soup, open a pattern over the board or
+                                            jump to a bookmark, pressing the key twice by
+                                            default, with Shift, except for jumps which are
+                                            still pressed twice, or just once,
+                                            --sim-nice lowers the priority of the simulation
+                                            thread to a niceness up to 19 and --sim-cpus
+                                            binds it to processors like 0,2-3, on Linux, and
+                                            --frame-drop holds the simulation back when the
+                                            window can't keep up, drawing every generation
+                                            with never or skipping at most N in a row, rather
+                                            than drawing the latest
+    rlife info <file> [--max-period N] [--thumbnail FILE] [--thumbnail-size N]
+                                            describe a pattern file, with the memory it takes
+                                            on every backend, writing a PNG no larger than N
+                                            pixels of it to the --thumbnail file
+    rlife convert <in> <out> [transforms]   convert between pattern formats, .rle, Life
+                                            1.05 or 1.06 .lif and plaintext .cells, applying
+                                            --rotate 90|180|270, --flip h|v|d|a,
+                                            --crop X,Y,W,H and --trim in the given order
+    rlife compose <layout> <out>            assemble a board from pattern files placed by a
+                                            layout file of size WxH, grid WxH, rule RULE and
+                                            tile FILE COLUMN ROW [DX DY] lines
+    rlife diff <a> <b>                      compare two patterns up to translation and symmetry
+    rlife frames <file> [--generations N] [--check FILE] [--png DIR]
+                                            draw generations 0 to N headlessly, printing a
+                                            hash of every frame, comparing them with the
+                                            hashes printed earlier into the --check file and
+                                            saving the frames as PNG files in DIR, patterns
+                                            of hex rules like B2/S34H drawn as hexagons and
+                                            Larger than Life rules like
+                                            R5,C0,M1,S34..58,B34..45,NM and Margolus block
+                                            rules like MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15
+                                            supported
+    rlife batch <dir> [--op census|info|normalize] [--format csv|json] [--output FILE]
+                                            process every pattern file in a directory
+    rlife enumerate <WxH> [--max-period N] [--format csv|json] [--output FILE]
+                                            catalog every pattern fitting in a box of up to
+                                            25 cells once up to symmetry, as an RLE body with
+                                            whether it dies or is a still life, oscillator or
+                                            ship, its period and its displacement per period
+    rlife search <WxH> --period N [--ship DX,DY] [--rule RULE] [--threads N] [--split N]
+                 [--checkpoint FILE] [--format csv|json] [--output FILE]
+                                            search a box for oscillators, or ships moving
+                                            DX,DY per period, of exactly the period that stay
+                                            in the box, splitting the search into 2^N subtrees
+                                            searched on threads and listing every pattern once
+                                            up to phase and symmetry, the subtrees searched and
+                                            the patterns found are kept in the --checkpoint
+                                            file, which resumes an interrupted search
+    rlife run [<file>] [--generations N] [--size WxH] [--seed N] [--density D] [--threads N]
+              [--verify] [--paranoid] [--until-settled] [--output FILE] [--manifest FILE]
+              [--from-manifest FILE]
+              [--log-json] [--log-every N] [--quiet] [--announce] [--speak COMMAND]
+              [--stdin] [--socket PATH]
+              [--snapshot-on stabilized,cycle,record,boundary] [--snapshot-format rle,png]
+              [--snapshot-dir DIR] [--series FILE] [--series-points N] [--sonify FILE]
+              [--spectrum DIR] [--spectrum-every N] [--spectrum-block N] [--clusters FILE]
+              [--emit X,Y,PERIOD,FILE]... [--plugin FILE]...
+              [--zone X,Y,W,H,RULE | FILE,RULE]... [--obstacles FILE]
+                                            simulate a pattern, or a random soup without one,
+                                            without the GUI, --verify checks every step against
+                                            the serial engine, --paranoid checks populations,
+                                            packed rows and owners every generation and
+                                            compares the step with the bit grid, sparse and
+                                            HashLife engines, --until-settled stops once the
+                                            board dies out or settles into a still life or
+                                            cycle of up to 64 generations, --emit stamps a
+                                            pattern every PERIOD generations, the settings are
+                                            written to FILE.manifest or --manifest and
+                                            --from-manifest repeats a run, overridden by the
+                                            options after it, events are logged every N
+                                            generations as JSON lines on stdout with
+                                            --log-json, giving the population, the LZ4 size
+                                            of the packed board, the entropy of its 3x3
+                                            blocks and its clusters of touching cells, whose
+                                            sizes --clusters writes to a .csv file every N
+                                            generations, and a progress line is shown on stderr
+                                            unless --quiet, --announce writes
+                                            what happens and the state every few seconds as
+                                            sentences on stdout for screen readers, spoken by
+                                            --speak COMMAND, like espeak, --stdin and the
+                                            Unix socket at --socket accept pause, resume,
+                                            step [N], dump FILE, status, quit, set X Y,
+                                            clear X Y and scores commands, cells set through
+                                            the socket are owned by the connection and pass
+                                            on to their offspring, and
+                                            --snapshot-on saves the board when events happen,
+                                            --series writes population and activity over the
+                                            run as .csv, .json or an .svg graph, downsampled to
+                                            at most N points, and --sonify plays population,
+                                            births and deaths as notes into a .mid or .wav file,
+                                            --spectrum saves the power spectrum of the density
+                                            of N by N blocks, given by --spectrum-block, every
+                                            N generations, 100 by default, into DIR as a .png
+                                            image and a .csv of power by wavenumber, showing
+                                            the scales structures form at,
+                                            --plugin loads a shared library seeding soups or
+                                            reporting on every generation, and --zone steps a
+                                            rectangle or the live cells of a mask pattern under
+                                            a rule of its own, like B1357/S1357 or B2/S for
+                                            Seeds, instead of the rule of the run, and
+                                            --obstacles keeps cells dead on walls drawn from a
+                                            file of shapes, one per line, like rect X Y W H,
+                                            line X0 Y0 X1 Y1, circle X Y R and polygon X Y ...,
+                                            filled when preceded by filled
+    rlife sweep [--size WxH] [--density D] [--seed N] [--generations N]
+                [--sample N | --all | --rules B3/S23,...] [--format csv|json] [--output FILE]
+                [--rule-file FILE] [--plugin FILE]...
+                                            run one random soup under many outer-totalistic
+                                            rules, reporting lifespan, density and activity,
+                                            --rule-file adds rules written one per line as
+                                            expressions of n, alive, x and y, like
+                                            alive = n == 3 || (alive && n == 2) || x % 7 == 0,
+                                            plugins can seed the soup and add rules of their
+                                            own
+    rlife damage [<file>] [--size WxH] [--density D] [--seed N] [--generations N]
+                 [--trials N] [--rule RULE] [--format csv|json] [--output FILE]
+                                            flip a random cell of a pattern, or of a new soup
+                                            for each of N trials, step both boards together
+                                            and report the mean and variance of the cells
+                                            where they differ and the fraction of trials still
+                                            differing at every generation, which dies out
+                                            under ordered rules and spreads under chaotic ones
+    rlife ensemble [--runs M] [--size WxH] [--density D] [--rule RULE] [--seed N]
+                   [--generations N] [--threads N] [--every N] [--format csv|json]
+                   [--output FILE]
+                                            run M random soups with the same settings and
+                                            seeds drawn from N on every core, reporting the
+                                            mean and variance of their population and density
+                                            and the fraction of them still alive every N
+                                            generations
+    rlife scan --vary PARAMETER=VALUES [--vary PARAMETER=VALUES] [--size WxH] [--density D]
+               [--rule RULE] [--runs M] [--seed N] [--generations N] [--threads N]
+               [--output FILE]
+                                            run an ensemble of M soups at every point of a
+                                            grid of one or two of density, rule and size,
+                                            given as ranges like density=0.1:0.9:0.1 or
+                                            lists like rule=B3/S23,B36/S23, writing a CSV row
+                                            of the population of the runs at the last
+                                            generation for each point as it is done, points
+                                            already in the --output file being skipped
+    rlife states <WxH> [--rule RULE] [--torus] [--format dot|graphml] [--output FILE]
+                                            write the graph of every state of a board of up
+                                            to 25 cells and the state it steps to, with dead
+                                            cells past the edges or wrapping with --torus
+    rlife cycles <WxH> [--rule RULE] [--torus] [--format csv|json] [--output FILE]
+                                            find the cycles every state of the same boards
+                                            falls into, with their period, basin of states
+                                            ending up in them, its share of all states and
+                                            the longest transient before reaching the cycle
+    rlife puzzle list | show <puzzle> [--output FILE] | check <puzzle> <solution>
+                 | hint <puzzle> [--beam N] [--all] [--output FILE]
+                                            list the built-in puzzles, describe a built-in
+                                            puzzle or puzzle file, saving it to be edited
+                                            within its region and budget, check that a
+                                            solution reaches the goal in time, or search for
+                                            one, printing the first cell to change or --all
+                                            of them and saving the solved board, keeping the
+                                            N boards closest to the goal at each step
+    rlife attach <socket> [--view WxH] [--follow]
+                                            watch a run started with --socket in a window,
+                                            Space pauses it and the right arrow steps it,
+                                            --view shows only part of the board, following
+                                            the live cells with --follow (F toggles it), the
+                                            mouse sets and clears cells, colored by the
+                                            viewer that placed them
+    rlife war [--budget N] [--generations N] [--rounds N]
+                                            two players take turns placing N cells each in
+                                            their half of the board, then it runs and each
+                                            scores the live cells descended from theirs
+    rlife transition [--runs M] [--size WxH] [--generations N] [--rule RULE] [--seed N]
+                                            sweep the initial density of soups from 1% to 99%,
+                                            running an ensemble of M soups for each and
+                                            plotting the density they end at and how long
+                                            they live as each density is done
+    rlife wireworld [<file>] [--size WxH] [--step-time MS]
+                                            run a WireWorld circuit read from a text file of
+                                            a character per cell, . empty, # wire, @ electron
+                                            head and ~ tail, or an empty map, the left button
+                                            draws wire, the right one sparks an electron on
+                                            it and the middle one clears cells, Space pauses
+    rlife brain [--rule RULE] [--size WxH] [--density D] [--seed N] [--step-time MS]
+                                            run Brian's Brain, or another Generations rule
+                                            like B2/S345/C4, from a random soup of firing
+                                            cells drawn white, refractory ones fading blue,
+                                            the left button fires cells, R reseeds and Space
+                                            pauses
+    rlife ant [--rule RL] [--ants N] [--size WxH] [--step-time MS] [--steps N]
+                                            run Langton's Ant, or ants turning as another
+                                            rule of a turn per color like LLRR says, N of
+                                            them along the middle of a wrapping board taking
+                                            --steps steps a frame, the left button adds an
+                                            ant, Space pauses and the right arrow steps
+
+keys in the GUI:
+    Space                                   pause and resume
+    U                                       pause and step back a generation, up to 256
+    A, R, T                                 toggle art mode, reseeding and time-lapse
+    F                                       start over from a random soup
+    + and -                                 draw more or fewer generations in time-lapse
+    E                                       explain one generation at a time in phases
+    N                                       shade dead cells by live neighbors
+    L, B                                    label the cell under the cursor or mark a
+                                            rectangle from two corners, typing text ended by
+                                            Enter, Delete removes them
+    Shift+1 to 9, 1 to 9                    bookmark the generation, jump back to it
+    C                                       show cursor coordinates and offsets from an
+                                            origin set with O, G snaps edits to a grid
+    M, P                                    record edits into a macro named when it stops,
+                                            replay it at the cursor, Tab picks another macro
+                                            and the period key turns the replay
+    Y                                       mirror edits horizontally, vertically, 4-fold,
+                                            diagonally or not at all
+    S, Shift+S, Shift+O                     save the session, save it in another file,
+                                            open a pattern file in place of the board
+
+environment:
+    RLIFE_LANG                              language of titles, prompts and announcements,
+                                            like es, falling back to LANG and then English";
+
+/// run the subcommand named by the first argument, None if there is no such subcommand
+pub fn run(args: &[String]) -> Option<Result<(), Box<dyn Error>>> {
+    let (command, rest) = args.split_first()?;
+    match command.as_str() {
+        "info" => Some(pattern::info(rest)),
+        "convert" => Some(pattern::convert(rest)),
+        "compose" => Some(pattern::compose(rest)),
+        "diff" => Some(pattern::diff(rest)),
+        "frames" => Some(frames::frames(rest)),
+        "batch" => Some(batch::batch(rest)),
+        "enumerate" => Some(enumerate::enumerate(rest)),
+        "run" => Some(run::run(rest)),
+        "search" => Some(search::search(rest)),
+        "sweep" => Some(sweep::sweep(rest)),
+        "damage" => Some(damage::damage(rest)),
+        "ensemble" => Some(ensemble::ensemble(rest)),
+        "scan" => Some(scan::scan(rest)),
+        "states" => Some(states::states(rest)),
+        "cycles" => Some(states::cycles(rest)),
+        "puzzle" => Some(puzzle::puzzle(rest)),
+        #[cfg(all(feature = "gui", unix))]
+        "attach" => Some(crate::gui::attach(rest)),
+        #[cfg(feature = "gui")]
+        "war" => Some(crate::gui::war(rest)),
+        #[cfg(feature = "gui")]
+        "transition" => Some(crate::gui::transition(rest)),
+        #[cfg(feature = "gui")]
+        "ant" => Some(crate::gui::ant(rest)),
+        #[cfg(feature = "gui")]
+        "brain" => Some(crate::gui::brain(rest)),
+        #[cfg(feature = "gui")]
+        "wireworld" => Some(crate::gui::wireworld(rest)),
+        _ => None,
+    }
+}
+
+// apply f to every item on one scoped thread per core, keeping the order of the items
+pub(crate) fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+    std::thread::scope(|scope| {
+        for _ in 0..threads.min(items.len()) {
+            scope.spawn(|| loop {
+                // take the next unprocessed item
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                match items.get(i) {
+                    Some(item) => {
+                        let result = f(item);
+                        results.lock().expect("Poisoned").push((i, result));
+                    }
+                    None => return,
+                }
+            });
+        }
+    });
+    let mut results = results.into_inner().expect("Poisoned");
+    results.sort_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+// parse the value following a flag
+pub(crate) fn flag_value<T: std::str::FromStr>(
+    flag: &str,
+    value: Option<&String>,
+) -> Result<T, Box<dyn Error>> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("{} requires a valid value", flag).into())
+}
+
+// parse the WxH value following a flag
+pub(crate) fn size_value(
+    flag: &str,
+    value: Option<&String>,
+) -> Result<(usize, usize), Box<dyn Error>> {
+    value
+        .and_then(|value| value.split_once('x'))
+        .and_then(|(w, h)| w.parse().ok().zip(h.parse().ok()))
+        .ok_or_else(|| format!("{} requires WxH", flag).into())
+}
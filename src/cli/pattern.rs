@@ -0,0 +1,222 @@
+// subcommands working on single pattern files
+use super::{flag_value, DEFAULT_MAX_PERIOD};
+use rlife::analysis::{self, Behavior};
+use rlife::formats::{self, Pattern};
+use rlife::stats;
+use rlife::transform::{self, Transform};
+use rlife::{BitGrid, HashLifeUniverse, Life, RuleLife, SparseLife, VecGrid};
+use std::error::Error;
+
+// longest side in pixels of thumbnails unless --thumbnail-size is given
+const DEFAULT_THUMBNAIL_SIZE: usize = 128;
+
+// print dimensions, population, extent, rule and behavior of a pattern file
+pub fn info(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut path = None;
+    let mut max_period = DEFAULT_MAX_PERIOD;
+    let (mut thumbnail, mut thumbnail_size) = (None, DEFAULT_THUMBNAIL_SIZE);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-period" => max_period = flag_value(arg, args.next())?,
+            "--thumbnail" => thumbnail = Some(flag_value::<String>(arg, args.next())?),
+            "--thumbnail-size" => thumbnail_size = flag_value(arg, args.next())?,
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let path = path.ok_or("info requires a pattern file")?;
+
+    let pattern = formats::load(path)?;
+    let grid = &pattern.grid;
+    let rule = pattern.parsed_rule()?;
+    println!("file: {}", path);
+    println!("dimensions: {}x{}", grid.width(), grid.height());
+    println!("population: {}", stats::population(grid));
+    match stats::bounding_box(grid) {
+        Some(bbox) => println!(
+            "bounding box: {}x{} at ({}, {})",
+            bbox.width, bbox.height, bbox.x, bbox.y
+        ),
+        None => println!("bounding box: empty"),
+    }
+    let histogram = stats::neighbor_histogram(&RuleLife::new(grid.clone(), rule));
+    let counts: Vec<String> = histogram
+        .counts
+        .iter()
+        .enumerate()
+        .map(|(neighbors, cells)| format!("{}:{}", neighbors, cells))
+        .collect();
+    println!("neighbor counts: {}", counts.join(" "));
+    println!(
+        "next generation: {} births, {} deaths",
+        histogram.births, histogram.deaths
+    );
+    println!(
+        "rule: {}",
+        pattern.rule.as_deref().unwrap_or(rlife::DEFAULT_RULE)
+    );
+    // footprint of the pattern on every backend, so that one can be chosen for it
+    let mut packed = BitGrid::new(grid.width(), grid.height());
+    packed.blit(grid, 0, 0, Transform::Identity);
+    let sparse = SparseLife::from_life(grid, 0, 0);
+    let hashlife = HashLifeUniverse::from_life(grid, 0, 0).node_stats();
+    println!(
+        "memory: dense {}, packed {}, sparse {}, hashlife {} in {} nodes",
+        stats::format_bytes(grid.memory_usage()),
+        stats::format_bytes(packed.memory_usage()),
+        stats::format_bytes(sparse.memory_usage()),
+        stats::format_bytes(hashlife.memory),
+        hashlife.nodes
+    );
+    let behavior = analysis::classify(grid, rule, max_period);
+    match behavior {
+        Behavior::Unknown if rule.is_born(0) => println!("behavior: unknown under a B0 rule"),
+        Behavior::Unknown => println!("behavior: unknown within {} generations", max_period),
+        behavior => println!("behavior: {}", behavior),
+    }
+    if let Some(thumbnail) = thumbnail {
+        std::fs::write(&thumbnail, formats::png::thumbnail(grid, thumbnail_size))?;
+        println!("thumbnail: {}", thumbnail);
+    }
+    Ok(())
+}
+
+// edit applied to a pattern by convert
+enum Operation {
+    Transform(Transform),
+    Crop(usize, usize, usize, usize),
+    Trim,
+}
+
+impl Operation {
+    // parse the operation for a flag, None if the flag isn't an operation
+    fn parse<'a>(
+        flag: &str,
+        args: &mut impl Iterator<Item = &'a String>,
+    ) -> Option<Result<Operation, Box<dyn Error>>> {
+        let operation =
+            match flag {
+                "--rotate" => flag_value::<u32>(flag, args.next()).and_then(|angle| match angle {
+                    90 => Ok(Operation::Transform(Transform::Rotate90)),
+                    180 => Ok(Operation::Transform(Transform::Rotate180)),
+                    270 => Ok(Operation::Transform(Transform::Rotate270)),
+                    _ => Err("--rotate must be 90, 180 or 270".into()),
+                }),
+                "--flip" => {
+                    flag_value::<String>(flag, args.next()).and_then(|axis| match axis.as_str() {
+                        "h" => Ok(Operation::Transform(Transform::FlipHorizontal)),
+                        "v" => Ok(Operation::Transform(Transform::FlipVertical)),
+                        "d" => Ok(Operation::Transform(Transform::FlipDiagonal)),
+                        "a" => Ok(Operation::Transform(Transform::FlipAntiDiagonal)),
+                        _ => Err("--flip must be h, v, d or a".into()),
+                    })
+                }
+                "--crop" => flag_value::<String>(flag, args.next()).and_then(|region| match region
+                    .split(',')
+                    .map(str::parse)
+                    .collect::<Result<Vec<_>, _>>()
+                {
+                    Ok(values) if values.len() == 4 => {
+                        Ok(Operation::Crop(values[0], values[1], values[2], values[3]))
+                    }
+                    _ => Err("--crop requires X,Y,W,H".into()),
+                }),
+                "--trim" => Ok(Operation::Trim),
+                _ => return None,
+            };
+        Some(operation)
+    }
+
+    // apply the operation, producing a new grid
+    fn apply(&self, grid: &VecGrid) -> VecGrid {
+        match *self {
+            Operation::Transform(t) => transform::transformed(grid, t),
+            Operation::Crop(x, y, width, height) => transform::cropped(grid, x, y, width, height),
+            Operation::Trim => transform::trimmed(grid),
+        }
+    }
+}
+
+// read a pattern file, apply transforms and write it in the format of the output file
+pub fn convert(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut paths = Vec::new();
+    let mut operations = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match Operation::parse(arg, &mut args) {
+            Some(operation) => operations.push(operation?),
+            None => paths.push(arg),
+        }
+    }
+    let (input, output) = match paths.as_slice() {
+        [input, output] => (input, output),
+        _ => return Err("convert requires an input and an output file".into()),
+    };
+
+    let pattern = formats::load(input)?;
+    let grid = operations
+        .iter()
+        .fold(pattern.grid, |grid, operation| operation.apply(&grid));
+    formats::save(
+        output,
+        &Pattern {
+            grid,
+            rule: pattern.rule,
+        },
+    )?;
+    Ok(())
+}
+
+// compose the board of a layout file from its tiles and write it as a pattern
+pub fn compose(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (layout, output) = match args {
+        [layout, output] => (layout, output),
+        _ => return Err("compose requires a layout and an output file".into()),
+    };
+    formats::save(output, &formats::layout::load(layout)?)?;
+    Ok(())
+}
+
+// report whether two patterns are the same up to translation and symmetry, printing the cells
+// that differ when they aren't
+pub fn diff(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (a, b) = match args {
+        [a, b] => (formats::load(a)?.grid, formats::load(b)?.grid),
+        _ => return Err("diff requires two pattern files".into()),
+    };
+    match analysis::symmetry_between(&a, &b) {
+        Some(Transform::Identity) if a == b => println!("patterns are identical"),
+        Some(Transform::Identity) => println!("patterns are identical up to translation"),
+        Some(t) => println!("patterns are identical up to translation and {}", t),
+        None => {
+            // overlay both patterns at their file origins
+            let (width, height) = (a.width().max(b.width()), a.height().max(b.height()));
+            let (mut only_a, mut only_b) = (0, 0);
+            println!("legend: - only in a, + only in b, o in both");
+            for y in 0..height {
+                let row: String = (0..width)
+                    .map(|x| match (a.is_alive(x, y), b.is_alive(x, y)) {
+                        (true, true) => 'o',
+                        (true, false) => {
+                            only_a += 1;
+                            '-'
+                        }
+                        (false, true) => {
+                            only_b += 1;
+                            '+'
+                        }
+                        (false, false) => '.',
+                    })
+                    .collect();
+                println!("{}", row);
+            }
+            return Err(format!(
+                "patterns differ: {} cells only in a, {} cells only in b",
+                only_a, only_b
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
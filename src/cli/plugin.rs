@@ -0,0 +1,188 @@
+// plugins loaded at runtime from shared libraries with a C ABI, providing rules, seeders
+// and analysis passes without recompiling rlife, a plugin exports
+//
+//     uint32_t rlife_plugin_abi(void);          // must return PLUGIN_ABI
+//     const char *rlife_plugin_name(void);
+//
+// and any of
+//
+//     // fill a soup, cells are width * height bytes in row-major order set to 0 or 1
+//     void rlife_plugin_seed(size_t width, size_t height, uint64_t seed, uint8_t *cells);
+//     // next state of the cell at (x, y), 0 or 1
+//     uint8_t rlife_plugin_rule(uint8_t alive, uint8_t neighbors, size_t x, size_t y);
+//     // a line reporting on a generation, or NULL for nothing, owned by the plugin
+//     const char *rlife_plugin_analyze(uint64_t generation, size_t width, size_t height,
+//                                      const uint8_t *cells);
+use rlife::{Life, VecGrid};
+use std::error::Error;
+use std::ffi::{c_char, c_void, CStr, CString};
+
+/// version of the plugin interface, bumped whenever a function changes
+pub const PLUGIN_ABI: u32 = 1;
+
+type AbiFn = unsafe extern "C" fn() -> u32;
+type NameFn = unsafe extern "C" fn() -> *const c_char;
+type SeedFn = unsafe extern "C" fn(usize, usize, u64, *mut u8);
+type RuleFn = unsafe extern "C" fn(u8, u8, usize, usize) -> u8;
+type AnalyzeFn = unsafe extern "C" fn(u64, usize, usize, *const u8) -> *const c_char;
+
+// shared library loaded as a plugin, unloaded when dropped
+pub struct Plugin {
+    handle: *mut c_void,
+    name: String,
+    seed: Option<SeedFn>,
+    rule: Option<RuleFn>,
+    analyze: Option<AnalyzeFn>,
+}
+
+impl Plugin {
+    /// load a plugin, checking that it was built for this version of the interface
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let error = |message: String| format!("couldn't load plugin {}: {}", path, message);
+        let handle = open(path).map_err(error)?;
+        // the handle is closed on errors too once it's owned by a plugin
+        let mut plugin = Self {
+            handle,
+            name: String::new(),
+            seed: None,
+            rule: None,
+            analyze: None,
+        };
+        let abi = plugin
+            .symbol("rlife_plugin_abi")
+            .ok_or_else(|| error("missing rlife_plugin_abi".to_string()))?;
+        let abi = unsafe { std::mem::transmute::<*mut c_void, AbiFn>(abi)() };
+        if abi != PLUGIN_ABI {
+            return Err(error(format!(
+                "built for interface version {}, expected {}",
+                abi, PLUGIN_ABI
+            ))
+            .into());
+        }
+        let name = plugin
+            .symbol("rlife_plugin_name")
+            .ok_or_else(|| error("missing rlife_plugin_name".to_string()))?;
+        let name = unsafe { std::mem::transmute::<*mut c_void, NameFn>(name)() };
+        plugin.name = string(name).ok_or_else(|| error("empty name".to_string()))?;
+        unsafe {
+            plugin.seed = plugin
+                .symbol("rlife_plugin_seed")
+                .map(|f| std::mem::transmute::<*mut c_void, SeedFn>(f));
+            plugin.rule = plugin
+                .symbol("rlife_plugin_rule")
+                .map(|f| std::mem::transmute::<*mut c_void, RuleFn>(f));
+            plugin.analyze = plugin
+                .symbol("rlife_plugin_analyze")
+                .map(|f| std::mem::transmute::<*mut c_void, AnalyzeFn>(f));
+        }
+        if plugin.seed.is_none() && plugin.rule.is_none() && plugin.analyze.is_none() {
+            return Err(error("provides no seeder, rule or analysis pass".to_string()).into());
+        }
+        Ok(plugin)
+    }
+
+    /// name the plugin gives itself
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// whether the plugin provides a seeder
+    pub fn is_seeder(&self) -> bool {
+        self.seed.is_some()
+    }
+
+    /// whether the plugin provides a rule
+    pub fn is_rule(&self) -> bool {
+        self.rule.is_some()
+    }
+
+    /// fill a grid with the seeder of the plugin, clearing it first
+    pub fn seed(&self, grid: &mut VecGrid, seed: u64) {
+        let seed_fn = match self.seed {
+            Some(seed_fn) => seed_fn,
+            None => return,
+        };
+        let (width, height) = (grid.width(), grid.height());
+        let mut cells = vec![0u8; width * height];
+        unsafe { seed_fn(width, height, seed, cells.as_mut_ptr()) };
+        for (i, &cell) in cells.iter().enumerate() {
+            grid.set_cell(i % width, i / width, cell != 0);
+        }
+    }
+
+    /// next state of a cell under the rule of the plugin, Conway's without one
+    pub fn next_state(&self, is_alive: bool, neighbors: usize, x: usize, y: usize) -> bool {
+        match self.rule {
+            Some(rule) => unsafe { rule(is_alive as u8, neighbors as u8, x, y) != 0 },
+            None => rlife::rule::Rule::default().next_state(is_alive, neighbors),
+        }
+    }
+
+    /// line the analysis pass of the plugin reports on a generation, if any
+    pub fn analyze(&self, generation: u64, life: &impl Life) -> Option<String> {
+        let analyze = self.analyze?;
+        let cells = cells(life);
+        let line = unsafe { analyze(generation, life.width(), life.height(), cells.as_ptr()) };
+        string(line)
+    }
+
+    // address of an exported symbol, if the plugin has it
+    #[cfg(unix)]
+    fn symbol(&self, name: &str) -> Option<*mut c_void> {
+        let c_name = CString::new(name).ok()?;
+        let symbol = unsafe { libc::dlsym(self.handle, c_name.as_ptr()) };
+        (!symbol.is_null()).then_some(symbol)
+    }
+
+    // plugins can't be opened without dlopen, so this is never reached
+    #[cfg(not(unix))]
+    fn symbol(&self, _: &str) -> Option<*mut c_void> {
+        None
+    }
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        unsafe {
+            libc::dlclose(self.handle);
+        }
+    }
+}
+
+// handle of a shared library, or the reason it couldn't be loaded
+#[cfg(unix)]
+fn open(path: &str) -> Result<*mut c_void, String> {
+    let c_path = CString::new(path).map_err(|err| err.to_string())?;
+    let handle = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    if handle.is_null() {
+        let reason = string(unsafe { libc::dlerror() });
+        return Err(reason.unwrap_or_else(|| "unknown error".to_string()));
+    }
+    Ok(handle)
+}
+
+// shared libraries are only loaded with dlopen so far
+#[cfg(not(unix))]
+fn open(_: &str) -> Result<*mut c_void, String> {
+    Err("plugins require a Unix system".to_string())
+}
+
+// cells of a board as bytes in row-major order
+fn cells(life: &impl Life) -> Vec<u8> {
+    (0..life.height())
+        .flat_map(|y| (0..life.width()).map(move |x| (x, y)))
+        .map(|(x, y)| life.is_alive(x, y) as u8)
+        .collect()
+}
+
+// copy of a string owned by a plugin, None for NULL or empty strings
+fn string(text: *const c_char) -> Option<String> {
+    if text.is_null() {
+        return None;
+    }
+    let text = unsafe { CStr::from_ptr(text) }
+        .to_string_lossy()
+        .into_owned();
+    (!text.is_empty()).then_some(text)
+}
@@ -0,0 +1,89 @@
+// priority and processor affinity of the simulation thread, so that rlife shares a machine
+// with other workloads predictably, like when left running as an installation display
+//
+// both are set for the thread alone on Linux only, elsewhere asking for them is an error
+#![cfg_attr(not(feature = "gui"), allow(dead_code))]
+
+/// greatest niceness, the lowest priority
+pub const MAX_NICE: i32 = 19;
+
+/// processors a thread can be bound to, those of the system's cpu_set_t
+pub const MAX_CPUS: usize = 1024;
+
+// priority and processors of a thread, left as the system has them unless given
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Priority {
+    /// niceness from 0 to MAX_NICE, which only ever lowers the priority so that no
+    /// privileges are needed
+    pub nice: Option<i32>,
+    /// processors the thread may run on
+    pub cpus: Option<Vec<usize>>,
+}
+
+impl Priority {
+    /// set the niceness from its text, bounded to 0 to MAX_NICE
+    pub fn set_nice(&mut self, value: &str) -> Result<(), String> {
+        let nice = value
+            .parse()
+            .ok()
+            .filter(|nice| (0..=MAX_NICE).contains(nice))
+            .ok_or_else(|| format!("invalid niceness '{}', expected 0 to {}", value, MAX_NICE))?;
+        self.nice = Some(nice);
+        Ok(())
+    }
+
+    /// set the processors from a list of them and ranges of them like 0,2-3
+    pub fn set_cpus(&mut self, value: &str) -> Result<(), String> {
+        let error = || format!("invalid processors '{}', expected a list like 0,2-3", value);
+        let mut cpus = Vec::new();
+        for part in value.split(',') {
+            let (first, last) = part.trim().split_once('-').unwrap_or((part, part));
+            let (first, last): (usize, usize) = first
+                .trim()
+                .parse()
+                .ok()
+                .zip(last.trim().parse().ok())
+                .filter(|&(first, last)| first <= last && last < MAX_CPUS)
+                .ok_or_else(error)?;
+            cpus.extend(first..=last);
+        }
+        self.cpus = Some(cpus);
+        Ok(())
+    }
+
+    /// apply to the calling thread
+    #[cfg(target_os = "linux")]
+    pub fn apply(&self) -> Result<(), String> {
+        if let Some(nice) = self.nice {
+            // on Linux the niceness of a thread id is that of the thread alone
+            let thread = unsafe { libc::syscall(libc::SYS_gettid) } as libc::id_t;
+            if unsafe { libc::setpriority(libc::PRIO_PROCESS, thread, nice) } != 0 {
+                let reason = std::io::Error::last_os_error();
+                return Err(format!("couldn't set the niceness to {}: {}", nice, reason));
+            }
+        }
+        if let Some(cpus) = &self.cpus {
+            let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+            cpus.iter()
+                .for_each(|&cpu| unsafe { libc::CPU_SET(cpu, &mut set) });
+            let size = std::mem::size_of::<libc::cpu_set_t>();
+            if unsafe { libc::sched_setaffinity(0, size, &set) } != 0 {
+                let reason = std::io::Error::last_os_error();
+                return Err(format!(
+                    "couldn't bind to processors {:?}: {}",
+                    cpus, reason
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// apply to the calling thread
+    #[cfg(not(target_os = "linux"))]
+    pub fn apply(&self) -> Result<(), String> {
+        match self.nice.is_some() || self.cpus.is_some() {
+            true => Err("thread priority and affinity can only be set on Linux".to_string()),
+            false => Ok(()),
+        }
+    }
+}
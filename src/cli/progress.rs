@@ -0,0 +1,84 @@
+// status line of long headless runs, written to stderr
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+// time between status lines
+const INTERVAL: Duration = Duration::from_millis(500);
+
+// width of the bar drawn on terminals
+const BAR_WIDTH: usize = 30;
+
+// progress towards a target generation
+pub struct Progress {
+    target: u64,
+    started: Instant,
+    printed: Instant,
+    enabled: bool,
+    terminal: bool,
+}
+
+impl Progress {
+    /// start reporting, nothing is written unless enabled
+    pub fn new(target: u64, enabled: bool) -> Self {
+        let now = Instant::now();
+        Self {
+            target,
+            started: now,
+            printed: now,
+            enabled,
+            terminal: std::io::stderr().is_terminal(),
+        }
+    }
+
+    /// report the generation reached, at most once per interval
+    pub fn update(&mut self, generation: u64, population: usize) {
+        if !self.enabled || self.printed.elapsed() < INTERVAL {
+            return;
+        }
+        self.printed = Instant::now();
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let rate = generation as f64 / elapsed.max(1e-9);
+        let remaining = self.target.saturating_sub(generation) as f64 / rate.max(1e-9);
+        let status = format!(
+            "generation {}/{} ({:.0} gens/s, ETA {}), population {}",
+            generation,
+            self.target,
+            rate,
+            duration(remaining),
+            population
+        );
+        match self.terminal {
+            // redraw a single line with a bar in front
+            true => {
+                let done = (generation as f64 / self.target.max(1) as f64).min(1.0);
+                let filled = (done * BAR_WIDTH as f64) as usize;
+                eprint!(
+                    "\r[{}{}] {}\x1b[K",
+                    "#".repeat(filled),
+                    "-".repeat(BAR_WIDTH - filled),
+                    status
+                );
+                let _ = std::io::stderr().flush();
+            }
+            false => eprintln!("{}", status),
+        }
+    }
+
+    /// end the status line so that later output starts on a fresh line
+    pub fn finish(&self) {
+        if self.enabled && self.terminal && self.printed != self.started {
+            eprintln!();
+        }
+    }
+}
+
+// seconds as h:mm:ss
+fn duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
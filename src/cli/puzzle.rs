@@ -0,0 +1,121 @@
+// puzzles: listing the built-in ones, writing out a puzzle to solve, checking solutions and
+// giving hints
+use super::flag_value;
+use rlife::formats;
+use rlife::puzzle::{self, Puzzle};
+use rlife::Life;
+use std::error::Error;
+
+// list, show or check puzzles, depending on the first argument
+pub fn puzzle(args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args.split_first() {
+        Some((command, rest)) if command == "list" && rest.is_empty() => {
+            for puzzle in puzzle::builtin() {
+                println!("{}: {}, {}", puzzle.name, puzzle.description, puzzle.goal);
+            }
+            Ok(())
+        }
+        Some((command, rest)) if command == "show" => show(rest),
+        Some((command, rest)) if command == "check" => check(rest),
+        Some((command, rest)) if command == "hint" => hint(rest),
+        _ => Err("puzzle requires list, show, check or hint".into()),
+    }
+}
+
+// built-in puzzle of a name, otherwise a puzzle file
+fn find(name: &str) -> Result<Puzzle, Box<dyn Error>> {
+    match puzzle::find_builtin(name) {
+        Some(puzzle) => Ok(puzzle),
+        None => Ok(Puzzle::parse(&std::fs::read_to_string(name)?)?),
+    }
+}
+
+// describe a puzzle, saving it with --output as a pattern to be edited into a solution
+fn show(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut name, mut output) = (None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ if name.is_none() => name = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let puzzle = find(name.ok_or("puzzle show requires a puzzle name or file")?)?;
+    let region = puzzle.region;
+    println!("puzzle: {}", puzzle.name);
+    println!("{}", puzzle.description);
+    println!("board: {}x{}", puzzle.grid.width(), puzzle.grid.height());
+    println!(
+        "editable region: {}x{} at ({}, {})",
+        region.width, region.height, region.x, region.y
+    );
+    println!("cell budget: {}", puzzle.budget);
+    println!("goal: {}", puzzle.goal);
+    if let Some(output) = output {
+        std::fs::write(&output, puzzle.to_string())?;
+        println!("saved: {}", output);
+    }
+    Ok(())
+}
+
+// check a pattern file against a puzzle, failing unless it solves it
+fn check(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (name, solution) = match args {
+        [name, solution] => (name, solution),
+        _ => return Err("puzzle check requires a puzzle and a solution file".into()),
+    };
+    let puzzle = find(name)?;
+    let board = formats::load(solution)?.grid;
+    match puzzle.check(&board)? {
+        Some(generation) => {
+            println!("solved at generation {}", generation);
+            Ok(())
+        }
+        None => Err(format!("not solved within {} generations", puzzle.goal.limit()).into()),
+    }
+}
+
+// search for a solution, printing the first cell to change or with --all every one of them,
+// saving the solved board with --output
+fn hint(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut name, mut beam, mut all, mut output) = (None, puzzle::DEFAULT_BEAM, false, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--beam" => beam = flag_value(arg, args.next())?,
+            "--all" => all = true,
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ if name.is_none() => name = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let puzzle = find(name.ok_or("puzzle hint requires a puzzle name or file")?)?;
+    let changes = puzzle
+        .solve(beam)
+        .ok_or("no solution found, a wider --beam searches further")?;
+    let shown = match all {
+        true => &changes[..],
+        false => &changes[..changes.len().min(1)],
+    };
+    for &(x, y) in shown {
+        let change = match puzzle.grid.is_alive(x, y) {
+            true => "clear",
+            false => "set",
+        };
+        println!("{} cell {},{}", change, x, y);
+    }
+    if !all && changes.len() > 1 {
+        println!("{} more cells to change after that", changes.len() - 1);
+    }
+    if let Some(output) = output {
+        let mut grid = puzzle.grid.clone();
+        for &(x, y) in &changes {
+            grid.set_cell(x, y, !grid.is_alive(x, y));
+        }
+        let rule = Some(rlife::DEFAULT_RULE.to_string());
+        formats::save(&output, &formats::Pattern { grid, rule })?;
+        println!("saved: {}", output);
+    }
+    Ok(())
+}
@@ -0,0 +1,820 @@
+// headless simulation of a pattern file or random soup
+use super::announce::Announcer;
+use super::control::{self, Command, Request};
+use super::events::EventLog;
+use super::interrupt;
+use super::manifest::{self, Manifest};
+use super::plugin::Plugin;
+use super::progress::Progress;
+use super::snapshot::{Snapshots, Trigger};
+use super::{flag_value, size_value};
+use log::warn;
+use rlife::cycle::CycleDetector;
+use rlife::formats::{self, Pattern};
+use rlife::invariants::{self, Violation};
+use rlife::obstacles::Obstacles;
+use rlife::ownership::{Ownership, NO_OWNER};
+use rlife::random::Rng;
+use rlife::report::{Table, Value};
+use rlife::rule::Rule;
+use rlife::series::TimeSeries;
+use rlife::sonify::Score;
+use rlife::spectrum::Spectrum;
+use rlife::zones::Zones;
+use rlife::{stats, Emitter, Life, RuleLife, Simulation, VecGrid};
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+// size of random soups unless --size is given
+const DEFAULT_SOUP_SIZE: (usize, usize) = (64, 64);
+
+// generations between milestone events unless --log-every is given
+const DEFAULT_LOG_EVERY: u64 = 100;
+
+// time between checks for Ctrl+C while paused
+const PAUSE_POLL: Duration = Duration::from_millis(100);
+
+// side of the squares of cells whose arrangements give the block entropy of events
+const ENTROPY_BLOCK: usize = 3;
+
+// most samples kept by --series unless --series-points is given
+const DEFAULT_SERIES_POINTS: usize = 1024;
+
+// generations between spectra saved by --spectrum unless --spectrum-every is given
+const DEFAULT_SPECTRUM_EVERY: u64 = 100;
+
+// simulate a pattern for a number of generations without the GUI
+pub fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut manifest = Manifest {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        pattern: None,
+        seed: Rng::from_time().next_u64(),
+        density: 0.35,
+        rule: rlife::DEFAULT_RULE.to_string(),
+        size: None,
+        generations: 100,
+        backend: manifest::BACKEND.to_string(),
+        threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        verify: false,
+        until_settled: false,
+        emitters: Vec::new(),
+        plugins: Vec::new(),
+        zones: Vec::new(),
+        obstacles: None,
+    };
+    let mut path = None;
+    let mut output = None;
+    let mut manifest_path = None;
+    let mut log = EventLog::default();
+    let mut log_every = DEFAULT_LOG_EVERY;
+    let mut quiet = false;
+    let mut paranoid = false;
+    let (mut announce, mut speak) = (false, None);
+    let mut stdin = false;
+    let mut socket = None;
+    let mut snapshots = Snapshots::default();
+    let mut series_path = None;
+    let mut sonify_path = None;
+    let mut series_points = DEFAULT_SERIES_POINTS;
+    let mut spectrum_dir = None;
+    let mut clusters_path = None;
+    let (mut spectrum_every, mut spectrum_block) = (DEFAULT_SPECTRUM_EVERY, 1);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // settings of an earlier run, options after it override them
+            "--from-manifest" => {
+                let file = flag_value::<String>(arg, args.next())?;
+                let loaded = Manifest::parse(&std::fs::read_to_string(&file)?)?;
+                if loaded.version != manifest.version {
+                    warn!(
+                        "{} was made by rlife {}, running with {}",
+                        file, loaded.version, manifest.version
+                    );
+                }
+                manifest = Manifest {
+                    version: manifest.version,
+                    ..loaded
+                };
+            }
+            "--generations" => manifest.generations = flag_value(arg, args.next())?,
+            "--size" => manifest.size = Some(size_value(arg, args.next())?),
+            "--seed" => manifest.seed = flag_value(arg, args.next())?,
+            "--density" => manifest.density = flag_value(arg, args.next())?,
+            "--threads" => manifest.threads = flag_value(arg, args.next())?,
+            "--verify" => manifest.verify = true,
+            "--until-settled" => manifest.until_settled = true,
+            "--paranoid" => paranoid = true,
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            "--manifest" => manifest_path = Some(flag_value::<String>(arg, args.next())?),
+            "--log-json" => log.json = true,
+            "--quiet" => quiet = true,
+            "--announce" => announce = true,
+            "--speak" => speak = Some(flag_value::<String>(arg, args.next())?),
+            "--stdin" => stdin = true,
+            "--socket" => socket = Some(flag_value::<String>(arg, args.next())?),
+            "--log-every" => log_every = flag_value(arg, args.next())?,
+            "--snapshot-on" => {
+                snapshots.triggers =
+                    Snapshots::parse_triggers(&flag_value::<String>(arg, args.next())?)?
+            }
+            "--snapshot-format" => {
+                snapshots.formats =
+                    Snapshots::parse_formats(&flag_value::<String>(arg, args.next())?)?
+            }
+            "--snapshot-dir" => snapshots.dir = flag_value::<String>(arg, args.next())?.into(),
+            "--series" => series_path = Some(flag_value::<String>(arg, args.next())?),
+            "--series-points" => series_points = flag_value(arg, args.next())?,
+            "--spectrum" => spectrum_dir = Some(flag_value::<PathBuf>(arg, args.next())?),
+            "--spectrum-every" => spectrum_every = flag_value(arg, args.next())?,
+            "--spectrum-block" => spectrum_block = flag_value(arg, args.next())?,
+            "--clusters" => clusters_path = Some(flag_value::<String>(arg, args.next())?),
+            "--sonify" => sonify_path = Some(flag_value::<String>(arg, args.next())?),
+            "--emit" => manifest.emitters.push(flag_value(arg, args.next())?),
+            "--plugin" => manifest.plugins.push(flag_value(arg, args.next())?),
+            "--zone" => manifest.zones.push(flag_value(arg, args.next())?),
+            "--obstacles" => manifest.obstacles = Some(flag_value(arg, args.next())?),
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    if path.is_some() {
+        manifest.pattern = path;
+    }
+    if (announce || speak.is_some()) && log.json {
+        return Err("--announce and --speak can't be combined with --log-json".into());
+    }
+    if manifest.backend != manifest::BACKEND {
+        return Err(format!("unsupported backend '{}'", manifest.backend).into());
+    }
+
+    let plugins = manifest
+        .plugins
+        .iter()
+        .map(|path| Plugin::load(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    for plugin in plugins.iter().filter(|plugin| plugin.is_rule()) {
+        warn!("the rule of plugin {} is only used by sweep", plugin.name());
+    }
+
+    // the pattern file decides the rule, soups are filled from the seed by the first
+    // plugin seeding them or else at random
+    let grid = match &manifest.pattern {
+        Some(path) => {
+            let pattern = formats::load(path)?;
+            manifest.rule = pattern
+                .rule
+                .unwrap_or_else(|| rlife::DEFAULT_RULE.to_string());
+            match manifest.size {
+                Some(size) => centered(&pattern.grid, size)?,
+                None => pattern.grid,
+            }
+        }
+        None => {
+            let (width, height) = *manifest.size.get_or_insert(DEFAULT_SOUP_SIZE);
+            let mut grid = VecGrid::new(width, height);
+            match plugins.iter().find(|plugin| plugin.is_seeder()) {
+                Some(plugin) => plugin.seed(&mut grid, manifest.seed),
+                None => grid.fill_random(manifest.density, &mut Rng::new(manifest.seed)),
+            }
+            grid
+        }
+    };
+
+    let (width, height) = (grid.width(), grid.height());
+    let threads = manifest.threads;
+    let rule: Rule = manifest.rule.parse()?;
+    // zones step cells under rules of their own, the rule of the run applying elsewhere
+    let zones = match manifest.zones.is_empty() {
+        true => None,
+        false => Some(zones(&manifest.zones, &manifest.rule, width, height)?),
+    };
+    if zones.is_some() && manifest.verify {
+        return Err("--verify can't be combined with --zone".into());
+    }
+    // emitted patterns and walls don't reach the reference board, which would seem to diverge
+    if !manifest.emitters.is_empty() && manifest.verify {
+        return Err("--verify can't be combined with --emit".into());
+    }
+    if manifest.obstacles.is_some() && manifest.verify {
+        return Err("--verify can't be combined with --obstacles".into());
+    }
+    let mut simulation = Simulation::new(grid, VecGrid::new(width, height));
+    for spec in &manifest.emitters {
+        simulation.add_emitter(emitter(spec)?);
+    }
+    // walls kill the cells on them, from the start and after every generation
+    if let Some(path) = &manifest.obstacles {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("couldn't read {}: {}", path, err))?;
+        let obstacles = Obstacles::parse(&contents, width, height)
+            .map_err(|err| format!("{}: {}", path, err))?;
+        obstacles.apply(simulation.life_mut());
+        simulation.add_hook(move |life, _| obstacles.apply(life));
+    }
+    let mut reference = VecGrid::new(width, height);
+    // the other engines only follow the rule, so they can't be compared with boards that
+    // zones, emitters or walls change too
+    let compare_engines =
+        zones.is_none() && manifest.emitters.is_empty() && manifest.obstacles.is_none();
+    if paranoid {
+        if !compare_engines {
+            warn!("--paranoid doesn't compare engines on runs with zones, emitters or walls");
+        }
+        invariants::check_life(simulation.life())?;
+    }
+    // generations remembered to notice the board settling into a still life or oscillator
+    let mut cycles = CycleDetector::default();
+    cycles.observe(simulation.life(), simulation.generation());
+    let mut settled = false;
+    // best population so far along with the peak generation waiting to be saved once the
+    // population falls again, and whether live cells touched the edge last generation
+    let mut record = (simulation.population(), None);
+    let mut touching = touches_edge(simulation.life());
+    let mut series = series_path.as_ref().map(|_| TimeSeries::new(series_points));
+    if let Some(series) = series.as_mut() {
+        series.push(0, simulation.population(), 0);
+    }
+    let mut score = sonify_path
+        .as_ref()
+        .map(|_| Score::new(super::sonify::GENERATION_MILLIS));
+    if let Some(score) = score.as_mut() {
+        score.record(None::<&VecGrid>, simulation.life());
+    }
+    let mut clusters = clusters_path
+        .as_ref()
+        .map(|_| Table::new(&["generation", "size", "clusters"]));
+    if let Some(table) = clusters.as_mut() {
+        cluster_rows(table, 0, simulation.life());
+    }
+    if let Some(dir) = &spectrum_dir {
+        std::fs::create_dir_all(dir)?;
+        let spectrum = Spectrum::new(simulation.life(), spectrum_block);
+        super::spectrum::save(dir, 0, &spectrum)?;
+    }
+    let started = Instant::now();
+    let mut progress = Progress::new(manifest.generations, !quiet);
+    census(&log, &simulation);
+    interrupt::install();
+
+    // commands from stdin and the control socket share one channel
+    let (request_tx, request_rx) = mpsc::channel();
+    if stdin {
+        control::stdin(request_tx.clone());
+    }
+    #[cfg(unix)]
+    let _socket = match &socket {
+        Some(path) => Some(
+            control::socket(path, request_tx.clone())
+                .map_err(|err| format!("couldn't listen on {}: {}", path, err))?,
+        ),
+        None => None,
+    };
+    #[cfg(not(unix))]
+    if socket.is_some() {
+        return Err("--socket requires a Unix system".into());
+    }
+    drop(request_tx);
+    let mut requests = (stdin || socket.is_some()).then_some(request_rx);
+    // cells set by clients of the control socket belong to them, along with their offspring
+    let mut ownership = socket.as_ref().map(|_| Ownership::new(width, height));
+    let mut controls = Controls::default();
+    let mut announcer = Announcer::new(announce, speak);
+    while simulation.generation() < manifest.generations {
+        // stop early on Ctrl+C, still writing the state reached so far
+        if interrupt::interrupted() {
+            progress.finish();
+            eprintln!(
+                "interrupted at generation {}, saving, press Ctrl+C again to exit immediately",
+                simulation.generation()
+            );
+            log.emit(
+                "interrupted",
+                &[("generation", (simulation.generation() as i64).into())],
+            );
+            announcer.action(format!(
+                "interrupted at generation {}",
+                simulation.generation()
+            ));
+            break;
+        }
+
+        // commands arrive at any time, a paused run waits for them,
+        // the error tells whether every source of commands has ended
+        let request = match &requests {
+            Some(requests) if controls.paused && controls.steps == 0 => requests
+                .recv_timeout(PAUSE_POLL)
+                .map_err(|err| err == mpsc::RecvTimeoutError::Disconnected),
+            Some(requests) => requests
+                .try_recv()
+                .map_err(|err| err == mpsc::TryRecvError::Disconnected),
+            None => Err(false),
+        };
+        match request {
+            Ok(mut request) => {
+                let edited = apply(
+                    &mut request,
+                    &mut controls,
+                    &mut simulation,
+                    ownership.as_mut(),
+                    &manifest.rule,
+                );
+                if let Some(action) = announcement(&request.command, simulation.generation()) {
+                    announcer.action(action);
+                }
+                if edited {
+                    // the edited board may settle again differently
+                    settled = false;
+                    cycles.clear();
+                }
+                if controls.quit {
+                    log.emit(
+                        "quit",
+                        &[("generation", (simulation.generation() as i64).into())],
+                    );
+                    break;
+                }
+            }
+            // a paused run can't be resumed once the input ends, so it resumes by itself
+            Err(true) => {
+                requests = None;
+                controls.paused = false;
+            }
+            Err(false) => (),
+        }
+        if controls.paused && controls.steps == 0 {
+            let population = simulation.population();
+            announcer.status(simulation.generation(), population, true);
+            continue;
+        }
+        if controls.paused {
+            controls.steps -= 1;
+            if controls.steps == 0 {
+                eprintln!("paused at generation {}", simulation.generation() + 1);
+            }
+        }
+
+        // the naive serial engine is the reference for the parallel one
+        if manifest.verify {
+            RuleLife::new(simulation.life().clone(), rule).next_generation(&mut reference);
+        }
+        match &zones {
+            Some(zones) => {
+                simulation.step_with(|current, next| zones.next_generation(current, next))
+            }
+            None => simulation.par_step_with(rule, threads),
+        }
+        if let (Some(ownership), Some(previous)) = (ownership.as_mut(), simulation.previous()) {
+            ownership.advance(previous, simulation.life());
+        }
+        if manifest.verify && *simulation.life() != reference {
+            return Err(format!(
+                "parallel step diverged from the serial engine at generation {}",
+                simulation.generation()
+            )
+            .into());
+        }
+        if paranoid {
+            check_invariants(&simulation, rule, ownership.as_ref(), compare_engines)
+                .map_err(|err| format!("generation {}: {}", simulation.generation(), err))?;
+        }
+
+        let generation = simulation.generation();
+        let population = simulation.population();
+        if let Some(series) = series.as_mut() {
+            series.push(
+                generation,
+                population,
+                simulation.births() + simulation.deaths(),
+            );
+        }
+        if let Some(score) = score.as_mut() {
+            score.record(simulation.previous(), simulation.life());
+        }
+        for (i, emitter) in simulation.emitters().iter().enumerate() {
+            if emitter.is_due(generation) {
+                // the board may settle again differently after being disturbed
+                settled = false;
+                cycles.clear();
+                log.emit(
+                    "perturbation",
+                    &[
+                        ("generation", (generation as i64).into()),
+                        ("emitter", i.into()),
+                        ("x", emitter.x.into()),
+                        ("y", emitter.y.into()),
+                    ],
+                );
+            }
+        }
+        for plugin in &plugins {
+            if let Some(report) = plugin.analyze(generation, simulation.life()) {
+                log.emit(
+                    "plugin",
+                    &[
+                        ("generation", (generation as i64).into()),
+                        ("plugin", plugin.name().into()),
+                        ("report", report.into()),
+                    ],
+                );
+            }
+        }
+        if let Some(dir) = &spectrum_dir {
+            if spectrum_every != 0 && generation.is_multiple_of(spectrum_every) {
+                let spectrum = Spectrum::new(simulation.life(), spectrum_block);
+                super::spectrum::save(dir, generation, &spectrum)?;
+            }
+        }
+        let sampled = log_every != 0 && generation.is_multiple_of(log_every);
+        if let (Some(table), true) = (clusters.as_mut(), sampled) {
+            cluster_rows(table, generation, simulation.life());
+        }
+        if log.json && sampled {
+            let rate = generation as f64 / started.elapsed().as_secs_f64().max(1e-9);
+            let life = simulation.life();
+            let clusters = stats::clusters(life);
+            log.emit(
+                "milestone",
+                &[
+                    ("generation", (generation as i64).into()),
+                    ("population", population.into()),
+                    ("generations_per_second", rate.into()),
+                    ("compressed_size", stats::compressed_size(life).into()),
+                    (
+                        "block_entropy",
+                        stats::block_entropy(life, ENTROPY_BLOCK).into(),
+                    ),
+                    ("clusters", clusters.count().into()),
+                    ("largest_cluster", clusters.largest.into()),
+                    ("largest_cluster_fraction", clusters.largest_fraction.into()),
+                ],
+            );
+        }
+        // report the first time the board dies out or repeats a recent state
+        let cycle = cycles.observe(simulation.life(), generation);
+        if !settled {
+            if population == 0 {
+                settled = true;
+                log.emit("extinct", &[("generation", (generation as i64).into())]);
+                announcer.action(format!("extinct at generation {}", generation));
+            } else if let Some(cycle) = cycle {
+                settled = true;
+                log.emit(
+                    "stabilized",
+                    &[
+                        ("generation", (generation as i64).into()),
+                        ("period", (cycle.period as i64).into()),
+                        ("start", (cycle.start as i64).into()),
+                    ],
+                );
+                announcer.action(format!(
+                    "settled with period {} at generation {}",
+                    cycle.period, cycle.start
+                ));
+                let trigger = match cycle.is_still_life() {
+                    true => Trigger::Stabilized,
+                    false => Trigger::Cycle,
+                };
+                snapshot(
+                    &snapshots,
+                    &log,
+                    trigger,
+                    generation,
+                    simulation.life(),
+                    &manifest.rule,
+                )?;
+            }
+        }
+        if snapshots.wants(Trigger::Record) {
+            if population > record.0 {
+                record = (population, Some((generation, simulation.life().clone())));
+            } else if population < record.0 {
+                if let Some((peak, grid)) = record.1.take() {
+                    snapshot(
+                        &snapshots,
+                        &log,
+                        Trigger::Record,
+                        peak,
+                        &grid,
+                        &manifest.rule,
+                    )?;
+                }
+            }
+        }
+        let touches = touches_edge(simulation.life());
+        if touches && !touching {
+            log.emit("boundary", &[("generation", (generation as i64).into())]);
+            announcer.action(format!("reached the edge at generation {}", generation));
+            snapshot(
+                &snapshots,
+                &log,
+                Trigger::Boundary,
+                generation,
+                simulation.life(),
+                &manifest.rule,
+            )?;
+        }
+        touching = touches;
+        progress.update(generation, population);
+        announcer.status(generation, population, controls.paused);
+        if settled && manifest.until_settled {
+            break;
+        }
+    }
+    progress.finish();
+    if let Some((peak, grid)) = record.1.take() {
+        snapshot(
+            &snapshots,
+            &log,
+            Trigger::Record,
+            peak,
+            &grid,
+            &manifest.rule,
+        )?;
+    }
+    census(&log, &simulation);
+    if let (Some(path), Some(series)) = (&series_path, &series) {
+        super::series::save(path, series)?;
+    }
+    if let (Some(path), Some(table)) = (&clusters_path, &clusters) {
+        std::fs::write(path, table.to_csv())?;
+    }
+    if let (Some(path), Some(score)) = (&sonify_path, &score) {
+        super::sonify::save(path, score)?;
+    }
+    if !log.json {
+        println!(
+            "generation {}: population {}",
+            simulation.generation(),
+            simulation.population()
+        );
+    }
+
+    // the manifest goes next to the output unless it is given a path of its own,
+    // recording the generations actually reached so that it repeats this output
+    let interrupted = interrupt::interrupted() && !controls.quit;
+    manifest.generations = simulation.generation();
+    let generation = simulation.generation();
+    if let Some(path) = manifest_path.or_else(|| output.as_ref().map(|o| format!("{}.manifest", o)))
+    {
+        std::fs::write(path, manifest.to_string())?;
+    }
+    if let Some(output) = output {
+        let pattern = Pattern {
+            grid: simulation.into_life(),
+            rule: Some(manifest.rule),
+        };
+        formats::save(output, &pattern)?;
+    }
+    if interrupted {
+        return Err(format!("interrupted at generation {}", generation).into());
+    }
+    Ok(())
+}
+
+// state of a run changed by commands
+#[derive(Default)]
+struct Controls {
+    /// no generations are calculated while paused, except for those asked for by step
+    paused: bool,
+    steps: u64,
+    quit: bool,
+}
+
+// carry out a command and reply to it, returns whether the board was edited
+fn apply(
+    request: &mut Request,
+    controls: &mut Controls,
+    simulation: &mut Simulation<VecGrid>,
+    ownership: Option<&mut Ownership>,
+    rule: &str,
+) -> bool {
+    let generation = simulation.generation();
+    let reply = match &request.command {
+        Command::Pause => {
+            controls.paused = true;
+            format!("paused at generation {}", generation)
+        }
+        Command::Resume => {
+            (controls.paused, controls.steps) = (false, 0);
+            format!("resumed at generation {}", generation)
+        }
+        &Command::Step(generations) => {
+            (controls.paused, controls.steps) = (true, generations);
+            format!(
+                "stepping {} generations from generation {}",
+                generations, generation
+            )
+        }
+        Command::Dump(path) => {
+            let pattern = Pattern {
+                grid: simulation.life().clone(),
+                rule: Some(rule.to_string()),
+            };
+            match formats::save(path, &pattern) {
+                Ok(()) => format!("saved generation {} to {}", generation, path),
+                Err(err) => format!("couldn't save {}: {}", path, err),
+            }
+        }
+        Command::Status => format!(
+            "generation {}: population {}{}",
+            generation,
+            simulation.population(),
+            if controls.paused { ", paused" } else { "" }
+        ),
+        Command::Quit => {
+            controls.quit = true;
+            format!("quitting at generation {}", generation)
+        }
+        &Command::Set(x, y) | &Command::Clear(x, y) => {
+            let is_alive = matches!(request.command, Command::Set(..));
+            let (width, height) = (simulation.life().width(), simulation.life().height());
+            if x >= width || y >= height {
+                request.reply(format!("cell {},{} is outside of the board", x, y));
+                return false;
+            }
+            simulation.life_mut().set_cell(x, y, is_alive);
+            let owner = if is_alive { request.client } else { NO_OWNER };
+            if let Some(ownership) = ownership {
+                ownership.set(x, y, owner);
+            }
+            request.reply(format!(
+                "cell {},{} {} by client {} at generation {}",
+                x,
+                y,
+                if is_alive { "set" } else { "cleared" },
+                request.client,
+                generation
+            ));
+            return true;
+        }
+        Command::Scores => {
+            let scores: Vec<String> = ownership
+                .map(|ownership| ownership.populations(simulation.life()))
+                .unwrap_or_default()
+                .iter()
+                .map(|(client, population)| format!("{}:{}", client, population))
+                .collect();
+            format!("scores at generation {}: {}", generation, scores.join(" "))
+        }
+        Command::Snapshot => {
+            request.snapshot(generation, simulation.life(), ownership.as_deref());
+            return false;
+        }
+    };
+    request.reply(reply);
+    false
+}
+
+// sentence announcing a command that changed the run, None for queries
+fn announcement(command: &Command, generation: u64) -> Option<String> {
+    Some(match command {
+        Command::Pause => format!("paused at generation {}", generation),
+        Command::Resume => format!("resumed at generation {}", generation),
+        Command::Step(generations) => format!("stepping {} generations", generations),
+        Command::Dump(path) => format!("saved generation {} to {}", generation, path),
+        Command::Quit => format!("quitting at generation {}", generation),
+        Command::Set(x, y) => format!("cell {} {} set", x, y),
+        Command::Clear(x, y) => format!("cell {} {} cleared", x, y),
+        Command::Status | Command::Scores | Command::Snapshot => return None,
+    })
+}
+
+// save a snapshot when the trigger is wanted, logging the files written
+fn snapshot(
+    snapshots: &Snapshots,
+    log: &EventLog,
+    trigger: Trigger,
+    generation: u64,
+    grid: &VecGrid,
+    rule: &str,
+) -> Result<(), Box<dyn Error>> {
+    if !snapshots.wants(trigger) {
+        return Ok(());
+    }
+    for path in snapshots.save(trigger, generation, grid, rule)? {
+        log.emit(
+            "snapshot",
+            &[
+                ("generation", (generation as i64).into()),
+                ("trigger", trigger.name().into()),
+                ("path", path.display().to_string().into()),
+            ],
+        );
+    }
+    Ok(())
+}
+
+// whether any live cell lies on the edge of the board
+fn touches_edge(life: &VecGrid) -> bool {
+    stats::bounding_box(life).is_some_and(|b| {
+        b.x == 0 || b.y == 0 || b.x + b.width == life.width() || b.y + b.height == life.height()
+    })
+}
+
+// invariants of the generation just calculated checked by --paranoid
+fn check_invariants(
+    simulation: &Simulation<VecGrid>,
+    rule: Rule,
+    ownership: Option<&Ownership>,
+    compare_engines: bool,
+) -> Result<(), Violation> {
+    invariants::check_life(simulation.life())?;
+    if let (true, Some(previous)) = (compare_engines, simulation.previous()) {
+        invariants::check_step(&RuleLife::new(previous.clone(), rule), simulation.life())?;
+    }
+    match ownership {
+        Some(ownership) => invariants::check_ownership(ownership, simulation.life()),
+        None => Ok(()),
+    }
+}
+
+// log population, extent, complexity, clusters, the changes coming next and the memory taken
+// by the grids of a generation
+fn census(log: &EventLog, simulation: &Simulation<VecGrid>) {
+    let (generation, life) = (simulation.generation(), simulation.life());
+    let histogram = stats::neighbor_histogram(life);
+    let (width, height) = stats::bounding_box(life).map_or((0, 0), |b| (b.width, b.height));
+    let clusters = stats::clusters(life);
+    let fields: [(&str, Value); 12] = [
+        ("generation", (generation as i64).into()),
+        ("population", stats::population(life).into()),
+        ("bounding_width", width.into()),
+        ("bounding_height", height.into()),
+        ("births", histogram.births.into()),
+        ("deaths", histogram.deaths.into()),
+        ("compressed_size", stats::compressed_size(life).into()),
+        (
+            "block_entropy",
+            stats::block_entropy(life, ENTROPY_BLOCK).into(),
+        ),
+        ("clusters", clusters.count().into()),
+        ("largest_cluster", clusters.largest.into()),
+        ("largest_cluster_fraction", clusters.largest_fraction.into()),
+        ("memory", simulation.memory_usage().into()),
+    ];
+    log.emit("census", &fields);
+}
+
+// add the number of clusters of every size of a generation to the --clusters table
+fn cluster_rows(table: &mut Table, generation: u64, life: &VecGrid) {
+    for (size, count) in stats::clusters(life).histogram {
+        table.push(vec![(generation as i64).into(), size.into(), count.into()]);
+    }
+}
+
+// grid of the given size with the pattern in its center
+fn centered(pattern: &VecGrid, (width, height): (usize, usize)) -> Result<VecGrid, Box<dyn Error>> {
+    if width < pattern.width() || height < pattern.height() {
+        return Err("--size is smaller than the pattern".into());
+    }
+    let (dx, dy) = (
+        (width - pattern.width()) / 2,
+        (height - pattern.height()) / 2,
+    );
+    let mut grid = VecGrid::new(width, height);
+    stats::live_cells(pattern).for_each(|(x, y)| grid.set_cell(x + dx, y + dy, true));
+    Ok(grid)
+}
+
+// zones of a board described by X,Y,W,H,RULE for rectangles or FILE,RULE for the live
+// cells of a mask pattern, later ones covering earlier ones
+fn zones(
+    specs: &[String],
+    rule: &str,
+    width: usize,
+    height: usize,
+) -> Result<Zones, Box<dyn Error>> {
+    let mut zones = Zones::new(width, height, rule.parse()?);
+    for spec in specs {
+        let fields: Vec<&str> = spec.split(',').collect();
+        match fields.as_slice() {
+            [x, y, w, h, rule] => zones.add_rect(
+                x.parse()?,
+                y.parse()?,
+                w.parse()?,
+                h.parse()?,
+                rule.parse::<Rule>()?,
+            ),
+            [path, rule] => zones.add_mask(&formats::load(path)?.grid, rule.parse()?),
+            _ => return Err("--zone requires X,Y,W,H,RULE or FILE,RULE".into()),
+        }
+    }
+    Ok(zones)
+}
+
+// emitter described by X,Y,PERIOD,FILE
+fn emitter(spec: &str) -> Result<Emitter, Box<dyn Error>> {
+    let fields: Vec<&str> = spec.splitn(4, ',').collect();
+    match fields.as_slice() {
+        [x, y, period, path] => Ok(Emitter::new(
+            formats::load(path)?.grid,
+            x.parse()?,
+            y.parse()?,
+            period.parse()?,
+        )),
+        _ => Err("--emit requires X,Y,PERIOD,FILE".into()),
+    }
+}
@@ -0,0 +1,136 @@
+// scans running an ensemble at every point of a grid of one or two parameters, resumable
+use super::{flag_value, size_value};
+use rlife::ensemble::{Curve, Ensemble};
+use rlife::report::Table;
+use rlife::rule::RuleLife;
+use rlife::scan::{self, Axis, Settings};
+use rlife::{Life, VecGrid};
+use std::error::Error;
+use std::io::{IsTerminal, Write};
+
+// runs at every point unless --runs is given
+const DEFAULT_RUNS: usize = 32;
+
+// run an ensemble of soups at every point of the axes given with --vary, writing a tidy CSV
+// row for each point as it is done, the values of the axes first and then the population of
+// the runs at the last generation, a point already in the --output file being skipped so
+// that an interrupted scan picks up where it stopped
+pub fn scan(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut base = Settings::default();
+    let mut axes: Vec<Axis> = Vec::new();
+    let mut ensemble = Ensemble {
+        runs: DEFAULT_RUNS,
+        generations: 500,
+        seed: 1,
+        threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--vary" => axes.push(flag_value(arg, args.next())?),
+            "--size" => (base.width, base.height) = size_value(arg, args.next())?,
+            "--density" => base.density = flag_value(arg, args.next())?,
+            "--rule" => base.rule = flag_value(arg, args.next())?,
+            "--runs" => ensemble.runs = flag_value(arg, args.next())?,
+            "--seed" => ensemble.seed = flag_value(arg, args.next())?,
+            "--generations" => ensemble.generations = flag_value(arg, args.next())?,
+            "--threads" => ensemble.threads = flag_value(arg, args.next())?,
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    if !(1..=2).contains(&axes.len()) {
+        return Err("scan requires one or two --vary axes".into());
+    }
+    if axes.len() == 2 && axes[0].parameter == axes[1].parameter {
+        return Err(format!("{} is varied twice", axes[0].parameter.name()).into());
+    }
+
+    let columns: Vec<&str> = axes
+        .iter()
+        .map(|axis| axis.parameter.name())
+        .chain([
+            "runs",
+            "mean_population",
+            "variance",
+            "mean_density",
+            "surviving",
+        ])
+        .collect();
+    let header = Table::new(&columns).to_csv();
+
+    // points already in the output file, by the values of their axes
+    let mut done = Vec::new();
+    let mut file: Box<dyn Write> = match &output {
+        Some(path) => {
+            match std::fs::read_to_string(path) {
+                Ok(text) if !text.is_empty() => {
+                    let mut lines = text.lines();
+                    if lines.next() != header.lines().next() {
+                        return Err(format!("{} is the output of another scan", path).into());
+                    }
+                    for line in lines.filter(|line| !line.trim().is_empty()) {
+                        let values: Vec<&str> = line.split(',').collect();
+                        done.push(values[..axes.len().min(values.len())].join(","));
+                    }
+                }
+                Ok(_) => std::fs::write(path, &header)?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    std::fs::write(path, &header)?
+                }
+                Err(e) => return Err(e.into()),
+            }
+            Box::new(std::fs::OpenOptions::new().append(true).open(path)?)
+        }
+        None => {
+            print!("{}", header);
+            Box::new(std::io::stdout())
+        }
+    };
+
+    let points: Vec<_> = scan::points(&axes, base)
+        .into_iter()
+        .filter(|(values, _)| !done.contains(&values.join(",")))
+        .collect();
+    let total = points.len() + done.len();
+    if !done.is_empty() {
+        eprintln!("resuming with {} of {} points done", done.len(), total);
+    }
+    let terminal = std::io::stderr().is_terminal();
+    for (i, (values, settings)) in points.into_iter().enumerate() {
+        let populations = ensemble.populations(|rng| {
+            let mut soup = VecGrid::new(settings.width, settings.height);
+            soup.fill_random(settings.density, rng);
+            RuleLife::new(soup, settings.rule)
+        });
+        let curve = Curve::new(&populations);
+        let last = |values: &[f64]| values.last().copied().unwrap_or(0.0);
+        let cells = (settings.width * settings.height).max(1) as f64;
+        let mut table = Table::new(&columns);
+        table.push(
+            values
+                .into_iter()
+                .map(Into::into)
+                .chain([
+                    ensemble.runs.into(),
+                    last(&curve.mean).into(),
+                    last(&curve.variance).into(),
+                    (last(&curve.mean) / cells).into(),
+                    last(&curve.surviving).into(),
+                ])
+                .collect(),
+        );
+        // written and flushed point by point, so that nothing done is lost to an interrupt
+        let row = table.to_csv();
+        file.write_all(&row.as_bytes()[header.len()..])?;
+        file.flush()?;
+        if terminal {
+            eprint!("\r{} of {} points done", done.len() + i + 1, total);
+        }
+    }
+    if terminal {
+        eprintln!();
+    }
+    Ok(())
+}
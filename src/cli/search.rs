@@ -0,0 +1,197 @@
+// search for oscillators and ships of a period fitting in a box, resumable from a checkpoint
+use super::{flag_value, size_value};
+use rlife::formats::rle;
+use rlife::report::Table;
+use rlife::search::{self, Search};
+use rlife::{compact, stats, Life, Rule, VecGrid};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+
+// choices splitting the search into subtrees unless --split is given
+const DEFAULT_SPLIT: usize = 6;
+
+// search a box for patterns of a period and displacement on one thread per core, writing a
+// table of them once up to phase and symmetry, the subtrees searched and the patterns found
+// so far are appended to the checkpoint file, where a later search with the same options
+// picks up
+pub fn search(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut size = None;
+    let mut period = None;
+    let mut ship = (0, 0);
+    let mut rule = Rule::default();
+    let mut threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut split = DEFAULT_SPLIT;
+    let mut checkpoint = None;
+    let mut json = false;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--period" => period = Some(flag_value(arg, args.next())?),
+            "--ship" => {
+                ship = args
+                    .next()
+                    .and_then(|value| value.split_once(','))
+                    .and_then(|(dx, dy)| dx.parse().ok().zip(dy.parse().ok()))
+                    .ok_or("--ship requires DX,DY")?
+            }
+            "--rule" => rule = flag_value(arg, args.next())?,
+            "--threads" => threads = flag_value(arg, args.next())?,
+            "--split" => split = flag_value(arg, args.next())?,
+            "--checkpoint" => checkpoint = Some(flag_value::<String>(arg, args.next())?),
+            "--format" => {
+                json = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err("--format must be csv or json".into()),
+                }
+            }
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ if size.is_none() => size = Some(size_value("search", Some(arg))?),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let (width, height) = size.ok_or("search requires a box size WxH")?;
+    let period: usize = period.ok_or("search requires --period")?;
+    if period == 0 {
+        return Err("--period must be at least 1".into());
+    }
+    if rule.is_born(0) {
+        return Err("rules with B0 can't be searched".into());
+    }
+    if split > 20 {
+        return Err("--split can be at most 20".into());
+    }
+    let search = Search {
+        dx: ship.0,
+        dy: ship.1,
+        rule,
+        ..Search::new(width, height, period)
+    };
+    let header = format!(
+        "search {}x{} period {} ship {},{} rule {} split {}",
+        width, height, period, ship.0, ship.1, rule, split
+    );
+
+    // subtrees already searched and what was found in them
+    let mut done = BTreeSet::new();
+    let mut found = BTreeSet::new();
+    let mut file = None;
+    if let Some(path) = &checkpoint {
+        match std::fs::read_to_string(path) {
+            Ok(text) => {
+                let mut lines = text.lines();
+                if lines.next() != Some(header.as_str()) {
+                    return Err(format!("{} is a checkpoint of another search", path).into());
+                }
+                for line in lines {
+                    match line.split_once(' ') {
+                        Some(("done", prefix)) => {
+                            done.insert(prefix.chars().map(|c| c == '1').collect::<Vec<_>>());
+                        }
+                        Some(("found", pattern)) => {
+                            found.insert(compact::decode(pattern)?);
+                        }
+                        _ => return Err(format!("invalid checkpoint line '{}'", line).into()),
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                std::fs::write(path, format!("{}\n", header))?
+            }
+            Err(e) => return Err(e.into()),
+        }
+        file = Some(std::fs::OpenOptions::new().append(true).open(path)?);
+    }
+
+    let prefixes: Vec<_> = Search::prefixes(split)
+        .into_iter()
+        .filter(|prefix| !done.contains(prefix))
+        .collect();
+    let total = 1usize << split;
+    if !done.is_empty() {
+        eprintln!(
+            "resuming with {} of {} subtrees searched",
+            done.len(),
+            total
+        );
+    }
+    let terminal = std::io::stderr().is_terminal();
+    let progress = Mutex::new((done.len(), found, file, None));
+    search.par_run(&prefixes, threads, |prefix, patterns| {
+        let mut progress = progress.lock().unwrap();
+        let (searched, found, file, error) = &mut *progress;
+        let mut lines = String::new();
+        for pattern in patterns {
+            let pattern = search::representative(&pattern, rule, period);
+            if !found.contains(&pattern) {
+                lines += &format!("found {}\n", compact::encode(&pattern));
+                found.insert(pattern);
+            }
+        }
+        let bits: String = prefix.iter().map(|&b| if b { '1' } else { '0' }).collect();
+        lines += &format!("done {}\n", bits);
+        if let Some(file) = file {
+            if let Err(e) = file.write_all(lines.as_bytes()) {
+                error.get_or_insert(e);
+            }
+        }
+        *searched += 1;
+        if terminal {
+            eprint!(
+                "\r{} of {} subtrees searched, {} found",
+                searched,
+                total,
+                found.len()
+            );
+        }
+    });
+    let (_, found, _, error) = progress.into_inner().unwrap();
+    if terminal {
+        eprintln!();
+    }
+    eprintln!("{} patterns found in {}x{}", found.len(), width, height);
+    if let Some(e) = error {
+        return Err(e.into());
+    }
+
+    let mut table = Table::new(&[
+        "pattern",
+        "width",
+        "height",
+        "population",
+        "period",
+        "dx",
+        "dy",
+    ]);
+    for pattern in &found {
+        table.push(vec![
+            body(pattern).into(),
+            pattern.width().into(),
+            pattern.height().into(),
+            stats::population(pattern).into(),
+            period.into(),
+            (ship.0 as i64).into(),
+            (ship.1 as i64).into(),
+        ]);
+    }
+    let report = match json {
+        true => table.to_json(),
+        false => table.to_csv(),
+    };
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+// RLE body of a pattern on one line, without the header
+fn body(pattern: &VecGrid) -> String {
+    rle::write(pattern, rlife::DEFAULT_RULE)
+        .lines()
+        .skip(1)
+        .collect()
+}
@@ -0,0 +1,137 @@
+// export of run time series as tables or as a graph of the whole run
+use rlife::report::Table;
+use rlife::series::TimeSeries;
+use std::error::Error;
+use std::fmt::Write;
+use std::path::Path;
+
+// size of the SVG graph in pixels, and the margin around the plot
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 300.0;
+const MARGIN: f64 = 40.0;
+
+/// write a series as CSV, JSON or an SVG graph depending on the extension of the path
+pub fn save(path: &str, series: &TimeSeries) -> Result<(), Box<dyn Error>> {
+    let extension = Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let contents = match extension.as_str() {
+        "csv" => table(series).to_csv(),
+        "json" => table(series).to_json(),
+        "svg" => svg(series),
+        _ => {
+            return Err(format!("--series must end in .csv, .json or .svg, not '{}'", path).into())
+        }
+    };
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+// one row per sample
+fn table(series: &TimeSeries) -> Table {
+    let mut table = Table::new(&[
+        "generation",
+        "generations",
+        "population",
+        "min_population",
+        "max_population",
+        "activity",
+    ]);
+    for sample in series.samples() {
+        table.push(vec![
+            (sample.generation as i64).into(),
+            (sample.generations as i64).into(),
+            sample.population.into(),
+            sample.min_population.into(),
+            sample.max_population.into(),
+            sample.activity.into(),
+        ]);
+    }
+    table
+}
+
+// population as a line over the range between least and greatest, activity below it
+fn svg(series: &TimeSeries) -> String {
+    let samples: Vec<_> = series.samples().collect();
+    let last = samples
+        .last()
+        .map_or(1, |s| s.generation + s.generations)
+        .max(1) as f64;
+    let top = samples
+        .iter()
+        .map(|s| (s.max_population as f64).max(s.activity))
+        .fold(1.0, f64::max);
+    let x = |generation: u64| MARGIN + generation as f64 / last * (WIDTH - 2.0 * MARGIN);
+    let y = |value: f64| HEIGHT - MARGIN - value / top * (HEIGHT - 2.0 * MARGIN);
+    let line = |value: &dyn Fn(&rlife::series::Sample) -> f64| -> String {
+        samples
+            .iter()
+            .map(|s| format!("{:.1},{:.1}", x(s.generation), y(value(s))))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = WIDTH,
+        h = HEIGHT
+    );
+    let _ = writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    // range of population within each sample, then the means
+    let band: Vec<String> = samples
+        .iter()
+        .map(|s| format!("{:.1},{:.1}", x(s.generation), y(s.max_population as f64)))
+        .chain(
+            samples
+                .iter()
+                .rev()
+                .map(|s| format!("{:.1},{:.1}", x(s.generation), y(s.min_population as f64))),
+        )
+        .collect();
+    let _ = writeln!(
+        out,
+        r##"<polygon points="{}" fill="#9ecae1" stroke="none"/>"##,
+        band.join(" ")
+    );
+    let _ = writeln!(
+        out,
+        r##"<polyline points="{}" fill="none" stroke="#08519c"/>"##,
+        line(&|s| s.population)
+    );
+    let _ = writeln!(
+        out,
+        r##"<polyline points="{}" fill="none" stroke="#e6550d"/>"##,
+        line(&|s| s.activity)
+    );
+    // axes with the extent of the run
+    let _ = writeln!(
+        out,
+        r#"<path d="M{m},{m} V{b} H{r}" fill="none" stroke="black"/>"#,
+        m = MARGIN,
+        b = HEIGHT - MARGIN,
+        r = WIDTH - MARGIN
+    );
+    let _ = writeln!(
+        out,
+        r#"<text x="{}" y="{}" font-size="12" font-family="sans-serif">{:.0}</text>"#,
+        4.0, MARGIN, top
+    );
+    let _ = writeln!(
+        out,
+        r#"<text x="{}" y="{}" font-size="12" font-family="sans-serif" text-anchor="end">generation {}</text>"#,
+        WIDTH - MARGIN,
+        HEIGHT - MARGIN / 2.0,
+        last
+    );
+    let _ = writeln!(
+        out,
+        r##"<text x="{}" y="{}" font-size="12" font-family="sans-serif"><tspan fill="#08519c">population</tspan> <tspan fill="#e6550d">activity</tspan></text>"##,
+        MARGIN,
+        MARGIN / 2.0
+    );
+    out.push_str("</svg>\n");
+    out
+}
@@ -0,0 +1,120 @@
+// snapshots of headless runs taken automatically when something interesting happens
+use rlife::formats::{self, Pattern};
+use rlife::VecGrid;
+use std::error::Error;
+use std::path::PathBuf;
+
+// side in pixels of a cell in PNG snapshots
+const PNG_SCALE: usize = 4;
+
+// event taking a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    /// the board became a still life
+    Stabilized,
+    /// the board started repeating with a period above one
+    Cycle,
+    /// the population peaked above every earlier generation, the peak is saved
+    Record,
+    /// a live cell reached the edge of the board
+    Boundary,
+}
+
+impl Trigger {
+    /// name used on the command line and in file names
+    pub fn name(self) -> &'static str {
+        match self {
+            Trigger::Stabilized => "stabilized",
+            Trigger::Cycle => "cycle",
+            Trigger::Record => "record",
+            Trigger::Boundary => "boundary",
+        }
+    }
+}
+
+// where and in which formats snapshots are saved
+pub struct Snapshots {
+    pub triggers: Vec<Trigger>,
+    /// extensions of the formats to save, rle or png
+    pub formats: Vec<String>,
+    pub dir: PathBuf,
+}
+
+impl Default for Snapshots {
+    fn default() -> Self {
+        Self {
+            triggers: Vec::new(),
+            formats: vec!["rle".to_string()],
+            dir: PathBuf::from("."),
+        }
+    }
+}
+
+impl Snapshots {
+    /// parse a comma separated list of triggers
+    pub fn parse_triggers(list: &str) -> Result<Vec<Trigger>, Box<dyn Error>> {
+        list.split(',')
+            .map(|name| match name {
+                "stabilized" => Ok(Trigger::Stabilized),
+                "cycle" => Ok(Trigger::Cycle),
+                "record" => Ok(Trigger::Record),
+                "boundary" => Ok(Trigger::Boundary),
+                _ => Err(format!(
+                    "unknown snapshot trigger '{}', expected stabilized, cycle, record or boundary",
+                    name
+                )
+                .into()),
+            })
+            .collect()
+    }
+
+    /// parse a comma separated list of formats
+    pub fn parse_formats(list: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        list.split(',')
+            .map(|format| match format {
+                "rle" | "png" => Ok(format.to_string()),
+                _ => {
+                    Err(format!("unknown snapshot format '{}', expected rle or png", format).into())
+                }
+            })
+            .collect()
+    }
+
+    /// whether snapshots are taken for a trigger
+    pub fn wants(&self, trigger: Trigger) -> bool {
+        self.triggers.contains(&trigger)
+    }
+
+    /// save the generation in every format, returning the paths written
+    pub fn save(
+        &self,
+        trigger: Trigger,
+        generation: u64,
+        grid: &VecGrid,
+        rule: &str,
+    ) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        std::fs::create_dir_all(&self.dir)?;
+        let mut paths = Vec::new();
+        for format in &self.formats {
+            let path = self.dir.join(format!(
+                "snapshot-{}-{:08}.{}",
+                trigger.name(),
+                generation,
+                format
+            ));
+            match format.as_str() {
+                // bigger cells than formats::save uses, so that small boards are visible
+                "png" => std::fs::write(&path, formats::png::write(grid, PNG_SCALE))?,
+                _ => formats::save(
+                    &path,
+                    &Pattern {
+                        grid: grid.clone(),
+                        rule: Some(rule.to_string()),
+                    },
+                )?,
+            }
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
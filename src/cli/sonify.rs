@@ -0,0 +1,25 @@
+// export of a run played as notes, as a MIDI file or synthesized audio
+use rlife::sonify::Score;
+use std::error::Error;
+use std::path::Path;
+
+/// time each generation of a run lasts when played
+pub const GENERATION_MILLIS: u32 = 125;
+
+// samples per second of synthesized audio
+const SAMPLE_RATE: u32 = 22050;
+
+/// write a score as MIDI or WAV depending on the extension of the path
+pub fn save(path: &str, score: &Score) -> Result<(), Box<dyn Error>> {
+    let extension = Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let contents = match extension.as_str() {
+        "mid" | "midi" => score.to_midi(),
+        "wav" => score.to_wav(SAMPLE_RATE),
+        _ => return Err(format!("--sonify must end in .mid or .wav, not '{}'", path).into()),
+    };
+    std::fs::write(path, contents)?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+// export of the power spectra of boards taken during a run, as images and radial profiles
+use rlife::formats::png;
+use rlife::report::Table;
+use rlife::spectrum::Spectrum;
+use std::error::Error;
+use std::path::Path;
+
+/// write the spectrum of a generation into dir as spectrum-GENERATION.png, frequency 0 in
+/// the middle, and spectrum-GENERATION.csv, the mean power and wavelength of every wavenumber
+pub fn save(dir: &Path, generation: u64, spectrum: &Spectrum) -> Result<(), Box<dyn Error>> {
+    let name = format!("spectrum-{:05}", generation);
+    std::fs::write(
+        dir.join(format!("{}.png", name)),
+        png::write_gray(&spectrum.levels(), spectrum.width),
+    )?;
+    let mut table = Table::new(&["wavenumber", "wavelength", "power"]);
+    for (k, &power) in spectrum.radial().iter().enumerate() {
+        table.push(vec![k.into(), spectrum.wavelength(k).into(), power.into()]);
+    }
+    std::fs::write(dir.join(format!("{}.csv", name)), table.to_csv())?;
+    Ok(())
+}
@@ -0,0 +1,118 @@
+// state transition graphs of tiny boards and their attractors, for teaching how finite
+// automata settle
+use super::{flag_value, size_value};
+use rlife::report::Table;
+use rlife::states::{self, Attractor, StateGraph};
+use rlife::Rule;
+use std::error::Error;
+use std::io::{BufWriter, Write};
+
+// step every state of a board and write the graph of states and successors as DOT or
+// GraphML
+pub fn states(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut size = None;
+    let mut rule = Rule::default();
+    let mut wraps = false;
+    let mut graphml = false;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rule" => rule = flag_value(arg, args.next())?,
+            "--torus" => wraps = true,
+            "--format" => {
+                graphml = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "dot" => false,
+                    "graphml" => true,
+                    _ => return Err("--format must be dot or graphml".into()),
+                }
+            }
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ if size.is_none() => size = Some(size_value("states", Some(arg))?),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let (width, height) = size.ok_or("states requires a board size WxH")?;
+    if width * height > states::MAX_STATE_CELLS {
+        return Err(format!("boards can have at most {} cells", states::MAX_STATE_CELLS).into());
+    }
+
+    let graph = StateGraph::new(width, height, rule, wraps);
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(BufWriter::new(std::io::stdout().lock())),
+    };
+    match graphml {
+        true => graph.write_graphml(&mut out)?,
+        false => graph.write_dot(&mut out)?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
+// find the cycles every state of a board falls into, writing a table of their periods,
+// basins and longest transients, largest basin first
+pub fn cycles(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut size = None;
+    let mut rule = Rule::default();
+    let mut wraps = false;
+    let mut json = false;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rule" => rule = flag_value(arg, args.next())?,
+            "--torus" => wraps = true,
+            "--format" => {
+                json = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err("--format must be csv or json".into()),
+                }
+            }
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            _ if size.is_none() => size = Some(size_value("cycles", Some(arg))?),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let (width, height) = size.ok_or("cycles requires a board size WxH")?;
+    if width * height > states::MAX_STATE_CELLS {
+        return Err(format!("boards can have at most {} cells", states::MAX_STATE_CELLS).into());
+    }
+
+    let graph = StateGraph::new(width, height, rule, wraps);
+    let mut attractors = graph.attractors();
+    attractors.sort_by_key(|attractor| std::cmp::Reverse(attractor.basin));
+    let mut table = Table::new(&["state", "cells", "period", "basin", "share", "transient"]);
+    for attractor in &attractors {
+        let state = attractor.cycle[0];
+        table.push(vec![
+            (state as usize).into(),
+            graph.cells(state).into(),
+            attractor.period().into(),
+            attractor.basin.into(),
+            (attractor.basin as f64 / graph.len() as f64).into(),
+            attractor.transient.into(),
+        ]);
+    }
+    let fixed = attractors.iter().filter(|a| a.period() == 1).count();
+    let longest = attractors.iter().map(Attractor::period).max().unwrap_or(0);
+    eprintln!(
+        "{} states in {} attractors: {} fixed points and {} cycles, the longest of period {}",
+        graph.len(),
+        attractors.len(),
+        fixed,
+        attractors.len() - fixed,
+        longest
+    );
+
+    let report = match json {
+        true => table.to_json(),
+        false => table.to_csv(),
+    };
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
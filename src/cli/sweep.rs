@@ -0,0 +1,217 @@
+// exploration of rule space: one soup run under many outer-totalistic rules
+use super::plugin::Plugin;
+use super::{flag_value, parallel_map, size_value};
+use rlife::expression::RuleExpression;
+use rlife::random::Rng;
+use rlife::report::{Table, Value};
+use rlife::rule::Rule;
+use rlife::{stats, Life, VecGrid};
+use std::collections::HashMap;
+use std::error::Error;
+
+// rules sampled at random unless --sample, --all or --rules is given
+const DEFAULT_SAMPLE: u32 = 256;
+
+// fate of a soup under a rule
+enum Outcome {
+    /// every cell died at the given generation
+    Dies(u64),
+    /// the board returned to an earlier state at the given generation, with a period
+    Settles(u64, u64),
+    /// still changing when the run ended
+    Active,
+}
+
+// run a soup under a set of rules and print lifespan, density and activity for each
+pub fn sweep(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut width, mut height) = (64, 64);
+    let mut density = 0.35;
+    let mut seed = 1;
+    let mut generations = 500;
+    let mut sample = Some(DEFAULT_SAMPLE);
+    let mut rules = None;
+    let mut plugins = Vec::new();
+    let mut expressions = Vec::new();
+    let mut json = false;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => (width, height) = size_value(arg, args.next())?,
+            "--density" => density = flag_value(arg, args.next())?,
+            "--seed" => seed = flag_value(arg, args.next())?,
+            "--generations" => generations = flag_value(arg, args.next())?,
+            "--sample" => sample = Some(flag_value(arg, args.next())?),
+            "--all" => sample = None,
+            "--rules" => {
+                let list: String = flag_value(arg, args.next())?;
+                rules = Some(
+                    list.split(',')
+                        .map(str::parse)
+                        .collect::<Result<Vec<Rule>, _>>()?,
+                );
+            }
+            "--format" => {
+                json = match flag_value::<String>(arg, args.next())?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err("--format must be csv or json".into()),
+                }
+            }
+            "--output" => output = Some(flag_value::<String>(arg, args.next())?),
+            "--rule-file" => {
+                let path: String = flag_value(arg, args.next())?;
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|err| format!("couldn't read {}: {}", path, err))?;
+                for line in contents.lines().map(str::trim) {
+                    if !line.is_empty() && !line.starts_with('#') {
+                        expressions.push(line.parse::<RuleExpression>()?);
+                    }
+                }
+            }
+            "--plugin" => plugins.push(Plugin::load(&flag_value::<String>(arg, args.next())?)?),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+
+    // the soup shared by every rule, seeded by the first plugin seeding soups if any, and
+    // the rules it runs under
+    let mut rng = Rng::new(seed);
+    let mut soup = VecGrid::new(width, height);
+    match plugins.iter().find(|plugin| plugin.is_seeder()) {
+        Some(plugin) => plugin.seed(&mut soup, seed),
+        None => soup.fill_random(density, &mut rng),
+    }
+    let rules = match (rules, sample) {
+        (Some(rules), _) => rules,
+        (None, None) => (0..Rule::COUNT).map(Rule::from_index).collect(),
+        (None, Some(sample)) => {
+            // distinct rules drawn with the same generator, so a seed fixes the sample too
+            let mut indices: Vec<u32> = (0..Rule::COUNT).collect();
+            let sample = sample.min(Rule::COUNT) as usize;
+            for i in 0..sample {
+                let j = i + (rng.next_u64() % (indices.len() - i) as u64) as usize;
+                indices.swap(i, j);
+            }
+            indices.truncate(sample);
+            indices.sort_unstable();
+            indices.into_iter().map(Rule::from_index).collect()
+        }
+    };
+
+    let mut table = Table::new(&[
+        "rule",
+        "outcome",
+        "lifespan",
+        "period",
+        "final_density",
+        "mean_density",
+        "activity",
+    ]);
+    for (rule, row) in rules.iter().zip(parallel_map(&rules, |&rule| {
+        measure(
+            &soup,
+            |is_alive, neighbors, _, _| rule.next_state(is_alive, neighbors),
+            generations,
+        )
+    })) {
+        table.push([vec![rule.to_string().into()], row].concat());
+    }
+    // rules written as expressions after the outer-totalistic ones
+    for (expression, row) in expressions
+        .iter()
+        .zip(parallel_map(&expressions, |expression| {
+            measure(
+                &soup,
+                |is_alive, neighbors, x, y| expression.next_state(is_alive, neighbors, x, y),
+                generations,
+            )
+        }))
+    {
+        table.push([vec![expression.to_string().into()], row].concat());
+    }
+    // rules of plugins last, one at a time since plugins needn't be thread safe
+    for plugin in plugins.iter().filter(|plugin| plugin.is_rule()) {
+        let row = measure(
+            &soup,
+            |is_alive, neighbors, x, y| plugin.next_state(is_alive, neighbors, x, y),
+            generations,
+        );
+        table.push([vec![format!("plugin:{}", plugin.name()).into()], row].concat());
+    }
+
+    let report = if json {
+        table.to_json()
+    } else {
+        table.to_csv()
+    };
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+// run the soup under a rule giving the next state of a cell from its state, live neighbors
+// and position, yielding the row values after the rule
+fn measure(
+    soup: &VecGrid,
+    rule: impl Fn(bool, usize, usize, usize) -> bool,
+    generations: u64,
+) -> Vec<Value> {
+    let cells = (soup.width() * soup.height()).max(1) as f64;
+    let mut current = soup.clone();
+    let mut next = VecGrid::new(soup.width(), soup.height());
+    let mut seen = HashMap::from([(stats::fingerprint(&current), 0)]);
+    let (mut density_sum, mut changes) = (0.0, 0);
+    let mut outcome = Outcome::Active;
+    let mut generation = 0;
+    while generation < generations {
+        changes += step(&current, &mut next, &rule);
+        std::mem::swap(&mut current, &mut next);
+        generation += 1;
+        let population = stats::population(&current);
+        density_sum += population as f64 / cells;
+        if population == 0 {
+            outcome = Outcome::Dies(generation);
+            break;
+        }
+        if let Some(first) = seen.insert(stats::fingerprint(&current), generation) {
+            outcome = Outcome::Settles(generation, generation - first);
+            break;
+        }
+    }
+
+    let (name, lifespan, period) = match outcome {
+        Outcome::Dies(generation) => ("dies", generation, 0),
+        Outcome::Settles(generation, period) => ("settles", generation, period),
+        Outcome::Active => ("active", generation, 0),
+    };
+    let steps = generation.max(1) as f64;
+    vec![
+        name.into(),
+        (lifespan as i64).into(),
+        (period as i64).into(),
+        (stats::population(&current) as f64 / cells).into(),
+        (density_sum / steps).into(),
+        (changes as f64 / cells / steps).into(),
+    ]
+}
+
+// calculate the next generation under a rule, returning how many cells changed
+fn step(
+    current: &VecGrid,
+    next: &mut VecGrid,
+    rule: &impl Fn(bool, usize, usize, usize) -> bool,
+) -> usize {
+    let width = current.width();
+    let mut changes = 0;
+    for (i, &count) in stats::neighbor_counts(current).iter().enumerate() {
+        let (x, y) = (i % width, i / width);
+        let is_alive = current.is_alive(x, y);
+        let will_live = rule(is_alive, count as usize, x, y);
+        changes += (is_alive != will_live) as usize;
+        next.set_cell(x, y, will_live);
+    }
+    changes
+}
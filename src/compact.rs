@@ -0,0 +1,147 @@
+// compact text form of boards, for keeping simulation state in JSON and other formats of
+// strings: the size followed by the cells as a bitset in base64, like 3x3:4gE for a glider
+//
+// cells are numbered row by row and cell n is bit n % 8 of byte n / 8, the base64 uses the
+// standard alphabet without padding, so a board takes a sixth of a character per cell
+// whatever it holds, unlike any representation with a value per cell
+//
+// with the serde feature boards serialize as their compact form
+use crate::{Life, VecGrid};
+use std::fmt;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// reason a compact board couldn't be decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError(String);
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid compact board: {}", self.0)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// compact form of a board
+pub fn encode(life: &impl Life) -> String {
+    let (width, height) = (life.width(), life.height());
+    let mut bytes = vec![0u8; (width * height).div_ceil(8)];
+    for y in 0..height {
+        for x in 0..width {
+            let n = y * width + x;
+            bytes[n / 8] |= (life.is_alive(x, y) as u8) << (n % 8);
+        }
+    }
+    let mut out = format!("{}x{}:", width, height);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, &byte)| {
+            bits | ((byte as u32) << (16 - 8 * i))
+        });
+        // a character per 6 bits of the chunk, rounded up
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[((bits >> (18 - 6 * i)) & 63) as usize] as char);
+        }
+    }
+    out
+}
+
+/// board of the size and cells of a compact form
+pub fn decode(s: &str) -> Result<VecGrid, DecodeError> {
+    let (width, height, bytes) = parse(s)?;
+    let mut grid = VecGrid::new(width, height);
+    set_cells(&mut grid, &bytes);
+    Ok(grid)
+}
+
+/// set every cell of a board to those of a compact form of the same size
+pub fn decode_into(s: &str, life: &mut impl Life) -> Result<(), DecodeError> {
+    let (width, height, bytes) = parse(s)?;
+    if (width, height) != (life.width(), life.height()) {
+        return Err(DecodeError(format!(
+            "{}x{} board given for a {}x{} one",
+            width,
+            height,
+            life.width(),
+            life.height()
+        )));
+    }
+    set_cells(life, &bytes);
+    Ok(())
+}
+
+// size and bitset of a compact form
+fn parse(s: &str) -> Result<(usize, usize, Vec<u8>), DecodeError> {
+    let (size, data) = s
+        .split_once(':')
+        .ok_or_else(|| DecodeError("expected WxH: before the cells".to_string()))?;
+    let (width, height) = size
+        .split_once('x')
+        .and_then(|(w, h)| w.parse::<usize>().ok().zip(h.parse::<usize>().ok()))
+        .ok_or_else(|| DecodeError(format!("invalid size '{}'", size)))?;
+    let cells = width
+        .checked_mul(height)
+        .ok_or_else(|| DecodeError(format!("invalid size '{}'", size)))?;
+    let bytes = cells.div_ceil(8);
+    // the characters needed for the bytes, as written by encode
+    if data.len() != bytes / 3 * 4 + [0, 2, 3][bytes % 3] {
+        return Err(DecodeError(format!("wrong length for {} cells", cells)));
+    }
+    let mut decoded = Vec::with_capacity(bytes + 2);
+    for chunk in data.as_bytes().chunks(4) {
+        let mut bits = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or_else(|| DecodeError(format!("unexpected character '{}'", c as char)))?;
+            bits |= (value as u32) << (18 - 6 * i);
+        }
+        decoded.extend((0..chunk.len() - 1).map(|i| (bits >> (16 - 8 * i)) as u8));
+    }
+    Ok((width, height, decoded))
+}
+
+// set the cells of a board from a bitset of its size
+fn set_cells(life: &mut impl Life, bytes: &[u8]) {
+    let width = life.width();
+    for y in 0..life.height() {
+        for x in 0..width {
+            let n = y * width + x;
+            life.set_cell(x, y, (bytes[n / 8] >> (n % 8)) & 1 == 1);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for VecGrid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode(self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for VecGrid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        decode(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const W: usize, const H: usize> serde::Serialize for crate::LifeGrid<W, H> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode(self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const W: usize, const H: usize> serde::Deserialize<'de> for crate::LifeGrid<W, H> {
+    /// refuses boards of any other size than W by H
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        let mut grid = Self::default();
+        decode_into(&s, &mut grid).map_err(serde::de::Error::custom)?;
+        Ok(grid)
+    }
+}
@@ -0,0 +1,96 @@
+// noticing a simulation settle: every generation is hashed, and the first one hashing like a
+// generation within a window of recent ones closes a cycle, still lifes being cycles of
+// period 1
+use crate::{stats, Life, Simulation};
+use std::collections::{HashMap, VecDeque};
+
+/// generations remembered by CycleDetector::default, the longest period it notices
+pub const DEFAULT_WINDOW: usize = 64;
+
+// repeating part of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// first generation of the cycle
+    pub start: u64,
+    /// generations between repeats, 1 for still lifes
+    pub period: u64,
+}
+
+impl Cycle {
+    /// whether the board stopped changing
+    pub fn is_still_life(&self) -> bool {
+        self.period == 1
+    }
+}
+
+// fingerprints of the generations observed lately
+#[derive(Debug, Clone)]
+pub struct CycleDetector {
+    window: usize,
+    recent: VecDeque<(u64, u64)>,
+    // latest generation of every fingerprint in recent
+    latest: HashMap<u64, u64>,
+}
+
+impl Default for CycleDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl CycleDetector {
+    /// remember the given number of generations, noticing cycles of up to that period
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            recent: VecDeque::with_capacity(window.max(1)),
+            latest: HashMap::new(),
+        }
+    }
+
+    /// hash a generation, returning the cycle it closes if it repeats a recent one, when
+    /// generations are observed one after another from the start the first cycle returned
+    /// starts where the run entered it
+    pub fn observe(&mut self, life: &impl Life, generation: u64) -> Option<Cycle> {
+        let fingerprint = stats::fingerprint(life);
+        let cycle = self.latest.get(&fingerprint).map(|&start| Cycle {
+            start,
+            period: generation - start,
+        });
+        if self.recent.len() == self.window {
+            if let Some((oldest, seen)) = self.recent.pop_front() {
+                if self.latest.get(&oldest) == Some(&seen) {
+                    self.latest.remove(&oldest);
+                }
+            }
+        }
+        self.recent.push_back((fingerprint, generation));
+        self.latest.insert(fingerprint, generation);
+        cycle
+    }
+
+    /// forget the generations observed, after the board was changed from outside
+    pub fn clear(&mut self) {
+        self.recent.clear();
+        self.latest.clear();
+    }
+
+    /// step a simulation until it enters a cycle, for at most the given number of generations,
+    /// the current generation included in the cycles it can close
+    pub fn run<L: Life>(
+        &mut self,
+        simulation: &mut Simulation<L>,
+        generations: u64,
+    ) -> Option<Cycle> {
+        if let Some(cycle) = self.observe(simulation.life(), simulation.generation()) {
+            return Some(cycle);
+        }
+        for _ in 0..generations {
+            simulation.step();
+            if let Some(cycle) = self.observe(simulation.life(), simulation.generation()) {
+                return Some(cycle);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,65 @@
+// damage spreading, a measure of chaos: a board and a copy of it with a single cell flipped
+// are stepped in lockstep and the cells where they differ counted every generation, the
+// damage dying out under ordered rules and spreading through the board under chaotic ones
+use crate::ensemble::Curve;
+use crate::random::Rng;
+use crate::{stats, Life, Simulation};
+
+// a board and a damaged copy of it stepped together
+pub struct Lockstep<L: Life> {
+    pub original: Simulation<L>,
+    pub damaged: Simulation<L>,
+}
+
+impl<L: Life + Clone> Lockstep<L> {
+    /// start from a board and a copy of it with the cell at (x, y) flipped
+    pub fn new(life: L, x: usize, y: usize) -> Self {
+        let mut damaged = life.clone();
+        damaged.set_cell(x, y, !life.is_alive(x, y));
+        Self {
+            original: Simulation::from_life(life),
+            damaged: Simulation::from_life(damaged),
+        }
+    }
+}
+
+impl<L: Life> Lockstep<L> {
+    /// calculate the next generation of both boards
+    pub fn step(&mut self) {
+        self.original.step();
+        self.damaged.step();
+    }
+
+    /// number of cells where the boards differ
+    pub fn distance(&self) -> usize {
+        stats::hamming_distance(self.original.life(), self.damaged.life())
+    }
+}
+
+/// cells where a board and a copy of it with the cell at (x, y) flipped differ, from the
+/// flip at generation 0 up to the given generation
+pub fn spread<L: Life + Clone>(life: &L, x: usize, y: usize, generations: u64) -> Vec<usize> {
+    let mut lockstep = Lockstep::new(life.clone(), x, y);
+    let mut distances = vec![lockstep.distance()];
+    for _ in 0..generations {
+        lockstep.step();
+        distances.push(lockstep.distance());
+    }
+    distances
+}
+
+/// damage spreading from a cell drawn at random
+pub fn spread_random<L: Life + Clone>(life: &L, generations: u64, rng: &mut Rng) -> Vec<usize> {
+    let cells = (life.width() * life.height()).max(1) as u64;
+    let cell = (rng.next_u64() % cells) as usize;
+    spread(
+        life,
+        cell % life.width().max(1),
+        cell / life.width().max(1),
+        generations,
+    )
+}
+
+/// mean and variance of the damage of many trials at every generation, along with the
+/// fraction of the trials whose damage hasn't died out
+pub type DamageCurve = Curve;
@@ -0,0 +1,213 @@
+// editing of boards: painting with the mouse, mirrored edits and macros of edits replayed
+// elsewhere in any orientation
+use crate::transform::Transform;
+use crate::Life;
+use std::fmt;
+
+// symmetry every edit is mirrored by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Symmetry {
+    /// edits are not mirrored
+    #[default]
+    None,
+    /// mirrored left to right
+    Horizontal,
+    /// mirrored top to bottom
+    Vertical,
+    /// mirrored left to right, top to bottom and both
+    FourFold,
+    /// mirrored across the top-left to bottom-right diagonal
+    Diagonal,
+}
+
+impl Symmetry {
+    /// every symmetry, in the order the GUI cycles through them
+    pub const ALL: [Symmetry; 5] = [
+        Symmetry::None,
+        Symmetry::Horizontal,
+        Symmetry::Vertical,
+        Symmetry::FourFold,
+        Symmetry::Diagonal,
+    ];
+
+    /// cell (x, y) of a width by height board and its mirror images, each once, images off
+    /// the board are left out
+    pub fn images(self, x: usize, y: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+        let (mx, my) = (width.saturating_sub(x + 1), height.saturating_sub(y + 1));
+        let mut images = match self {
+            Symmetry::None => vec![(x, y)],
+            Symmetry::Horizontal => vec![(x, y), (mx, y)],
+            Symmetry::Vertical => vec![(x, y), (x, my)],
+            Symmetry::FourFold => vec![(x, y), (mx, y), (x, my), (mx, my)],
+            Symmetry::Diagonal => vec![(x, y), (y, x)],
+        };
+        images.retain(|&(ix, iy)| ix < width && iy < height);
+        images.sort_unstable();
+        images.dedup();
+        images
+    }
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Symmetry::None => "no symmetry",
+            Symmetry::Horizontal => "horizontal symmetry",
+            Symmetry::Vertical => "vertical symmetry",
+            Symmetry::FourFold => "4-fold symmetry",
+            Symmetry::Diagonal => "diagonal symmetry",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// set a cell and its mirror images under a symmetry
+pub fn set_mirrored(life: &mut impl Life, x: usize, y: usize, is_alive: bool, symmetry: Symmetry) {
+    let (width, height) = (life.width(), life.height());
+    for (ix, iy) in symmetry.images(x, y, width, height) {
+        life.set_cell(ix, iy, is_alive);
+    }
+}
+
+// painting cells with a mouse button held down, like paint tools: pressing samples the cell
+// under the pointer and the stroke paints the opposite state over every cell it enters, so a
+// click toggles a cell and a double click toggles it back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Brush {
+    /// no button is held
+    #[default]
+    Up,
+    /// a button is held, painting cells alive or dead, along with the last cell entered
+    Painting {
+        is_alive: bool,
+        last: (usize, usize),
+    },
+}
+
+impl Brush {
+    /// follow the button and the cell under the pointer, if any, once per frame, giving the
+    /// cell to set and its new state whenever the stroke changes one
+    pub fn update(
+        &mut self,
+        pressed: bool,
+        cell: Option<(usize, usize)>,
+        life: &impl Life,
+    ) -> Option<(usize, usize, bool)> {
+        let (x, y) = match (pressed, cell) {
+            (false, _) => {
+                *self = Brush::Up;
+                return None;
+            }
+            (true, None) => return None,
+            (true, Some(cell)) => cell,
+        };
+        let is_alive = match *self {
+            Brush::Up => !life.is_alive(x, y),
+            Brush::Painting { is_alive, last } if last != (x, y) => is_alive,
+            // the pointer stayed on the last cell
+            Brush::Painting { .. } => return None,
+        };
+        *self = Brush::Painting {
+            is_alive,
+            last: (x, y),
+        };
+        (life.is_alive(x, y) != is_alive).then_some((x, y, is_alive))
+    }
+}
+
+// cell edit relative to the first edit of a macro
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edit {
+    pub dx: i64,
+    pub dy: i64,
+    pub is_alive: bool,
+}
+
+// named sequence of cell edits, anchored at the first one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Macro {
+    name: String,
+    anchor: Option<(usize, usize)>,
+    edits: Vec<Edit>,
+}
+
+impl Macro {
+    /// create an empty macro, whitespace in the name is replaced so that it stays one word
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.split_whitespace().collect::<Vec<_>>().join("-"),
+            anchor: None,
+            edits: Vec::new(),
+        }
+    }
+
+    /// macro made of edits recorded earlier
+    pub fn from_edits(name: &str, edits: Vec<Edit>) -> Self {
+        Self {
+            edits,
+            ..Self::new(name)
+        }
+    }
+
+    /// name of the macro, a single word
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// edits in the order they were recorded
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    /// record setting a cell, repeating the last edit is ignored so that dragging over a cell
+    /// records it once
+    pub fn record(&mut self, x: usize, y: usize, is_alive: bool) {
+        let (ax, ay) = *self.anchor.get_or_insert((x, y));
+        let edit = Edit {
+            dx: x as i64 - ax as i64,
+            dy: y as i64 - ay as i64,
+            is_alive,
+        };
+        if self.edits.last() != Some(&edit) {
+            self.edits.push(edit);
+        }
+    }
+
+    /// cells set by replaying the macro with its first edit at (x, y), turned by the
+    /// transform around that edit, cells left or above of the board are skipped
+    pub fn placed(
+        &self,
+        x: usize,
+        y: usize,
+        transform: Transform,
+    ) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        // the transform works on the rectangle spanned by the edits, so find it first,
+        // then keep the first edit where it is
+        let (min_x, max_x) = min_max(self.edits.iter().map(|edit| edit.dx));
+        let (min_y, max_y) = min_max(self.edits.iter().map(|edit| edit.dy));
+        let (width, height) = ((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize);
+        let position = move |dx: i64, dy: i64| {
+            let (tx, ty) =
+                transform.apply((dx - min_x) as usize, (dy - min_y) as usize, width, height);
+            (tx as i64, ty as i64)
+        };
+        let (ax, ay) = position(0, 0);
+        self.edits.iter().filter_map(move |edit| {
+            let (tx, ty) = position(edit.dx, edit.dy);
+            let cx = usize::try_from(x as i64 + tx - ax).ok()?;
+            let cy = usize::try_from(y as i64 + ty - ay).ok()?;
+            Some((cx, cy, edit.is_alive))
+        })
+    }
+
+    /// replay the macro onto a board, see placed
+    pub fn replay(&self, life: &mut impl Life, x: usize, y: usize, transform: Transform) {
+        self.placed(x, y, transform)
+            .for_each(|(x, y, is_alive)| life.set_cell(x, y, is_alive));
+    }
+}
+
+// least and greatest offsets, including the anchor at zero
+fn min_max(values: impl Iterator<Item = i64>) -> (i64, i64) {
+    values.fold((0, 0), |(min, max), v| (min.min(v), max.max(v)))
+}
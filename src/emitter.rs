@@ -0,0 +1,53 @@
+// patterns stamped onto the board periodically by the simulation, like virtual guns
+use crate::{stats, Life, VecGrid};
+
+// pattern stamped at a fixed position every period generations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emitter {
+    /// pattern stamped by the emitter
+    pub pattern: VecGrid,
+
+    /// column of the top left corner of the stamp
+    pub x: usize,
+
+    /// row of the top left corner of the stamp
+    pub y: usize,
+
+    /// generations between stamps, 0 disables the emitter
+    pub period: u64,
+
+    /// whether the dead cells of the pattern also clear the board under them
+    pub overwrite: bool,
+}
+
+impl Emitter {
+    /// create an emitter that adds the live cells of a pattern every period generations
+    pub fn new(pattern: VecGrid, x: usize, y: usize, period: u64) -> Self {
+        Self {
+            pattern,
+            x,
+            y,
+            period,
+            overwrite: false,
+        }
+    }
+
+    /// whether the emitter stamps at the given generation
+    pub fn is_due(&self, generation: u64) -> bool {
+        self.period != 0 && generation.is_multiple_of(self.period)
+    }
+
+    /// stamp the pattern onto a grid, parts outside of the grid are clipped
+    pub fn stamp(&self, life: &mut impl Life) {
+        if self.overwrite {
+            for y in 0..self.pattern.height() {
+                for x in 0..self.pattern.width() {
+                    life.set_cell(self.x + x, self.y + y, self.pattern.is_alive(x, y));
+                }
+            }
+        } else {
+            stats::live_cells(&self.pattern)
+                .for_each(|(x, y)| life.set_cell(self.x + x, self.y + y, true));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// ensembles of independent runs with the same settings and their own seeds, for statistics
+// that no single soup gives, like how likely soups of a density are to die out by a
+// generation
+use crate::random::Rng;
+use crate::{Life, Simulation};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+// settings shared by the runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ensemble {
+    /// number of runs
+    pub runs: usize,
+    /// generations each run is stepped for
+    pub generations: u64,
+    /// seed the seeds of the runs are drawn from
+    pub seed: u64,
+    /// threads the runs are shared between
+    pub threads: usize,
+}
+
+impl Ensemble {
+    /// seeds of the runs, drawn up front so that the results don't depend on the threads
+    pub fn seeds(&self) -> Vec<u64> {
+        let mut rng = Rng::new(self.seed);
+        (0..self.runs).map(|_| rng.next_u64()).collect()
+    }
+
+    /// populations of every run from generation 0, in the order of their seeds, each run
+    /// stepping the board made by board from a generator of its seed
+    pub fn populations<L: Life + Clone>(
+        &self,
+        board: impl Fn(&mut Rng) -> L + Sync,
+    ) -> Vec<Vec<usize>> {
+        let seeds = self.seeds();
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(seeds.len()));
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.clamp(1, seeds.len().max(1)) {
+                scope.spawn(|| {
+                    // take the next run nobody started
+                    while let Some(i) =
+                        Some(next.fetch_add(1, Ordering::Relaxed)).filter(|&i| i < seeds.len())
+                    {
+                        let seed = seeds[i];
+                        let mut simulation = Simulation::from_life(board(&mut Rng::new(seed)));
+                        let mut populations = vec![simulation.population()];
+                        for _ in 0..self.generations {
+                            simulation.step();
+                            populations.push(simulation.population());
+                        }
+                        results.lock().expect("Poisoned").push((i, populations));
+                    }
+                });
+            }
+        });
+        let mut results = results.into_inner().expect("Poisoned");
+        results.sort_by_key(|&(i, _)| i);
+        results
+            .into_iter()
+            .map(|(_, populations)| populations)
+            .collect()
+    }
+}
+
+// a measure of many runs at every generation
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Curve {
+    /// mean of the runs
+    pub mean: Vec<f64>,
+    /// variance of the runs
+    pub variance: Vec<f64>,
+    /// fraction of the runs above 0, the survival curve of populations
+    pub surviving: Vec<f64>,
+}
+
+impl Curve {
+    /// statistics of the values of runs, as many generations long as the longest one,
+    /// shorter runs left out past their end
+    pub fn new(runs: &[Vec<usize>]) -> Self {
+        let generations = runs.iter().map(Vec::len).max().unwrap_or(0);
+        let mut curve = Self::default();
+        for generation in 0..generations {
+            let values: Vec<f64> = runs
+                .iter()
+                .filter_map(|run| run.get(generation))
+                .map(|&value| value as f64)
+                .collect();
+            let count = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / count;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+            curve.mean.push(mean);
+            curve.variance.push(variance);
+            curve
+                .surviving
+                .push(values.iter().filter(|&&v| v > 0.0).count() as f64 / count);
+        }
+        curve
+    }
+}
+
+/// generation each run died out at, its last generation for runs still alive at the end
+pub fn lifespans(runs: &[Vec<usize>]) -> Vec<u64> {
+    runs.iter()
+        .map(|run| {
+            run.iter()
+                .position(|&population| population == 0)
+                .unwrap_or(run.len().saturating_sub(1)) as u64
+        })
+        .collect()
+}
@@ -0,0 +1,106 @@
+// one generation evaluated in phases, for showing how the rule produces the next generation
+use crate::Life;
+
+// step reached in evaluating a generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// live neighbors of every cell have been counted
+    Counted,
+    /// cells to be born and to die have been decided
+    Decided,
+    /// births and deaths have been applied, the grid holds the next generation
+    Committed,
+}
+
+// state of a generation being evaluated a phase at a time
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    phase: Phase,
+    width: usize,
+    counts: Vec<u8>,
+    births: Vec<(usize, usize)>,
+    deaths: Vec<(usize, usize)>,
+}
+
+impl Explanation {
+    /// start explaining the next generation of a grid, counting neighbors
+    pub fn new(life: &impl Life) -> Self {
+        let mut explanation = Self {
+            phase: Phase::Counted,
+            width: life.width(),
+            counts: Vec::new(),
+            births: Vec::new(),
+            deaths: Vec::new(),
+        };
+        explanation.count(life);
+        explanation
+    }
+
+    /// move on to the next phase, committing applies the changes to the grid and the phase
+    /// after that starts explaining the generation following it
+    pub fn advance(&mut self, life: &mut impl Life) -> Phase {
+        match self.phase {
+            Phase::Counted => self.decide(life),
+            Phase::Decided => {
+                self.births
+                    .iter()
+                    .for_each(|&(x, y)| life.set_cell(x, y, true));
+                self.deaths
+                    .iter()
+                    .for_each(|&(x, y)| life.set_cell(x, y, false));
+                self.phase = Phase::Committed;
+            }
+            Phase::Committed => self.count(life),
+        }
+        self.phase
+    }
+
+    /// phase reached so far
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// live neighbors of cell (x, y) when the generation was counted
+    pub fn neighbors(&self, x: usize, y: usize) -> usize {
+        self.counts[y * self.width + x] as usize
+    }
+
+    /// cells coming alive, empty until decided
+    pub fn births(&self) -> &[(usize, usize)] {
+        &self.births
+    }
+
+    /// cells dying, empty until decided
+    pub fn deaths(&self) -> &[(usize, usize)] {
+        &self.deaths
+    }
+
+    // mark the changes as applied, when something else calculated the next generation
+    pub(crate) fn committed(&mut self) {
+        self.phase = Phase::Committed;
+    }
+
+    // count the live neighbors of every cell
+    fn count(&mut self, life: &impl Life) {
+        self.width = life.width();
+        self.counts = (0..life.height())
+            .flat_map(|y| (0..life.width()).map(move |x| (x, y)))
+            .map(|(x, y)| life.number_of_neighbors(x, y) as u8)
+            .collect();
+        self.births.clear();
+        self.deaths.clear();
+        self.phase = Phase::Counted;
+    }
+
+    // decide which cells change state, asking the rule of the grid
+    fn decide(&mut self, life: &impl Life) {
+        for (x, y) in (0..life.height()).flat_map(|y| (0..life.width()).map(move |x| (x, y))) {
+            match (life.is_alive(x, y), life.next_state(x, y)) {
+                (false, true) => self.births.push((x, y)),
+                (true, false) => self.deaths.push((x, y)),
+                _ => (),
+            }
+        }
+        self.phase = Phase::Decided;
+    }
+}
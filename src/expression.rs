@@ -0,0 +1,367 @@
+// rules written as expressions over a cell, like
+//
+//     alive = n == 3 || (alive && n == 2) || (x % 7 == 0 && n >= 5)
+//
+// where n is the number of live neighbors, alive is 1 for a live cell and 0 otherwise and
+// x and y are its coordinates, with the integer and logical operators of C and Rust,
+// comparisons and logic giving 1 or 0, and division and remainder by 0 giving 0, compiled
+// once into a table when the rule doesn't depend on position and into closures otherwise
+use crate::rule::Rule;
+use std::fmt;
+use std::str::FromStr;
+
+// what an expression can look at
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    alive: i64,
+    n: i64,
+    x: i64,
+    y: i64,
+}
+
+// compiled expression
+type Compiled = Box<dyn Fn(&Cell) -> i64 + Send + Sync>;
+
+// how the next state is calculated
+enum Eval {
+    /// next states of dead and live cells indexed by live neighbors
+    Table([[bool; 9]; 2]),
+    /// closures walking the expression
+    Closure(Compiled),
+}
+
+// rule given as an expression, see the top of the module
+pub struct RuleExpression {
+    source: String,
+    eval: Eval,
+}
+
+impl RuleExpression {
+    /// state of a cell in the next generation
+    pub fn next_state(&self, is_alive: bool, neighbors: usize, x: usize, y: usize) -> bool {
+        match &self.eval {
+            Eval::Table(table) => neighbors <= 8 && table[is_alive as usize][neighbors],
+            Eval::Closure(f) => {
+                f(&Cell {
+                    alive: is_alive as i64,
+                    n: neighbors as i64,
+                    x: x as i64,
+                    y: y as i64,
+                }) != 0
+            }
+        }
+    }
+
+    /// the outer-totalistic rule the expression amounts to, None when it depends on the
+    /// position of cells
+    pub fn rule(&self) -> Option<Rule> {
+        match &self.eval {
+            Eval::Table(table) => {
+                let mask = |states: &[bool; 9]| {
+                    (0..9)
+                        .filter(|&n| states[n])
+                        .fold(0u16, |mask, n| mask | 1 << n)
+                };
+                Some(Rule::new(mask(&table[0]), mask(&table[1])))
+            }
+            Eval::Closure(_) => None,
+        }
+    }
+}
+
+impl fmt::Debug for RuleExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RuleExpression").field(&self.source).finish()
+    }
+}
+
+impl fmt::Display for RuleExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+// reason an expression couldn't be parsed, along with the expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseExpressionError {
+    source: String,
+    message: String,
+}
+
+impl fmt::Display for ParseExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid rule '{}': {}", self.source, self.message)
+    }
+}
+
+impl std::error::Error for ParseExpressionError {}
+
+impl FromStr for RuleExpression {
+    type Err = ParseExpressionError;
+
+    /// parse an expression, optionally preceded by "alive ="
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |message: String| ParseExpressionError {
+            source: s.trim().to_string(),
+            message,
+        };
+        let mut tokens = tokenize(s).map_err(error)?;
+        if let [Token::Ident(name), Token::Assign, ..] = tokens.as_slice() {
+            if name == "alive" {
+                tokens.drain(..2);
+            }
+        }
+        let mut parser = Parser { tokens, next: 0 };
+        let node = parser.or().map_err(error)?;
+        if let Some(token) = parser.tokens.get(parser.next) {
+            return Err(error(format!("unexpected {}", token)));
+        }
+        let eval = match node.is_positional() {
+            false => {
+                let f = node.compile();
+                let mut table = [[false; 9]; 2];
+                for (alive, states) in table.iter_mut().enumerate() {
+                    for (n, state) in states.iter_mut().enumerate() {
+                        let cell = Cell {
+                            alive: alive as i64,
+                            n: n as i64,
+                            x: 0,
+                            y: 0,
+                        };
+                        *state = f(&cell) != 0;
+                    }
+                }
+                Eval::Table(table)
+            }
+            true => Eval::Closure(node.compile()),
+        };
+        Ok(Self {
+            source: s.trim().to_string(),
+            eval,
+        })
+    }
+}
+
+// piece of an expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(i64),
+    Ident(String),
+    Op(&'static str),
+    Assign,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "'{}'", n),
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Op(op) => write!(f, "'{}'", op),
+            Token::Assign => f.write_str("'='"),
+            Token::Open => f.write_str("'('"),
+            Token::Close => f.write_str("')'"),
+        }
+    }
+}
+
+// operators, longest first so that "<=" isn't read as "<" and "="
+const OPERATORS: [&str; 15] = [
+    "||", "&&", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "!", "=",
+];
+
+// split an expression into tokens
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = s.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let length = if c.is_ascii_digit() {
+            let length = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let number = rest[..length]
+                .parse()
+                .map_err(|_| format!("number {} is too big", &rest[..length]))?;
+            tokens.push(Token::Number(number));
+            length
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let length = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            tokens.push(Token::Ident(rest[..length].to_string()));
+            length
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            1
+        } else {
+            let op = OPERATORS
+                .iter()
+                .copied()
+                .find(|op| rest.starts_with(op))
+                .ok_or_else(|| format!("unexpected '{}'", c))?;
+            tokens.push(if op == "=" {
+                Token::Assign
+            } else {
+                Token::Op(op)
+            });
+            op.len()
+        };
+        rest = rest[length..].trim_start();
+    }
+    Ok(tokens)
+}
+
+// parsed expression
+#[derive(Debug, Clone)]
+enum Node {
+    Number(i64),
+    Var(fn(&Cell) -> i64),
+    Positional(fn(&Cell) -> i64),
+    Not(Box<Node>),
+    Negate(Box<Node>),
+    Binary(&'static str, Box<Node>, Box<Node>),
+}
+
+impl Node {
+    /// whether the value depends on the position of the cell
+    fn is_positional(&self) -> bool {
+        match self {
+            Node::Number(_) | Node::Var(_) => false,
+            Node::Positional(_) => true,
+            Node::Not(node) | Node::Negate(node) => node.is_positional(),
+            Node::Binary(_, left, right) => left.is_positional() || right.is_positional(),
+        }
+    }
+
+    /// closures calculating the value for a cell
+    fn compile(&self) -> Compiled {
+        match self {
+            &Node::Number(n) => Box::new(move |_| n),
+            &Node::Var(f) | &Node::Positional(f) => Box::new(f),
+            Node::Not(node) => {
+                let node = node.compile();
+                Box::new(move |cell| (node(cell) == 0) as i64)
+            }
+            Node::Negate(node) => {
+                let node = node.compile();
+                Box::new(move |cell| node(cell).wrapping_neg())
+            }
+            Node::Binary(op, left, right) => {
+                let (left, right) = (left.compile(), right.compile());
+                match *op {
+                    "||" => Box::new(move |cell| (left(cell) != 0 || right(cell) != 0) as i64),
+                    "&&" => Box::new(move |cell| (left(cell) != 0 && right(cell) != 0) as i64),
+                    "==" => Box::new(move |cell| (left(cell) == right(cell)) as i64),
+                    "!=" => Box::new(move |cell| (left(cell) != right(cell)) as i64),
+                    "<" => Box::new(move |cell| (left(cell) < right(cell)) as i64),
+                    "<=" => Box::new(move |cell| (left(cell) <= right(cell)) as i64),
+                    ">" => Box::new(move |cell| (left(cell) > right(cell)) as i64),
+                    ">=" => Box::new(move |cell| (left(cell) >= right(cell)) as i64),
+                    "+" => Box::new(move |cell| left(cell).wrapping_add(right(cell))),
+                    "-" => Box::new(move |cell| left(cell).wrapping_sub(right(cell))),
+                    "*" => Box::new(move |cell| left(cell).wrapping_mul(right(cell))),
+                    "/" => Box::new(move |cell| left(cell).checked_div(right(cell)).unwrap_or(0)),
+                    "%" => Box::new(move |cell| left(cell).checked_rem(right(cell)).unwrap_or(0)),
+                    _ => unreachable!("unknown operator {}", op),
+                }
+            }
+        }
+    }
+}
+
+// recursive descent parser, one function per level of precedence from the loosest
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl Parser {
+    fn or(&mut self) -> Result<Node, String> {
+        self.binary(&["||"], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Node, String> {
+        self.binary(&["&&"], Self::comparison)
+    }
+
+    fn comparison(&mut self) -> Result<Node, String> {
+        let left = self.sum()?;
+        match self.operator(&["==", "!=", "<", "<=", ">", ">="]) {
+            Some(op) => Ok(Node::Binary(op, Box::new(left), Box::new(self.sum()?))),
+            None => Ok(left),
+        }
+    }
+
+    fn sum(&mut self) -> Result<Node, String> {
+        self.binary(&["+", "-"], Self::product)
+    }
+
+    fn product(&mut self) -> Result<Node, String> {
+        self.binary(&["*", "/", "%"], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        match self.operator(&["!", "-"]) {
+            Some("!") => Ok(Node::Not(Box::new(self.unary()?))),
+            Some(_) => Ok(Node::Negate(Box::new(self.unary()?))),
+            None => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Node, String> {
+        let token = self
+            .tokens
+            .get(self.next)
+            .cloned()
+            .ok_or("unexpected end")?;
+        self.next += 1;
+        match token {
+            Token::Number(n) => Ok(Node::Number(n)),
+            Token::Ident(name) => match name.as_str() {
+                "n" => Ok(Node::Var(|cell| cell.n)),
+                "alive" => Ok(Node::Var(|cell| cell.alive)),
+                "x" => Ok(Node::Positional(|cell| cell.x)),
+                "y" => Ok(Node::Positional(|cell| cell.y)),
+                "true" => Ok(Node::Number(1)),
+                "false" => Ok(Node::Number(0)),
+                _ => Err(format!("unknown variable '{}'", name)),
+            },
+            Token::Open => {
+                let node = self.or()?;
+                match self.tokens.get(self.next) {
+                    Some(Token::Close) => {
+                        self.next += 1;
+                        Ok(node)
+                    }
+                    _ => Err("expected ')'".to_string()),
+                }
+            }
+            token => Err(format!("unexpected {}", token)),
+        }
+    }
+
+    // left associative operators of one level
+    fn binary(
+        &mut self,
+        ops: &[&'static str],
+        operand: fn(&mut Self) -> Result<Node, String>,
+    ) -> Result<Node, String> {
+        let mut node = operand(self)?;
+        while let Some(op) = self.operator(ops) {
+            node = Node::Binary(op, Box::new(node), Box::new(operand(self)?));
+        }
+        Ok(node)
+    }
+
+    // take the next token if it's one of the operators
+    fn operator(&mut self, ops: &[&'static str]) -> Option<&'static str> {
+        match self.tokens.get(self.next) {
+            Some(&Token::Op(op)) if ops.contains(&op) => {
+                self.next += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// plaintext patterns drawn with . for dead and O for alive cells, lines starting with ! are
+// comments, the format of many small patterns and handy for writing boards by hand
+use super::{Error, Pattern};
+use crate::{Life, VecGrid};
+
+/// parse the contents of a .cells file, sized to its longest row and number of rows
+pub fn parse(input: &str) -> Result<Pattern, Error> {
+    let rows: Vec<(usize, &str)> = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end()))
+        .filter(|(_, line)| !line.starts_with('!'))
+        .collect();
+    // blank lines at the end are the end of the file rather than empty rows
+    let height = rows
+        .iter()
+        .rposition(|(_, line)| !line.is_empty())
+        .map_or(0, |last| last + 1);
+    let width = rows
+        .iter()
+        .map(|(_, line)| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut grid = VecGrid::new(width, height);
+    for (y, &(line_no, line)) in rows[..height].iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                'O' | '*' => grid.set_cell(x, y, true),
+                '.' => (),
+                _ => {
+                    return Err(Error::Parse {
+                        line: line_no,
+                        message: format!("unexpected character '{}'", c),
+                    })
+                }
+            }
+        }
+    }
+    Ok(Pattern { grid, rule: None })
+}
+
+/// serialize a grid as a .cells file named by a comment, every row written in full so that
+/// the size of the grid is kept
+pub fn write(life: &impl Life, name: &str) -> String {
+    let mut out = format!("!Name: {}\n", name);
+    for y in 0..life.height() {
+        out.extend((0..life.width()).map(|x| if life.is_alive(x, y) { 'O' } else { '.' }));
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,134 @@
+// layouts composing a big board from pattern files, for assembling constructions from
+// their components, written as
+//
+//     size 200x120
+//     grid 64x64
+//     tile gun.rle 0 0
+//     tile eater.rle 1 0 12 -4
+//
+// where every tile is placed on the cell at the given column and row of the last grid
+// before it, moved by an optional offset in cells, before any grid the column and row are
+// cells themselves, the board fits every tile unless it is given a size, and a rule line
+// sets the rule instead of the first tile declaring one
+use super::{Error, Pattern};
+use crate::{stats, Life, VecGrid};
+use std::path::{Path, PathBuf};
+
+// pattern file placed on the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tile {
+    /// pattern file, relative to the layout file
+    pub path: PathBuf,
+    /// top left corner of the pattern on the board
+    pub x: usize,
+    pub y: usize,
+}
+
+// tiles of a board along with its size and rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    /// board size, None to fit every tile
+    pub size: Option<(usize, usize)>,
+    /// rule of the board, None for the rule of the tiles
+    pub rule: Option<String>,
+    pub tiles: Vec<Tile>,
+}
+
+impl Layout {
+    /// parse the contents of a layout file, tile paths stay relative
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut layout = Self {
+            size: None,
+            rule: None,
+            tiles: Vec::new(),
+        };
+        let mut grid = (1, 1);
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: &str| Error::Parse {
+                line: i + 1,
+                message: message.to_string(),
+            };
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let number = |word: &str| {
+                word.parse::<i64>()
+                    .map_err(|_| error(&format!("invalid number '{}'", word)))
+            };
+            let cell = |word: &str| {
+                word.parse::<usize>()
+                    .map_err(|_| error(&format!("invalid column or row '{}'", word)))
+            };
+            match words.as_slice() {
+                ["size", size] => {
+                    layout.size = Some(dimensions(size).ok_or_else(|| error("invalid size"))?)
+                }
+                ["grid", size] => grid = dimensions(size).ok_or_else(|| error("invalid grid"))?,
+                ["rule", rule] => layout.rule = Some(rule.to_string()),
+                ["tile", path, column, row, rest @ ..] => {
+                    let (dx, dy) = match rest {
+                        [] => (0, 0),
+                        [dx, dy] => (number(dx)?, number(dy)?),
+                        _ => return Err(error("expected tile FILE COLUMN ROW [DX DY]")),
+                    };
+                    let x = (cell(column)? * grid.0) as i64 + dx;
+                    let y = (cell(row)? * grid.1) as i64 + dy;
+                    if x < 0 || y < 0 {
+                        return Err(error("tile lies off the board"));
+                    }
+                    layout.tiles.push(Tile {
+                        path: PathBuf::from(path),
+                        x: x as usize,
+                        y: y as usize,
+                    });
+                }
+                _ => return Err(error(&format!("invalid layout line '{}'", line))),
+            }
+        }
+        Ok(layout)
+    }
+
+    /// read the tiles, relative to a directory, and place them on a board
+    pub fn compose(&self, dir: impl AsRef<Path>) -> Result<Pattern, Error> {
+        let mut rule = self.rule.clone();
+        let mut placed = Vec::with_capacity(self.tiles.len());
+        for tile in &self.tiles {
+            let path = dir.as_ref().join(&tile.path);
+            let pattern = super::load(&path).map_err(|err| match err {
+                Error::Parse { line, message } => Error::Parse {
+                    line,
+                    message: format!("{}: {}", path.display(), message),
+                },
+                err => err,
+            })?;
+            rule = rule.or(pattern.rule);
+            placed.push((pattern.grid, tile.x, tile.y));
+        }
+
+        let (width, height) = self.size.unwrap_or_else(|| {
+            placed.iter().fold((0, 0), |(width, height), (grid, x, y)| {
+                (width.max(x + grid.width()), height.max(y + grid.height()))
+            })
+        });
+        let mut grid = VecGrid::new(width, height);
+        for (tile, x, y) in &placed {
+            stats::live_cells(tile).for_each(|(tx, ty)| grid.set_cell(x + tx, y + ty, true));
+        }
+        Ok(Pattern { grid, rule })
+    }
+}
+
+/// read a layout file and compose its board, tiles are read relative to the layout file
+pub fn load(path: impl AsRef<Path>) -> Result<Pattern, Error> {
+    let path = path.as_ref();
+    let layout = Layout::parse(&std::fs::read_to_string(path)?)?;
+    layout.compose(path.parent().unwrap_or(Path::new("")))
+}
+
+// dimensions written WxH
+fn dimensions(size: &str) -> Option<(usize, usize)> {
+    let (width, height) = size.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
@@ -0,0 +1,177 @@
+// Life 1.05 and Life 1.06 patterns, the .lif files of older collections
+//
+// Life 1.06 lists the coordinates of live cells, one "x y" pair per line, Life 1.05 draws
+// blocks of cells with . for dead and * for alive, each placed by a "#P x y" line, with the
+// rule given as "#N" for Conway's or "#R survival/birth", like "#R 23/36" for HighLife,
+// coordinates of both may be negative and patterns are sized to fit their live cells
+use super::{Error, Pattern};
+use crate::rule::Rule;
+use crate::{stats, Life, VecGrid, DEFAULT_RULE};
+
+// widest block written to Life 1.05 files, longer lines aren't allowed by the format
+const MAX_LINE_LENGTH: usize = 80;
+
+// coordinates of live cells as read from a file
+type Cells = Vec<(i64, i64)>;
+
+/// parse the contents of a Life 1.05 or 1.06 file, told apart by their first line
+pub fn parse(input: &str) -> Result<Pattern, Error> {
+    let header = input.lines().next().unwrap_or_default().trim();
+    let (cells, rule) = match header {
+        "#Life 1.06" => (parse_106(input)?, None),
+        "#Life 1.05" => parse_105(input)?,
+        _ => return Err(parse_err(1, "expected #Life 1.05 or #Life 1.06 header")),
+    };
+    let (min_x, min_y) = cells
+        .iter()
+        .fold((i64::MAX, i64::MAX), |(mx, my), &(x, y)| {
+            (mx.min(x), my.min(y))
+        });
+    let (max_x, max_y) = cells
+        .iter()
+        .fold((i64::MIN, i64::MIN), |(mx, my), &(x, y)| {
+            (mx.max(x), my.max(y))
+        });
+    let mut grid = match cells.is_empty() {
+        true => VecGrid::new(0, 0),
+        false => VecGrid::new((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize),
+    };
+    for (x, y) in cells {
+        grid.set_cell((x - min_x) as usize, (y - min_y) as usize, true);
+    }
+    Ok(Pattern { grid, rule })
+}
+
+/// parse the contents of a Life 1.05 or 1.06 file onto a board of any kind with the top left
+/// corner of the pattern's live cells at (x, y), cells past the edges of the board are
+/// dropped, giving the rule declared by the file, if any
+pub fn parse_into(
+    input: &str,
+    life: &mut impl Life,
+    x: usize,
+    y: usize,
+) -> Result<Option<String>, Error> {
+    let pattern = parse(input)?;
+    stats::live_cells(&pattern.grid).for_each(|(px, py)| life.set_cell(x + px, y + py, true));
+    Ok(pattern.rule)
+}
+
+// error for a 1-based line
+fn parse_err(line: usize, message: &str) -> Error {
+    Error::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+// live cells of a Life 1.06 file
+fn parse_106(input: &str) -> Result<Cells, Error> {
+    let mut cells = Vec::new();
+    for (i, line) in input.lines().enumerate().skip(1) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut coordinates = line.split_whitespace().map(str::parse::<i64>);
+        match (coordinates.next(), coordinates.next(), coordinates.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) => cells.push((x, y)),
+            _ => return Err(parse_err(i + 1, "expected a pair of integer coordinates")),
+        }
+    }
+    Ok(cells)
+}
+
+// live cells and rule of a Life 1.05 file
+fn parse_105(input: &str) -> Result<(Cells, Option<String>), Error> {
+    let (mut cells, mut rule) = (Vec::new(), None);
+    // position of the next row of the current block, rows before any #P start at the origin
+    let (mut block_x, mut y) = (0, 0);
+    for (i, line) in input.lines().enumerate().skip(1) {
+        let line_no = i + 1;
+        let line = line.trim();
+        if let Some(offset) = line.strip_prefix("#P") {
+            let mut offset = offset.split_whitespace().map(str::parse::<i64>);
+            (block_x, y) = match (offset.next(), offset.next(), offset.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => (x, y),
+                _ => return Err(parse_err(line_no, "expected #P with integer coordinates")),
+            };
+        } else if line.starts_with("#N") {
+            rule = Some(DEFAULT_RULE.to_string());
+        } else if let Some(rulestring) = line.strip_prefix("#R") {
+            let (survival, birth) = rulestring
+                .trim()
+                .split_once('/')
+                .ok_or_else(|| parse_err(line_no, "expected #R survival/birth"))?;
+            let bs = format!("B{}/S{}", birth, survival);
+            bs.parse::<Rule>()
+                .map_err(|err| parse_err(line_no, &err.to_string()))?;
+            rule = Some(bs);
+        } else if line.is_empty() || line.starts_with('#') {
+            // descriptions and other lines of comments
+        } else {
+            for (dx, c) in line.chars().enumerate() {
+                match c {
+                    '*' => cells.push((block_x + dx as i64, y)),
+                    '.' => (),
+                    _ => return Err(parse_err(line_no, "unexpected character")),
+                }
+            }
+            y += 1;
+        }
+    }
+    Ok((cells, rule))
+}
+
+/// serialize the live cells of a grid as Life 1.06 coordinates
+pub fn write_106(life: &impl Life) -> String {
+    let mut out = String::from("#Life 1.06\n");
+    for (x, y) in stats::live_cells(life) {
+        out.push_str(&format!("{} {}\n", x, y));
+    }
+    out
+}
+
+/// serialize a grid as Life 1.05 blocks at most 80 cells wide, declaring the given rule,
+/// rules not in B/S notation are written as Conway's
+pub fn write_105(life: &impl Life, rule: &str) -> String {
+    let mut out = String::from("#Life 1.05\n");
+    let rule: Rule = rule.parse().unwrap_or_default();
+    match rule == Rule::default() {
+        true => out.push_str("#N\n"),
+        false => {
+            // B36/S23 is written as 23/36
+            let rule = rule.to_string();
+            let (birth, survival) = rule.split_once('/').unwrap_or_default();
+            let (birth, survival) = (
+                birth.trim_start_matches('B'),
+                survival.trim_start_matches('S'),
+            );
+            out.push_str(&format!("#R {}/{}\n", survival, birth));
+        }
+    }
+    for block_x in (0..life.width()).step_by(MAX_LINE_LENGTH) {
+        let columns = block_x..life.width().min(block_x + MAX_LINE_LENGTH);
+        let row_is_empty = |y: usize| !columns.clone().any(|x| life.is_alive(x, y));
+        // blocks cover the rows from the first to the last with live cells, if any
+        let Some(top) = (0..life.height()).find(|&y| !row_is_empty(y)) else {
+            continue;
+        };
+        let bottom = (0..life.height())
+            .rfind(|&y| !row_is_empty(y))
+            .unwrap_or(top);
+        out.push_str(&format!("#P {} {}\n", block_x, top));
+        for y in top..=bottom {
+            let row: String = columns
+                .clone()
+                .map(|x| if life.is_alive(x, y) { '*' } else { '.' })
+                .collect();
+            // trailing dead cells are implicit, but rows can't be empty
+            match row.trim_end_matches('.') {
+                "" => out.push('.'),
+                row => out.push_str(row),
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
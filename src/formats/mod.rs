@@ -0,0 +1,124 @@
+use crate::rule::{ParseRuleError, Rule};
+use crate::{VecGrid, DEFAULT_RULE};
+use std::fmt;
+use std::path::Path;
+
+pub mod cells;
+pub mod layout;
+pub mod lif;
+pub mod png;
+pub mod rle;
+
+// pattern read from a file, sized to the dimensions declared by the file
+pub struct Pattern {
+    /// cells of the pattern
+    pub grid: VecGrid,
+
+    /// rulestring declared by the file, if any
+    pub rule: Option<String>,
+}
+
+impl Pattern {
+    /// rule declared by the file, Conway's when it declares none
+    pub fn parsed_rule(&self) -> Result<Rule, Error> {
+        match &self.rule {
+            Some(rule) => Ok(rule.parse()?),
+            None => Ok(Rule::default()),
+        }
+    }
+}
+
+// errors produced while reading or writing pattern files
+#[derive(Debug)]
+pub enum Error {
+    /// underlying I/O failure
+    Io(std::io::Error),
+
+    /// malformed file contents, with the 1-based line number
+    Parse { line: usize, message: String },
+
+    /// file extension doesn't match any known format
+    UnsupportedFormat(String),
+
+    /// rule declared by the file that can't be stepped
+    Rule(ParseRuleError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            Error::UnsupportedFormat(ext) => write!(f, "unsupported pattern format '{}'", ext),
+            Error::Rule(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<ParseRuleError> for Error {
+    fn from(err: ParseRuleError) -> Self {
+        Error::Rule(err)
+    }
+}
+
+/// extensions of the formats that can be loaded and saved, images can be saved as png too,
+/// .lif and .life files are read as Life 1.05 or 1.06 and written as Life 1.05
+pub const EXTENSIONS: &[&str] = &["rle", "lif", "life", "cells"];
+
+/// whether a path has the extension of a known format
+pub fn is_supported(path: impl AsRef<Path>) -> bool {
+    EXTENSIONS.contains(&extension(path.as_ref()).as_str())
+}
+
+/// read a pattern file, choosing the format from its extension
+pub fn load(path: impl AsRef<Path>) -> Result<Pattern, Error> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)?;
+    match extension(path).as_str() {
+        "rle" => rle::parse(&contents),
+        "lif" | "life" => lif::parse(&contents),
+        "cells" => cells::parse(&contents),
+        ext => Err(Error::UnsupportedFormat(ext.to_string())),
+    }
+}
+
+/// write a pattern file, choosing the format from its extension
+pub fn save(path: impl AsRef<Path>, pattern: &Pattern) -> Result<(), Error> {
+    let path = path.as_ref();
+    let contents = match extension(path).as_str() {
+        "rle" => rle::write(
+            &pattern.grid,
+            pattern.rule.as_deref().unwrap_or(DEFAULT_RULE),
+        )
+        .into_bytes(),
+        "lif" | "life" => lif::write_105(
+            &pattern.grid,
+            pattern.rule.as_deref().unwrap_or(DEFAULT_RULE),
+        )
+        .into_bytes(),
+        // the format has no rule, the file is named after itself
+        "cells" => {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            cells::write(&pattern.grid, &name).into_bytes()
+        }
+        "png" => png::write(&pattern.grid, 1),
+        ext => return Err(Error::UnsupportedFormat(ext.to_string())),
+    };
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+// lowercase extension of a path, empty if there is none
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
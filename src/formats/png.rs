@@ -0,0 +1,147 @@
+// PNG images of grids, write only, live cells white on black like the GUI, and of frames
+use crate::{stats, Life};
+
+// largest block of uncompressed deflate data
+const MAX_STORED_BLOCK: usize = 65535;
+
+// gray level of thumbnail blocks with few live cells, so that sparse blocks don't vanish
+const MIN_BLOCK_LEVEL: f64 = 64.0;
+
+/// encode a grid as a grayscale PNG, each cell drawn as a scale by scale square
+pub fn write(life: &impl Life, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    encode(life.width() * scale, life.height() * scale, |x, y| {
+        255 * life.is_alive(x / scale, y / scale) as u8
+    })
+}
+
+/// small image of a grid with its longer side at most side pixels, grids bigger than that
+/// are drawn as blocks of cells shaded by the fraction of them alive, smaller ones are
+/// scaled up
+pub fn thumbnail(life: &impl Life, side: usize) -> Vec<u8> {
+    let side = side.max(1);
+    let longest = life.width().max(life.height()).max(1);
+    if longest <= side {
+        return write(life, side / longest);
+    }
+    let block = longest.div_ceil(side);
+    let width = life.width().div_ceil(block);
+    let densities = stats::block_densities(life, block);
+    encode(
+        width,
+        life.height().div_ceil(block),
+        |x, y| match densities[y * width + x] {
+            d if d > 0.0 => (MIN_BLOCK_LEVEL + d * (255.0 - MIN_BLOCK_LEVEL)) as u8,
+            _ => 0,
+        },
+    )
+}
+
+/// encode a frame buffer of 0RGB pixels, as drawn by render, of the given width as an RGB PNG
+pub fn write_frame(buffer: &[u32], width: usize) -> Vec<u8> {
+    let height = match width {
+        0 => 0,
+        width => buffer.len() / width,
+    };
+    let mut pixels = Vec::with_capacity((3 * width + 1) * height);
+    for row in buffer.chunks_exact(width.max(1)).take(height) {
+        pixels.push(0);
+        pixels.extend(
+            row.iter()
+                .flat_map(|pixel| pixel.to_be_bytes().into_iter().skip(1)),
+        );
+    }
+    // 8 bit RGB
+    encode_rows(width, height, 2, &pixels)
+}
+
+/// encode gray levels of the given width row by row, like an image made by analysis, as a
+/// grayscale PNG
+pub fn write_gray(levels: &[u8], width: usize) -> Vec<u8> {
+    let height = match width {
+        0 => 0,
+        width => levels.len() / width,
+    };
+    encode(width, height, |x, y| levels[y * width + x])
+}
+
+// encode a width by height grayscale image with the given pixel levels
+fn encode(width: usize, height: usize, level: impl Fn(usize, usize) -> u8) -> Vec<u8> {
+    // every row starts with filter type 0, followed by one byte per pixel
+    let mut pixels = Vec::with_capacity((width + 1) * height);
+    for y in 0..height {
+        pixels.push(0);
+        pixels.extend((0..width).map(|x| level(x, y)));
+    }
+    // 8 bit grayscale
+    encode_rows(width, height, 0, &pixels)
+}
+
+// encode filtered rows of pixels of a color type with 8 bits per channel
+fn encode_rows(width: usize, height: usize, color_type: u8, pixels: &[u8]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // default compression, filtering and no interlacing
+    header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut png, b"IHDR", &header);
+    chunk(&mut png, b"IDAT", &zlib_stored(pixels));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+// append a chunk with its length and checksum
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(kind.iter().chain(data));
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// zlib stream of uncompressed deflate blocks, grids are small enough not to bother compressing
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    // even empty data needs a final block
+    let mut blocks: Vec<&[u8]> = data.chunks(MAX_STORED_BLOCK).collect();
+    if blocks.is_empty() {
+        blocks.push(&[]);
+    }
+    let last = blocks.len() - 1;
+    for (i, block) in blocks.into_iter().enumerate() {
+        out.push((i == last) as u8);
+        out.extend_from_slice(&(block.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+// CRC-32 as used by PNG chunks
+fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+// Adler-32 checksum ending zlib streams
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
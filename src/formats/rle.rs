@@ -0,0 +1,144 @@
+// Run Length Encoded patterns, as used by most pattern collections
+use super::{Error, Pattern};
+use crate::{stats, Life, VecGrid};
+
+/// parse the contents of an RLE file
+pub fn parse(input: &str) -> Result<Pattern, Error> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        // skip comments and blank lines
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    // header line: x = m, y = n[, rule = abc]
+    let (line_no, header) = lines.next().ok_or(Error::Parse {
+        line: 1,
+        message: "missing header line".to_string(),
+    })?;
+    let parse_err = |line: usize, message: &str| Error::Parse {
+        line,
+        message: message.to_string(),
+    };
+    let (mut width, mut height, mut rule): (_, _, Option<String>) = (None, None, None);
+    for field in header.split(',') {
+        // rules with commas of their own, like R5,C0,M1,S34..58,B34..45,NM, go on past the
+        // fields
+        if let (false, Some(rule)) = (field.contains('='), &mut rule) {
+            *rule = format!("{},{}", rule, field.trim());
+            continue;
+        }
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| parse_err(line_no, "malformed header field"))?;
+        let value = value.trim();
+        match key.trim() {
+            "x" => width = value.parse::<usize>().ok(),
+            "y" => height = value.parse::<usize>().ok(),
+            "rule" => rule = Some(value.to_string()),
+            _ => (),
+        }
+    }
+    let (width, height) = width
+        .zip(height)
+        .ok_or_else(|| parse_err(line_no, "header must declare numeric x and y"))?;
+
+    // body: runs of <count><tag> where b is dead, o is alive and $ ends a row
+    let mut grid = VecGrid::new(width, height);
+    let (mut x, mut y) = (0, 0);
+    'body: for (line_no, line) in lines {
+        let mut count = 0;
+        for c in line.chars() {
+            match c {
+                '0'..='9' => count = count * 10 + c.to_digit(10).unwrap() as usize,
+                '!' => break 'body,
+                '$' => {
+                    y += count.max(1);
+                    x = 0;
+                    count = 0;
+                }
+                c if c.is_whitespace() => (),
+                c if c.is_ascii_alphabetic() => {
+                    let run = count.max(1);
+                    if x + run > width || y >= height {
+                        return Err(parse_err(line_no, "cells outside of declared size"));
+                    }
+                    // any state other than b is treated as alive
+                    if c != 'b' {
+                        (x..x + run).for_each(|cx| grid.set_cell(cx, y, true));
+                    }
+                    x += run;
+                    count = 0;
+                }
+                _ => return Err(parse_err(line_no, "unexpected character")),
+            }
+        }
+    }
+
+    Ok(Pattern { grid, rule })
+}
+
+/// parse the contents of an RLE file onto a board of any kind with the pattern's top left
+/// corner at (x, y), cells past the edges of the board are dropped, giving the rule declared
+/// by the file, if any
+pub fn parse_into(
+    input: &str,
+    life: &mut impl Life,
+    x: usize,
+    y: usize,
+) -> Result<Option<String>, Error> {
+    let pattern = parse(input)?;
+    stats::live_cells(&pattern.grid).for_each(|(px, py)| life.set_cell(x + px, y + py, true));
+    Ok(pattern.rule)
+}
+
+// longest line written to RLE bodies, as recommended by the format
+const MAX_LINE_LENGTH: usize = 70;
+
+/// serialize a grid as RLE, declaring the given rule in the header
+pub fn write(life: &impl Life, rule: &str) -> String {
+    let (width, height) = (life.width(), life.height());
+    let mut out = format!("x = {}, y = {}, rule = {}\n", width, height, rule);
+
+    // runs of (count, tag), row ends are kept pending so empty rows and the last row collapse
+    let mut tokens = Vec::new();
+    let mut pending_rows = 0;
+    for y in 0..height {
+        let mut x = 0;
+        while x < width {
+            let is_alive = life.is_alive(x, y);
+            let run = (x..width)
+                .take_while(|&cx| life.is_alive(cx, y) == is_alive)
+                .count();
+            // trailing dead cells are implicit
+            if is_alive || x + run < width {
+                if pending_rows > 0 {
+                    tokens.push((pending_rows, '$'));
+                    pending_rows = 0;
+                }
+                tokens.push((run, if is_alive { 'o' } else { 'b' }));
+            }
+            x += run;
+        }
+        pending_rows += 1;
+    }
+    tokens.push((1, '!'));
+
+    // wrap tokens into lines without splitting any of them
+    let mut line = String::new();
+    for (count, tag) in tokens {
+        let token = match count {
+            1 => tag.to_string(),
+            n => format!("{}{}", n, tag),
+        };
+        if line.len() + token.len() > MAX_LINE_LENGTH {
+            out.push_str(&line);
+            out.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    out.push_str(&line);
+    out.push('\n');
+    out
+}
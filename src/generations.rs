@@ -0,0 +1,162 @@
+// Generations rules, outer-totalistic rules whose dying cells fade through states of their own
+// before the cell is empty again, written B2/S/C3 for Brian's Brain or B2/S345/C4 for Star
+// Wars: live cells are state 1 and only they count as neighbors, a live cell that doesn't
+// survive moves on to state 2 and every later state to the next, back to 0 after the last
+//
+// the Golly form of survival, birth and states like 345/2/4 is accepted too
+use crate::multistate::{MultiStateLife, StateGrid};
+use crate::render::StatePalette;
+use crate::rule::Rule;
+use std::fmt;
+use std::str::FromStr;
+
+/// state of live cells
+pub const ALIVE: u8 = 1;
+
+// outer-totalistic births and survivals along with the number of states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenerationsRule {
+    pub rule: Rule,
+    states: u8,
+}
+
+impl GenerationsRule {
+    /// Brian's Brain, where every live cell dies the generation after it is born: cells are
+    /// ready (0), firing (1) or refractory (2), ready cells fire with exactly 2 firing
+    /// neighbors and firing ones are refractory for a generation
+    pub const BRIANS_BRAIN: &'static str = "B2/S/C3";
+
+    /// rule with births and survivals of a B/S rule and at least 2 states, 2 states being
+    /// the B/S rule itself
+    pub fn new(rule: Rule, states: u8) -> Self {
+        Self {
+            rule,
+            states: states.max(2),
+        }
+    }
+
+    /// number of states, the empty and live ones included
+    pub fn states(self) -> u8 {
+        self.states
+    }
+
+    /// colors of the states, empty cells black, live ones white and dying ones fading from
+    /// blue towards black
+    pub fn palette(self) -> StatePalette {
+        let dying = (self.states - 2) as u32;
+        let fading = (0..dying).map(|i| {
+            let channel =
+                |shift: u32| (((0x4080ff >> shift) & 0xff) * (dying - i) / dying) << shift;
+            channel(16) | channel(8) | channel(0)
+        });
+        StatePalette::new([0x000000, 0xffffff].into_iter().chain(fading).collect())
+    }
+
+    /// state of a cell in the next generation given its live neighbors
+    pub fn next_state(self, state: u8, neighbors: usize) -> u8 {
+        match state {
+            0 => self.rule.is_born(neighbors) as u8,
+            ALIVE if self.rule.survives(neighbors) => ALIVE,
+            state => (state + 1) % self.states,
+        }
+    }
+}
+
+impl fmt::Display for GenerationsRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/C{}", self.rule, self.states)
+    }
+}
+
+// reason a Generations rulestring couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGenerationsError(String);
+
+impl fmt::Display for ParseGenerationsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid Generations rule '{}', expected B/S/C notation like B2/S/C3",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseGenerationsError {}
+
+impl FromStr for GenerationsRule {
+    type Err = ParseGenerationsError;
+
+    /// parse B/S/C notation, case insensitive, or survival/birth/states like 345/2/4, with
+    /// 2 to 255 states
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseGenerationsError(s.to_string());
+        let (rule, states) = s.trim().rsplit_once('/').ok_or_else(error)?;
+        let states = states
+            .strip_prefix(['C', 'c', 'G', 'g'])
+            .unwrap_or(states)
+            .parse::<u8>()
+            .ok()
+            .filter(|&states| states >= 2)
+            .ok_or_else(error)?;
+        let rule = match rule.split_once('/') {
+            // digits only, survival first
+            Some((survival, birth))
+                if (survival.to_owned() + birth)
+                    .chars()
+                    .all(|c| c.is_ascii_digit()) =>
+            {
+                format!("B{}/S{}", birth, survival).parse()
+            }
+            _ => rule.parse(),
+        }
+        .map_err(|_| error())?;
+        Ok(Self::new(rule, states))
+    }
+}
+
+// board stepped under a Generations rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationsLife {
+    pub grid: StateGrid,
+    pub rule: GenerationsRule,
+}
+
+impl GenerationsLife {
+    /// step a grid of states under a rule
+    pub fn new(grid: StateGrid, rule: GenerationsRule) -> Self {
+        Self { grid, rule }
+    }
+
+    /// number of live cells, the fading ones left out
+    pub fn population(&self) -> usize {
+        self.grid.count(ALIVE)
+    }
+}
+
+impl MultiStateLife for GenerationsLife {
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn states(&self) -> u8 {
+        self.rule.states()
+    }
+
+    fn state(&self, x: usize, y: usize) -> u8 {
+        self.grid.get(x, y)
+    }
+
+    fn set_state(&mut self, x: usize, y: usize, state: u8) {
+        self.grid.set(x, y, state.min(self.rule.states() - 1))
+    }
+
+    fn next_state(&self, x: usize, y: usize) -> u8 {
+        self.rule
+            .next_state(self.state(x, y), self.neighbors_in_state(x, y, ALIVE))
+    }
+}
@@ -0,0 +1,286 @@
+// labels and rectangles marking structures on the board, drawn over the cells
+use super::typing::Typing;
+use crate::cli::announce::Announcer;
+use log::debug;
+use rlife::input::Action;
+use std::fmt;
+
+// color of label text
+const TEXT_COLOR: u32 = 0xffa500;
+
+// color of rectangle outlines, blended with the cells underneath
+const RECT_COLOR: u32 = 0xffd700;
+
+// 3x5 glyphs, one row per byte with the leftmost pixel in the highest of three bits
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+
+// what an annotation marks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    /// text with its top left corner at the anchor
+    Label,
+    /// outline of a rectangle with its top left corner at the anchor, the text inside it
+    Rect { width: usize, height: usize },
+}
+
+// annotation anchored to a cell, so that it stays with the structure it marks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub x: usize,
+    pub y: usize,
+    pub shape: Shape,
+    pub text: String,
+}
+
+impl Annotation {
+    /// rectangle spanning two opposite corners, inclusive
+    pub fn rect((x0, y0): (usize, usize), (x1, y1): (usize, usize)) -> Self {
+        Self {
+            x: x0.min(x1),
+            y: y0.min(y1),
+            shape: Shape::Rect {
+                width: x0.abs_diff(x1) + 1,
+                height: y0.abs_diff(y1) + 1,
+            },
+            text: String::new(),
+        }
+    }
+
+    /// whether a cell is covered by the annotation, a label covers its text
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        let (width, height) = match self.shape {
+            Shape::Label => (
+                (self.text.chars().count() * (GLYPH_WIDTH + 1)).max(1),
+                GLYPH_HEIGHT,
+            ),
+            Shape::Rect { width, height } => (width, height),
+        };
+        (self.x..self.x + width).contains(&x) && (self.y..self.y + height).contains(&y)
+    }
+
+    /// parse an annotation as written by Display, like "label 3 4 gun #2"
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let error = || format!("invalid annotation '{}'", line.trim());
+        let mut words = line.trim().splitn(2, ' ');
+        let kind = words.next().ok_or_else(error)?;
+        let fields = match kind {
+            "label" => 2,
+            "rect" => 4,
+            _ => return Err(error()),
+        };
+        // the numbers, then the rest of the line is the text
+        let mut rest = words.next().unwrap_or("").splitn(fields + 1, ' ');
+        let numbers = (0..fields)
+            .map(|_| rest.next().and_then(|n| n.parse().ok()).ok_or_else(error))
+            .collect::<Result<Vec<usize>, _>>()?;
+        let shape = match numbers[2..] {
+            [width, height] if width > 0 && height > 0 => Shape::Rect { width, height },
+            [] => Shape::Label,
+            _ => return Err(error()),
+        };
+        Ok(Self {
+            x: numbers[0],
+            y: numbers[1],
+            shape,
+            text: rest.next().unwrap_or("").to_string(),
+        })
+    }
+
+    /// draw the annotation over a frame buffer of the given width
+    pub fn render(&self, width: usize, buffer: &mut [u32]) {
+        let height = buffer.len() / width;
+        let mut plot = |x: usize, y: usize, color: u32| {
+            if x < width && y < height {
+                buffer[x + y * width] = color;
+            }
+        };
+        let (text_x, text_y) = match self.shape {
+            Shape::Label => (self.x, self.y),
+            Shape::Rect {
+                width: w,
+                height: h,
+            } => {
+                let (right, bottom) = (self.x + w - 1, self.y + h - 1);
+                for x in self.x..=right {
+                    plot(x, self.y, RECT_COLOR);
+                    plot(x, bottom, RECT_COLOR);
+                }
+                for y in self.y..=bottom {
+                    plot(self.x, y, RECT_COLOR);
+                    plot(right, y, RECT_COLOR);
+                }
+                (self.x + 1, self.y + 1)
+            }
+        };
+        for (i, c) in self.text.chars().enumerate() {
+            for (dy, row) in glyph(c).iter().enumerate() {
+                for dx in 0..GLYPH_WIDTH {
+                    if row >> (GLYPH_WIDTH - 1 - dx) & 1 == 1 {
+                        plot(text_x + i * (GLYPH_WIDTH + 1) + dx, text_y + dy, TEXT_COLOR);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.shape {
+            Shape::Label => write!(f, "label {} {} {}", self.x, self.y, self.text),
+            Shape::Rect { width, height } => write!(
+                f,
+                "rect {} {} {} {} {}",
+                self.x, self.y, width, height, self.text
+            ),
+        }
+    }
+}
+
+// annotations of the board and the first corner of a rectangle being marked
+#[derive(Default)]
+pub struct Marking {
+    pub annotations: Vec<Annotation>,
+    corner: Option<(usize, usize)>,
+}
+
+impl Marking {
+    /// mark the board with the annotations of a session
+    pub fn new(annotations: Vec<Annotation>) -> Self {
+        Self {
+            annotations,
+            corner: None,
+        }
+    }
+
+    /// label the cell under the cursor, mark a rectangle between two corners and remove the
+    /// annotations under the mouse on their actions, returns the annotation to type the text
+    /// of next
+    pub fn handle(
+        &mut self,
+        actions: &[Action],
+        mouse: Option<(usize, usize)>,
+        cursor: Option<(usize, usize)>,
+        announcer: &mut Announcer,
+    ) -> Option<Typing> {
+        let mut typing = None;
+
+        // label the cell under the cursor, typing its text next
+        if actions.contains(&Action::Label) {
+            typing = cursor.map(|(x, y)| {
+                Typing::Annotation(Annotation {
+                    x,
+                    y,
+                    shape: Shape::Label,
+                    text: String::new(),
+                })
+            });
+        }
+
+        // mark a rectangle with a corner at the cursor, then the opposite one and its text
+        if actions.contains(&Action::MarkRectangle) {
+            match (self.corner.take(), cursor) {
+                (Some(first), Some(second)) => {
+                    typing = Some(Typing::Annotation(Annotation::rect(first, second)));
+                }
+                (None, cursor) => self.corner = cursor,
+                (Some(_), None) => (),
+            }
+        }
+
+        // remove the annotations under the cursor
+        if actions.contains(&Action::DeleteAnnotations) {
+            if let Some((x, y)) = mouse {
+                let count = self.annotations.len();
+                self.annotations
+                    .retain(|annotation| !annotation.contains(x, y));
+                announcer.action(format!(
+                    "removed {} annotations",
+                    count - self.annotations.len()
+                ));
+            }
+        }
+        typing
+    }
+
+    /// add an annotation once its text has been typed, labels without text are dropped while
+    /// rectangles mark something on their own
+    pub fn add(&mut self, annotation: Annotation, announcer: &mut Announcer) {
+        if annotation.text.is_empty() && annotation.shape == Shape::Label {
+            return;
+        }
+        debug!("adding annotation {}", annotation);
+        announcer.action(format!("added {}", annotation));
+        self.annotations.push(annotation);
+    }
+
+    /// draw the annotations followed by others over a frame buffer of the given width, along
+    /// with the corner of a rectangle being marked, returns whether anything was drawn
+    pub fn render<'a>(
+        &'a self,
+        others: impl Iterator<Item = &'a Annotation>,
+        width: usize,
+        buffer: &mut [u32],
+    ) -> bool {
+        let mut drawn = false;
+        for annotation in self.annotations.iter().chain(others) {
+            annotation.render(width, buffer);
+            drawn = true;
+        }
+        if let Some(corner) = self.corner {
+            Annotation::rect(corner, corner).render(width, buffer);
+            drawn = true;
+        }
+        drawn
+    }
+}
+
+// glyph of a character, letters are drawn in upper case and unknown characters as '?'
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c.to_ascii_uppercase() {
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b110, 0b001, 0b010, 0b100, 0b111],
+        '3' => [0b110, 0b001, 0b010, 0b001, 0b110],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b110, 0b001, 0b110],
+        '6' => [0b011, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b110],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        ' ' => [0; GLYPH_HEIGHT],
+        _ => [0b110, 0b001, 0b010, 0b000, 0b010],
+    }
+}
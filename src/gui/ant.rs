@@ -0,0 +1,80 @@
+// Langton's Ant and its generalizations in a window, ants drawn over the colored cells
+use crate::cli::messages::{self, Message};
+use crate::cli::{flag_value, size_value};
+use minifb::{MouseButton, MouseMode};
+use rlife::ant::{AntRule, AntWorld, Heading};
+use rlife::render::{render_states_to_buffer, StatePalette, Viewport};
+use std::error::Error;
+use std::time::Duration;
+
+// time per frame of steps and steps per frame unless --step-time or --steps is given
+const DEFAULT_STEP_TIME: u64 = 20;
+const DEFAULT_STEPS: u64 = 10;
+
+// color of the cells ants are on
+const ANT_COLOR: u32 = 0xff2020;
+
+/// run ants on a wrapping board, Langton's own unless --rule gives a turn per color like
+/// LLRR, --ants of them spread along the middle row heading north, the left button adding
+/// another, Space pausing and the right arrow stepping while paused
+pub fn ant(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut rule = AntRule::default();
+    let (mut width, mut height) = (super::WIDTH, super::HEIGHT);
+    let mut ants = 1;
+    let mut step_time = DEFAULT_STEP_TIME;
+    let mut steps = DEFAULT_STEPS;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rule" => rule = flag_value(arg, args.next())?,
+            "--size" => (width, height) = size_value(arg, args.next())?,
+            "--ants" => ants = flag_value(arg, args.next())?,
+            "--step-time" => step_time = flag_value(arg, args.next())?,
+            "--steps" => steps = flag_value(arg, args.next())?,
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let mut world = AntWorld::new(width, height, rule);
+    for i in 0..ants {
+        world.add_ant((i + 1) * width / (ants + 1), height / 2, Heading::North);
+    }
+    // the first color black and the others around the hue circle
+    let colors = world.rule.colors() as usize;
+    let palette = StatePalette::new(
+        (0..colors)
+            .map(|color| match color {
+                0 => 0x000000,
+                color => super::art::hsv(color as f32 / colors as f32, 0.8, 1.0),
+            })
+            .collect(),
+    );
+
+    super::multistate::run_with(
+        "ant",
+        world,
+        Duration::from_millis(step_time),
+        |world| world.step_by(steps),
+        |world, buffer| {
+            render_states_to_buffer(world, buffer, &palette, Viewport::whole_states(world));
+            for ant in &world.ants {
+                buffer[ant.y * width + ant.x] = ANT_COLOR;
+            }
+        },
+        |world, window| {
+            if window.get_mouse_down(MouseButton::Left) {
+                if let Some((x, y)) = window.get_mouse_pos(MouseMode::Discard) {
+                    let (x, y) = (x as usize, y as usize);
+                    if !world.ants.iter().any(|ant| (ant.x, ant.y) == (x, y)) {
+                        world.add_ant(x, y, Heading::North);
+                    }
+                }
+            }
+        },
+        |world, _| {
+            messages::format(
+                Message::AntStatus,
+                &[&world.generation(), &world.ants.len(), &world.rule],
+            )
+        },
+    )
+}
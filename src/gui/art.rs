@@ -0,0 +1,57 @@
+// ambient art mode: fading trails and drifting hues, the board is reseeded once it settles
+use rlife::Life;
+
+// fraction of brightness a dead cell keeps every frame
+const DECAY: f32 = 0.93;
+
+// hue change per frame, in turns of the color wheel
+const HUE_DRIFT: f32 = 0.0005;
+
+// per cell brightness and color state of the art mode
+pub struct Art {
+    glow: Vec<f32>,
+    hue: f32,
+}
+
+impl Art {
+    /// create the state for a board with the given number of cells
+    pub fn new(cells: usize) -> Self {
+        Self {
+            glow: vec![0.0; cells],
+            hue: 0.0,
+        }
+    }
+
+    /// draw a frame, live cells shine in the current hue and dead cells fade out
+    pub fn render<L: Life>(&mut self, life: &L, buffer: &mut [u32]) {
+        self.hue = (self.hue + HUE_DRIFT).fract();
+        let width = life.width();
+        for (i, (glow, pixel)) in self.glow.iter_mut().zip(buffer.iter_mut()).enumerate() {
+            *glow = if life.is_alive(i % width, i / width) {
+                1.0
+            } else {
+                *glow * DECAY
+            };
+            // trails shift towards the next hues as they fade
+            *pixel = hsv(self.hue + (1.0 - *glow) * 0.3, 0.8, *glow);
+        }
+    }
+}
+
+// pixel for a hue in turns, saturation and value in [0, 1]
+pub fn hsv(hue: f32, saturation: f32, value: f32) -> u32 {
+    let h = hue.rem_euclid(1.0) * 6.0;
+    let c = value * saturation;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = value - c;
+    let channel = |v: f32| ((v + m) * 255.0) as u32;
+    channel(r) << 16 | channel(g) << 8 | channel(b)
+}
@@ -0,0 +1,305 @@
+// viewer for a headless run in another process, attached through its control socket
+use crate::cli::control::Update;
+use crate::cli::messages::{self, Message};
+use crate::cli::size_value;
+use log::info;
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode, Scale, Window, WindowOptions};
+use rlife::ownership::{Ownership, NO_OWNER};
+use rlife::{stats, transform, Life};
+use std::error::Error;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+// largest window side in pixels, the scale is the largest one fitting the board in it, boards
+// bigger than that are drawn as blocks of cells shaded by density
+const MAX_WINDOW_SIDE: usize = 1280;
+
+// gray level of blocks with few live cells, so that sparse blocks don't vanish
+const MIN_BLOCK_LEVEL: f64 = 64.0;
+
+// fraction of the way to the live cells the camera moves each frame while following them
+const FOLLOW_RATE: f64 = 0.2;
+
+// connection to the control socket of a run
+struct Connection {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl Connection {
+    // send a command and wait for its reply, None once the run has finished
+    fn request(&mut self, command: &str) -> Result<Option<String>, Box<dyn Error>> {
+        writeln!(self.writer, "{}", command)?;
+        let mut line = String::new();
+        Ok(match self.reader.read_line(&mut line)? {
+            0 => None,
+            _ if line.trim() == "run finished" => None,
+            _ => Some(line),
+        })
+    }
+}
+
+// part of the board shown, given by its center so that following moves smoothly
+struct Camera {
+    center: (f64, f64),
+    width: usize,
+    height: usize,
+    follow: bool,
+}
+
+impl Camera {
+    // top left cell of the view, kept on a board of the given size
+    fn origin(&self, board_width: usize, board_height: usize) -> (usize, usize) {
+        let place = |center: f64, view: usize, board: usize| {
+            (center - view as f64 / 2.0)
+                .round()
+                .clamp(0.0, board.saturating_sub(view) as f64) as usize
+        };
+        (
+            place(self.center.0, self.width, board_width),
+            place(self.center.1, self.height, board_height),
+        )
+    }
+
+    // move towards the centroid of the live cells when following them
+    fn track(&mut self, life: &impl Life) {
+        if let Some((x, y)) = stats::centroid(life).filter(|_| self.follow) {
+            self.center.0 += (x + 0.5 - self.center.0) * FOLLOW_RATE;
+            self.center.1 += (y + 0.5 - self.center.1) * FOLLOW_RATE;
+        }
+    }
+}
+
+/// show the run listening on a control socket until the window is closed or the run ends,
+/// Space pauses and resumes it, the right arrow steps a paused run and F toggles following
+/// the live cells when only part of the board is in view, the mouse sets and clears cells
+/// and cells are colored by the client that placed them
+pub fn attach(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (path, mut args) = match args.split_first() {
+        Some((path, rest)) if !path.starts_with("--") => (path, rest.iter()),
+        _ => return Err("attach requires the control socket of a run".into()),
+    };
+    let (mut view, mut follow) = (None, false);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--view" => view = Some(size_value(arg, args.next())?),
+            "--follow" => follow = true,
+            _ => return Err(format!("unknown option '{}'", arg).into()),
+        }
+    }
+    let stream = UnixStream::connect(path)
+        .map_err(|err| format!("couldn't connect to {}: {}", path, err))?;
+    let mut connection = Connection {
+        reader: BufReader::new(stream.try_clone()?),
+        writer: stream,
+    };
+
+    // start from the whole board, then only the changed cells are sent
+    let (mut generation, mut grid, owned) = match connection.request("frame")? {
+        Some(line) => match Update::parse(&line)? {
+            Update::Frame {
+                generation,
+                grid,
+                owners,
+            } => (generation, grid, owners),
+            Update::Changes { .. } => return Err("expected a frame from the run".into()),
+        },
+        None => return Ok(()),
+    };
+    let (board_width, board_height) = (grid.width(), grid.height());
+    let mut ownership = Ownership::new(board_width, board_height);
+    owned
+        .into_iter()
+        .for_each(|(x, y, owner)| ownership.set(x, y, owner));
+    info!(
+        "attached to {} at generation {}, board {}x{}",
+        path, generation, board_width, board_height
+    );
+
+    // part of the board in view, all of it unless --view is given, starting in the middle
+    let (view_width, view_height) = view.map_or((board_width, board_height), |(w, h)| {
+        (
+            w.clamp(1, board_width.max(1)),
+            h.clamp(1, board_height.max(1)),
+        )
+    });
+    let mut camera = Camera {
+        center: (board_width as f64 / 2.0, board_height as f64 / 2.0),
+        width: view_width,
+        height: view_height,
+        follow,
+    };
+
+    // cells per pixel along each side, more than one when the view doesn't fit
+    let block = view_width.max(view_height).div_ceil(MAX_WINDOW_SIDE).max(1);
+    let (width, height) = (view_width.div_ceil(block), view_height.div_ceil(block));
+
+    let scale = [
+        (Scale::X32, 32),
+        (Scale::X16, 16),
+        (Scale::X8, 8),
+        (Scale::X4, 4),
+        (Scale::X2, 2),
+    ]
+    .into_iter()
+    .find(|&(_, factor)| width.max(height) * factor <= MAX_WINDOW_SIDE)
+    .map_or(Scale::X1, |(scale, _)| scale);
+    let mut window = Window::new(
+        "rlife attach - ESC to exit",
+        width,
+        height,
+        WindowOptions {
+            scale,
+            ..Default::default()
+        },
+    )?;
+    window.limit_update_rate(Some(Duration::from_millis(30)));
+
+    let mut buffer = vec![0; width * height];
+    let mut paused = false;
+    // last cell set or cleared, so that holding the button down sends each cell once
+    let mut painted = None;
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        // remote control of the run
+        if window.is_key_pressed(Key::Space, KeyRepeat::No) {
+            paused = !paused;
+            if connection
+                .request(if paused { "pause" } else { "resume" })?
+                .is_none()
+            {
+                break;
+            }
+        }
+        if paused
+            && window.is_key_pressed(Key::Right, KeyRepeat::Yes)
+            && connection.request("step")?.is_none()
+        {
+            break;
+        }
+
+        // bring the board up to date
+        let line = match connection.request("changes")? {
+            Some(line) => line,
+            None => break,
+        };
+        let owners = match Update::parse(&line)? {
+            Update::Frame {
+                generation: g,
+                grid: frame,
+                owners,
+            } => {
+                (generation, grid) = (g, frame);
+                ownership = Ownership::new(grid.width(), grid.height());
+                owners
+            }
+            Update::Changes {
+                generation: g,
+                cells,
+                owners,
+            } => {
+                generation = g;
+                for (x, y) in cells {
+                    let is_alive = grid.is_alive(x, y);
+                    grid.set_cell(x, y, !is_alive);
+                }
+                owners
+            }
+        };
+        owners
+            .into_iter()
+            .for_each(|(x, y, owner)| ownership.set(x, y, owner));
+
+        // following the live cells, F switches it on and off
+        if window.is_key_pressed(Key::F, KeyRepeat::No) {
+            camera.follow = !camera.follow;
+        }
+        camera.track(&grid);
+        let (x, y) = camera.origin(grid.width(), grid.height());
+
+        // the left button sets cells for this client and the right one clears them
+        let button = match (
+            window.get_mouse_down(MouseButton::Left),
+            window.get_mouse_down(MouseButton::Right),
+        ) {
+            (true, _) => Some("set"),
+            (false, true) => Some("clear"),
+            (false, false) => None,
+        };
+        let cell = window
+            .get_mouse_pos(MouseMode::Discard)
+            .map(|(mx, my)| (x + mx as usize * block, y + my as usize * block));
+        match (button, cell) {
+            (Some(command), Some(cell)) if painted != Some((command, cell)) => {
+                painted = Some((command, cell));
+                let command = format!("{} {} {}", command, cell.0, cell.1);
+                if connection.request(&command)?.is_none() {
+                    break;
+                }
+            }
+            (None, _) => painted = None,
+            _ => (),
+        }
+
+        if block == 1 {
+            for (i, pixel) in buffer.iter_mut().enumerate() {
+                let (cx, cy) = (x + i % width, y + i / width);
+                *pixel = match (grid.is_alive(cx, cy), ownership.owner(cx, cy)) {
+                    (false, _) => 0,
+                    (true, NO_OWNER) => u32::MAX,
+                    (true, owner) => client_color(owner),
+                };
+            }
+        } else {
+            let shown = transform::cropped(&grid, x, y, view_width, view_height);
+            let densities = stats::block_densities(&shown, block);
+            for (pixel, density) in buffer.iter_mut().zip(densities) {
+                let level = match density {
+                    d if d > 0.0 => MIN_BLOCK_LEVEL + d * (255.0 - MIN_BLOCK_LEVEL),
+                    _ => 0.0,
+                };
+                *pixel = level as u32 * 0x010101;
+            }
+        }
+        let mut status =
+            messages::format(Message::Status, &[&generation, &stats::population(&grid)]);
+        if block > 1 {
+            status += &format!(
+                ", {}",
+                messages::format(Message::CellsPerPixel, &[&block, &block])
+            );
+        }
+        if (view_width, view_height) != (grid.width(), grid.height()) {
+            status += &format!(", {}", messages::format(Message::ViewAt, &[&x, &y]));
+            if camera.follow {
+                status += &format!(" {}", messages::text(Message::Following));
+            }
+        }
+        let scores: Vec<String> = ownership
+            .populations(&grid)
+            .iter()
+            .map(|(client, population)| format!("{}:{}", client, population))
+            .collect();
+        if !scores.is_empty() {
+            status += &format!(
+                ", {}",
+                messages::format(Message::Clients, &[&scores.join(" ")])
+            );
+        }
+        if paused {
+            status += &format!(", {}", messages::text(Message::Paused));
+        }
+        window.set_title(&format!(
+            "rlife attach - {} - {}",
+            status,
+            messages::text(Message::EscToExit)
+        ));
+        window.update_with_buffer(&buffer, width, height)?;
+    }
+    Ok(())
+}
+
+// color of the cells of a client, hues far apart for consecutive clients
+fn client_color(client: u8) -> u32 {
+    super::art::hsv(client as f32 * 0.618, 0.8, 1.0)
+}
@@ -0,0 +1,130 @@
+// sessions saved every so often, on exit and from a panic hook to a file of their own, so
+// that hours of building aren't lost to a crash or a stray Escape, and offered back on the
+// next launch, the files being handled by rlife::autosave
+use super::session::{self, Session};
+use log::{error, info, warn};
+use rlife::autosave;
+use rlife::{stats, VecGrid};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub use rlife::autosave::path;
+
+/// time between autosaves unless --autosave is given
+pub const DEFAULT_EVERY: Duration = Duration::from_secs(60);
+
+// session autosaved last, written again from the panic hook with the board of the moment
+type Latest = Arc<Mutex<Option<Session>>>;
+
+// autosave file along with the session last saved to it
+pub struct Autosave {
+    file: autosave::Autosave,
+    latest: Latest,
+}
+
+impl Autosave {
+    /// autosave to a file every so often, and from a panic hook with the board a function
+    /// gives, None when it can't be had, along with the rest of the last session autosaved
+    pub fn new(
+        path: PathBuf,
+        every: Duration,
+        board: impl Fn() -> Option<VecGrid> + Send + Sync + 'static,
+    ) -> Self {
+        let latest = Latest::default();
+        let previous = std::panic::take_hook();
+        let (hook_path, hook_latest) = (path.clone(), latest.clone());
+        std::panic::set_hook(Box::new(move |info| {
+            // the lock is only held while saving, which a panic there shouldn't repeat
+            if let Ok(mut latest) = hook_latest.try_lock() {
+                if let Some(grid) = board() {
+                    match latest.as_mut() {
+                        Some(session) => session.grid = grid,
+                        None => {
+                            *latest = Some(Session {
+                                grid,
+                                annotations: Vec::new(),
+                                bookmarks: Vec::new(),
+                                macros: Vec::new(),
+                            })
+                        }
+                    }
+                }
+                if let Some(session) = latest.as_ref() {
+                    match autosave::write(&hook_path, &session::write(session)) {
+                        Ok(()) => eprintln!("autosaved the session to {}", hook_path.display()),
+                        Err(err) => eprintln!("couldn't autosave the session: {}", err),
+                    }
+                }
+            }
+            previous(info)
+        }));
+        Self {
+            file: autosave::Autosave::new(path, every, Instant::now()),
+            latest,
+        }
+    }
+
+    /// whether it's time to autosave again
+    pub fn is_due(&self) -> bool {
+        self.file.is_due(Instant::now())
+    }
+
+    /// save a session to the autosave file, logging failures rather than stopping
+    pub fn save(&mut self, session: Session) {
+        let mut latest = self.latest.lock().expect("Poisoned");
+        let path = self.file.path().display().to_string();
+        match self.file.save(&session::write(&session), Instant::now()) {
+            Ok(()) => info!("autosaved the session to {}", path),
+            Err(err) => error!("couldn't autosave to {}: {}", path, err),
+        }
+        *latest = Some(session);
+    }
+}
+
+/// session autosaved by an earlier run, if it holds anything and is newer than the session
+/// file, restored without asking when restore is set and otherwise only once the user
+/// agrees on a terminal
+pub fn recover(path: &Path, session: Option<&Path>, restore: bool) -> Option<Session> {
+    let recovered = match autosave::recover(path, session) {
+        Ok(recovered) => recovered?,
+        Err(err) => {
+            warn!("couldn't read autosave {}: {}", path.display(), err);
+            return None;
+        }
+    };
+    let autosaved = match session::parse(&recovered.contents) {
+        Ok(autosaved) => autosaved,
+        Err(err) => {
+            warn!("couldn't read autosave {}: {}", path.display(), err);
+            return None;
+        }
+    };
+    if stats::population(&autosaved.grid) == 0 && autosaved.annotations.is_empty() {
+        return None;
+    }
+    let minutes = recovered.age.as_secs() / 60;
+    if !restore {
+        if !std::io::stdin().is_terminal() {
+            info!(
+                "a session was autosaved {} minutes ago, --restore brings it back",
+                minutes
+            );
+            return None;
+        }
+        eprint!(
+            "restore the session autosaved {} minutes ago to {}? [y/N] ",
+            minutes,
+            path.display()
+        );
+        let _ = std::io::stderr().flush();
+        let mut answer = String::new();
+        std::io::stdin().lock().read_line(&mut answer).ok()?;
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            return None;
+        }
+    }
+    info!("restoring the session autosaved to {}", path.display());
+    Some(autosaved)
+}
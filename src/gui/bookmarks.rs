@@ -0,0 +1,157 @@
+// generations saved under number keys to jump back to while exploring or building, along with
+// the part of the board shown so that jumping back shows what was being looked at
+use super::{replace_board, State};
+use log::info;
+use rlife::formats::rle;
+use rlife::input::Action;
+use rlife::render::Viewport;
+use rlife::{transform, Life, VecGrid, DEFAULT_RULE};
+use std::fmt;
+use std::sync::atomic::Ordering;
+
+/// number of bookmark slots, one per key from 1 to 9
+pub const SLOTS: usize = rlife::input::BOOKMARK_SLOTS;
+
+// copy of a generation saved in a slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    /// slot from 1 to SLOTS
+    pub slot: usize,
+    pub generation: u64,
+    pub grid: VecGrid,
+    /// part of the board shown, and with it how far it was zoomed in
+    pub view: Viewport,
+}
+
+impl Bookmark {
+    /// parse a bookmark as written by Display, like
+    /// "bookmark 1 120 64x36 view 16,9,32x18 bo$2bo$3o!", bookmarks without a view showing
+    /// the whole board
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let error = || format!("invalid bookmark '{}'", line.trim());
+        let mut words = line.split_whitespace();
+        if words.next() != Some("bookmark") {
+            return Err(error());
+        }
+        let slot = words
+            .next()
+            .and_then(|slot| slot.parse().ok())
+            .filter(|slot| (1..=SLOTS).contains(slot))
+            .ok_or_else(error)?;
+        let generation = words
+            .next()
+            .and_then(|g| g.parse().ok())
+            .ok_or_else(error)?;
+        let (width, height) = words
+            .next()
+            .and_then(|size| size.split_once('x'))
+            .ok_or_else(error)?;
+        let mut body = words.next().unwrap_or("!");
+        let view = match body {
+            "view" => {
+                let view = words.next().and_then(parse_view).ok_or_else(error)?;
+                body = words.next().unwrap_or("!");
+                Some(view)
+            }
+            _ => None,
+        };
+        let pattern = rle::parse(&format!("x = {}, y = {}\n{}", width, height, body))
+            .map_err(|err| err.to_string())?;
+        let view = view.unwrap_or_else(|| Viewport::whole(&pattern.grid));
+        if view.width == 0
+            || view.height == 0
+            || view.x + view.width > pattern.grid.width()
+            || view.y + view.height > pattern.grid.height()
+        {
+            return Err(error());
+        }
+        Ok(Self {
+            slot,
+            generation,
+            grid: pattern.grid,
+            view,
+        })
+    }
+}
+
+impl fmt::Display for Bookmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the RLE body on one line, without its header
+        let rle = rle::write(&self.grid, DEFAULT_RULE);
+        let body: String = rle.lines().skip(1).collect();
+        write!(
+            f,
+            "bookmark {} {} {}x{} view {},{},{}x{} {}",
+            self.slot,
+            self.generation,
+            self.grid.width(),
+            self.grid.height(),
+            self.view.x,
+            self.view.y,
+            self.view.width,
+            self.view.height,
+            body
+        )
+    }
+}
+
+/// Shift and a number key bookmark the generation along with the part of the board shown,
+/// the number key alone jumps back to it
+pub fn handle(
+    bookmarks: &mut Vec<Bookmark>,
+    actions: &[Action],
+    state: &mut State,
+    view: &mut Viewport,
+) {
+    for &action in actions {
+        match action {
+            Action::Bookmark(slot) => {
+                let curr = state.board.read().expect("Poisoned");
+                bookmarks.retain(|bookmark| bookmark.slot != slot);
+                bookmarks.push(Bookmark {
+                    slot,
+                    generation: state.generation.load(Ordering::Relaxed),
+                    grid: transform::cropped(curr.as_ref(), 0, 0, curr.width(), curr.height()),
+                    view: *view,
+                });
+                info!("bookmarked generation in slot {}", slot);
+                state
+                    .announcer
+                    .action(format!("bookmarked in slot {}", slot));
+            }
+            Action::JumpToBookmark(slot) => {
+                let Some(bookmark) = bookmarks.iter().find(|b| b.slot == slot) else {
+                    continue;
+                };
+                info!(
+                    "jumping to generation {} in slot {}",
+                    bookmark.generation, slot
+                );
+                state.announcer.action(format!(
+                    "jumped to generation {} in slot {}",
+                    bookmark.generation, slot
+                ));
+                replace_board(
+                    state.board.write().expect("Poisoned").as_mut(),
+                    &bookmark.grid,
+                );
+                state.restart(bookmark.generation);
+                *view = bookmark.view;
+            }
+            _ => (),
+        }
+    }
+}
+
+// viewport written like "16,9,32x18", None when it's malformed
+fn parse_view(view: &str) -> Option<Viewport> {
+    let (x, rest) = view.split_once(',')?;
+    let (y, size) = rest.split_once(',')?;
+    let (width, height) = size.split_once('x')?;
+    Some(Viewport {
+        x: x.parse().ok()?,
+        y: y.parse().ok()?,
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+    })
+}
@@ -0,0 +1,63 @@
+// Brian's Brain and the other Generations rules in a window, from random soups
+use crate::cli::messages::{self, Message};
+use crate::cli::{flag_value, size_value};
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode};
+use rlife::generations::{GenerationsLife, GenerationsRule, ALIVE};
+use rlife::multistate::{MultiStateLife, StateGrid};
+use rlife::random::Rng;
+use rlife::{Life, VecGrid};
+use std::error::Error;
+use std::time::Duration;
+
+// time per generation unless --step-time is given, in milliseconds
+const DEFAULT_STEP_TIME: u64 = 100;
+
+// fraction of cells firing in random soups unless --density is given
+const DEFAULT_DENSITY: f64 = 0.2;
+
+/// run Brian's Brain, or another Generations rule given with --rule, from a random soup of
+/// firing cells, ready cells drawn black, firing ones white and refractory ones fading blue,
+/// the left button fires cells and R reseeds, Space pauses and the right arrow steps while
+/// paused
+pub fn brain(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut rule: GenerationsRule = GenerationsRule::BRIANS_BRAIN.parse()?;
+    let (mut width, mut height) = (super::WIDTH, super::HEIGHT);
+    let mut density = DEFAULT_DENSITY;
+    let mut seed = None;
+    let mut step_time = DEFAULT_STEP_TIME;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rule" => rule = flag_value(arg, args.next())?,
+            "--size" => (width, height) = size_value(arg, args.next())?,
+            "--density" => density = flag_value(arg, args.next())?,
+            "--seed" => seed = Some(flag_value(arg, args.next())?),
+            "--step-time" => step_time = flag_value(arg, args.next())?,
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let mut rng = seed.map_or_else(Rng::from_time, Rng::new);
+    let mut soup = move || {
+        let mut firing = VecGrid::new(width, height);
+        firing.fill_random(density, &mut rng);
+        GenerationsLife::new(StateGrid::from_life(&firing, ALIVE), rule)
+    };
+
+    super::multistate::run(
+        "brain",
+        soup(),
+        &rule.palette(),
+        Duration::from_millis(step_time),
+        |brain, window| {
+            if window.is_key_pressed(Key::R, KeyRepeat::No) {
+                *brain = soup();
+            }
+            if window.get_mouse_down(MouseButton::Left) {
+                if let Some((x, y)) = window.get_mouse_pos(MouseMode::Discard) {
+                    brain.set_state(x as usize, y as usize, ALIVE);
+                }
+            }
+        },
+        |brain, generation| messages::format(Message::Status, &[&generation, &brain.population()]),
+    )
+}
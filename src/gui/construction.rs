@@ -0,0 +1,127 @@
+// construction mode for building by hand: exact coordinates, offsets from an origin and an
+// optional grid the cursor snaps to
+use super::{toggled, HEIGHT, WIDTH};
+use crate::cli::announce::Announcer;
+use log::debug;
+use rlife::input::Action;
+
+// color of dead cells on the snap grid, of the origin and of the cell under the cursor
+const GRID_COLOR: u32 = 0x303060;
+const ORIGIN_COLOR: u32 = 0x00c0c0;
+const CURSOR_COLOR: u32 = 0xc000c0;
+
+// origin offsets are measured from, starting in the top left corner, and the spacing of the
+// snap grid, if snapping
+#[derive(Default)]
+pub struct Construction {
+    pub origin: (usize, usize),
+    pub snap: Option<usize>,
+}
+
+impl Construction {
+    /// snap grid point nearest to a cell, or the cell itself when not snapping, the grid is
+    /// anchored at the origin and points are kept within width by height
+    pub fn snapped(&self, (x, y): (usize, usize), width: usize, height: usize) -> (usize, usize) {
+        let n = match self.snap {
+            Some(n) => n as i64,
+            None => return (x, y),
+        };
+        let snap = |v: usize, origin: usize, size: usize| {
+            let offset = v as i64 - origin as i64;
+            // round to the nearest multiple, halves away from the origin
+            let point = origin as i64 + (offset + offset.signum() * n / 2) / n * n;
+            // fall back a step when the nearest point is off the board
+            match point {
+                p if p >= size as i64 => (p - n).max(0),
+                p if p < 0 => (p + n).min(size as i64 - 1),
+                p => p,
+            }
+        };
+        (
+            snap(x, self.origin.0, width) as usize,
+            snap(y, self.origin.1, height) as usize,
+        )
+    }
+
+    /// coordinates of a cell and its offset from the origin
+    pub fn readout(&self, (x, y): (usize, usize)) -> String {
+        format!(
+            "x {} y {}, dx {:+} dy {:+} from origin",
+            x,
+            y,
+            x as i64 - self.origin.0 as i64,
+            y as i64 - self.origin.1 as i64
+        )
+    }
+
+    /// draw the snap grid, origin and cursor over a frame buffer, live cells stay visible
+    pub fn render(&self, cursor: Option<(usize, usize)>, width: usize, buffer: &mut [u32]) {
+        let height = buffer.len() / width;
+        let mark = |buffer: &mut [u32], (x, y): (usize, usize), color: u32| {
+            if x < width && y < height {
+                let pixel = &mut buffer[x + y * width];
+                // half brightness over live cells
+                *pixel = match *pixel {
+                    0 => color,
+                    _ => (color >> 1 & 0x7f7f7f) | 0x808080,
+                };
+            }
+        };
+        if let Some(n) = self.snap {
+            let (ox, oy) = (self.origin.0 % n, self.origin.1 % n);
+            for y in (oy..height).step_by(n) {
+                for x in (ox..width).step_by(n) {
+                    mark(buffer, (x, y), GRID_COLOR);
+                }
+            }
+        }
+        mark(buffer, self.origin, ORIGIN_COLOR);
+        if let Some(cursor) = cursor {
+            mark(buffer, cursor, CURSOR_COLOR);
+        }
+    }
+}
+
+/// toggle construction mode, then set the origin at the mouse and toggle snapping to a grid
+/// of the given spacing on their actions
+pub fn handle(
+    construction: &mut Option<Construction>,
+    actions: &[Action],
+    mouse: Option<(usize, usize)>,
+    snap: usize,
+    announcer: &mut Announcer,
+) {
+    if actions.contains(&Action::ToggleConstruction) {
+        *construction = match construction {
+            Some(_) => None,
+            None => Some(Construction::default()),
+        };
+        debug!("construction mode: {}", construction.is_some());
+        announcer.action(toggled("construction mode", construction.is_some()));
+    }
+    if let Some(construction) = construction.as_mut() {
+        if actions.contains(&Action::SetOrigin) {
+            construction.origin = mouse.unwrap_or(construction.origin);
+            let (x, y) = construction.origin;
+            announcer.action(format!("origin at x {} y {}", x, y));
+        }
+        if actions.contains(&Action::ToggleSnap) {
+            construction.snap = match construction.snap {
+                Some(_) => None,
+                None => Some(snap),
+            };
+            announcer.action(toggled("snapping", construction.snap.is_some()));
+        }
+    }
+}
+
+/// cell the cursor edits, the cell under the mouse put on the snap grid in construction mode
+pub fn cursor(
+    construction: Option<&Construction>,
+    mouse: Option<(usize, usize)>,
+) -> Option<(usize, usize)> {
+    match (construction, mouse) {
+        (Some(construction), Some(mouse)) => Some(construction.snapped(mouse, WIDTH, HEIGHT)),
+        _ => mouse,
+    }
+}
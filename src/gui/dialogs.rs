@@ -0,0 +1,55 @@
+// native file dialogs choosing the pattern to open and the file a session is saved in, as
+// paths can't be typed into the window, shown with the dialogs feature, without it no file
+// is ever chosen
+#[cfg(feature = "dialogs")]
+use crate::cli::messages::{self, Message};
+use std::path::{Path, PathBuf};
+
+/// whether the window was built with file dialogs
+pub const AVAILABLE: bool = cfg!(feature = "dialogs");
+
+// name suggested for sessions saved for the first time
+#[cfg(feature = "dialogs")]
+const SESSION_NAME: &str = "session.rle";
+
+/// pattern file chosen to open, starting in the folder of a file opened before, None when
+/// the dialog is cancelled
+#[cfg(feature = "dialogs")]
+pub fn open_pattern(near: Option<&Path>) -> Option<PathBuf> {
+    let mut dialog = rfd::FileDialog::new()
+        .set_title(messages::text(Message::OpenPattern))
+        .add_filter("Patterns", rlife::formats::EXTENSIONS);
+    if let Some(directory) = near.and_then(Path::parent) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog.pick_file()
+}
+
+/// file chosen to save the session in, suggesting the file it was saved in before, None
+/// when the dialog is cancelled
+#[cfg(feature = "dialogs")]
+pub fn save_session(near: Option<&Path>) -> Option<PathBuf> {
+    let name = near
+        .and_then(Path::file_name)
+        .map_or(SESSION_NAME.into(), |name| name.to_string_lossy());
+    let mut dialog = rfd::FileDialog::new()
+        .set_title(messages::text(Message::SaveSession))
+        .add_filter("Sessions", &["rle"])
+        .set_file_name(name);
+    if let Some(directory) = near.and_then(Path::parent) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog.save_file()
+}
+
+/// without the dialogs feature there's nothing to choose with
+#[cfg(not(feature = "dialogs"))]
+pub fn open_pattern(_near: Option<&Path>) -> Option<PathBuf> {
+    None
+}
+
+/// without the dialogs feature there's nothing to choose with
+#[cfg(not(feature = "dialogs"))]
+pub fn save_session(_near: Option<&Path>) -> Option<PathBuf> {
+    None
+}
@@ -0,0 +1,163 @@
+// edits made by hand: cells painted with the mouse and mirrored by a symmetry, and macros
+// recorded from them, replayed at the cursor in any orientation
+use super::typing::Typing;
+use super::{State, HEIGHT, WIDTH};
+use crate::cli::announce::Announcer;
+use crate::cli::messages::{self, Message};
+use log::{debug, info};
+use rlife::edit::{self, Brush, Edit, Macro, Symmetry};
+use rlife::input::Action;
+use rlife::transform::Transform;
+
+// recorded macros, the one being recorded, the one replayed and the orientation it's replayed
+// in as a position in Transform::ALL, the symmetry edits are mirrored by as a position in
+// Symmetry::ALL and the stroke painted with the mouse
+pub struct Editing {
+    pub macros: Vec<Macro>,
+    recording: Option<Macro>,
+    selected: usize,
+    orientation: usize,
+    symmetry: usize,
+    brush: Brush,
+}
+
+impl Editing {
+    /// edit without symmetry, replaying the last of the macros of a session
+    pub fn new(macros: Vec<Macro>) -> Self {
+        Self {
+            selected: macros.len().saturating_sub(1),
+            macros,
+            recording: None,
+            orientation: 0,
+            symmetry: 0,
+            brush: Brush::default(),
+        }
+    }
+
+    /// record, select, turn and replay macros and change the symmetry on their actions,
+    /// returns the macro to type the name of once its recording stopped
+    pub fn handle(
+        &mut self,
+        actions: &[Action],
+        cursor: Option<(usize, usize)>,
+        state: &mut State,
+    ) -> Option<Typing> {
+        let mut typing = None;
+
+        // start recording edits into a macro, then stop and type its name
+        if actions.contains(&Action::ToggleRecording) {
+            self.recording = match self.recording.take() {
+                Some(recorded) if !recorded.edits().is_empty() => {
+                    typing = Some(Typing::MacroName(String::new(), recorded.edits().to_vec()));
+                    None
+                }
+                Some(_) => None,
+                None => Some(Macro::new("")),
+            };
+            debug!("recording macro: {}", self.recording.is_some());
+            if self.recording.is_some() {
+                state.announcer.action("recording a macro");
+            }
+        }
+
+        // replay the selected macro at the cursor, Tab selects the next macro and the
+        // period key turns the replay to the next orientation
+        if actions.contains(&Action::NextMacro) && !self.macros.is_empty() {
+            self.selected = (self.selected + 1) % self.macros.len();
+            let name = self.macros[self.selected].name();
+            state.announcer.action(format!("selected macro {}", name));
+        }
+        if actions.contains(&Action::TurnReplay) {
+            self.orientation = (self.orientation + 1) % Transform::ALL.len();
+            let orientation = Transform::ALL[self.orientation];
+            state.announcer.action(format!("replaying {}", orientation));
+        }
+        if let (true, Some(replayed), Some((x, y))) = (
+            actions.contains(&Action::ReplayMacro),
+            self.macros.get(self.selected),
+            cursor,
+        ) {
+            debug!("replaying macro {} at ({}, {})", replayed.name(), x, y);
+            state.announcer.action(format!(
+                "replayed macro {} at x {} y {}",
+                replayed.name(),
+                x,
+                y
+            ));
+            let mut curr = state.board.write().expect("Poisoned");
+            for (x, y, is_alive) in replayed.placed(x, y, Transform::ALL[self.orientation]) {
+                let symmetry = Symmetry::ALL[self.symmetry];
+                edit::set_mirrored(curr.as_mut(), x, y, is_alive, symmetry);
+                // replays are recorded too, so macros can be built from smaller ones
+                if let Some(recording) = self.recording.as_mut().filter(|_| x < WIDTH && y < HEIGHT)
+                {
+                    recording.record(x, y, is_alive);
+                }
+            }
+            drop(curr);
+            state.explain_again();
+        }
+
+        // mirror every edit by the next symmetry
+        if actions.contains(&Action::NextSymmetry) {
+            self.symmetry = (self.symmetry + 1) % Symmetry::ALL.len();
+            debug!("editing with {}", Symmetry::ALL[self.symmetry]);
+            state
+                .announcer
+                .action(format!("editing with {}", Symmetry::ALL[self.symmetry]));
+        }
+        typing
+    }
+
+    /// keep the macro just recorded under the name typed for it, numbered when none was
+    pub fn name(&mut self, name: &str, edits: Vec<Edit>, announcer: &mut Announcer) {
+        let name = match name.trim() {
+            "" => format!("macro-{}", self.macros.len() + 1),
+            name => name.to_string(),
+        };
+        let recorded = Macro::from_edits(&name, edits);
+        let recorded_action = format!(
+            "recorded macro {} of {} edits",
+            recorded.name(),
+            recorded.edits().len()
+        );
+        info!("{}", recorded_action);
+        announcer.action(recorded_action);
+        self.macros.push(recorded);
+        self.selected = self.macros.len() - 1;
+    }
+
+    /// paint the opposite state of the cell pressed on with either button, returns whether a
+    /// cell was painted
+    pub fn paint(&mut self, pressed: bool, cursor: Option<(usize, usize)>, state: &State) -> bool {
+        let curr = state.board.read().expect("Poisoned");
+        let Some((x, y, is_alive)) = self.brush.update(pressed, cursor, curr.as_ref()) else {
+            return false;
+        };
+        drop(curr);
+        debug!("setting Cell ({}, {}): {}", x, y, is_alive);
+        let mut curr = state.board.write().expect("Poisoned");
+        edit::set_mirrored(curr.as_mut(), x, y, is_alive, Symmetry::ALL[self.symmetry]);
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record(x, y, is_alive);
+        }
+        true
+    }
+
+    /// symmetry and the macro being recorded or replayed, for the title
+    pub fn status(&self) -> impl Iterator<Item = String> {
+        let symmetry = (self.symmetry != 0).then(|| Symmetry::ALL[self.symmetry].to_string());
+        let macros = match (&self.recording, self.macros.get(self.selected)) {
+            (Some(recording), _) => Some(messages::format(
+                Message::RecordingMacro,
+                &[&recording.edits().len()],
+            )),
+            (None, Some(selected)) => Some(messages::format(
+                Message::SelectedMacro,
+                &[&selected.name(), &Transform::ALL[self.orientation]],
+            )),
+            (None, None) => None,
+        };
+        symmetry.into_iter().chain(macros)
+    }
+}
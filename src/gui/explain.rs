@@ -0,0 +1,66 @@
+// educational explain mode: one generation animated phase by phase
+use rlife::explain::{Explanation, Phase};
+use rlife::Life;
+use std::time::{Duration, Instant};
+
+// time each phase stays on screen
+const PHASE_TIME: Duration = Duration::from_millis(800);
+
+// colors of live cells, cells being born and cells dying
+const ALIVE: u32 = 0xffffff;
+const BIRTH: u32 = 0x20e040;
+const DEATH: u32 = 0xe02020;
+
+// generation being explained along with the time its current phase was shown
+pub struct Explain {
+    explanation: Explanation,
+    shown_at: Instant,
+}
+
+impl Explain {
+    /// start explaining the next generation of a grid
+    pub fn new(life: &impl Life) -> Self {
+        Self {
+            explanation: Explanation::new(life),
+            shown_at: Instant::now(),
+        }
+    }
+
+    /// move to the next phase once the current one has been shown long enough,
+    /// returns whether a generation was committed
+    pub fn tick(&mut self, life: &mut impl Life) -> bool {
+        if self.shown_at.elapsed() < PHASE_TIME {
+            return false;
+        }
+        self.shown_at = Instant::now();
+        self.explanation.advance(life) == Phase::Committed
+    }
+
+    /// draw the current phase: neighbor counts shade dead cells, then births and deaths
+    /// are highlighted, then the new generation is shown as is
+    pub fn render(&self, life: &impl Life, buffer: &mut [u32]) {
+        let width = life.width();
+        for (i, pixel) in buffer.iter_mut().enumerate() {
+            let (x, y) = (i % width, i / width);
+            *pixel = match (self.explanation.phase(), life.is_alive(x, y)) {
+                (_, true) => ALIVE,
+                (Phase::Counted, false) => {
+                    // faint blue, brighter with more neighbors
+                    self.explanation.neighbors(x, y) as u32 * 0x18
+                }
+                _ => 0,
+            };
+        }
+        if self.explanation.phase() == Phase::Decided {
+            let mut paint = |&(x, y): &(usize, usize), color| buffer[y * width + x] = color;
+            self.explanation
+                .births()
+                .iter()
+                .for_each(|cell| paint(cell, BIRTH));
+            self.explanation
+                .deaths()
+                .iter()
+                .for_each(|cell| paint(cell, DEATH));
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// files the session is saved in and the last pattern was opened from, chosen again in dialogs
+// with the dialogs feature
+use super::annotations::Annotation;
+use super::bookmarks::Bookmark;
+use super::{dialogs, place_pattern, save_session, Board, State};
+use log::error;
+use rlife::edit::Macro;
+use rlife::input::Action;
+use std::path::PathBuf;
+
+// announced when opening or saving needs a file dialog the window was built without
+const NO_DIALOGS: &str = "choosing files needs the dialogs feature";
+
+// file the session is saved in on exit, if any, and the pattern file opened last
+pub struct Files {
+    pub session: Option<PathBuf>,
+    pattern: Option<PathBuf>,
+}
+
+impl Files {
+    /// save the session and open patterns next to the files given on the command line
+    pub fn new(session: Option<PathBuf>, pattern: Option<PathBuf>) -> Self {
+        Self { session, pattern }
+    }
+
+    /// save the session and open a pattern on their actions
+    pub fn handle(
+        &mut self,
+        actions: &[Action],
+        state: &mut State,
+        annotations: &[Annotation],
+        bookmarks: &[Bookmark],
+        macros: &[Macro],
+    ) {
+        // save the session now, in a file chosen in a dialog when it has none yet or with
+        // Shift, it's saved on exit too
+        let save_as = actions.contains(&Action::SaveSessionAs);
+        if save_as || actions.contains(&Action::SaveSession) {
+            if save_as || self.session.is_none() {
+                match dialogs::save_session(self.session.as_deref()) {
+                    Some(path) => self.session = Some(path),
+                    None if !dialogs::AVAILABLE => state.announcer.action(NO_DIALOGS),
+                    None => (),
+                }
+            }
+            if let Some(path) = self.session.as_ref() {
+                let curr = state.board.read().expect("Poisoned");
+                save_session(path, curr.as_ref(), annotations, bookmarks, macros);
+                state.announcer.action("saved the session");
+            }
+        }
+
+        // start over from a pattern file chosen in a dialog
+        if actions.contains(&Action::OpenPattern) {
+            match dialogs::open_pattern(self.pattern.as_deref()) {
+                Some(path) => {
+                    // the board is only replaced once the pattern could be placed
+                    let mut opened: Box<Board> = Box::default();
+                    match place_pattern(opened.as_mut(), &path) {
+                        Ok(()) => {
+                            *state.board.write().expect("Poisoned") = opened;
+                            state.restart(0);
+                            let name = path.file_name().unwrap_or_default();
+                            state
+                                .announcer
+                                .action(format!("opened {}", name.to_string_lossy()))
+                        }
+                        Err(err) => {
+                            error!("{}", err);
+                            state
+                                .announcer
+                                .action(format!("couldn't open {}", path.display()));
+                        }
+                    }
+                    self.pattern = Some(path);
+                }
+                None if !dialogs::AVAILABLE => state.announcer.action(NO_DIALOGS),
+                None => (),
+            }
+        }
+    }
+}
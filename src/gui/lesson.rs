@@ -0,0 +1,102 @@
+// lessons written by educators: boards stepped through one after another, each with a
+// question or instruction, annotations, what moves on and what the board should show by
+// then, in files like
+//
+//     step
+//     prompt how many cells does the blinker have after a generation? press Space
+//     board blinker.rle
+//     stamp glider.rle 20 10
+//     label 2 2 blinker
+//     advance generations 1
+//     expect population 3
+//
+// patterns are read relative to the lesson file, boards start paused unless a step says
+// running, and blank lines and lines starting with # are ignored
+use super::annotations::Annotation;
+use super::session::Session;
+use super::tutorial::{Advance, Expect, Step, Tutorial};
+use rlife::formats;
+use rlife::transform::Transform;
+use rlife::{Life, VecGrid};
+use std::error::Error;
+use std::path::Path;
+
+/// read a lesson file
+pub fn load(path: impl AsRef<Path>) -> Result<Tutorial, Box<dyn Error>> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)?;
+    parse(&contents, path.parent().unwrap_or(Path::new("")))
+}
+
+/// parse the contents of a lesson file, reading patterns relative to a directory
+pub fn parse(contents: &str, dir: &Path) -> Result<Tutorial, Box<dyn Error>> {
+    let mut steps = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: String| format!("line {}: {}", i + 1, message);
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        if keyword == "step" {
+            steps.push(Step {
+                prompt: String::new(),
+                paused: true,
+                advance: Advance::Enter,
+                expect: Vec::new(),
+                session: Session {
+                    grid: VecGrid::new(super::WIDTH, super::HEIGHT),
+                    annotations: Vec::new(),
+                    bookmarks: Vec::new(),
+                    macros: Vec::new(),
+                },
+            });
+            continue;
+        }
+        let step = steps
+            .last_mut()
+            .ok_or_else(|| error("expected a step line first".to_string()))?;
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        let number = |word: &str| {
+            word.parse::<usize>()
+                .map_err(|_| error(format!("invalid number '{}'", word)))
+        };
+        match (keyword, words.as_slice()) {
+            ("prompt", _) => step.prompt = rest.trim().to_string(),
+            ("running", []) => step.paused = false,
+            ("board", [file]) => {
+                let pattern = formats::load(dir.join(file))
+                    .map_err(|err| error(format!("couldn't read {}: {}", file, err)))?;
+                step.session.grid = VecGrid::new(super::WIDTH, super::HEIGHT);
+                step.session
+                    .grid
+                    .blit(&pattern.grid, 0, 0, Transform::Identity);
+            }
+            ("stamp", [file, x, y]) => {
+                let pattern = formats::load(dir.join(file))
+                    .map_err(|err| error(format!("couldn't read {}: {}", file, err)))?;
+                step.session
+                    .grid
+                    .blit(&pattern.grid, number(x)?, number(y)?, Transform::Identity);
+            }
+            ("label" | "rect", _) => step
+                .session
+                .annotations
+                .push(Annotation::parse(line).map_err(error)?),
+            ("advance", ["enter"]) => step.advance = Advance::Enter,
+            ("advance", ["pause"]) => step.advance = Advance::Pause,
+            ("advance", ["generations", n]) => {
+                step.advance = Advance::Generations(number(n)? as u64)
+            }
+            ("advance", ["population", n]) => step.advance = Advance::Population(number(n)?),
+            ("expect", ["population", n]) => step.expect.push(Expect::Population(number(n)?)),
+            ("expect", ["alive", x, y]) => step.expect.push(Expect::Alive(number(x)?, number(y)?)),
+            ("expect", ["dead", x, y]) => step.expect.push(Expect::Dead(number(x)?, number(y)?)),
+            _ => return Err(error(format!("invalid lesson line '{}'", line)).into()),
+        }
+    }
+    if steps.is_empty() {
+        return Err("lesson has no steps".into());
+    }
+    Ok(Tutorial::new(steps))
+}
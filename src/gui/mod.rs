@@ -0,0 +1,811 @@
+// interactive window showing the simulation
+use crate::cli::announce::Announcer;
+use crate::cli::messages::{self, Message};
+use crate::cli::priority::Priority;
+use log::{debug, error, info, warn};
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+use rlife::edit::Macro;
+use rlife::formats;
+use rlife::input::{self, Action, ActionGuard, Guard, InputEvent, Keymap, Verdict};
+use rlife::random::Rng;
+use rlife::render::FrameDrop;
+use rlife::transform;
+use rlife::{stats, History, Life, LifeGrid, Rule};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, RwLock};
+use std::time::{Duration, Instant};
+
+mod annotations;
+mod ant;
+mod art;
+#[cfg(unix)]
+mod attach;
+mod autosave;
+mod bookmarks;
+mod brain;
+mod construction;
+mod dialogs;
+mod editing;
+mod explain;
+mod files;
+mod lesson;
+mod multistate;
+mod neighbors;
+mod playback;
+mod reseed;
+mod session;
+mod transition;
+mod tutorial;
+mod typing;
+mod view;
+mod war;
+mod wireworld;
+
+pub use ant::ant;
+#[cfg(unix)]
+pub use attach::attach;
+pub use brain::brain;
+pub use transition::transition;
+pub use war::war;
+pub use wireworld::wireworld;
+
+// width of map grid
+const WIDTH: usize = 64;
+
+// height of map grid
+const HEIGHT: usize = 36;
+
+// time per step of simulation
+const SIM_STEP_TIME: Duration = Duration::from_millis(300);
+
+// generations kept for stepping back
+const HISTORY_GENERATIONS: usize = 256;
+
+// fraction of cells alive in random soups
+const SOUP_DENSITY: f64 = 0.35;
+
+// generations between drawn frames when time-lapse is switched on without --time-lapse
+const DEFAULT_TIME_LAPSE: u64 = 10;
+
+// spacing of the construction mode snap grid unless --snap is given
+const DEFAULT_SNAP: usize = 8;
+
+// furthest the board can be zoomed in, the size of the board being divisible by every zoom
+const MAX_ZOOM: usize = 4;
+
+// time a settled board stays on screen before it is reseeded, unless --reseed-after is given
+const DEFAULT_RESEED_DELAY: Duration = Duration::from_secs(3);
+
+// settings chosen on the command line
+#[derive(Default)]
+struct Options {
+    /// start in art mode
+    art: bool,
+
+    /// reseed automatically once the board dies out or settles, after this delay
+    reseed_after: Option<Duration>,
+
+    /// simulate at full speed, drawing only every this many generations
+    time_lapse: Option<u64>,
+
+    /// spacing of the construction mode snap grid
+    snap: Option<usize>,
+
+    /// session file loaded at startup if it exists and saved on exit
+    session: Option<PathBuf>,
+
+    /// time between autosaves of the session, zero turns them off
+    autosave: Option<Duration>,
+
+    /// restore an autosaved session without asking
+    restore: bool,
+
+    /// take the guided tour
+    tutorial: bool,
+
+    /// lesson file stepped through like the tour
+    lesson: Option<PathBuf>,
+
+    /// announce actions and the state on stdout
+    announce: bool,
+
+    /// command speaking announcements
+    speak: Option<String>,
+
+    /// key binding file overriding the default bindings
+    keys: Option<PathBuf>,
+
+    /// what it takes to quit, start over or open a pattern over the board
+    guard: Guard,
+
+    /// pattern file placed in the middle of the board at startup
+    pattern: Option<PathBuf>,
+
+    /// seed of the random soups, taken from the clock when not given
+    seed: Option<u64>,
+
+    /// niceness and processors of the simulation thread
+    priority: Priority,
+
+    /// what to do when the simulation runs ahead of the frames drawn
+    frame_drop: FrameDrop,
+}
+
+impl Options {
+    // parse the GUI options
+    fn parse(args: &[String]) -> Result<Self, Box<dyn Error>> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--art" => options.art = true,
+                "--reseed-after" => {
+                    let seconds: f64 = crate::cli::flag_value(arg, args.next())?;
+                    options.reseed_after = Some(Duration::from_secs_f64(seconds));
+                }
+                "--time-lapse" => {
+                    let every: u64 = crate::cli::flag_value(arg, args.next())?;
+                    options.time_lapse = Some(every.max(1));
+                }
+                "--snap" => {
+                    let spacing: usize = crate::cli::flag_value(arg, args.next())?;
+                    options.snap = Some(spacing.max(1));
+                }
+                "--session" => {
+                    options.session =
+                        Some(crate::cli::flag_value::<String>(arg, args.next())?.into())
+                }
+                "--autosave" => {
+                    let seconds: f64 = crate::cli::flag_value(arg, args.next())?;
+                    options.autosave = Some(Duration::from_secs_f64(seconds));
+                }
+                "--restore" => options.restore = true,
+                "--tutorial" => options.tutorial = true,
+                "--announce" => options.announce = true,
+                "--speak" => options.speak = Some(crate::cli::flag_value(arg, args.next())?),
+                "--pattern" => {
+                    options.pattern =
+                        Some(crate::cli::flag_value::<String>(arg, args.next())?.into())
+                }
+                "--keys" => {
+                    options.keys = Some(crate::cli::flag_value::<String>(arg, args.next())?.into())
+                }
+                "--guard" => options.guard = crate::cli::flag_value(arg, args.next())?,
+                "--lesson" => {
+                    options.lesson =
+                        Some(crate::cli::flag_value::<String>(arg, args.next())?.into())
+                }
+                "--seed" => options.seed = Some(crate::cli::flag_value(arg, args.next())?),
+                "--sim-nice" => options
+                    .priority
+                    .set_nice(&crate::cli::flag_value::<String>(arg, args.next())?)?,
+                "--sim-cpus" => options
+                    .priority
+                    .set_cpus(&crate::cli::flag_value::<String>(arg, args.next())?)?,
+                "--frame-drop" => options.frame_drop = crate::cli::flag_value(arg, args.next())?,
+                _ => {
+                    return Err(format!(
+                        "unknown option '{}'\n{}",
+                        arg,
+                        messages::text(Message::Usage)
+                    )
+                    .into())
+                }
+            }
+        }
+        // tours replace the board, which would then be saved over the session
+        if options.tutorial && options.lesson.is_some() {
+            return Err("--tutorial can't be combined with --lesson".into());
+        }
+        if (options.tutorial || options.lesson.is_some()) && options.session.is_some() {
+            return Err("--tutorial and --lesson can't be combined with --session".into());
+        }
+        if (options.tutorial || options.lesson.is_some()) && options.pattern.is_some() {
+            return Err("--tutorial and --lesson can't be combined with --pattern".into());
+        }
+        Ok(options)
+    }
+}
+
+// commands sent from the I/O thread to the simulation thread
+enum Command {
+    /// pause or resume the simulation
+    SetPaused(bool),
+    /// change the time per step of simulation, zero runs at full speed
+    SetStepTime(Duration),
+    /// go back to the previous generation if it is kept
+    StepBack,
+}
+
+// board shown in the window
+type Board = LifeGrid<WIDTH, HEIGHT>;
+
+// simulation as the modes of the window see and change it, along with the announcements of
+// what they do
+struct State {
+    /// board shared with the simulation thread
+    board: Arc<RwLock<Box<Board>>>,
+    /// generation of the board, shared with the simulation thread
+    generation: Arc<AtomicU64>,
+    /// commands to the simulation thread, dropped when the window closes
+    commands: mpsc::Sender<Command>,
+    /// whether the user paused the simulation
+    paused: bool,
+    /// generation being explained if any, the simulation thread is paused while explaining
+    explain: Option<explain::Explain>,
+    /// whether the board settled
+    reseed: reseed::Reseed,
+    /// generation last drawn
+    drawn_generation: u64,
+    /// announcements of actions for screen readers, the last one is shown in the title too
+    announcer: Announcer,
+}
+
+impl State {
+    // start explaining the board over after it was edited, if explaining
+    fn explain_again(&mut self) {
+        if self.explain.is_some() {
+            let explain = explain::Explain::new(self.board.read().expect("Poisoned").as_ref());
+            self.explain = Some(explain);
+        }
+    }
+
+    // carry on from a board put in place of the one simulated, at a generation
+    fn restart(&mut self, generation: u64) {
+        self.generation.store(generation, Ordering::Relaxed);
+        self.drawn_generation = generation;
+        self.explain_again();
+        self.reseed.reset();
+    }
+}
+
+// simulate life, stepping a copy of the shared grid that keeps the last generations to go
+// back to, and sharing the bytes the history takes, holding back from running further ahead
+// of the generation last shown than the frame drop policy allows
+fn sim_task<T: Life>(
+    grid: Arc<RwLock<Box<T>>>,
+    mut history: History<T>,
+    commands: mpsc::Receiver<Command>,
+    generation: Arc<AtomicU64>,
+    memory: Arc<AtomicUsize>,
+    frame_drop: FrameDrop,
+    shown: Arc<AtomicU64>,
+) {
+    // time last update was made
+    let mut last_update = Instant::now();
+    let mut step_time = SIM_STEP_TIME;
+    let mut paused = false;
+    loop {
+        // while paused there is nothing to do until the next command
+        let command = match paused {
+            true => commands
+                .recv()
+                .map_err(|_| mpsc::TryRecvError::Disconnected),
+            false => commands.try_recv(),
+        };
+        match command {
+            Ok(Command::SetPaused(pause)) => {
+                paused = pause;
+                info!(
+                    "received {} signal",
+                    if paused { "pause" } else { "resume" }
+                );
+            }
+            Ok(Command::SetStepTime(time)) => {
+                info!("setting step time to {:?}", time);
+                step_time = time;
+            }
+            Ok(Command::StepBack)
+                if sync_history(&mut history, &grid, &generation) && history.undo() =>
+            {
+                debug!("stepped back to generation {}", history.generation());
+                replace_board(grid.write().expect("Poisoned").as_mut(), history.life());
+                generation.store(history.generation(), Ordering::Relaxed);
+                memory.store(history.memory_usage(), Ordering::Relaxed);
+            }
+            // simulate next step
+            Err(mpsc::TryRecvError::Empty)
+                if step_time <= last_update.elapsed()
+                    && frame_drop.may_step(
+                        generation.load(Ordering::Relaxed),
+                        shown.load(Ordering::Relaxed),
+                    ) =>
+            {
+                debug!("generating next generation");
+                sync_history(&mut history, &grid, &generation);
+                history.step();
+                debug!("updating map");
+                replace_board(grid.write().expect("Poisoned").as_mut(), history.life());
+                generation.store(history.generation(), Ordering::Relaxed);
+                memory.store(history.memory_usage(), Ordering::Relaxed);
+                last_update = Instant::now();
+            }
+            // return on channel disconnection, when program ends
+            Err(mpsc::TryRecvError::Disconnected) => {
+                info!("received disconnect signal");
+                return;
+            }
+            // do nothing if it's not time for the next step or there's nothing to step back to
+            _ => (),
+        }
+    }
+}
+
+// bring the board of the history up to date with the shared grid, edited or replaced by the
+// window since the last step, forgetting the kept generations when the window moved to
+// another generation, false if it did
+fn sync_history<T: Life>(
+    history: &mut History<T>,
+    grid: &RwLock<Box<T>>,
+    generation: &AtomicU64,
+) -> bool {
+    replace_board(history.life_mut(), grid.read().expect("Poisoned").as_ref());
+    let current = generation.load(Ordering::Relaxed);
+    if current != history.generation() {
+        history.reset(current);
+        return false;
+    }
+    true
+}
+
+// keys pressed since the last frame, repeats of held keys included
+fn input_events(window: &Window) -> Vec<InputEvent> {
+    let shift = window.is_key_down(Key::LeftShift) || window.is_key_down(Key::RightShift);
+    let first = window.get_keys_pressed(KeyRepeat::No).unwrap_or_default();
+    window
+        .get_keys_pressed(KeyRepeat::Yes)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|key| {
+            Some(InputEvent {
+                key: input_key(key)?,
+                shift,
+                repeat: !first.contains(&key),
+            })
+        })
+        .collect()
+}
+
+// key of a minifb key, None for keys no action can be bound to
+fn input_key(key: Key) -> Option<input::Key> {
+    use Key::*;
+    const DIGITS: [Key; 10] = [Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9];
+    const LETTERS: [Key; 26] = [
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    ];
+    const SYMBOLS: [(Key, char); 11] = [
+        (Equal, '='),
+        (Minus, '-'),
+        (Period, '.'),
+        (Comma, ','),
+        (Slash, '/'),
+        (Semicolon, ';'),
+        (Apostrophe, '\''),
+        (LeftBracket, '['),
+        (RightBracket, ']'),
+        (Backslash, '\\'),
+        (Backquote, '`'),
+    ];
+    let c = if let Some(i) = DIGITS.iter().position(|&k| k == key) {
+        (b'0' + i as u8) as char
+    } else if let Some(i) = LETTERS.iter().position(|&k| k == key) {
+        (b'a' + i as u8) as char
+    } else if let Some(&(_, c)) = SYMBOLS.iter().find(|&&(k, _)| k == key) {
+        c
+    } else {
+        return match key {
+            Space => Some(input::Key::Space),
+            Enter => Some(input::Key::Enter),
+            Escape => Some(input::Key::Escape),
+            Tab => Some(input::Key::Tab),
+            Backspace => Some(input::Key::Backspace),
+            Delete => Some(input::Key::Delete),
+            Left => Some(input::Key::Left),
+            Right => Some(input::Key::Right),
+            Up => Some(input::Key::Up),
+            Down => Some(input::Key::Down),
+            _ => None,
+        };
+    };
+    Some(input::Key::Char(c))
+}
+
+/// open the window and run until it is closed
+pub fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let options = Options::parse(args)?;
+    info!("starting up");
+
+    // setup window
+    info!("setting up window");
+    let mut window = Window::new(
+        "Conway's Game of Life - ESC to exit",
+        WIDTH,
+        HEIGHT,
+        WindowOptions {
+            scale: minifb::Scale::X16,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    window.limit_update_rate(Some(Duration::from_millis(30)));
+    let (typed_tx, typed_rx) = mpsc::channel();
+    window.set_input_callback(Box::new(typing::Typed(typed_tx)));
+
+    // setup shared state
+    info!("setting up thread shared state");
+    let (command_tx, command_rx) = mpsc::channel();
+    let mut state = State {
+        board: Arc::new(RwLock::new(Box::default())),
+        generation: Arc::new(AtomicU64::new(0)),
+        commands: command_tx,
+        paused: false,
+        explain: None,
+        reseed: reseed::Reseed::new(options.reseed_after.unwrap_or(DEFAULT_RESEED_DELAY)),
+        drawn_generation: 0,
+        announcer: Announcer::new(options.announce, options.speak.clone()),
+    };
+    let memory = Arc::new(AtomicUsize::new(0));
+    // generation last drawn or skipped on purpose in time-lapse
+    let shown = Arc::new(AtomicU64::new(0));
+
+    // a session autosaved by a run that crashed or was quit by mistake comes back first, tours
+    // replace the board and are never autosaved
+    let touring = options.tutorial || options.lesson.is_some();
+    let autosave_path = autosave::path(options.session.as_deref());
+    let restored = match touring {
+        true => None,
+        false => autosave::recover(&autosave_path, options.session.as_deref(), options.restore),
+    };
+    let loaded = match (restored, options.session.as_ref()) {
+        (Some(restored), _) => Some(restored),
+        (None, Some(path)) if path.exists() => {
+            info!("loading session {}", path.display());
+            Some(session::load(path)?)
+        }
+        (None, _) => None,
+    };
+    let (annotations, mut bookmarks, macros) = match loaded {
+        Some(session) => {
+            let mut curr = state.board.write().expect("Poisoned");
+            stats::live_cells(&session.grid)
+                .filter(|&(x, y)| x < WIDTH && y < HEIGHT)
+                .for_each(|(x, y)| curr.set_cell(x, y, true));
+            (session.annotations, session.bookmarks, session.macros)
+        }
+        None => Default::default(),
+    };
+
+    // pattern centered on the board, over the session's
+    if let Some(path) = &options.pattern {
+        place_pattern(state.board.write().expect("Poisoned").as_mut(), path)?;
+    }
+
+    // annotations marking structures, edits and recorded macros, and the files the session
+    // is saved in and patterns are opened from
+    let mut marking = annotations::Marking::new(annotations);
+    let mut editing = editing::Editing::new(macros);
+    let mut files = files::Files::new(options.session.clone(), options.pattern.clone());
+
+    // autosaves every so often, on exit and from a panic hook with the board of the moment,
+    // unless touring or turned off with --autosave 0
+    let every = options.autosave.unwrap_or(autosave::DEFAULT_EVERY);
+    let mut autosave = (!touring && !every.is_zero()).then(|| {
+        let curr = state.board.clone();
+        autosave::Autosave::new(autosave_path, every, move || {
+            let curr = curr.try_read().ok()?;
+            Some(transform::cropped(curr.as_ref(), 0, 0, WIDTH, HEIGHT))
+        })
+    });
+
+    // setup simulation thread
+    info!("setting up simulation thread");
+    let life_sim_thread = {
+        // clone Arc to share state
+        let curr = state.board.clone();
+        let generation = state.generation.clone();
+        let memory = memory.clone();
+        let shown = shown.clone();
+        let (priority, frame_drop) = (options.priority.clone(), options.frame_drop);
+
+        // create new thread
+        std::thread::spawn(move || {
+            info!("simulation thread started");
+            if let Err(err) = priority.apply() {
+                warn!("{}", err);
+            }
+            sim_task(
+                curr,
+                History::new(Board::default(), Board::default(), HISTORY_GENERATIONS),
+                command_rx,
+                generation,
+                memory,
+                frame_drop,
+                shown,
+            );
+            info!("simulation thread finished");
+        })
+    };
+
+    // what the window shows, how the simulation moves on and the soups it starts over from
+    let mut view = view::View::new(options.art);
+    let mut playback = playback::Playback::new(options.time_lapse, shown, &state);
+    let rng = options.seed.map_or_else(Rng::from_time, Rng::new);
+    let mut seeding = reseed::Seeding::new(rng, options.reseed_after.is_some());
+    if options.art {
+        seeding.fill(state.board.write().expect("Poisoned").as_mut());
+        window.set_cursor_visibility(false);
+    }
+
+    // text being typed, construction mode state, None when it is off, and the spacing of its
+    // snap grid
+    let mut typing: Option<typing::Typing> = None;
+    let mut construction: Option<construction::Construction> = None;
+    let snap = options.snap.unwrap_or(DEFAULT_SNAP);
+
+    // actions of the keys
+    let keymap = match &options.keys {
+        Some(path) => {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|err| format!("couldn't read {}: {}", path.display(), err))?;
+            Keymap::parse(&contents).map_err(|err| format!("{}: {}", path.display(), err))?
+        }
+        None => Keymap::default(),
+    };
+    let mut guard = ActionGuard::new(options.guard);
+
+    // guided tour or lesson
+    let mut tour = tutorial::Tour::new(match &options.lesson {
+        Some(path) => Some(lesson::load(path)?),
+        None => options.tutorial.then(tutorial::Tutorial::tour),
+    });
+
+    // I/O thread
+    info!("starting I/O handling");
+    while window.is_open() {
+        // manage user input
+        let events = input_events(&window);
+        let actions = actions(&events, &typing, &keymap, &mut guard, &mut state.announcer);
+        if actions.contains(&Action::Quit) {
+            break;
+        }
+        let mouse = window
+            .get_mouse_pos(MouseMode::Discard)
+            .map(|(x, y)| view.cell((x as usize, y as usize)));
+        let cursor = construction::cursor(construction.as_ref(), mouse);
+
+        // Enter moves the tour on unless it ends typing
+        let enter = typing.is_none() && actions.contains(&Action::Continue);
+
+        // typed text, keys only act once nothing is being typed
+        let typed: String = typed_rx.try_iter().collect();
+        if typing.is_some() {
+            match typing::update(&mut typing, &typed, &events) {
+                Some(typing::Typing::Annotation(annotation)) => {
+                    marking.add(annotation, &mut state.announcer)
+                }
+                Some(typing::Typing::MacroName(name, edits)) => {
+                    editing.name(&name, edits, &mut state.announcer)
+                }
+                None => (),
+            }
+        } else {
+            playback.handle(&actions, &mut state);
+            seeding.handle(&actions, &mut state);
+            construction::handle(
+                &mut construction,
+                &actions,
+                mouse,
+                snap,
+                &mut state.announcer,
+            );
+            let annotated = marking.handle(&actions, mouse, cursor, &mut state.announcer);
+            bookmarks::handle(&mut bookmarks, &actions, &mut state, &mut view.viewport);
+            view.handle(&actions, mouse, &mut window, &mut state.announcer);
+            typing = editing.handle(&actions, cursor, &mut state).or(annotated);
+            let (annotations, macros) = (&marking.annotations, &editing.macros);
+            files.handle(&actions, &mut state, annotations, &bookmarks, macros);
+        }
+
+        // mouse input
+        let pressed =
+            window.get_mouse_down(MouseButton::Left) || window.get_mouse_down(MouseButton::Right);
+        let edited = editing.paint(pressed, cursor, &state);
+
+        tour.follow(enter, &mut state);
+
+        if let Some(autosave) = autosave.as_mut().filter(|autosave| autosave.is_due()) {
+            let curr = state.board.read().expect("Poisoned");
+            let session = snapshot(
+                curr.as_ref(),
+                &marking.annotations,
+                &bookmarks,
+                &editing.macros,
+            );
+            autosave.save(session);
+        }
+
+        let bytes = memory.load(Ordering::Relaxed);
+        let modes = [
+            tour.status(),
+            playback.status(),
+            (bytes != 0).then(|| messages::format(Message::Memory, &[&stats::format_bytes(bytes)])),
+            construction
+                .as_ref()
+                .zip(cursor)
+                .map(|(construction, cursor)| construction.readout(cursor)),
+        ];
+        let modes = modes.into_iter().flatten().chain(editing.status());
+        window.set_title(&title(&mut state, modes));
+
+        playback.explain(&mut state, edited);
+        seeding.reseed_settled(&mut state, view.is_art());
+        if !playback.draws(&mut state) {
+            window.update();
+            continue;
+        }
+
+        // update screen
+        let curr = state.board.read().expect("poisoned");
+        view.draw(&mut window, &curr, state.explain.as_ref(), |buffer| {
+            let typed = typing.as_ref().and_then(typing::Typing::annotation);
+            let annotated = marking.render(tour.annotations().chain(typed), WIDTH, buffer);
+            if let Some(construction) = &construction {
+                construction.render(cursor, WIDTH, buffer);
+            }
+            annotated || construction.is_some()
+        });
+    }
+
+    // drop channel as signal that program has ended
+    info!("sending shutdown signal to simulation thread");
+    drop(state.commands);
+
+    // wait for simulation thread
+    info!("waiting for simulation thread to finish");
+    life_sim_thread.join().unwrap();
+
+    let curr = state.board.read().expect("Poisoned");
+    if let Some(autosave) = autosave.as_mut() {
+        autosave.save(snapshot(
+            curr.as_ref(),
+            &marking.annotations,
+            &bookmarks,
+            &editing.macros,
+        ));
+    }
+    if let Some(path) = files.session.as_ref() {
+        save_session(
+            path,
+            curr.as_ref(),
+            &marking.annotations,
+            &bookmarks,
+            &editing.macros,
+        );
+    }
+    Ok(())
+}
+
+// actions of the keys pressed this frame, while typing only of keys other than characters so
+// that text can't trigger actions, and of actions throwing the board away only once the guard
+// lets them through
+fn actions(
+    events: &[InputEvent],
+    typing: &Option<typing::Typing>,
+    keymap: &Keymap,
+    guard: &mut ActionGuard,
+    announcer: &mut Announcer,
+) -> Vec<Action> {
+    let now = Instant::now();
+    let mut actions = Vec::new();
+    for event in events
+        .iter()
+        .filter(|event| typing.is_none() || !matches!(event.key, input::Key::Char(_)))
+    {
+        let Some(action) = keymap.action(event) else {
+            continue;
+        };
+        match guard.check(action, event.shift, now) {
+            Verdict::Allowed => actions.push(action),
+            Verdict::Confirm => announcer.action(format!("press again to {}", action)),
+            Verdict::NeedsShift => announcer.action(format!("hold Shift to {}", action)),
+        }
+    }
+    actions
+}
+
+// title of the window: the generation, population and whether paused, then the status of
+// each mode and the last action, announcing the state too
+fn title(state: &mut State, modes: impl Iterator<Item = String>) -> String {
+    let reached = state.generation.load(Ordering::Relaxed);
+    let population = stats::population(state.board.read().expect("Poisoned").as_ref());
+    state.announcer.status(reached, population, state.paused);
+    let mut title = format!(
+        "{} - {}",
+        messages::text(Message::WindowTitle),
+        messages::format(Message::Status, &[&reached, &population])
+    );
+    if state.paused {
+        title += &format!(", {}", messages::text(Message::Paused));
+    }
+    for status in modes {
+        title += &format!(" - {}", status);
+    }
+    if let Some(action) = state.announcer.last_action() {
+        title += &format!(" - {}", messages::format(Message::LastAction, &[&action]));
+    }
+    format!("{} - {}", title, messages::text(Message::EscToExit))
+}
+
+// place a pattern file in the middle of the board, the window only runs Conway's rule
+fn place_pattern(life: &mut impl Life, path: &Path) -> Result<(), Box<dyn Error>> {
+    info!("loading pattern {}", path.display());
+    let pattern = formats::load(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    // patterns of other rules would turn into something else under it, so they're refused
+    // rather than placed
+    if let Some(rule) = pattern.rule {
+        let parsed: Rule = rule
+            .parse()
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        if parsed != Rule::default() {
+            return Err(format!(
+                "{} runs under {} but the window only runs {}",
+                path.display(),
+                rule,
+                Rule::default()
+            )
+            .into());
+        }
+    }
+    let (x, y) = (
+        life.width().saturating_sub(pattern.grid.width()) / 2,
+        life.height().saturating_sub(pattern.grid.height()) / 2,
+    );
+    stats::live_cells(&pattern.grid).for_each(|(px, py)| life.set_cell(x + px, y + py, true));
+    Ok(())
+}
+
+// announcement of a mode being switched on or off
+fn toggled(mode: &str, on: bool) -> String {
+    format!("{} {}", mode, if on { "on" } else { "off" })
+}
+
+// set every cell of a board to the one of a grid, cells outside of the grid die
+fn replace_board(life: &mut impl Life, grid: &impl Life) {
+    let (width, height) = (life.width(), life.height());
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .for_each(|(x, y)| life.set_cell(x, y, grid.is_alive(x, y)));
+}
+
+// save the board, annotations, bookmarks and macros, logging failures rather than losing the
+// rest of the session
+fn save_session(
+    path: &Path,
+    life: &impl Life,
+    annotations: &[annotations::Annotation],
+    bookmarks: &[bookmarks::Bookmark],
+    macros: &[Macro],
+) {
+    let session = snapshot(life, annotations, bookmarks, macros);
+    match session::save(path, &session) {
+        Ok(()) => info!("saved session {}", path.display()),
+        Err(err) => error!("couldn't save session {}: {}", path.display(), err),
+    }
+}
+
+// the board, annotations, bookmarks and macros as a session
+fn snapshot(
+    life: &impl Life,
+    annotations: &[annotations::Annotation],
+    bookmarks: &[bookmarks::Bookmark],
+    macros: &[Macro],
+) -> session::Session {
+    session::Session {
+        grid: transform::cropped(life, 0, 0, life.width(), life.height()),
+        annotations: annotations.to_vec(),
+        bookmarks: bookmarks.to_vec(),
+        macros: macros.to_vec(),
+    }
+}
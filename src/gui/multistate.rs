@@ -0,0 +1,93 @@
+// window stepping a multi-state automaton drawn in the colors of its states, shared by the
+// automata with more states than alive and dead like WireWorld, Brian's Brain and the ants
+use crate::cli::messages::{self, Message};
+use minifb::{Key, KeyRepeat, Scale, Window, WindowOptions};
+use rlife::multistate::MultiStateLife;
+use rlife::render::{render_states_to_buffer, StatePalette, Viewport};
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+/// show an automaton in a window named after it, stepping it every step time until ESC,
+/// Space pausing it and the right arrow stepping it while paused, edits being made to it
+/// before each frame and the status of the title given the generation
+pub(super) fn run<M: MultiStateLife + Clone>(
+    name: &str,
+    life: M,
+    palette: &StatePalette,
+    step_time: Duration,
+    edit: impl FnMut(&mut M, &Window),
+    status: impl Fn(&M, u64) -> String,
+) -> Result<(), Box<dyn Error>> {
+    let mut next = life.clone();
+    run_with(
+        name,
+        life,
+        step_time,
+        |life| {
+            life.next_generation(&mut next);
+            std::mem::swap(life, &mut next);
+        },
+        |life, buffer| render_states_to_buffer(life, buffer, palette, Viewport::whole_states(life)),
+        edit,
+        status,
+    )
+}
+
+/// show an automaton like run, stepping and drawing it with functions of its own
+pub(super) fn run_with<M: MultiStateLife>(
+    name: &str,
+    mut life: M,
+    step_time: Duration,
+    mut step: impl FnMut(&mut M),
+    draw: impl Fn(&M, &mut [u32]),
+    mut edit: impl FnMut(&mut M, &Window),
+    status: impl Fn(&M, u64) -> String,
+) -> Result<(), Box<dyn Error>> {
+    let (width, height) = (life.width(), life.height());
+    if width == 0 || height == 0 {
+        return Err(format!("the {} map is empty", name).into());
+    }
+    let mut window = Window::new(
+        &format!("rlife {} - ESC to exit", name),
+        width,
+        height,
+        WindowOptions {
+            scale: Scale::X8,
+            ..Default::default()
+        },
+    )?;
+    window.limit_update_rate(Some(Duration::from_millis(15)));
+
+    let mut buffer = vec![0; width * height];
+    let (mut generation, mut paused) = (0u64, false);
+    let mut last_step = Instant::now();
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        if window.is_key_pressed(Key::Space, KeyRepeat::No) {
+            paused = !paused;
+        }
+        let stepping = match paused {
+            true => window.is_key_pressed(Key::Right, KeyRepeat::Yes),
+            false => last_step.elapsed() >= step_time,
+        };
+        if stepping {
+            step(&mut life);
+            generation += 1;
+            last_step = Instant::now();
+        }
+        edit(&mut life, &window);
+
+        draw(&life, &mut buffer);
+        let mut title = status(&life, generation);
+        if paused {
+            title += &format!(", {}", messages::text(Message::Paused));
+        }
+        window.set_title(&format!(
+            "rlife {} - {} - {}",
+            name,
+            title,
+            messages::text(Message::EscToExit)
+        ));
+        window.update_with_buffer(&buffer, width, height)?;
+    }
+    Ok(())
+}
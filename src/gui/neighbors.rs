@@ -0,0 +1,23 @@
+// neighbor-count layer: dead cells shaded by how many live neighbors they have
+use super::art::hsv;
+use rlife::{stats, Life};
+
+// brightness of a dead cell with all eight neighbors alive, kept faint next to live cells
+const MAX_VALUE: f32 = 0.4;
+
+/// draw live cells white and dead cells on a gradient from dark blue at one neighbor to
+/// red at eight, cells without neighbors stay black
+pub fn render(life: &impl Life, buffer: &mut [u32]) {
+    let width = life.width();
+    let counts = stats::neighbor_counts(life);
+    for (i, (pixel, &count)) in buffer.iter_mut().zip(&counts).enumerate() {
+        *pixel = match (life.is_alive(i % width, i / width), count) {
+            (true, _) => u32::MAX,
+            (false, 0) => 0,
+            (false, count) => {
+                let level = count as f32 / 8.0;
+                hsv(0.66 * (1.0 - level), 0.9, MAX_VALUE * level.sqrt())
+            }
+        };
+    }
+}
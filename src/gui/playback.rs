@@ -0,0 +1,143 @@
+// how the simulation moves on: pausing, stepping back, explaining a generation phase by
+// phase and time-lapse, which simulates at full speed and only draws every so many generations
+use super::explain::Explain;
+use super::{toggled, Command, State, DEFAULT_TIME_LAPSE, SIM_STEP_TIME};
+use crate::cli::messages::{self, Message};
+use log::debug;
+use rlife::input::Action;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// generations between drawn frames while in time-lapse mode, the number time-lapse is
+// switched on with and the generation last drawn or skipped on purpose, which the simulation
+// thread keeps from running too far ahead of
+pub struct Playback {
+    time_lapse: Option<u64>,
+    every: u64,
+    shown: Arc<AtomicU64>,
+}
+
+impl Playback {
+    /// start in time-lapse when given how many generations to draw one of
+    pub fn new(time_lapse: Option<u64>, shown: Arc<AtomicU64>, state: &State) -> Self {
+        if time_lapse.is_some() {
+            state
+                .commands
+                .send(Command::SetStepTime(Duration::ZERO))
+                .unwrap();
+        }
+        Self {
+            time_lapse,
+            every: time_lapse.unwrap_or(DEFAULT_TIME_LAPSE),
+            shown,
+        }
+    }
+
+    /// pause, step back, explain and time-lapse on their actions
+    pub fn handle(&mut self, actions: &[Action], state: &mut State) {
+        // pause/resume simulation
+        if actions.contains(&Action::TogglePause) {
+            debug!("sending simulation toggle signal");
+            state.paused = !state.paused;
+            if state.explain.is_none() {
+                state
+                    .commands
+                    .send(Command::SetPaused(state.paused))
+                    .unwrap();
+            }
+            state
+                .announcer
+                .action(if state.paused { "paused" } else { "resumed" });
+        }
+
+        // pause and go back a generation, unless explaining
+        if actions.contains(&Action::StepBack) && state.explain.is_none() {
+            state.paused = true;
+            state.commands.send(Command::SetPaused(true)).unwrap();
+            state.commands.send(Command::StepBack).unwrap();
+            state.announcer.action("stepped back a generation");
+        }
+
+        // toggle explain mode, animating one generation at a time in phases
+        if actions.contains(&Action::ToggleExplain) {
+            // pause before counting so that the simulation doesn't move on underneath
+            state
+                .commands
+                .send(Command::SetPaused(state.paused || state.explain.is_none()))
+                .unwrap();
+            state.explain = match state.explain {
+                Some(_) => None,
+                None => Some(Explain::new(state.board.read().expect("Poisoned").as_ref())),
+            };
+            debug!("explain mode: {}", state.explain.is_some());
+            state
+                .announcer
+                .action(toggled("explaining", state.explain.is_some()));
+        }
+
+        // toggle time-lapse, simulating at full speed while it is on
+        if actions.contains(&Action::ToggleTimeLapse) {
+            self.time_lapse = match self.time_lapse {
+                Some(_) => None,
+                None => Some(self.every),
+            };
+            let step_time = self.time_lapse.map_or(SIM_STEP_TIME, |_| Duration::ZERO);
+            state
+                .commands
+                .send(Command::SetStepTime(step_time))
+                .unwrap();
+            state
+                .announcer
+                .action(toggled("time-lapse", self.time_lapse.is_some()));
+        }
+
+        // draw more or fewer generations while in time-lapse
+        if let Some(every) = self.time_lapse.as_mut() {
+            if actions.contains(&Action::MoreFrames) {
+                *every = every.saturating_mul(2);
+                state
+                    .announcer
+                    .action(format!("drawing every {} generations", every));
+            } else if actions.contains(&Action::FewerFrames) {
+                *every = (*every / 2).max(1);
+                state
+                    .announcer
+                    .action(format!("drawing every {} generations", every));
+            }
+        }
+    }
+
+    /// explain the next phase, starting over when the user edited the generation
+    pub fn explain(&self, state: &mut State, edited: bool) {
+        if let Some(explain) = state.explain.as_mut() {
+            let mut curr = state.board.write().expect("Poisoned");
+            if edited {
+                *explain = Explain::new(curr.as_ref());
+            } else if explain.tick(curr.as_mut()) {
+                state.generation.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// whether to draw the current generation, in time-lapse only after enough generations
+    /// so that the previous frame is kept in between
+    pub fn draws(&self, state: &mut State) -> bool {
+        let current = state.generation.load(Ordering::Relaxed);
+        // the generations in between are skipped by design rather than dropped
+        self.shown.store(current, Ordering::Relaxed);
+        if let (Some(every), None) = (self.time_lapse, &state.explain) {
+            if (state.drawn_generation..state.drawn_generation + every).contains(&current) {
+                return false;
+            }
+        }
+        state.drawn_generation = current;
+        true
+    }
+
+    /// time-lapse for the title, None when it's off
+    pub fn status(&self) -> Option<String> {
+        let every = self.time_lapse?;
+        Some(messages::format(Message::TimeLapse, &[&every]))
+    }
+}
@@ -0,0 +1,106 @@
+// detection of extinct or settled boards so they can be replaced by a fresh soup
+use super::{toggled, State, SOUP_DENSITY};
+use log::{debug, info};
+use rlife::input::Action;
+use rlife::random::Rng;
+use rlife::{stats, Life};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// generations remembered to recognize still lifes and short period oscillators
+const HISTORY: usize = 16;
+
+// watches successive generations for death or repetition
+pub struct Reseed {
+    delay: Duration,
+    history: VecDeque<u64>,
+    settled_at: Option<Instant>,
+}
+
+impl Reseed {
+    /// create a detector that reports a board delay after it settled
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            history: VecDeque::with_capacity(HISTORY),
+            settled_at: None,
+        }
+    }
+
+    /// whether the board has been settled long enough to be reseeded, call once per frame
+    pub fn is_due<L: Life>(&mut self, life: &L) -> bool {
+        let fingerprint = stats::fingerprint(life);
+        // only a new generation changes the fingerprint
+        if self.history.back() != Some(&fingerprint) {
+            if self.history.contains(&fingerprint) || stats::population(life) == 0 {
+                self.settled_at.get_or_insert_with(Instant::now);
+            }
+            if self.history.len() == HISTORY {
+                self.history.pop_front();
+            }
+            self.history.push_back(fingerprint);
+        }
+        self.settled_at
+            .is_some_and(|settled_at| settled_at.elapsed() >= self.delay)
+    }
+
+    /// forget the settled state after the board has been reseeded or edited
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.settled_at = None;
+    }
+}
+
+// random soups the board starts over from, by hand or once it settled when reseeding
+// automatically
+pub struct Seeding {
+    rng: Rng,
+    auto: bool,
+}
+
+impl Seeding {
+    /// draw soups from a random number generator, reseeding settled boards if auto
+    pub fn new(rng: Rng, auto: bool) -> Self {
+        Self { rng, auto }
+    }
+
+    /// fill a board with a random soup
+    pub fn fill(&mut self, life: &mut impl Life) {
+        life.fill_random(SOUP_DENSITY, &mut self.rng);
+    }
+
+    /// toggle reseeding automatically and start over from a soup on their actions
+    pub fn handle(&mut self, actions: &[Action], state: &mut State) {
+        // toggle automatic reseeding of settled boards
+        if actions.contains(&Action::ToggleReseed) {
+            self.auto = !self.auto;
+            state.reseed.reset();
+            debug!("automatic reseeding: {}", self.auto);
+            state.announcer.action(toggled("reseeding", self.auto));
+        }
+
+        // start over from a random soup
+        if actions.contains(&Action::FillRandom) {
+            info!("filling the board with a random soup");
+            state
+                .announcer
+                .action("filled the board with a random soup");
+            self.fill(state.board.write().expect("Poisoned").as_mut());
+            state.restart(0);
+        }
+    }
+
+    /// reseed once the board has settled, art mode always does, unless explaining
+    pub fn reseed_settled(&mut self, state: &mut State, art: bool) {
+        let settled = state.explain.is_none()
+            && state
+                .reseed
+                .is_due(state.board.read().expect("Poisoned").as_ref());
+        if (self.auto || art) && settled {
+            info!("board settled, reseeding");
+            state.announcer.action("reseeded the settled board");
+            self.fill(state.board.write().expect("Poisoned").as_mut());
+            state.reseed.reset();
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// interactive sessions saved to a file, an RLE pattern of the board with the rest of the
+// session in comment lines, so that session files still open as ordinary patterns
+use super::annotations::Annotation;
+use super::bookmarks::Bookmark;
+use rlife::edit::{Edit, Macro};
+use rlife::formats::rle;
+use rlife::{VecGrid, DEFAULT_RULE};
+use std::error::Error;
+use std::path::Path;
+
+// start of the comment lines holding session state
+const PREFIX: &str = "#C rlife ";
+
+// board and everything drawn over it
+pub struct Session {
+    pub grid: VecGrid,
+    pub annotations: Vec<Annotation>,
+    pub bookmarks: Vec<Bookmark>,
+    pub macros: Vec<Macro>,
+}
+
+/// read a session file
+pub fn load(path: impl AsRef<Path>) -> Result<Session, Box<dyn Error>> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
+/// parse the contents of a session file
+pub fn parse(contents: &str) -> Result<Session, Box<dyn Error>> {
+    let (mut annotations, mut bookmarks, mut macros) = (Vec::new(), Vec::new(), Vec::new());
+    for line in contents
+        .lines()
+        .filter_map(|line| line.strip_prefix(PREFIX))
+    {
+        match line.split_whitespace().next() {
+            Some("bookmark") => bookmarks.push(Bookmark::parse(line)?),
+            Some("macro") => macros.push(parse_macro(line)?),
+            _ => annotations.push(Annotation::parse(line)?),
+        }
+    }
+    Ok(Session {
+        grid: rle::parse(contents)?.grid,
+        annotations,
+        bookmarks,
+        macros,
+    })
+}
+
+/// write a session file
+pub fn save(path: impl AsRef<Path>, session: &Session) -> Result<(), Box<dyn Error>> {
+    std::fs::write(path, write(session))?;
+    Ok(())
+}
+
+/// contents of a session file
+pub fn write(session: &Session) -> String {
+    let mut contents: String = session
+        .annotations
+        .iter()
+        .map(|annotation| format!("{}{}\n", PREFIX, annotation))
+        .chain(
+            session
+                .bookmarks
+                .iter()
+                .map(|bookmark| format!("{}{}\n", PREFIX, bookmark)),
+        )
+        .chain(
+            session
+                .macros
+                .iter()
+                .map(|m| format!("{}{}\n", PREFIX, write_macro(m))),
+        )
+        .collect();
+    contents.push_str(&rle::write(&session.grid, DEFAULT_RULE));
+    contents
+}
+
+// parse a macro line like "macro glider-maker 0,0,o 1,0,o 1,1,b"
+fn parse_macro(line: &str) -> Result<Macro, String> {
+    let error = || format!("invalid macro '{}'", line.trim());
+    let mut words = line.split_whitespace().skip(1);
+    let name = words.next().ok_or_else(error)?;
+    let edits = words
+        .map(|edit| {
+            let mut fields = edit.split(',');
+            match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                (Some(dx), Some(dy), Some(state @ ("o" | "b")), None) => Ok(Edit {
+                    dx: dx.parse().map_err(|_| error())?,
+                    dy: dy.parse().map_err(|_| error())?,
+                    is_alive: state == "o",
+                }),
+                _ => Err(error()),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Macro::from_edits(name, edits))
+}
+
+// macro line read by parse_macro, cells set alive are o and dead b as in RLE
+fn write_macro(m: &Macro) -> String {
+    let edits: String = m
+        .edits()
+        .iter()
+        .map(|edit| {
+            let state = if edit.is_alive { 'o' } else { 'b' };
+            format!(" {},{},{}", edit.dx, edit.dy, state)
+        })
+        .collect();
+    format!("macro {}{}", m.name(), edits)
+}
@@ -0,0 +1,178 @@
+// density phase-transition demo: an ensemble of soups for every initial density from 1% to
+// 99%, the density they settle at and how long they live plotted as each density is done
+use crate::cli::messages::{self, Message};
+use crate::cli::{flag_value, size_value};
+use log::info;
+use minifb::{Key, Scale, Window, WindowOptions};
+use rlife::ensemble::{self, Curve, Ensemble};
+use rlife::{Life, Rule, RuleLife, VecGrid};
+use std::error::Error;
+use std::ops::RangeInclusive;
+use std::sync::mpsc;
+use std::time::Duration;
+
+// initial densities swept, in percent
+const DENSITIES: RangeInclusive<usize> = 1..=99;
+
+// soups per density and generations they run for, unless --runs or --generations is given
+const DEFAULT_RUNS: usize = 16;
+const DEFAULT_GENERATIONS: u64 = 1000;
+
+// pixels per density, height of each plot and gap between them
+const BAR_WIDTH: usize = 2;
+const PLOT_HEIGHT: usize = 60;
+const GAP: usize = 4;
+
+// colors of the final density and lifespan bars, the axes and the density being run
+const DENSITY_COLOR: u32 = 0x40e060;
+const LIFESPAN_COLOR: u32 = 0xffa030;
+const AXIS_COLOR: u32 = 0x505050;
+const CURRENT_COLOR: u32 = 0x202050;
+
+// outcome of the ensemble of one initial density
+struct Point {
+    percent: usize,
+    /// mean density at the last generation
+    final_density: f64,
+    /// mean generation the soups died out at, the last one for soups still alive
+    lifespan: f64,
+    /// fraction of the soups still alive at the last generation
+    surviving: f64,
+}
+
+/// sweep the initial density of soups from 1% to 99% with an ensemble of soups for each on a
+/// background thread, plotting the final density above and the lifespan below as the
+/// ensembles finish
+pub fn transition(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut width, mut height) = (super::WIDTH, super::HEIGHT);
+    let mut rule = Rule::default();
+    let mut ensemble = Ensemble {
+        runs: DEFAULT_RUNS,
+        generations: DEFAULT_GENERATIONS,
+        seed: 1,
+        threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => ensemble.runs = flag_value(arg, args.next())?,
+            "--size" => (width, height) = size_value(arg, args.next())?,
+            "--generations" => ensemble.generations = flag_value(arg, args.next())?,
+            "--rule" => rule = flag_value(arg, args.next())?,
+            "--seed" => ensemble.seed = flag_value(arg, args.next())?,
+            _ => return Err(format!("unknown option '{}'", arg).into()),
+        }
+    }
+
+    // the ensembles run one density after another, stopping once the window is closed
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let cells = (width * height).max(1) as f64;
+        for percent in DENSITIES {
+            let populations = ensemble.populations(|rng| {
+                let mut soup = VecGrid::new(width, height);
+                soup.fill_random(percent as f64 / 100.0, rng);
+                RuleLife::new(soup, rule)
+            });
+            let curve = Curve::new(&populations);
+            let lifespans = ensemble::lifespans(&populations);
+            let point = Point {
+                percent,
+                final_density: curve.mean.last().copied().unwrap_or(0.0) / cells,
+                lifespan: lifespans.iter().sum::<u64>() as f64 / lifespans.len().max(1) as f64,
+                surviving: curve.surviving.last().copied().unwrap_or(0.0),
+            };
+            if sender.send(point).is_err() {
+                break;
+            }
+        }
+    });
+
+    let (frame_width, frame_height) = (BAR_WIDTH * (DENSITIES.end() + 1), 2 * PLOT_HEIGHT + GAP);
+    let mut window = Window::new(
+        "rlife transition - ESC to exit",
+        frame_width,
+        frame_height,
+        WindowOptions {
+            scale: Scale::X4,
+            ..Default::default()
+        },
+    )?;
+    window.limit_update_rate(Some(Duration::from_millis(30)));
+
+    let mut points: Vec<Point> = Vec::new();
+    let mut buffer = vec![0; frame_width * frame_height];
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        for point in receiver.try_iter() {
+            info!(
+                "density {}%: final density {:.4}, lifespan {:.1}, surviving {:.2}",
+                point.percent, point.final_density, point.lifespan, point.surviving
+            );
+            points.push(point);
+        }
+        draw(&points, ensemble.generations, &mut buffer, frame_width);
+        let status = match points.last() {
+            Some(point) => messages::format(
+                Message::TransitionPoint,
+                &[
+                    &point.percent,
+                    &format!("{:.3}", point.final_density),
+                    &format!("{:.0}", point.lifespan),
+                    &format!("{:.0}", point.surviving * 100.0),
+                ],
+            ),
+            None => messages::format(Message::TransitionRunning, &[&ensemble.runs]),
+        };
+        window.set_title(&format!(
+            "rlife transition - {} - {}",
+            status,
+            messages::text(Message::EscToExit)
+        ));
+        window.update_with_buffer(&buffer, frame_width, frame_height)?;
+    }
+    Ok(())
+}
+
+// final density bars in the upper plot, scaled to the densest, and lifespan bars in the lower
+// plot, scaled to the generations run, with the density being run marked in both
+fn draw(points: &[Point], generations: u64, buffer: &mut [u32], width: usize) {
+    buffer.fill(0);
+    let densest = points
+        .iter()
+        .map(|point| point.final_density)
+        .fold(f64::EPSILON, f64::max);
+    let next = points
+        .last()
+        .map_or(*DENSITIES.start(), |point| point.percent + 1);
+    let lower = PLOT_HEIGHT + GAP;
+    let mut bar = |percent: usize, top: usize, fraction: f64, color: u32| {
+        let length = (fraction.clamp(0.0, 1.0) * (PLOT_HEIGHT - 1) as f64).round() as usize;
+        for y in top + PLOT_HEIGHT - 1 - length..top + PLOT_HEIGHT - 1 {
+            let start = y * width + percent * BAR_WIDTH;
+            buffer[start..start + BAR_WIDTH].fill(color);
+        }
+    };
+    if DENSITIES.contains(&next) {
+        bar(next, 0, 1.0, CURRENT_COLOR);
+        bar(next, lower, 1.0, CURRENT_COLOR);
+    }
+    for point in points {
+        bar(
+            point.percent,
+            0,
+            point.final_density / densest,
+            DENSITY_COLOR,
+        );
+        bar(
+            point.percent,
+            lower,
+            point.lifespan / generations.max(1) as f64,
+            LIFESPAN_COLOR,
+        );
+    }
+    // axes along the bottom of each plot
+    for top in [0, lower] {
+        let y = top + PLOT_HEIGHT - 1;
+        buffer[y * width..(y + 1) * width].fill(AXIS_COLOR);
+    }
+}
@@ -0,0 +1,287 @@
+// guided tour for new users: staged boards with annotations over them and a prompt each,
+// moving on once the user has done what the prompt asks, lessons are tours read from files
+use super::annotations::Annotation;
+use super::session::{self, Session};
+use super::{replace_board, Command, State};
+use crate::cli::messages::{self, Message};
+use log::debug;
+use rlife::{stats, Life};
+use std::fmt;
+use std::sync::atomic::Ordering;
+
+// steps of the tour: the prompt, whether the board starts paused, what moves on and the
+// staged board as a session file
+const TOUR: &[(&str, bool, Advance, &str)] = &[
+    (
+        "welcome to rlife, every cell lives or dies by its eight neighbors, press Enter",
+        true,
+        Advance::Enter,
+        "#C rlife label 8 8 welcome to
+#C rlife label 8 16 rlife
+x = 64, y = 36, rule = B3/S23
+!
+",
+    ),
+    (
+        "clicking a cell toggles it and dragging paints the same change over more, draw 5 cells",
+        true,
+        Advance::Population(5),
+        "#C rlife label 2 2 draw here
+x = 64, y = 36, rule = B3/S23
+!
+",
+    ),
+    (
+        "live cells with 2 or 3 live neighbors survive and dead cells with 3 are born, \
+         press Space and watch 6 generations",
+        true,
+        Advance::Generations(6),
+        "#C rlife label 18 8 blinker
+#C rlife rect 28 15 7 5
+x = 64, y = 36, rule = B3/S23
+17$30b3o!
+",
+    ),
+    (
+        "a glider moves across the board by repeating itself, press Space to pause it",
+        false,
+        Advance::Pause,
+        "#C rlife label 10 2 glider
+x = 64, y = 36, rule = B3/S23
+2$3bo$4bo$2b3o!
+",
+    ),
+    (
+        "E explains a generation in phases, try it on the glider, then press Enter",
+        true,
+        Advance::Enter,
+        "#C rlife label 10 2 glider
+x = 64, y = 36, rule = B3/S23
+2$3bo$4bo$2b3o!
+",
+    ),
+    (
+        "five cells can keep changing for a long time, watch 100 generations",
+        false,
+        Advance::Generations(100),
+        "#C rlife label 18 4 r-pentomino
+x = 64, y = 36, rule = B3/S23
+16$32b2o$31b2o$32bo!
+",
+    ),
+    (
+        "that's the tour, the other keys are listed in the usage text, press Enter to finish",
+        true,
+        Advance::Enter,
+        "#C rlife label 20 16 have fun
+x = 64, y = 36, rule = B3/S23
+!
+",
+    ),
+];
+
+// what moves a step on to the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Advance {
+    /// pressing Enter
+    Enter,
+    /// the board running this many generations since it was staged
+    Generations(u64),
+    /// pausing with Space
+    Pause,
+    /// drawing until at least this many cells are alive
+    Population(usize),
+}
+
+// observation expected of the board when a step moves on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expect {
+    /// exactly this many live cells
+    Population(usize),
+    /// the cell is alive
+    Alive(usize, usize),
+    /// the cell is dead
+    Dead(usize, usize),
+}
+
+impl Expect {
+    /// whether the board shows what is expected
+    pub fn holds(self, life: &impl Life) -> bool {
+        match self {
+            Expect::Population(n) => stats::population(life) == n,
+            Expect::Alive(x, y) => life.is_alive(x, y),
+            Expect::Dead(x, y) => !life.is_alive(x, y),
+        }
+    }
+}
+
+impl fmt::Display for Expect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expect::Population(n) => write!(f, "population {}", n),
+            Expect::Alive(x, y) => write!(f, "alive {} {}", x, y),
+            Expect::Dead(x, y) => write!(f, "dead {} {}", x, y),
+        }
+    }
+}
+
+// part of a tour: the staged board and annotations, the prompt, what moves on and what the
+// board should show by then
+pub struct Step {
+    pub prompt: String,
+    pub paused: bool,
+    pub advance: Advance,
+    pub expect: Vec<Expect>,
+    pub session: Session,
+}
+
+// what the user did since the step was staged, checked against its Advance
+pub struct Progress {
+    pub enter: bool,
+    pub generations: u64,
+    pub paused: bool,
+}
+
+// steps of a tour, the one being shown and how the expectations of the previous one went
+pub struct Tutorial {
+    steps: Vec<Step>,
+    current: usize,
+    observed: Option<String>,
+}
+
+impl Tutorial {
+    /// tour through steps in order
+    pub fn new(steps: Vec<Step>) -> Self {
+        Self {
+            steps,
+            current: 0,
+            observed: None,
+        }
+    }
+
+    /// the built-in tour of the rules and the GUI
+    pub fn tour() -> Self {
+        let steps = TOUR
+            .iter()
+            .map(|&(prompt, paused, advance, board)| Step {
+                prompt: prompt.to_string(),
+                paused,
+                advance,
+                expect: Vec::new(),
+                session: session::parse(board).expect("Invalid tutorial board"),
+            })
+            .collect();
+        Self::new(steps)
+    }
+
+    /// step being shown, None once the tour is over
+    pub fn step(&self) -> Option<&Step> {
+        self.steps.get(self.current)
+    }
+
+    /// move on to the next step when the user did what the current one asks, checking what
+    /// the board is expected to show by then, returns the step to stage if it did
+    pub fn advance(&mut self, progress: &Progress, life: &impl Life) -> Option<&Step> {
+        let step = self.step()?;
+        let done = match step.advance {
+            Advance::Enter => progress.enter,
+            Advance::Generations(n) => progress.generations >= n,
+            Advance::Pause => progress.paused,
+            Advance::Population(n) => stats::population(life) >= n,
+        };
+        if !done {
+            return None;
+        }
+        let missed: Vec<String> = step
+            .expect
+            .iter()
+            .filter(|expect| !expect.holds(life))
+            .map(|expect| expect.to_string())
+            .collect();
+        self.observed = match (step.expect.is_empty(), missed.is_empty()) {
+            (true, _) => None,
+            (false, true) => Some(messages::text(Message::AsExpected).to_string()),
+            (false, false) => Some(messages::format(Message::Expected, &[&missed.join(", ")])),
+        };
+        self.current += 1;
+        self.step()
+    }
+
+    /// position in the tour and the prompt of the step, for the title
+    pub fn status(&self) -> Option<String> {
+        let step = self.step()?;
+        let mut status = messages::format(
+            Message::TutorialStep,
+            &[&(self.current + 1), &self.steps.len(), &step.prompt],
+        );
+        if let Some(observed) = &self.observed {
+            status += &format!(
+                " ({})",
+                messages::format(Message::TutorialObserved, &[observed])
+            );
+        }
+        Some(status)
+    }
+}
+
+// tour being taken in the window, None when not taking one, and whether its current step is
+// yet to be staged
+pub struct Tour {
+    tutorial: Option<Tutorial>,
+    stage: bool,
+}
+
+impl Tour {
+    /// take a tour, staging its first step
+    pub fn new(tutorial: Option<Tutorial>) -> Self {
+        Self {
+            stage: tutorial.is_some(),
+            tutorial,
+        }
+    }
+
+    /// move the tour on once the user did what the step asks, then stage the next step,
+    /// enter being whether Enter was pressed
+    pub fn follow(&mut self, enter: bool, state: &mut State) {
+        let Some(tutorial) = self.tutorial.as_mut() else {
+            return;
+        };
+        let progress = Progress {
+            enter,
+            generations: state.generation.load(Ordering::Relaxed),
+            paused: state.paused,
+        };
+        let curr = state.board.read().expect("Poisoned");
+        self.stage |= tutorial.advance(&progress, curr.as_ref()).is_some();
+        drop(curr);
+        if let Some(step) = tutorial.step().filter(|_| self.stage) {
+            debug!("staging tutorial step: {}", step.prompt);
+            state
+                .announcer
+                .action(tutorial.status().unwrap_or_default());
+            replace_board(
+                state.board.write().expect("Poisoned").as_mut(),
+                &step.session.grid,
+            );
+            state.explain = None;
+            state.restart(0);
+            state.paused = step.paused;
+            state
+                .commands
+                .send(Command::SetPaused(state.paused))
+                .unwrap();
+            self.stage = false;
+        }
+    }
+
+    /// position in the tour and the prompt of the step, for the title
+    pub fn status(&self) -> Option<String> {
+        self.tutorial.as_ref()?.status()
+    }
+
+    /// annotations of the step being shown
+    pub fn annotations(&self) -> impl Iterator<Item = &Annotation> {
+        let step = self.tutorial.as_ref().and_then(Tutorial::step);
+        step.into_iter().flat_map(|step| &step.session.annotations)
+    }
+}
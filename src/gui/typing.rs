@@ -0,0 +1,61 @@
+// text typed into the window: the text of annotations and the names of recorded macros,
+// ended by Enter
+use super::annotations::Annotation;
+use minifb::InputCallback;
+use rlife::edit::Edit;
+use rlife::input::{InputEvent, Key};
+use std::sync::mpsc;
+
+// text being typed into the window
+pub enum Typing {
+    /// text of a new annotation
+    Annotation(Annotation),
+    /// name of the macro just recorded
+    MacroName(String, Vec<Edit>),
+}
+
+impl Typing {
+    // text typed so far
+    fn text(&mut self) -> &mut String {
+        match self {
+            Typing::Annotation(annotation) => &mut annotation.text,
+            Typing::MacroName(name, _) => name,
+        }
+    }
+
+    /// annotation whose text is being typed, drawn as it is typed
+    pub fn annotation(&self) -> Option<&Annotation> {
+        match self {
+            Typing::Annotation(annotation) => Some(annotation),
+            Typing::MacroName(..) => None,
+        }
+    }
+}
+
+/// add the characters typed since the last frame to the text being typed, they are dropped
+/// when nothing is, and take what was typed once Enter ends it
+pub fn update(typing: &mut Option<Typing>, typed: &str, events: &[InputEvent]) -> Option<Typing> {
+    let text = typing.as_mut()?.text();
+    text.push_str(typed);
+    if events.iter().any(|event| event.key == Key::Backspace) {
+        text.pop();
+    }
+    match events
+        .iter()
+        .any(|event| event.key == Key::Enter && !event.repeat)
+    {
+        true => typing.take(),
+        false => None,
+    }
+}
+
+// characters typed into the window, sent to the I/O thread loop
+pub struct Typed(pub mpsc::Sender<char>);
+
+impl InputCallback for Typed {
+    fn add_char(&mut self, uni_char: u32) {
+        if let Some(c) = char::from_u32(uni_char).filter(|c| !c.is_control()) {
+            let _ = self.0.send(c);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+// what the window shows of the board: the part of it zoomed in on, and the cells themselves,
+// art or the neighbors of dead cells, drawn into a frame buffer only changed cells are
+// redrawn in
+use super::explain::Explain;
+use super::{art, neighbors, toggled, Board, HEIGHT, MAX_ZOOM, WIDTH};
+use crate::cli::announce::Announcer;
+use log::debug;
+use minifb::Window;
+use rlife::input::Action;
+use rlife::render::{render_changes, render_to_buffer, zoom_frame, Palette, Viewport};
+
+// frame buffer and the one it is scaled up into when zoomed in, the part of the board shown,
+// the board as last drawn into the frame buffer, None when other pixels were drawn over it,
+// art mode state, None when showing plain cells, and whether the neighbor layer is shown
+pub struct View {
+    buffer: [u32; WIDTH * HEIGHT],
+    zoomed: [u32; WIDTH * HEIGHT],
+    pub viewport: Viewport,
+    drawn: Option<Box<Board>>,
+    art: Option<art::Art>,
+    neighbor_layer: bool,
+}
+
+impl View {
+    /// show the whole board, as art if asked to
+    pub fn new(art: bool) -> Self {
+        Self {
+            buffer: [0x0; WIDTH * HEIGHT],
+            zoomed: [0x0; WIDTH * HEIGHT],
+            viewport: Viewport {
+                x: 0,
+                y: 0,
+                width: WIDTH,
+                height: HEIGHT,
+            },
+            drawn: None,
+            art: art.then(|| art::Art::new(WIDTH * HEIGHT)),
+            neighbor_layer: false,
+        }
+    }
+
+    /// zoom, pan and switch art and the neighbor layer on their actions, hiding the cursor
+    /// while in art mode
+    pub fn handle(
+        &mut self,
+        actions: &[Action],
+        mouse: Option<(usize, usize)>,
+        window: &mut Window,
+        announcer: &mut Announcer,
+    ) {
+        // toggle art mode, hiding the cursor while it is on
+        if actions.contains(&Action::ToggleArt) {
+            self.art = match self.art {
+                Some(_) => None,
+                None => Some(art::Art::new(WIDTH * HEIGHT)),
+            };
+            debug!("art mode: {}", self.art.is_some());
+            announcer.action(toggled("art mode", self.art.is_some()));
+            window.set_cursor_visibility(self.art.is_none());
+        }
+
+        // toggle shading dead cells by their number of live neighbors
+        if actions.contains(&Action::ToggleNeighbors) {
+            self.neighbor_layer = !self.neighbor_layer;
+            debug!("neighbor layer: {}", self.neighbor_layer);
+            announcer.action(toggled("neighbor shading", self.neighbor_layer));
+        }
+
+        // zoom in on the cursor, or the middle of the view without one, and out again
+        let view = self.viewport;
+        let zoom = view.zoom(WIDTH);
+        let zoom = match (
+            actions.contains(&Action::ZoomIn),
+            actions.contains(&Action::ZoomOut),
+        ) {
+            (true, false) if zoom < MAX_ZOOM => Some((zoom * 2, mouse.unwrap_or(view.center()))),
+            (false, true) if zoom > 1 => Some((zoom / 2, view.center())),
+            _ => None,
+        };
+        if let Some((zoom, center)) = zoom {
+            self.viewport = Viewport::zoomed(WIDTH, HEIGHT, zoom, center);
+            announcer.action(format!("zoomed {} times", zoom));
+        }
+        for &action in actions {
+            let (dx, dy) = match action {
+                Action::PanLeft => (-1, 0),
+                Action::PanRight => (1, 0),
+                Action::PanUp => (0, -1),
+                Action::PanDown => (0, 1),
+                _ => continue,
+            };
+            self.viewport = self.viewport.moved(dx, dy, WIDTH, HEIGHT);
+        }
+    }
+
+    /// cell under a pixel of the window
+    pub fn cell(&self, pixel: (usize, usize)) -> (usize, usize) {
+        self.viewport.cell(pixel, WIDTH, HEIGHT)
+    }
+
+    /// whether art mode is on
+    pub fn is_art(&self) -> bool {
+        self.art.is_some()
+    }
+
+    /// draw the board, or the generation being explained, with what overlay draws over it
+    /// unless in art mode, overlay returns whether it drew anything, and update the window
+    pub fn draw(
+        &mut self,
+        window: &mut Window,
+        curr: &Board,
+        explain: Option<&Explain>,
+        overlay: impl FnOnce(&mut [u32]) -> bool,
+    ) {
+        let buffer = &mut self.buffer;
+        match (explain, self.art.as_mut()) {
+            (Some(explain), _) => explain.render(curr, buffer),
+            (None, _) if self.neighbor_layer => neighbors::render(curr, buffer),
+            (None, Some(art)) => art.render(curr, buffer),
+            // only the cells changed since the board last drawn need new pixels
+            (None, None) => match &self.drawn {
+                Some(drawn) => render_changes(
+                    drawn.as_ref(),
+                    curr,
+                    buffer,
+                    &Palette::default(),
+                    Viewport::whole(curr),
+                ),
+                None => render_to_buffer(curr, buffer, &Palette::default(), Viewport::whole(curr)),
+            },
+        }
+        // annotations and the like go over everything but art
+        let overlaid = self.art.is_none() && overlay(buffer.as_mut_slice());
+        let overlaid = overlaid || explain.is_some() || self.neighbor_layer || self.art.is_some();
+
+        // pixels drawn over the board have to be redrawn whole next frame
+        self.drawn = match overlaid {
+            true => None,
+            false => {
+                let mut grid = self.drawn.take().unwrap_or_default();
+                grid.copy_from_slice(curr.as_slice());
+                Some(grid)
+            }
+        };
+
+        // update screen with buffer, scaled up to the part of the board shown
+        match self.viewport.zoom(WIDTH) {
+            1 => window.update_with_buffer(buffer, WIDTH, HEIGHT).unwrap(),
+            _ => {
+                zoom_frame(buffer, WIDTH, self.viewport, &mut self.zoomed);
+                window
+                    .update_with_buffer(&self.zoomed, WIDTH, HEIGHT)
+                    .unwrap();
+            }
+        }
+    }
+}
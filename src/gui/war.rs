@@ -0,0 +1,146 @@
+// hotseat Life war in a window, both players taking turns at the same mouse
+use crate::cli::flag_value;
+use crate::cli::messages::{self, Message};
+use log::info;
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode, Scale, Window, WindowOptions};
+use rlife::ownership::NO_OWNER;
+use rlife::war::{Turn, War, PLAYERS};
+use rlife::Life;
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+// cells each player places per round, generations run per round and rounds per game
+const DEFAULT_BUDGET: usize = 20;
+const DEFAULT_GENERATIONS: u64 = 100;
+const DEFAULT_ROUNDS: u32 = 3;
+
+// time between generations while a round runs
+const WAR_STEP_TIME: Duration = Duration::from_millis(100);
+
+// colors of the live cells of each player and of the dead cells in their territory
+const PLAYER_COLORS: [u32; 2] = [0xff4040, 0x4080ff];
+const TERRITORY_COLORS: [u32; 2] = [0x200808, 0x081020];
+
+/// play Life war with two players at one window, each round they place their cells in their
+/// half of the board with the left button, Enter ending a turn early, then the board runs and
+/// every live cell a player owns at the end of the round scores a point
+pub fn war(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut budget, mut generations, mut rounds) =
+        (DEFAULT_BUDGET, DEFAULT_GENERATIONS, DEFAULT_ROUNDS);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--budget" => budget = flag_value(arg, args.next())?,
+            "--generations" => generations = flag_value(arg, args.next())?,
+            "--rounds" => rounds = flag_value(arg, args.next())?,
+            _ => return Err(format!("unknown option '{}'", arg).into()),
+        }
+    }
+    if budget == 0 {
+        return Err("--budget must be at least 1".into());
+    }
+
+    let mut war = War::new(super::WIDTH, super::HEIGHT, budget, generations, rounds);
+    let (width, height) = (war.life().width(), war.life().height());
+    let mut window = Window::new(
+        "rlife war - ESC to exit",
+        width,
+        height,
+        WindowOptions {
+            scale: Scale::X16,
+            ..Default::default()
+        },
+    )?;
+    window.limit_update_rate(Some(Duration::from_millis(30)));
+
+    let mut buffer = vec![0; width * height];
+    let mut last_step = Instant::now();
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        match war.turn() {
+            Turn::Placing(_) => {
+                if window.get_mouse_down(MouseButton::Left) {
+                    if let Some((x, y)) = window.get_mouse_pos(MouseMode::Discard) {
+                        // clicks outside the territory or on live cells are ignored
+                        let _ = war.place(x as usize, y as usize);
+                    }
+                }
+                if window.is_key_pressed(Key::Enter, KeyRepeat::No) {
+                    war.end_turn();
+                }
+                last_step = Instant::now();
+            }
+            Turn::Running(_) => {
+                if last_step.elapsed() >= WAR_STEP_TIME {
+                    war.step();
+                    last_step = Instant::now();
+                    if let Turn::Placing(_) | Turn::Over = war.turn() {
+                        let [first, second] = war.scores();
+                        info!("round scored, scores {} to {}", first, second);
+                    }
+                }
+            }
+            Turn::Over => (),
+        }
+
+        for (i, pixel) in buffer.iter_mut().enumerate() {
+            let (x, y) = (i % width, i / width);
+            *pixel = match (war.life().is_alive(x, y), war.owner(x, y)) {
+                (false, _) => TERRITORY_COLORS[player_index(war.territory(x))],
+                (true, NO_OWNER) => u32::MAX,
+                (true, owner) => PLAYER_COLORS[player_index(owner)],
+            };
+        }
+        window.set_title(&format!(
+            "rlife war - {} - {}",
+            status(&war),
+            messages::text(Message::EscToExit)
+        ));
+        window.update_with_buffer(&buffer, width, height)?;
+    }
+    Ok(())
+}
+
+// index of a player in PLAYERS
+fn player_index(player: u8) -> usize {
+    PLAYERS.iter().position(|&p| p == player).unwrap_or(0)
+}
+
+// what is going on in the game, for the title
+fn status(war: &War) -> String {
+    let [first, second] = war.scores();
+    match war.turn() {
+        Turn::Placing(player) => messages::format(
+            Message::WarPlacing,
+            &[
+                &war.round(),
+                &war.rounds(),
+                &player,
+                &war.remaining_budget(),
+                &first,
+                &second,
+            ],
+        ),
+        Turn::Running(remaining) => {
+            let [alive_first, alive_second] = war.populations();
+            messages::format(
+                Message::WarRunning,
+                &[
+                    &war.round(),
+                    &war.rounds(),
+                    &remaining,
+                    &alive_first,
+                    &alive_second,
+                    &first,
+                    &second,
+                ],
+            )
+        }
+        Turn::Over => match war.winner() {
+            Some(player) => messages::format(
+                Message::WarWinner,
+                &[&player, &first.max(second), &first.min(second)],
+            ),
+            None => messages::format(Message::WarTie, &[&first]),
+        },
+    }
+}
@@ -0,0 +1,56 @@
+// WireWorld circuits in a window, drawn in the colors of their states and edited with the mouse
+use crate::cli::messages::{self, Message};
+use crate::cli::{flag_value, size_value};
+use minifb::{MouseButton, MouseMode};
+use rlife::multistate::{MultiStateLife, StateGrid};
+use rlife::wireworld::{WireWorld, CONDUCTOR, EMPTY, HEAD};
+use std::error::Error;
+use std::time::Duration;
+
+// time per generation unless --step-time is given, in milliseconds
+const DEFAULT_STEP_TIME: u64 = 100;
+
+/// run a WireWorld map, read from a text file of a character per cell or empty, the left
+/// button draws wire, the right one sparks an electron head on wire and the middle one
+/// clears cells, Space pauses and the right arrow steps while paused
+pub fn wireworld(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut path = None;
+    let (mut width, mut height) = (super::WIDTH, super::HEIGHT);
+    let mut step_time = DEFAULT_STEP_TIME;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => (width, height) = size_value(arg, args.next())?,
+            "--step-time" => step_time = flag_value(arg, args.next())?,
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg).into()),
+        }
+    }
+    let world = match path {
+        Some(path) => std::fs::read_to_string(path)?.parse::<WireWorld>()?,
+        None => WireWorld::new(StateGrid::new(width, height)),
+    };
+
+    super::multistate::run(
+        "wireworld",
+        world,
+        &WireWorld::palette(),
+        Duration::from_millis(step_time),
+        |world, window| {
+            let Some((x, y)) = window.get_mouse_pos(MouseMode::Discard) else {
+                return;
+            };
+            let (x, y) = (x as usize, y as usize);
+            if window.get_mouse_down(MouseButton::Left) {
+                world.set_state(x, y, CONDUCTOR);
+            } else if window.get_mouse_down(MouseButton::Right) && world.state(x, y) == CONDUCTOR {
+                world.set_state(x, y, HEAD);
+            } else if window.get_mouse_down(MouseButton::Middle) {
+                world.set_state(x, y, EMPTY);
+            }
+        },
+        |world, generation| {
+            messages::format(Message::WireWorldStatus, &[&generation, &world.heads()])
+        },
+    )
+}
@@ -0,0 +1,619 @@
+// HashLife: an unbounded universe stored as a quadtree whose identical subtrees are shared,
+// with the future of every subtree remembered once calculated, so that repetitive patterns
+// can be advanced by millions of generations at once
+//
+// a node of level k is a square of 2^k by 2^k cells, level 0 nodes being single cells, and
+// the result of a node of level k >= 2 is its centre of level k - 1 advanced by up to
+// 2^(k-2) generations, which only depends on the node itself
+use crate::invariants::Violation;
+use crate::rule::Rule;
+use crate::{Life, Steppable, VecGrid};
+use std::collections::HashMap;
+
+// index of a node in the arena
+type Id = u32;
+
+const DEAD: Id = 0;
+const ALIVE: Id = 1;
+
+// levels deeper than this wouldn't fit their coordinates into i64
+const MAX_LEVEL: u8 = 62;
+
+// nodes are compacted when there are more of them than this before stepping
+const COMPACT_NODES: usize = 1 << 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Node {
+    // quadrants, unused for cells
+    nw: Id,
+    ne: Id,
+    sw: Id,
+    se: Id,
+    level: u8,
+    population: u64,
+}
+
+// sizes of a universe, from HashLifeUniverse::node_stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeStats {
+    /// distinct nodes in the arena, reachable from the root or not
+    pub nodes: usize,
+    /// level of the root, the universe being 2^levels cells wide
+    pub levels: u8,
+    /// results of nodes remembered
+    pub results: usize,
+    /// estimated bytes taken by the arena and the caches
+    pub memory: usize,
+}
+
+// quadtree universe of live cells along with the rule they follow
+#[derive(Debug, Clone)]
+pub struct HashLifeUniverse {
+    nodes: Vec<Node>,
+    // node of the given quadrants, so that identical subtrees are stored once
+    ids: HashMap<[Id; 4], Id>,
+    // centre of a node advanced by 2^step generations
+    results: HashMap<(Id, u8), Id>,
+    // empty node of every level
+    empty: Vec<Id>,
+    root: Id,
+    // cell at the top left corner of the root
+    origin: (i64, i64),
+    rule: Rule,
+    generation: u64,
+}
+
+impl Default for HashLifeUniverse {
+    fn default() -> Self {
+        Self::with_rule(Rule::default())
+    }
+}
+
+impl HashLifeUniverse {
+    /// empty universe following Conway's rule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// empty universe following another rule, rules where dead cells with no live neighbors
+    /// come alive (B0) would fill the plane and aren't supported
+    pub fn with_rule(rule: Rule) -> Self {
+        assert!(
+            !rule.is_born(0),
+            "B0 rules can't be simulated on an unbounded plane"
+        );
+        let cell = |population| Node {
+            nw: DEAD,
+            ne: DEAD,
+            sw: DEAD,
+            se: DEAD,
+            level: 0,
+            population,
+        };
+        let mut universe = Self {
+            nodes: vec![cell(0), cell(1)],
+            ids: HashMap::new(),
+            results: HashMap::new(),
+            empty: vec![DEAD],
+            root: DEAD,
+            origin: (-4, -4),
+            rule,
+            generation: 0,
+        };
+        universe.root = universe.empty(3);
+        universe
+    }
+
+    /// universe holding the live cells of a board with its top left corner at (x, y)
+    pub fn from_life(life: &impl Life, x: i64, y: i64) -> Self {
+        let mut universe = Self::with_rule(life.rule());
+        for cy in 0..life.height() {
+            for cx in 0..life.width() {
+                if life.is_alive(cx, cy) {
+                    universe.set_cell(x + cx as i64, y + cy as i64, true);
+                }
+            }
+        }
+        universe
+    }
+
+    /// rule the universe follows
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// number of generations calculated so far
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// number of live cells
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root as usize].population
+    }
+
+    /// number of distinct nodes stored, as a measure of memory use
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// sizes of the node arena and the caches
+    pub fn node_stats(&self) -> NodeStats {
+        // a control byte per slot as in the standard hash map
+        let entry = |size: usize| size + 1;
+        NodeStats {
+            nodes: self.nodes.len(),
+            levels: self.nodes[self.root as usize].level,
+            results: self.results.len(),
+            memory: std::mem::size_of::<Self>()
+                + self.nodes.capacity() * std::mem::size_of::<Node>()
+                + self.ids.capacity() * entry(std::mem::size_of::<([Id; 4], Id)>())
+                + self.results.capacity() * entry(std::mem::size_of::<((Id, u8), Id)>())
+                + self.empty.capacity() * std::mem::size_of::<Id>(),
+        }
+    }
+
+    /// bytes taken by the universe, its nodes and caches included
+    pub fn memory_usage(&self) -> usize {
+        self.node_stats().memory
+    }
+
+    /// set state of cell
+    pub fn set_cell(&mut self, x: i64, y: i64, is_alive: bool) {
+        if !is_alive && !self.contains(x, y) {
+            return;
+        }
+        while !self.contains(x, y) {
+            self.expand();
+        }
+        let (ox, oy) = self.origin;
+        let (x, y) = (x.abs_diff(ox), y.abs_diff(oy));
+        self.root = self.set(self.root, x, y, is_alive);
+    }
+
+    /// state of cell
+    pub fn is_alive(&self, x: i64, y: i64) -> bool {
+        if !self.contains(x, y) {
+            return false;
+        }
+        let (ox, oy) = self.origin;
+        let (mut x, mut y) = (x.abs_diff(ox), y.abs_diff(oy));
+        let mut id = self.root;
+        while self.nodes[id as usize].level > 0 {
+            let node = self.nodes[id as usize];
+            let half = 1 << (node.level - 1);
+            id = match (x >= half, y >= half) {
+                (false, false) => node.nw,
+                (true, false) => node.ne,
+                (false, true) => node.sw,
+                (true, true) => node.se,
+            };
+            (x, y) = (x % half, y % half);
+        }
+        id == ALIVE
+    }
+
+    /// live cells by row from the top, skipping empty parts of the universe as a whole
+    pub fn cells(&self) -> Vec<(i64, i64)> {
+        let mut cells = Vec::with_capacity(self.population() as usize);
+        self.collect(self.root, self.origin, &mut cells);
+        cells.sort_unstable_by_key(|&(x, y)| (y, x));
+        cells
+    }
+
+    /// top left corner, width and height of the smallest rectangle holding every live cell,
+    /// None when there are none, found from the tree without visiting the cells
+    pub fn bounds(&self) -> Option<(i64, i64, u64, u64)> {
+        let (left, top, right, bottom) = self.extent(self.root, &mut HashMap::new())?;
+        let (ox, oy) = self.origin;
+        Some((
+            ox.wrapping_add_unsigned(left),
+            oy.wrapping_add_unsigned(top),
+            right - left + 1,
+            bottom - top + 1,
+        ))
+    }
+
+    /// live cells copied onto a grid just holding them, along with the position of its top
+    /// left corner, None without live cells
+    pub fn to_grid(&self) -> Option<(VecGrid, (i64, i64))> {
+        let (x, y, width, height) = self.bounds()?;
+        let mut grid = VecGrid::new(width as usize, height as usize);
+        self.cells()
+            .into_iter()
+            .for_each(|(cx, cy)| grid.set_cell((cx - x) as usize, (cy - y) as usize, true));
+        Some((grid, (x, y)))
+    }
+
+    /// calculate 2^k generations at once, the first jump of a size takes as long as
+    /// calculating the pattern's structure, repeating it is mostly looking results up
+    pub fn step_pow2(&mut self, k: u8) {
+        assert!(
+            k <= MAX_LEVEL - 3,
+            "can't step by more than 2^59 generations"
+        );
+        if self.nodes.len() > COMPACT_NODES {
+            self.compact();
+        }
+        // a pattern grows by at most one cell per generation, so the live cells are kept
+        // within the centre of the centre with at least 2^k cells around them
+        while self.level() < k + 3 || !self.is_padded() {
+            self.expand();
+        }
+        let quarter = 1i64 << (self.level() - 2);
+        self.root = self.result(self.root, k);
+        self.origin = (self.origin.0 + quarter, self.origin.1 + quarter);
+        self.generation += 1 << k;
+    }
+
+    /// calculate the given number of generations, as jumps by its powers of two
+    pub fn advance(&mut self, generations: u64) {
+        (0..64)
+            .filter(|&k| (generations >> k) & 1 == 1)
+            .for_each(|k| self.step_pow2(k));
+    }
+
+    /// forget remembered results and the nodes no longer part of the universe and drop the
+    /// empty border of the root down to the live cells, to free their memory, later steps
+    /// have to calculate them anew, done on its own before stepping once there are more than
+    /// a few million nodes
+    pub fn compact(&mut self) {
+        while self.level() > 3 {
+            let centre = self.centre(self.root);
+            if self.nodes[centre as usize].population != self.population() {
+                break;
+            }
+            let quarter = 1i64 << (self.level() - 2);
+            self.root = centre;
+            self.origin = (self.origin.0 + quarter, self.origin.1 + quarter);
+        }
+        let mut universe = Self::with_rule(self.rule);
+        let mut copies = HashMap::from([(DEAD, DEAD), (ALIVE, ALIVE)]);
+        universe.root = universe.copy(self, self.root, &mut copies);
+        universe.origin = self.origin;
+        universe.generation = self.generation;
+        *self = universe;
+    }
+
+    /// move every live cell so that the middle of their bounds is at (0, 0), keeping the
+    /// coordinates of patterns that travel far small, returning the offset added to the
+    /// coordinates of every cell, only the origin of the root changes
+    pub fn recenter(&mut self) -> (i64, i64) {
+        let Some((x, y, width, height)) = self.bounds() else {
+            return (0, 0);
+        };
+        let offset = (
+            0i64.wrapping_sub(x.wrapping_add_unsigned(width / 2)),
+            0i64.wrapping_sub(y.wrapping_add_unsigned(height / 2)),
+        );
+        self.origin = (
+            self.origin.0.wrapping_add(offset.0),
+            self.origin.1.wrapping_add(offset.1),
+        );
+        offset
+    }
+
+    /// check that every node's cached population is the sum of its quadrants', that nodes are
+    /// stored once and that remembered results and empty nodes are of the right levels
+    pub fn check_invariants(&self) -> Result<(), Violation> {
+        let error = |message: String| Err(Violation(message));
+        let leaves = [(DEAD, 0), (ALIVE, 1)];
+        for (id, population) in leaves {
+            let node = self.nodes[id as usize];
+            if (node.level, node.population) != (0, population) {
+                return error(format!("cell {} has population {}", id, node.population));
+            }
+        }
+        for (id, node) in self.nodes.iter().enumerate().skip(2) {
+            let quadrants = [node.nw, node.ne, node.sw, node.se];
+            // quadrants are always stored before the nodes made of them
+            if quadrants.iter().any(|&q| q as usize >= id) {
+                return error(format!("node {} refers to a later node", id));
+            }
+            let quadrants = quadrants.map(|q| self.nodes[q as usize]);
+            if quadrants.iter().any(|q| q.level + 1 != node.level) {
+                return error(format!(
+                    "node {} of level {} has quadrants of other levels",
+                    id, node.level
+                ));
+            }
+            let population: u64 = quadrants.iter().map(|q| q.population).sum();
+            if population != node.population {
+                return error(format!(
+                    "node {} has population {} but its quadrants {}",
+                    id, node.population, population
+                ));
+            }
+            if self.ids.get(&[node.nw, node.ne, node.sw, node.se]) != Some(&(id as Id)) {
+                return error(format!("node {} is stored more than once", id));
+            }
+        }
+        if self.ids.len() + 2 != self.nodes.len() {
+            return error(format!(
+                "{} nodes but {} are hashed",
+                self.nodes.len(),
+                self.ids.len()
+            ));
+        }
+        for (&(id, step), &result) in &self.results {
+            let (node, result) = (self.nodes[id as usize], self.nodes[result as usize]);
+            if result.level + 1 != node.level || step + 2 > node.level {
+                return error(format!(
+                    "result of node {} after 2^{} generations",
+                    id, step
+                ));
+            }
+        }
+        for (level, &id) in self.empty.iter().enumerate() {
+            let node = self.nodes[id as usize];
+            if node.level as usize != level || node.population != 0 {
+                return error(format!("empty node of level {} isn't empty", level));
+            }
+        }
+        Ok(())
+    }
+
+    fn level(&self) -> u8 {
+        self.nodes[self.root as usize].level
+    }
+
+    fn contains(&self, x: i64, y: i64) -> bool {
+        let (ox, oy) = self.origin;
+        let side = 1u64 << self.level();
+        x >= ox && y >= oy && x.abs_diff(ox) < side && y.abs_diff(oy) < side
+    }
+
+    // node of the given quadrants, stored once
+    fn join(&mut self, nw: Id, ne: Id, sw: Id, se: Id) -> Id {
+        if let Some(&id) = self.ids.get(&[nw, ne, sw, se]) {
+            return id;
+        }
+        let node = |id: Id| self.nodes[id as usize];
+        let node = Node {
+            nw,
+            ne,
+            sw,
+            se,
+            level: node(nw).level + 1,
+            population: node(nw).population
+                + node(ne).population
+                + node(sw).population
+                + node(se).population,
+        };
+        let id = self.nodes.len() as Id;
+        self.nodes.push(node);
+        self.ids.insert([nw, ne, sw, se], id);
+        id
+    }
+
+    fn empty(&mut self, level: u8) -> Id {
+        while self.empty.len() <= level as usize {
+            let below = *self.empty.last().unwrap_or(&DEAD);
+            let id = self.join(below, below, below, below);
+            self.empty.push(id);
+        }
+        self.empty[level as usize]
+    }
+
+    // put the root in the centre of a root twice its size
+    fn expand(&mut self) {
+        let level = self.level();
+        assert!(level < MAX_LEVEL, "universe grew too large");
+        let root = self.nodes[self.root as usize];
+        let empty = self.empty(level - 1);
+        let nw = self.join(empty, empty, empty, root.nw);
+        let ne = self.join(empty, empty, root.ne, empty);
+        let sw = self.join(empty, root.sw, empty, empty);
+        let se = self.join(root.se, empty, empty, empty);
+        self.root = self.join(nw, ne, sw, se);
+        let half = 1i64 << (level - 1);
+        self.origin = (self.origin.0 - half, self.origin.1 - half);
+    }
+
+    // whether every live cell lies within the centre of the centre of the root
+    fn is_padded(&mut self) -> bool {
+        let centre = self.centre(self.root);
+        let inner = self.centre(centre);
+        self.nodes[inner as usize].population == self.population()
+    }
+
+    // node of the level below around the centre of a node of level 2 or more
+    fn centre(&mut self, id: Id) -> Id {
+        let node = self.nodes[id as usize];
+        let quadrant = |id: Id| self.nodes[id as usize];
+        let (nw, ne, sw, se) = (
+            quadrant(node.nw).se,
+            quadrant(node.ne).sw,
+            quadrant(node.sw).ne,
+            quadrant(node.se).nw,
+        );
+        self.join(nw, ne, sw, se)
+    }
+
+    fn set(&mut self, id: Id, x: u64, y: u64, is_alive: bool) -> Id {
+        let node = self.nodes[id as usize];
+        if node.level == 0 {
+            return if is_alive { ALIVE } else { DEAD };
+        }
+        let half = 1 << (node.level - 1);
+        let (cx, cy) = (x % half, y % half);
+        let [mut nw, mut ne, mut sw, mut se] = [node.nw, node.ne, node.sw, node.se];
+        match (x >= half, y >= half) {
+            (false, false) => nw = self.set(nw, cx, cy, is_alive),
+            (true, false) => ne = self.set(ne, cx, cy, is_alive),
+            (false, true) => sw = self.set(sw, cx, cy, is_alive),
+            (true, true) => se = self.set(se, cx, cy, is_alive),
+        }
+        self.join(nw, ne, sw, se)
+    }
+
+    fn collect(&self, id: Id, (x, y): (i64, i64), cells: &mut Vec<(i64, i64)>) {
+        let node = self.nodes[id as usize];
+        if node.population == 0 {
+            return;
+        }
+        if node.level == 0 {
+            cells.push((x, y));
+            return;
+        }
+        let half = 1i64 << (node.level - 1);
+        self.collect(node.nw, (x, y), cells);
+        self.collect(node.ne, (x + half, y), cells);
+        self.collect(node.sw, (x, y + half), cells);
+        self.collect(node.se, (x + half, y + half), cells);
+    }
+
+    // leftmost, topmost, rightmost and bottommost live cells of a node relative to its top
+    // left corner, shared nodes being looked at once
+    fn extent(
+        &self,
+        id: Id,
+        extents: &mut HashMap<Id, Option<(u64, u64, u64, u64)>>,
+    ) -> Option<(u64, u64, u64, u64)> {
+        let node = self.nodes[id as usize];
+        if node.population == 0 {
+            return None;
+        }
+        if node.level == 0 {
+            return Some((0, 0, 0, 0));
+        }
+        if let Some(&extent) = extents.get(&id) {
+            return extent;
+        }
+        let half = 1u64 << (node.level - 1);
+        let quadrants = [
+            (node.nw, 0, 0),
+            (node.ne, half, 0),
+            (node.sw, 0, half),
+            (node.se, half, half),
+        ];
+        let extent = quadrants
+            .into_iter()
+            .filter_map(|(quadrant, x, y)| {
+                let (l, t, r, b) = self.extent(quadrant, extents)?;
+                Some((l + x, t + y, r + x, b + y))
+            })
+            .reduce(|(l, t, r, b), (left, top, right, bottom)| {
+                (l.min(left), t.min(top), r.max(right), b.max(bottom))
+            });
+        extents.insert(id, extent);
+        extent
+    }
+
+    fn copy(&mut self, from: &Self, id: Id, copies: &mut HashMap<Id, Id>) -> Id {
+        if let Some(&copy) = copies.get(&id) {
+            return copy;
+        }
+        let node = from.nodes[id as usize];
+        let nw = self.copy(from, node.nw, copies);
+        let ne = self.copy(from, node.ne, copies);
+        let sw = self.copy(from, node.sw, copies);
+        let se = self.copy(from, node.se, copies);
+        let copy = self.join(nw, ne, sw, se);
+        copies.insert(id, copy);
+        copy
+    }
+
+    // centre of a node of level 2 or more advanced by 2^step generations, step being at most
+    // the level minus 2
+    fn result(&mut self, id: Id, step: u8) -> Id {
+        if let Some(&result) = self.results.get(&(id, step)) {
+            return result;
+        }
+        let node = self.nodes[id as usize];
+        let result = if node.population == 0 {
+            self.empty(node.level - 1)
+        } else if node.level == 2 {
+            self.result_of_cells(id)
+        } else {
+            self.result_of_quadrants(node, step)
+        };
+        self.results.insert((id, step), result);
+        result
+    }
+
+    // the 2 by 2 centre of a 4 by 4 node after a generation, by the rule itself
+    fn result_of_cells(&mut self, id: Id) -> Id {
+        let node = self.nodes[id as usize];
+        let mut cells = [[false; 4]; 4];
+        for (i, quadrant) in [node.nw, node.ne, node.sw, node.se].into_iter().enumerate() {
+            let quadrant = self.nodes[quadrant as usize];
+            let (x, y) = (i % 2 * 2, i / 2 * 2);
+            cells[y][x] = quadrant.nw == ALIVE;
+            cells[y][x + 1] = quadrant.ne == ALIVE;
+            cells[y + 1][x] = quadrant.sw == ALIVE;
+            cells[y + 1][x + 1] = quadrant.se == ALIVE;
+        }
+        let next = |x: usize, y: usize| {
+            let neighbors = (y - 1..=y + 1)
+                .flat_map(|ny| (x - 1..=x + 1).map(move |nx| (nx, ny)))
+                .filter(|&(nx, ny)| (nx, ny) != (x, y) && cells[ny][nx])
+                .count();
+            match self.rule.next_state(cells[y][x], neighbors) {
+                true => ALIVE,
+                false => DEAD,
+            }
+        };
+        let (nw, ne, sw, se) = (next(1, 1), next(2, 1), next(1, 2), next(2, 2));
+        self.join(nw, ne, sw, se)
+    }
+
+    // the centre of a larger node from the results of nine overlapping nodes of the level
+    // below, advanced by half the step each, then of four nodes made from those, advanced by
+    // the other half, where steps shorter than the largest skip the first half
+    fn result_of_quadrants(&mut self, node: Node, step: u8) -> Id {
+        let quadrant = |id: Id| self.nodes[id as usize];
+        let (nw, ne, sw, se) = (
+            quadrant(node.nw),
+            quadrant(node.ne),
+            quadrant(node.sw),
+            quadrant(node.se),
+        );
+        let nodes = [
+            node.nw,
+            self.join(nw.ne, ne.nw, nw.se, ne.sw),
+            node.ne,
+            self.join(nw.sw, nw.se, sw.nw, sw.ne),
+            self.join(nw.se, ne.sw, sw.ne, se.nw),
+            self.join(ne.sw, ne.se, se.nw, se.ne),
+            node.sw,
+            self.join(sw.ne, se.nw, sw.se, se.sw),
+            node.se,
+        ];
+        let full = step == node.level - 2;
+        let mut parts = [DEAD; 9];
+        for (part, &id) in parts.iter_mut().zip(&nodes) {
+            *part = match full {
+                true => self.result(id, step - 1),
+                false => self.centre(id),
+            };
+        }
+        let step = if full { step - 1 } else { step };
+        let mut quarter = |a: usize, b: usize, c: usize, d: usize| {
+            let joined = self.join(parts[a], parts[b], parts[c], parts[d]);
+            self.result(joined, step)
+        };
+        let (nw, ne, sw, se) = (
+            quarter(0, 1, 3, 4),
+            quarter(1, 2, 4, 5),
+            quarter(3, 4, 6, 7),
+            quarter(4, 5, 7, 8),
+        );
+        self.join(nw, ne, sw, se)
+    }
+}
+
+impl Steppable for HashLifeUniverse {
+    fn step(&mut self) {
+        self.step_pow2(0)
+    }
+
+    fn generation(&self) -> u64 {
+        HashLifeUniverse::generation(self)
+    }
+
+    /// jump by the powers of two of the generations, as advance does
+    fn step_by(&mut self, generations: u64) {
+        self.advance(generations)
+    }
+}
@@ -0,0 +1,124 @@
+// hexagonal Life on a square grid, the way Golly emulates it: every cell has the six
+// neighbors left and right, above and below, up left and down right, so that shifting each
+// row half a cell to the right of the one below it lays the cells out as hexagons, the way
+// render::render_hex_to_buffer draws them
+//
+// hex rules are written in B/S notation followed by H, like B2/S34H, with counts up to 6
+use crate::neighborhood::Neighborhood;
+use crate::rule::Rule;
+use crate::Life;
+use std::fmt;
+use std::str::FromStr;
+
+/// offsets of the six neighbors of a cell
+pub const NEIGHBORS: [(isize, isize); 6] = [(-1, -1), (0, -1), (-1, 0), (1, 0), (0, 1), (1, 1)];
+
+/// number of live hexagonal neighbors of a cell, across the edges on maps that wrap
+pub fn number_of_neighbors(life: &impl Life, x: usize, y: usize) -> usize {
+    Neighborhood::Hexagonal.count(life, x, y)
+}
+
+// outer-totalistic rule over the six hexagonal neighbors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexRule(pub Rule);
+
+impl fmt::Display for HexRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}H", self.0)
+    }
+}
+
+// reason a hex rulestring couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseHexRuleError(String);
+
+impl fmt::Display for ParseHexRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid hex rule '{}', expected B/S notation ending in H like B2/S34H",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseHexRuleError {}
+
+impl FromStr for HexRule {
+    type Err = ParseHexRuleError;
+
+    /// parse B/S notation followed by H or h, with counts from 0 to 6
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseHexRuleError(s.to_string());
+        let rule: Rule = s
+            .trim()
+            .strip_suffix(['H', 'h'])
+            .ok_or_else(error)?
+            .parse()
+            .map_err(|_| error())?;
+        match (7..=8).any(|n| rule.is_born(n) || rule.survives(n)) {
+            true => Err(error()),
+            false => Ok(Self(rule)),
+        }
+    }
+}
+
+// board stepped under a hex rule, counting the six hexagonal neighbors of every cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexLife<L: Life> {
+    pub life: L,
+    pub rule: HexRule,
+}
+
+impl<L: Life> HexLife<L> {
+    /// step a board under a hex rule
+    pub fn new(life: L, rule: HexRule) -> Self {
+        Self { life, rule }
+    }
+}
+
+impl<L: Life> Life for HexLife<L> {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        self.life.set_cell(x, y, is_alive)
+    }
+
+    fn width(&self) -> usize {
+        self.life.width()
+    }
+
+    fn height(&self) -> usize {
+        self.life.height()
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.life.is_alive(x, y)
+    }
+
+    fn population_in(&self, x: usize, y: usize, width: usize, height: usize) -> usize {
+        self.life.population_in(x, y, width, height)
+    }
+
+    fn neighborhood(&self) -> Neighborhood {
+        Neighborhood::Hexagonal
+    }
+
+    fn number_of_neighbors(&self, x: usize, y: usize) -> usize {
+        number_of_neighbors(&self.life, x, y)
+    }
+
+    fn set_packed(&mut self, x: usize, y: usize, word: u64) {
+        self.life.set_packed(x, y, word)
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() - std::mem::size_of::<L>() + self.life.memory_usage()
+    }
+
+    fn wraps(&self) -> bool {
+        self.life.wraps()
+    }
+
+    fn rule(&self) -> Rule {
+        self.rule.0
+    }
+}
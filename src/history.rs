@@ -0,0 +1,209 @@
+// generations of a board kept to step back through, each packed 64 cells to a word so that
+// a long history of a large board stays small
+//
+// the packed rows of a generation are cut into chunks shared with the generation next to it
+// wherever they are unchanged, so that keeping hundreds of generations of a large board where
+// little moves costs memory for the changes rather than for whole boards
+use crate::Life;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+
+// words of packed rows in a chunk, 2048 cells
+const CHUNK_WORDS: usize = 32;
+
+// cells of a generation as packed rows, one after the other, in chunks of CHUNK_WORDS words
+// the last one of which can be shorter
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Frame {
+    generation: u64,
+    chunks: Vec<Arc<[u64]>>,
+}
+
+// board stepped like a Simulation that remembers its last generations, which undo and
+// rewind_to go back to and redo replays
+pub struct History<L: Life> {
+    life: L,
+    scratch: L,
+    generation: u64,
+    capacity: usize,
+    // generations before the current one, oldest first
+    past: VecDeque<Frame>,
+    // generations undone, the next one last
+    future: Vec<Frame>,
+}
+
+impl<L: Life> History<L> {
+    /// start from a board keeping up to capacity earlier generations, scratch must have the
+    /// same dimensions
+    pub fn new(life: L, scratch: L, capacity: usize) -> Self {
+        assert_eq!(
+            (life.width(), life.height()),
+            (scratch.width(), scratch.height()),
+            "scratch grid must match dimensions"
+        );
+        Self {
+            life,
+            scratch,
+            generation: 0,
+            capacity,
+            past: VecDeque::new(),
+            future: Vec::new(),
+        }
+    }
+
+    /// current generation
+    pub fn life(&self) -> &L {
+        &self.life
+    }
+
+    /// current generation, for editing, the undone generations no longer following from it
+    /// are forgotten
+    pub fn life_mut(&mut self) -> &mut L {
+        self.future.clear();
+        &mut self.life
+    }
+
+    /// number of the current generation
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// earliest generation that can be rewound to
+    pub fn oldest(&self) -> u64 {
+        self.past
+            .front()
+            .map_or(self.generation, |frame| frame.generation)
+    }
+
+    /// latest generation that can be redone
+    pub fn newest(&self) -> u64 {
+        self.future
+            .first()
+            .map_or(self.generation, |frame| frame.generation)
+    }
+
+    /// calculate the next generation, replaying it if it was undone
+    pub fn step(&mut self) {
+        if self.redo() {
+            return;
+        }
+        self.life.next_generation(&mut self.scratch);
+        let frame = self.frame(self.past.back());
+        self.remember(frame);
+        std::mem::swap(&mut self.life, &mut self.scratch);
+        self.generation += 1;
+    }
+
+    /// go back to the previous generation, false if it isn't kept
+    pub fn undo(&mut self) -> bool {
+        let Some(previous) = self.past.pop_back() else {
+            return false;
+        };
+        let frame = self.frame(Some(&previous));
+        self.future.push(frame);
+        self.restore(previous);
+        true
+    }
+
+    /// go forward to the generation last undone, false if there is none
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.future.pop() else {
+            return false;
+        };
+        let frame = self.frame(Some(&next));
+        self.remember(frame);
+        self.restore(next);
+        true
+    }
+
+    /// undo or redo up to a generation, false and leaving the board as it is if it isn't kept
+    pub fn rewind_to(&mut self, generation: u64) -> bool {
+        if !(self.oldest()..=self.newest()).contains(&generation) {
+            return false;
+        }
+        while self.generation > generation && self.undo() {}
+        while self.generation < generation && self.redo() {}
+        true
+    }
+
+    /// forget every kept generation, numbering the current one as given, for when the board
+    /// is replaced by another
+    pub fn reset(&mut self, generation: u64) {
+        self.past.clear();
+        self.future.clear();
+        self.generation = generation;
+    }
+
+    /// bytes taken by the board, the scratch grid and the kept generations, chunks shared
+    /// between generations counted once
+    pub fn memory_usage(&self) -> usize {
+        let frames = self.past.iter().chain(&self.future);
+        let mut chunks = HashSet::new();
+        let mut bytes = self.life.memory_usage() + self.scratch.memory_usage();
+        for frame in frames {
+            bytes += std::mem::size_of::<Frame>()
+                + frame.chunks.capacity() * std::mem::size_of::<Arc<[u64]>>();
+            for chunk in &frame.chunks {
+                if chunks.insert(Arc::as_ptr(chunk)) {
+                    // the counts of the Arc along with the words
+                    bytes += 2 * std::mem::size_of::<usize>() + chunk.len() * 8;
+                }
+            }
+        }
+        bytes
+    }
+
+    /// stop keeping generations, returning the current one
+    pub fn into_life(self) -> L {
+        self.life
+    }
+
+    // packed cells of the current generation, sharing the chunks unchanged from a frame of
+    // the same board
+    fn frame(&self, like: Option<&Frame>) -> Frame {
+        let rows: Vec<u64> = self.life.iter_rows_packed().flatten().collect();
+        let chunks = rows
+            .chunks(CHUNK_WORDS)
+            .enumerate()
+            .map(
+                |(i, words)| match like.and_then(|frame| frame.chunks.get(i)) {
+                    Some(chunk) if **chunk == *words => Arc::clone(chunk),
+                    _ => Arc::from(words),
+                },
+            )
+            .collect();
+        Frame {
+            generation: self.generation,
+            chunks,
+        }
+    }
+
+    // keep a generation before the current one, dropping the oldest past the capacity
+    fn remember(&mut self, frame: Frame) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.past.len() == self.capacity {
+            self.past.pop_front();
+        }
+        self.past.push_back(frame);
+    }
+
+    // make a kept generation the current one
+    fn restore(&mut self, frame: Frame) {
+        let words = self.life.width().div_ceil(64).max(1);
+        let rows = frame.chunks.iter().flat_map(|chunk| chunk.iter());
+        for (i, &word) in rows.enumerate() {
+            self.life.set_packed(i % words * 64, i / words, word);
+        }
+        self.generation = frame.generation;
+    }
+}
+
+impl<L: Life + Clone> History<L> {
+    /// start from a board, using a copy of it as scratch
+    pub fn from_life(life: L, capacity: usize) -> Self {
+        let scratch = life.clone();
+        Self::new(life, scratch, capacity)
+    }
+}
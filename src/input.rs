@@ -0,0 +1,477 @@
+// input shared by frontends: each turns the keys of its toolkit into input events, and a
+// keymap turns those into the actions the application carries out, so that key bindings,
+// and everything driven by them like macros and the tutorial, behave the same in every
+// frontend, pointer strokes go through edit::Brush
+//
+// key bindings are read from files with a binding per line, like
+//
+//     p pause
+//     shift+1 bookmark-1
+//
+// binding the key to the action over the defaults, the action none leaving the key unbound,
+// like 'escape none', blank lines and lines starting with # are ignored
+//
+// actions throwing away the board, like quitting or starting over from a soup, are guarded
+// against stray keys, carried out only once pressed twice or with shift as the guard says
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+// key independent of the toolkit reporting it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// key of a printable character, letters in lowercase
+    Char(char),
+    Space,
+    Enter,
+    Escape,
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+// names of the keys that aren't characters
+const NAMED_KEYS: [(Key, &str); 10] = [
+    (Key::Space, "space"),
+    (Key::Enter, "enter"),
+    (Key::Escape, "escape"),
+    (Key::Tab, "tab"),
+    (Key::Backspace, "backspace"),
+    (Key::Delete, "delete"),
+    (Key::Left, "left"),
+    (Key::Right, "right"),
+    (Key::Up, "up"),
+    (Key::Down, "down"),
+];
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self, NAMED_KEYS.iter().find(|(key, _)| key == self)) {
+            (_, Some((_, name))) => write!(f, "{}", name),
+            (Key::Char(c), None) => write!(f, "{}", c),
+            (key, None) => write!(f, "{:?}", key),
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        if let Some(&(key, _)) = NAMED_KEYS.iter().find(|(_, name)| *name == s) {
+            return Ok(key);
+        }
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if !c.is_whitespace() && !c.is_control() => Ok(Key::Char(c)),
+            _ => Err(format!("unknown key '{}'", s)),
+        }
+    }
+}
+
+// key press reported by a frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputEvent {
+    pub key: Key,
+    /// whether shift was held
+    pub shift: bool,
+    /// whether the press repeats a key being held down
+    pub repeat: bool,
+}
+
+impl InputEvent {
+    /// first press of a key without shift
+    pub fn pressed(key: Key) -> Self {
+        Self {
+            key,
+            shift: false,
+            repeat: false,
+        }
+    }
+}
+
+// what the user asked for, whichever key they used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    TogglePause,
+    /// pause and go back a generation
+    StepBack,
+    ToggleExplain,
+    ToggleTimeLapse,
+    /// time-lapse draws half as many frames
+    FewerFrames,
+    /// time-lapse draws twice as many frames
+    MoreFrames,
+    ToggleArt,
+    ToggleNeighbors,
+    ToggleReseed,
+    /// start over from a random soup
+    FillRandom,
+    ToggleConstruction,
+    /// move the construction origin to the pointer
+    SetOrigin,
+    ToggleSnap,
+    /// label the cell under the pointer
+    Label,
+    /// mark a corner of a rectangle at the pointer
+    MarkRectangle,
+    /// remove the annotations under the pointer
+    DeleteAnnotations,
+    /// save the generation in a slot from 1 to 9
+    Bookmark(usize),
+    /// jump to the generation saved in a slot
+    JumpToBookmark(usize),
+    /// start recording a macro, or stop and name it
+    ToggleRecording,
+    NextMacro,
+    /// turn replays of macros to the next orientation
+    TurnReplay,
+    /// replay the selected macro at the pointer
+    ReplayMacro,
+    NextSymmetry,
+    SaveSession,
+    /// save the session in a file chosen in a dialog
+    SaveSessionAs,
+    /// place a pattern file chosen in a dialog in the middle of the board
+    OpenPattern,
+    /// show half as much of the board, twice as large
+    ZoomIn,
+    /// show twice as much of the board, half as large
+    ZoomOut,
+    /// move the part of the board shown by a cell
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    /// move the tutorial on
+    Continue,
+    Quit,
+}
+
+// names of the actions without a slot, as written in key binding files
+const ACTION_NAMES: [(Action, &str); 32] = [
+    (Action::TogglePause, "pause"),
+    (Action::StepBack, "step-back"),
+    (Action::ToggleExplain, "explain"),
+    (Action::ToggleTimeLapse, "time-lapse"),
+    (Action::FewerFrames, "fewer-frames"),
+    (Action::MoreFrames, "more-frames"),
+    (Action::ToggleArt, "art"),
+    (Action::ToggleNeighbors, "neighbors"),
+    (Action::ToggleReseed, "reseed"),
+    (Action::FillRandom, "fill-random"),
+    (Action::ToggleConstruction, "construction"),
+    (Action::SetOrigin, "origin"),
+    (Action::ToggleSnap, "snap"),
+    (Action::Label, "label"),
+    (Action::MarkRectangle, "rectangle"),
+    (Action::DeleteAnnotations, "delete-annotations"),
+    (Action::ToggleRecording, "record-macro"),
+    (Action::NextMacro, "next-macro"),
+    (Action::TurnReplay, "turn-replay"),
+    (Action::ReplayMacro, "replay-macro"),
+    (Action::NextSymmetry, "symmetry"),
+    (Action::SaveSession, "save"),
+    (Action::SaveSessionAs, "save-as"),
+    (Action::OpenPattern, "open"),
+    (Action::ZoomIn, "zoom-in"),
+    (Action::ZoomOut, "zoom-out"),
+    (Action::PanLeft, "pan-left"),
+    (Action::PanRight, "pan-right"),
+    (Action::PanUp, "pan-up"),
+    (Action::PanDown, "pan-down"),
+    (Action::Continue, "continue"),
+    (Action::Quit, "quit"),
+];
+
+/// number of bookmark slots
+pub const BOOKMARK_SLOTS: usize = 9;
+
+impl Action {
+    /// whether holding the key down repeats the action
+    pub fn repeats(self) -> bool {
+        matches!(
+            self,
+            Action::FewerFrames
+                | Action::MoreFrames
+                | Action::PanLeft
+                | Action::PanRight
+                | Action::PanUp
+                | Action::PanDown
+        )
+    }
+
+    /// whether the action throws the board away, and with it edits not saved
+    pub fn is_destructive(self) -> bool {
+        matches!(
+            self,
+            Action::FillRandom | Action::OpenPattern | Action::JumpToBookmark(_) | Action::Quit
+        )
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self, ACTION_NAMES.iter().find(|(action, _)| action == self)) {
+            (_, Some((_, name))) => write!(f, "{}", name),
+            (Action::Bookmark(slot), None) => write!(f, "bookmark-{}", slot),
+            (Action::JumpToBookmark(slot), None) => write!(f, "jump-{}", slot),
+            (action, None) => write!(f, "{:?}", action),
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(&(action, _)) = ACTION_NAMES.iter().find(|(_, name)| *name == s) {
+            return Ok(action);
+        }
+        let slot = |slot: &str| {
+            slot.parse()
+                .ok()
+                .filter(|slot| (1..=BOOKMARK_SLOTS).contains(slot))
+        };
+        let action = match s.split_once('-') {
+            Some(("bookmark", n)) => slot(n).map(Action::Bookmark),
+            Some(("jump", n)) => slot(n).map(Action::JumpToBookmark),
+            _ => None,
+        };
+        action.ok_or_else(|| format!("unknown action '{}'", s))
+    }
+}
+
+// actions bound to keys, with or without shift
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: Vec<(Key, bool, Action)>,
+}
+
+impl Default for Keymap {
+    /// the bindings of the window
+    fn default() -> Self {
+        let mut keymap = Self {
+            bindings: Vec::new(),
+        };
+        let letters = [
+            ('u', Action::StepBack),
+            ('e', Action::ToggleExplain),
+            ('t', Action::ToggleTimeLapse),
+            ('=', Action::MoreFrames),
+            ('-', Action::FewerFrames),
+            ('a', Action::ToggleArt),
+            ('n', Action::ToggleNeighbors),
+            ('r', Action::ToggleReseed),
+            ('f', Action::FillRandom),
+            ('c', Action::ToggleConstruction),
+            ('o', Action::SetOrigin),
+            ('g', Action::ToggleSnap),
+            ('l', Action::Label),
+            ('b', Action::MarkRectangle),
+            ('m', Action::ToggleRecording),
+            ('.', Action::TurnReplay),
+            ('p', Action::ReplayMacro),
+            ('y', Action::NextSymmetry),
+            ('s', Action::SaveSession),
+            (']', Action::ZoomIn),
+            ('[', Action::ZoomOut),
+        ];
+        for (c, action) in letters {
+            keymap.bind(Key::Char(c), false, action);
+        }
+        keymap.bind(Key::Space, false, Action::TogglePause);
+        keymap.bind(Key::Delete, false, Action::DeleteAnnotations);
+        keymap.bind(Key::Tab, false, Action::NextMacro);
+        keymap.bind(Key::Enter, false, Action::Continue);
+        keymap.bind(Key::Escape, false, Action::Quit);
+        keymap.bind(Key::Left, false, Action::PanLeft);
+        keymap.bind(Key::Right, false, Action::PanRight);
+        keymap.bind(Key::Up, false, Action::PanUp);
+        keymap.bind(Key::Down, false, Action::PanDown);
+        keymap.bind(Key::Char('s'), true, Action::SaveSessionAs);
+        keymap.bind(Key::Char('o'), true, Action::OpenPattern);
+        for slot in 1..=BOOKMARK_SLOTS {
+            let key = Key::Char(char::from_digit(slot as u32, 10).unwrap_or('0'));
+            keymap.bind(key, false, Action::JumpToBookmark(slot));
+            keymap.bind(key, true, Action::Bookmark(slot));
+        }
+        keymap
+    }
+}
+
+impl Keymap {
+    /// default bindings overridden by those of a key binding file, see the top of the module
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut keymap = Self::default();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| format!("line {}: {}", i + 1, message);
+            let (key, action) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| error(format!("expected KEY ACTION, got '{}'", line)))?;
+            let (key, shift) = match key.strip_prefix("shift+") {
+                Some(key) => (key, true),
+                None => (key, false),
+            };
+            let key = key.parse().map_err(error)?;
+            match action.trim() {
+                "none" => keymap.unbind(key, shift),
+                action => keymap.bind(key, shift, action.parse().map_err(error)?),
+            }
+        }
+        Ok(keymap)
+    }
+
+    /// bind a key, with shift held or not, to an action in place of its current one
+    pub fn bind(&mut self, key: Key, shift: bool, action: Action) {
+        self.bindings.retain(|&(k, s, _)| (k, s) != (key, shift));
+        self.bindings.push((key, shift, action));
+    }
+
+    /// leave a key, with shift held or not, without an action
+    pub fn unbind(&mut self, key: Key, shift: bool) {
+        self.bindings.retain(|&(k, s, _)| (k, s) != (key, shift));
+    }
+
+    /// key and whether shift is held of every binding
+    pub fn bindings(&self) -> impl Iterator<Item = (Key, bool, Action)> + '_ {
+        self.bindings.iter().copied()
+    }
+
+    /// action of a key press, keys pressed with shift and bound without it trigger their
+    /// action too, and repeats only trigger actions that repeat
+    pub fn action(&self, event: &InputEvent) -> Option<Action> {
+        let bound = |shift: bool| {
+            self.bindings
+                .iter()
+                .find(|&&(key, s, _)| (key, s) == (event.key, shift))
+                .map(|&(_, _, action)| action)
+        };
+        let action = match event.shift {
+            true => bound(true).or_else(|| bound(false)),
+            false => bound(false),
+        }?;
+        (!event.repeat || action.repeats()).then_some(action)
+    }
+}
+
+/// time a destructive action waits for its key to be pressed again
+pub const CONFIRM_TIME: Duration = Duration::from_secs(3);
+
+// what it takes to carry out a destructive action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Guard {
+    /// pressing its key once, as any other action
+    None,
+    /// pressing its key with shift held, jumps to bookmarks being confirmed instead as their
+    /// keys bookmark with shift held
+    Shift,
+    /// pressing its key twice within CONFIRM_TIME
+    #[default]
+    Confirm,
+}
+
+impl fmt::Display for Guard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Guard::None => write!(f, "none"),
+            Guard::Shift => write!(f, "shift"),
+            Guard::Confirm => write!(f, "confirm"),
+        }
+    }
+}
+
+// reason a guard name couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGuardError(String);
+
+impl fmt::Display for ParseGuardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid guard '{}', expected none, shift or confirm",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseGuardError {}
+
+impl FromStr for Guard {
+    type Err = ParseGuardError;
+
+    /// parse a name as written by Display, case insensitive
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(Guard::None),
+            "shift" => Ok(Guard::Shift),
+            "confirm" => Ok(Guard::Confirm),
+            _ => Err(ParseGuardError(s.to_string())),
+        }
+    }
+}
+
+// whether an action pressed goes ahead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Allowed,
+    /// held back until its key is pressed again
+    Confirm,
+    /// held back as shift wasn't held
+    NeedsShift,
+}
+
+// guard of the destructive actions, with the one waiting to be confirmed
+#[derive(Debug, Clone, Default)]
+pub struct ActionGuard {
+    guard: Guard,
+    pending: Option<(Action, Instant)>,
+}
+
+impl ActionGuard {
+    /// guard destructive actions as a guard says
+    pub fn new(guard: Guard) -> Self {
+        Self {
+            guard,
+            pending: None,
+        }
+    }
+
+    /// whether an action pressed at a time, with shift held or not, goes ahead, pressing
+    /// any other action in between cancelling a confirmation
+    pub fn check(&mut self, action: Action, shift: bool, now: Instant) -> Verdict {
+        let pending = self.pending.take();
+        if !action.is_destructive() {
+            return Verdict::Allowed;
+        }
+        let guard = match (self.guard, action) {
+            (Guard::Shift, Action::JumpToBookmark(_)) => Guard::Confirm,
+            (guard, _) => guard,
+        };
+        match guard {
+            Guard::None => Verdict::Allowed,
+            Guard::Shift if shift => Verdict::Allowed,
+            Guard::Shift => Verdict::NeedsShift,
+            Guard::Confirm => match pending {
+                Some((waiting, since))
+                    if waiting == action && now.duration_since(since) <= CONFIRM_TIME =>
+                {
+                    Verdict::Allowed
+                }
+                _ => {
+                    self.pending = Some((action, now));
+                    Verdict::Confirm
+                }
+            },
+        }
+    }
+}
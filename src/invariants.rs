@@ -0,0 +1,169 @@
+// checks of what backends promise about their state, run after every generation by
+// rlife run --paranoid to catch bugs in new engines as soon as they happen instead of
+// as odd patterns thousands of generations later
+use crate::ownership::{Ownership, NO_OWNER};
+use crate::{BitGrid, HashLifeUniverse, Life, SparseLife};
+use std::fmt;
+
+// broken invariant, describing where the state disagreed with itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation(pub String);
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invariant violated: {}", self.0)
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// check that the populations a board reports, over the whole board, its quadrants and its
+/// packed rows, match its cells, and that packed rows have no cells past the right edge
+pub fn check_life(life: &impl Life) -> Result<(), Violation> {
+    let (width, height) = (life.width(), life.height());
+    let count = |x0: usize, y0: usize, x1: usize, y1: usize| {
+        (y0..y1)
+            .flat_map(|y| (x0..x1).map(move |x| (x, y)))
+            .filter(|&(x, y)| life.is_alive(x, y))
+            .count()
+    };
+    let (half_width, half_height) = (width / 2, height / 2);
+    let regions = [
+        (0, 0, width, height),
+        (0, 0, half_width, half_height),
+        (half_width, 0, width, half_height),
+        (0, half_height, half_width, height),
+        (half_width, half_height, width, height),
+    ];
+    for (x0, y0, x1, y1) in regions {
+        let (cached, counted) = (
+            life.population_in(x0, y0, x1 - x0, y1 - y0),
+            count(x0, y0, x1, y1),
+        );
+        if cached != counted {
+            return Err(Violation(format!(
+                "population of {}x{} at ({}, {}) is {} but {} cells are alive",
+                x1 - x0,
+                y1 - y0,
+                x0,
+                y0,
+                cached,
+                counted
+            )));
+        }
+    }
+    for (y, row) in life.iter_rows_packed().enumerate() {
+        for (i, &word) in row.iter().enumerate() {
+            let cells = (0..64).fold(0, |cells, bit| {
+                cells | (life.is_alive(i * 64 + bit, y) as u64) << bit
+            });
+            // cells past the right edge read as dead, so stray bits show up here too
+            if word != cells {
+                return Err(Violation(format!(
+                    "packed word {} of row {} is {:#x} but the cells are {:#x}",
+                    i, y, word, cells
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// check that two boards of the same size hold the same cells, naming the backend that
+/// disagrees with the expected board
+pub fn check_same(name: &str, expected: &impl Life, actual: &impl Life) -> Result<(), Violation> {
+    if (expected.width(), expected.height()) != (actual.width(), actual.height()) {
+        return Err(Violation(format!(
+            "{} is {}x{} instead of {}x{}",
+            name,
+            actual.width(),
+            actual.height(),
+            expected.width(),
+            expected.height()
+        )));
+    }
+    let mut cells = (0..expected.height()).flat_map(|y| (0..expected.width()).map(move |x| (x, y)));
+    match cells.find(|&(x, y)| expected.is_alive(x, y) != actual.is_alive(x, y)) {
+        Some((x, y)) => Err(Violation(format!(
+            "{} has cell ({}, {}) {} instead of {}",
+            name,
+            x,
+            y,
+            state(actual.is_alive(x, y)),
+            state(expected.is_alive(x, y))
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// check that the bit-sliced, sparse and HashLife engines agree with a generation calculated
+/// from the previous one under its rule, for bounded boards where all cells outside are dead,
+/// and that the HashLife quadtree stays consistent
+pub fn check_step(previous: &impl Life, next: &impl Life) -> Result<(), Violation> {
+    let (width, height) = (previous.width(), previous.height());
+    let (mut current, mut stepped) = (BitGrid::new(width, height), BitGrid::new(width, height));
+    copy_packed(previous, &mut current);
+    current.next_generation_with(previous.rule(), &mut stepped);
+    check_same("bit grid", next, &stepped)?;
+
+    if previous.rule().is_born(0) {
+        // an unbounded plane would fill up, only the bounded engines can run these
+        return Ok(());
+    }
+    // cells born past the edges of the board are ignored, they can't have affected it yet
+    let restricted = |is_alive: &dyn Fn(i64, i64) -> bool| {
+        let mut board = BitGrid::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                board.set_cell(x, y, is_alive(x as i64, y as i64));
+            }
+        }
+        board
+    };
+    let mut sparse = SparseLife::from_life(previous, 0, 0);
+    sparse.step();
+    check_same(
+        "sparse set",
+        next,
+        &restricted(&|x, y| sparse.is_alive(x, y)),
+    )?;
+
+    let mut hashlife = HashLifeUniverse::from_life(previous, 0, 0);
+    hashlife.advance(1);
+    hashlife.check_invariants()?;
+    check_same(
+        "HashLife",
+        next,
+        &restricted(&|x, y| hashlife.is_alive(x, y)),
+    )
+}
+
+/// check that only live cells have owners
+pub fn check_ownership(ownership: &Ownership, life: &impl Life) -> Result<(), Violation> {
+    let mut cells = (0..life.height()).flat_map(|y| (0..life.width()).map(move |x| (x, y)));
+    match cells.find(|&(x, y)| ownership.owner(x, y) != NO_OWNER && !life.is_alive(x, y)) {
+        Some((x, y)) => Err(Violation(format!(
+            "dead cell ({}, {}) is owned by {}",
+            x,
+            y,
+            ownership.owner(x, y)
+        ))),
+        None => Ok(()),
+    }
+}
+
+// copy a board a word at a time, going through the packed rows checked by check_life
+fn copy_packed(from: &impl Life, to: &mut impl Life) {
+    for (y, row) in from.iter_rows_packed().enumerate() {
+        for (i, word) in row.into_iter().enumerate() {
+            to.set_packed(i * 64, y, word);
+        }
+    }
+}
+
+fn state(is_alive: bool) -> &'static str {
+    match is_alive {
+        true => "alive",
+        false => "dead",
+    }
+}
@@ -1,4 +1,90 @@
+use neighborhood::Neighborhood;
+use random::Rng;
 use std::ops::{Deref, DerefMut};
+use transform::Transform;
+
+#[cfg(feature = "analysis")]
+pub mod analysis;
+pub mod ant;
+pub mod ascii;
+#[cfg(feature = "io")]
+pub mod autosave;
+mod bitgrid;
+mod board;
+pub mod compact;
+pub mod cycle;
+#[cfg(feature = "analysis")]
+pub mod damage;
+pub mod edit;
+mod emitter;
+#[cfg(feature = "analysis")]
+pub mod ensemble;
+pub mod explain;
+pub mod expression;
+#[cfg(feature = "io")]
+pub mod formats;
+pub mod generations;
+pub mod hashlife;
+pub mod hex;
+pub mod history;
+pub mod input;
+pub mod invariants;
+pub mod ltl;
+pub mod margolus;
+pub mod multistate;
+pub mod neighborhood;
+pub mod obstacles;
+pub mod ownership;
+#[cfg(feature = "io")]
+pub mod puzzle;
+pub mod random;
+pub mod render;
+#[cfg(feature = "io")]
+pub mod report;
+pub mod rule;
+#[cfg(feature = "analysis")]
+pub mod scan;
+#[cfg(feature = "analysis")]
+pub mod search;
+pub mod series;
+#[cfg(feature = "simd")]
+pub mod simd;
+mod simulation;
+#[cfg(feature = "audio")]
+pub mod sonify;
+pub mod sparse;
+#[cfg(feature = "analysis")]
+pub mod spectrum;
+#[cfg(feature = "analysis")]
+pub mod states;
+pub mod stats;
+pub mod transform;
+pub mod war;
+pub mod wireworld;
+pub mod zones;
+
+pub use bitgrid::BitGrid;
+pub use board::Board;
+pub use emitter::Emitter;
+pub use hashlife::HashLifeUniverse;
+pub use history::History;
+pub use rule::{Rule, RuleLife};
+pub use simulation::{Hook, Simulation};
+pub use sparse::SparseLife;
+
+/// rulestring of Conway's Game of Life, assumed when no other rule is given
+pub const DEFAULT_RULE: &str = "B3/S23";
+
+// commonly used items, for glob importing
+pub mod prelude {
+    #[cfg(feature = "io")]
+    pub use crate::formats::Pattern;
+    pub use crate::transform::Transform;
+    pub use crate::{
+        BitGrid, Board, Emitter, HashLifeUniverse, History, Life, LifeGrid, Rule, RuleLife,
+        Simulation, SparseLife, Steppable, TorusGrid, VecGrid,
+    };
+}
 
 // minimum behavior needed to be able to implement life
 pub trait Life {
@@ -14,30 +100,137 @@ pub trait Life {
     /// state of cell
     fn is_alive(&self, x: usize, y: usize) -> bool;
 
-    /// calculate the number of live neighbors of cell
-    fn number_of_neighbors(&self, x: usize, y: usize) -> usize {
-        let range = |v: usize| (v.saturating_sub(1)..=v.saturating_add(1));
-        // cartesian product of iterators
-        range(x)
-            .map(|rx| range(y).map(move |ry| (rx, ry)))
-            .flatten()
-            // count neightbors, excluding itself
-            .filter(|&(a, b)| !(a == x && b == y) && self.is_alive(a, b))
+    /// number of live cells in the width by height rectangle with its top left corner at
+    /// (x, y), backends keeping the populations of regions, like quadtrees, can answer
+    /// without visiting every cell
+    fn population_in(&self, x: usize, y: usize, width: usize, height: usize) -> usize {
+        let (right, bottom) = (
+            x.saturating_add(width).min(self.width()),
+            y.saturating_add(height).min(self.height()),
+        );
+        (y..bottom)
+            .flat_map(|cy| (x..right).map(move |cx| (cx, cy)))
+            .filter(|&(cx, cy)| self.is_alive(cx, cy))
             .count()
     }
 
-    /// calculate the next generation of the map
+    /// cells counted as neighbors of a cell, the 8 around it unless the implementation has
+    /// another, like NeighborhoodLife
+    fn neighborhood(&self) -> Neighborhood {
+        Neighborhood::Moore
+    }
+
+    /// calculate the number of live neighbors of cell, across the edges on maps that wrap
+    fn number_of_neighbors(&self, x: usize, y: usize) -> usize {
+        self.neighborhood().count(self, x, y)
+    }
+
+    /// rows of the map from the top, each packed into 64 cells per word with the leftmost
+    /// cell in the lowest bit, made one at a time so that exporters and analyzers can stream
+    /// boards too large to copy whole
+    fn iter_rows_packed(&self) -> impl Iterator<Item = Vec<u64>> + '_ {
+        let width = self.width();
+        (0..self.height()).map(move |y| {
+            let mut row = vec![0; width.div_ceil(64)];
+            (0..width)
+                .filter(|&x| self.is_alive(x, y))
+                .for_each(|x| row[x / 64] |= 1 << (x % 64));
+            row
+        })
+    }
+
+    /// set the cells from (x, y) rightwards from the bits of a word, lowest bit first, cells
+    /// past the right edge are left out, backends packing cells into words can store it whole
+    fn set_packed(&mut self, x: usize, y: usize, word: u64) {
+        (x..self.width().min(x.saturating_add(64)))
+            .for_each(|cx| self.set_cell(cx, y, (word >> (cx - x)) & 1 == 1));
+    }
+
+    /// whether coordinates past the edges wrap around to the opposite edge, like on
+    /// TorusGrid, rather than being off the map
+    fn wraps(&self) -> bool {
+        false
+    }
+
+    /// set the live cells of a pattern, rotated or reflected by a transform, with the top left
+    /// corner of the transformed pattern at (x, y), parts past the edges wrap around on maps
+    /// that wrap and are clipped on the others
+    fn blit(&mut self, pattern: &impl Life, x: usize, y: usize, transform: Transform)
+    where
+        Self: Sized,
+    {
+        let (width, height, wraps) = (self.width(), self.height(), self.wraps());
+        if width == 0 || height == 0 {
+            return;
+        }
+        for (px, py) in stats::live_cells(pattern) {
+            let (tx, ty) = transform.apply(px, py, pattern.width(), pattern.height());
+            if wraps {
+                let (cx, cy) = (
+                    (x % width + tx % width) % width,
+                    (y % height + ty % height) % height,
+                );
+                self.set_cell(cx, cy, true);
+            } else if let (Some(cx), Some(cy)) = (x.checked_add(tx), y.checked_add(ty)) {
+                if cx < width && cy < height {
+                    self.set_cell(cx, cy, true);
+                }
+            }
+        }
+    }
+
+    /// set every cell alive with probability density, the same seed of rng always making the
+    /// same soup
+    fn fill_random(&mut self, density: f64, rng: &mut Rng) {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.set_cell(x, y, rng.next_f64() < density);
+            }
+        }
+    }
+
+    /// bytes taken by the map, its heap allocations included, so that backends can be compared
+    /// by the footprint of a pattern
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+
+    /// rule the map is stepped under, Conway's B3/S23 unless the implementation has another,
+    /// like RuleLife
+    fn rule(&self) -> Rule {
+        Rule::default()
+    }
+
+    /// state of cell in the next generation
+    fn next_state(&self, x: usize, y: usize) -> bool {
+        self.rule()
+            .next_state(self.is_alive(x, y), self.number_of_neighbors(x, y))
+    }
+
+    /// calculate the next generation of the map, row by row in plain loops so that stepping
+    /// dense grids never allocates, implementations overriding it should keep it that way
     fn next_generation(&self, other: &mut impl Life) {
-        // cartesian product of iterators
-        (0..Life::width(self))
-            .map(|x| (0..Life::height(self)).map(move |y| (x, y)))
-            .flatten()
-            // rules of life
-            .for_each(|(x, y)| match self.number_of_neighbors(x, y) {
-                3 => other.set_cell(x, y, true),                // rule for life
-                2 => other.set_cell(x, y, self.is_alive(x, y)), // rule for stagnation
-                _ => other.set_cell(x, y, false),               // rule for death
-            })
+        for y in 0..Life::height(self) {
+            for x in 0..Life::width(self) {
+                other.set_cell(x, y, self.next_state(x, y));
+            }
+        }
+    }
+}
+
+// universe advanced a generation at a time whatever its stepping looks like, boards stepped
+// cell by cell through Simulation, the unbounded universes, ants and block automata updating
+// 2x2 blocks at once alike, for frontends that only step and draw
+pub trait Steppable {
+    /// calculate the next generation
+    fn step(&mut self);
+
+    /// number of generations calculated so far
+    fn generation(&self) -> u64;
+
+    /// calculate the given number of generations
+    fn step_by(&mut self, generations: u64) {
+        (0..generations).for_each(|_| self.step());
     }
 }
 
@@ -69,6 +262,41 @@ impl<const W: usize, const H: usize> DerefMut for LifeGrid<W, H> {
     }
 }
 
+impl<const W: usize, const H: usize> LifeGrid<W, H> {
+    /// calculate the next generation into other, splitting the rows into one band per thread
+    /// like VecGrid::par_next_generation, with the same result as next_generation
+    pub fn par_next_generation(&self, other: &mut LifeGrid<W, H>, threads: usize) {
+        if W == 0 || H == 0 {
+            return;
+        }
+        let band_rows = H.div_ceil(threads.max(1));
+        std::thread::scope(|scope| {
+            for (band, rows) in other.data.chunks_mut(band_rows).enumerate() {
+                scope.spawn(move || {
+                    for (i, row) in rows.iter_mut().enumerate() {
+                        let y = band * band_rows + i;
+                        for (x, cell) in row.iter_mut().enumerate() {
+                            *cell = self.next_state(x, y);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    /// calculate the next generation into other a row at a time with vector instructions,
+    /// with the same result as next_generation
+    #[cfg(feature = "simd")]
+    pub fn simd_next_generation(&self, other: &mut LifeGrid<W, H>) {
+        let dead = [false; W];
+        for (y, next) in other.data.iter_mut().enumerate() {
+            let above = y.checked_sub(1).map_or(&dead, |y| &self.data[y]);
+            let below = self.data.get(y + 1).unwrap_or(&dead);
+            simd::next_row(self.rule(), above, &self.data[y], below, next);
+        }
+    }
+}
+
 // implement Life for LifeGrid
 impl<const W: usize, const H: usize> Life for LifeGrid<W, H> {
     fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
@@ -91,4 +319,181 @@ impl<const W: usize, const H: usize> Life for LifeGrid<W, H> {
     fn height(&self) -> usize {
         H
     }
+
+    /// a row at a time with vector instructions, each row calculated before being set in
+    /// other
+    #[cfg(feature = "simd")]
+    fn next_generation(&self, other: &mut impl Life) {
+        let dead = [false; W];
+        let mut next = [false; W];
+        for y in 0..H {
+            let above = y.checked_sub(1).map_or(&dead, |y| &self.data[y]);
+            let below = self.data.get(y + 1).unwrap_or(&dead);
+            simd::next_row(self.rule(), above, &self.data[y], below, &mut next);
+            for (x, &is_alive) in next.iter().enumerate() {
+                other.set_cell(x, y, is_alive);
+            }
+        }
+    }
+}
+
+// grid wrapping around its edges like a torus, so that patterns leaving one side come back
+// on the other, coordinates past the edges wrap too
+pub struct TorusGrid<const W: usize, const H: usize> {
+    data: [[bool; W]; H],
+}
+
+impl<const W: usize, const H: usize> Default for TorusGrid<W, H> {
+    fn default() -> Self {
+        Self {
+            data: [[false; W]; H],
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Deref for TorusGrid<W, H> {
+    type Target = [[bool; W]; H];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<const W: usize, const H: usize> DerefMut for TorusGrid<W, H> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<const W: usize, const H: usize> Life for TorusGrid<W, H> {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        if W > 0 && H > 0 {
+            self.data[y % H][x % W] = is_alive;
+        }
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        W > 0 && H > 0 && self.data[y % H][x % W]
+    }
+
+    fn width(&self) -> usize {
+        W
+    }
+
+    fn height(&self) -> usize {
+        H
+    }
+
+    fn wraps(&self) -> bool {
+        true
+    }
+}
+
+// heap allocated grid with dimensions chosen at runtime, stored row-major
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VecGrid {
+    width: usize,
+    height: usize,
+    data: Vec<bool>,
+}
+
+impl VecGrid {
+    /// create an empty grid of the given size
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![false; width * height],
+        }
+    }
+
+    /// change the size of the grid in place, keeping the cells that still fit with the top
+    /// left corner fixed and leaving new cells dead
+    pub fn resize(&mut self, width: usize, height: usize) {
+        if (width, height) == (self.width, self.height) {
+            return;
+        }
+        let mut data = vec![false; width * height];
+        for y in 0..height.min(self.height) {
+            let columns = width.min(self.width);
+            data[y * width..y * width + columns]
+                .copy_from_slice(&self.data[y * self.width..y * self.width + columns]);
+        }
+        *self = Self {
+            width,
+            height,
+            data,
+        };
+    }
+
+    /// calculate the next generation into other, splitting the rows into one band per thread
+    ///
+    /// every cell only depends on the previous generation, so the result is identical to
+    /// next_generation for any number of threads
+    pub fn par_next_generation(&self, other: &mut VecGrid, threads: usize) {
+        self.par_next_generation_with(self.rule(), other, threads)
+    }
+
+    /// calculate the next generation under a rule into other, one band of rows per thread
+    /// like par_next_generation
+    pub fn par_next_generation_with(&self, rule: Rule, other: &mut VecGrid, threads: usize) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids must have the same dimensions"
+        );
+        if self.data.is_empty() {
+            return;
+        }
+        let band_rows = self.height.div_ceil(threads.max(1));
+        std::thread::scope(|scope| {
+            for (band, cells) in other.data.chunks_mut(band_rows * self.width).enumerate() {
+                scope.spawn(move || {
+                    let first_row = band * band_rows;
+                    cells.iter_mut().enumerate().for_each(|(i, cell)| {
+                        let (x, y) = (i % self.width, first_row + i / self.width);
+                        *cell = rule.next_state(self.is_alive(x, y), self.number_of_neighbors(x, y))
+                    });
+                });
+            }
+        });
+    }
+}
+
+// implement Life for VecGrid
+impl Life for VecGrid {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        if x < self.width && y < self.height {
+            self.data[y * self.width + x] = is_alive;
+        }
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.data[y * self.width + x]
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.data.capacity()
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn iter_rows_packed(&self) -> impl Iterator<Item = Vec<u64>> + '_ {
+        (0..self.height).map(|y| {
+            self.data[y * self.width..(y + 1) * self.width]
+                .chunks(64)
+                .map(|word| {
+                    word.iter()
+                        .enumerate()
+                        .fold(0, |packed, (i, &alive)| packed | (alive as u64) << i)
+                })
+                .collect()
+        })
+    }
 }
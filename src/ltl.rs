@@ -0,0 +1,183 @@
+// Larger than Life, outer-totalistic rules over every cell up to a radius, with ranges of
+// neighbor counts causing birth and survival rather than single counts, written the way Golly
+// does, like R5,C0,M1,S34..58,B34..45,NM for Bosco's Rule: the radius, 0 or 2 states, whether
+// the cell itself counts as a neighbor of its own, the survival and birth ranges and the
+// Moore neighborhood
+use crate::neighborhood::Neighborhood;
+use crate::Life;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// greatest radius accepted, as in Golly
+pub const MAX_RADIUS: usize = 500;
+
+// radius, ranges of counts and whether the middle cell counts
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LtlRule {
+    pub radius: usize,
+    /// whether a live cell counts itself among its neighbors
+    pub middle: bool,
+    pub survival: RangeInclusive<usize>,
+    pub birth: RangeInclusive<usize>,
+}
+
+impl LtlRule {
+    /// Bosco's Rule, with its bugs and rhythmic oscillators
+    pub const BOSCO: &'static str = "R5,C0,M1,S34..58,B34..45,NM";
+
+    /// neighborhood counted by the rule
+    pub fn neighborhood(&self) -> Neighborhood {
+        match self.radius {
+            1 => Neighborhood::Moore,
+            radius => Neighborhood::ExtendedMoore(radius),
+        }
+    }
+
+    /// state of a cell in the next generation given its live neighbors, the cell itself
+    /// left out
+    pub fn next_state(&self, is_alive: bool, neighbors: usize) -> bool {
+        match is_alive {
+            true => self.survival.contains(&(neighbors + self.middle as usize)),
+            false => self.birth.contains(&neighbors),
+        }
+    }
+}
+
+impl fmt::Display for LtlRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "R{},C0,M{},S{}..{},B{}..{},NM",
+            self.radius,
+            self.middle as u8,
+            self.survival.start(),
+            self.survival.end(),
+            self.birth.start(),
+            self.birth.end()
+        )
+    }
+}
+
+// reason a Larger than Life rulestring couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLtlRuleError(String);
+
+impl fmt::Display for ParseLtlRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid Larger than Life rule '{}', expected one like R5,C0,M1,S34..58,B34..45,NM",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseLtlRuleError {}
+
+impl FromStr for LtlRule {
+    type Err = ParseLtlRuleError;
+
+    /// parse Golly's notation, case insensitive, with a radius up to MAX_RADIUS, C and N
+    /// being optional but only 0 or 2 states and the Moore neighborhood supported, and a
+    /// single count like S5 standing for S5..5
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseLtlRuleError(s.to_string());
+        let (mut radius, mut middle, mut survival, mut birth) = (None, false, None, None);
+        for part in s.trim().split(',') {
+            let part = part.trim().to_uppercase();
+            let (key, value) = part.split_at(part.chars().next().map_or(0, char::len_utf8));
+            let range = |value: &str| -> Option<RangeInclusive<usize>> {
+                let (start, end) = value.split_once("..").unwrap_or((value, value));
+                Some(start.parse().ok()?..=end.parse().ok()?)
+            };
+            match key {
+                "R" if radius.is_none() => {
+                    radius = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|radius| (1..=MAX_RADIUS).contains(radius))
+                            .ok_or_else(error)?,
+                    )
+                }
+                "C" if ["0", "2"].contains(&value) => (),
+                "M" if ["0", "1"].contains(&value) => middle = value == "1",
+                "S" if survival.is_none() => survival = Some(range(value).ok_or_else(error)?),
+                "B" if birth.is_none() => birth = Some(range(value).ok_or_else(error)?),
+                "N" if value == "M" => (),
+                _ => return Err(error()),
+            }
+        }
+        match (radius, survival, birth) {
+            (Some(radius), Some(survival), Some(birth)) => Ok(Self {
+                radius,
+                middle,
+                survival,
+                birth,
+            }),
+            _ => Err(error()),
+        }
+    }
+}
+
+// board stepped under a Larger than Life rule, counting every cell up to its radius
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LtlLife<L: Life> {
+    pub life: L,
+    pub rule: LtlRule,
+}
+
+impl<L: Life> LtlLife<L> {
+    /// step a board under a Larger than Life rule
+    pub fn new(life: L, rule: LtlRule) -> Self {
+        Self { life, rule }
+    }
+}
+
+impl<L: Life> Life for LtlLife<L> {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        self.life.set_cell(x, y, is_alive)
+    }
+
+    fn width(&self) -> usize {
+        self.life.width()
+    }
+
+    fn height(&self) -> usize {
+        self.life.height()
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.life.is_alive(x, y)
+    }
+
+    fn population_in(&self, x: usize, y: usize, width: usize, height: usize) -> usize {
+        self.life.population_in(x, y, width, height)
+    }
+
+    fn neighborhood(&self) -> Neighborhood {
+        self.rule.neighborhood()
+    }
+
+    fn number_of_neighbors(&self, x: usize, y: usize) -> usize {
+        self.rule.neighborhood().count(&self.life, x, y)
+    }
+
+    fn set_packed(&mut self, x: usize, y: usize, word: u64) {
+        self.life.set_packed(x, y, word)
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() - std::mem::size_of::<L>() + self.life.memory_usage()
+    }
+
+    fn wraps(&self) -> bool {
+        self.life.wraps()
+    }
+
+    fn next_state(&self, x: usize, y: usize) -> bool {
+        self.rule
+            .next_state(self.is_alive(x, y), self.number_of_neighbors(x, y))
+    }
+}
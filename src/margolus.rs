@@ -0,0 +1,194 @@
+// block cellular automata on the Margolus neighborhood: the board is cut into 2x2 blocks,
+// every block is replaced as a whole by the rule's table, and the partition shifts a cell
+// down and right every other generation so that blocks overlap those of the generation
+// before, as in Critters and the billiard-ball model
+//
+// blocks are numbered by their cells, the top left one being 1, the top right 2, the bottom
+// left 4 and the bottom right 8, and rules written as in MCell, MS,D followed by what each
+// of the 16 blocks becomes, like MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15 for the
+// billiard-ball model
+use crate::{Life, Steppable};
+use std::fmt;
+use std::str::FromStr;
+
+// block each block becomes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MargolusRule {
+    pub table: [u8; 16],
+}
+
+impl MargolusRule {
+    /// the billiard-ball model: a lone cell moves to the opposite corner and two cells in a
+    /// diagonal turn into the other diagonal, so that balls travel and bounce off each other
+    pub const BILLIARD_BALLS: &'static str = "MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15";
+
+    /// Critters: blocks of 2 cells stay, every other block is inverted and those of 3 cells
+    /// turned around as well, a reversible rule full of gliders
+    pub const CRITTERS: &'static str = "MS,D15;14;13;3;11;5;6;1;7;9;10;2;12;4;8;0";
+
+    /// Tron: blocks all alive or all dead are inverted
+    pub const TRON: &'static str = "MS,D15;1;2;3;4;5;6;7;8;9;10;11;12;13;14;0";
+
+    /// block a block becomes
+    pub fn next_block(&self, block: u8) -> u8 {
+        self.table[block as usize & 15]
+    }
+
+    /// whether no two blocks become the same one, so that every generation has exactly one
+    /// generation before it
+    pub fn is_reversible(&self) -> bool {
+        (0..16u8).all(|block| self.table.contains(&block))
+    }
+
+    /// rule undoing this one, None unless it is reversible
+    pub fn inverse(&self) -> Option<Self> {
+        let mut inverse = *self;
+        for block in 0..16u8 {
+            inverse.table[self.next_block(block) as usize] = block;
+        }
+        self.is_reversible().then_some(inverse)
+    }
+}
+
+impl fmt::Display for MargolusRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let blocks: Vec<String> = self.table.iter().map(u8::to_string).collect();
+        write!(f, "MS,D{}", blocks.join(";"))
+    }
+}
+
+// reason a Margolus rule couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMargolusRuleError(String);
+
+impl fmt::Display for ParseMargolusRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid Margolus rule '{}', expected critters, billiards, tron or MS,D and 16 \
+             blocks like MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseMargolusRuleError {}
+
+impl FromStr for MargolusRule {
+    type Err = ParseMargolusRuleError;
+
+    /// parse MCell's notation, case insensitive, or the name of a known rule
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseMargolusRuleError(s.to_string());
+        let rule = s.trim().to_uppercase();
+        let rule = match rule.as_str() {
+            "CRITTERS" => Self::CRITTERS,
+            "BILLIARDS" | "BBM" => Self::BILLIARD_BALLS,
+            "TRON" => Self::TRON,
+            rule => rule,
+        };
+        let blocks: Vec<u8> = rule
+            .strip_prefix("MS,D")
+            .ok_or_else(error)?
+            .split(';')
+            .map(|block| block.trim().parse().ok().filter(|&block| block < 16))
+            .collect::<Option<_>>()
+            .ok_or_else(error)?;
+        Ok(Self {
+            table: blocks.try_into().map_err(|_| error())?,
+        })
+    }
+}
+
+// board stepped block by block under a Margolus rule, the blocks past the edges of boards
+// that don't wrap losing their cells outside of the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MargolusLife<L: Life> {
+    pub life: L,
+    pub rule: MargolusRule,
+    generation: u64,
+}
+
+impl<L: Life> MargolusLife<L> {
+    /// step a board under a Margolus rule, starting with the blocks at even coordinates
+    pub fn new(life: L, rule: MargolusRule) -> Self {
+        Self {
+            life,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// offset of the blocks of the current generation, 0 or 1 in both directions
+    pub fn phase(&self) -> usize {
+        (self.generation % 2) as usize
+    }
+
+    /// replace every block of the current partition, in place as blocks don't overlap, the
+    /// blocks of odd generations holding the cells of the first row and column together with
+    /// those of the last on boards that wrap, which should then be of even sizes
+    pub fn step(&mut self) {
+        self.replace_blocks(self.rule, self.phase());
+        self.generation += 1;
+    }
+
+    /// go back to the previous generation under the inverse of a reversible rule, false at
+    /// generation 0 or for rules that aren't reversible, on boards that don't wrap cells lost
+    /// past the edges stay lost
+    pub fn step_back(&mut self) -> bool {
+        let Some(inverse) = self.rule.inverse().filter(|_| self.generation > 0) else {
+            return false;
+        };
+        self.generation -= 1;
+        self.replace_blocks(inverse, self.phase());
+        true
+    }
+
+    /// number of generations calculated so far
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    // replace every block of the partition of a phase as a rule says
+    fn replace_blocks(&mut self, rule: MargolusRule, phase: usize) {
+        let (width, height) = (self.life.width() as isize, self.life.height() as isize);
+        let wraps = self.life.wraps();
+        let first = match (phase, wraps) {
+            (0, _) => 0,
+            (_, true) => 1,
+            (_, false) => -1,
+        };
+        // cell of the board at coordinates that can be past its edges, None outside of it
+        let cell = |x: isize, y: isize| match wraps {
+            true => Some((x.rem_euclid(width) as usize, y.rem_euclid(height) as usize)),
+            false => ((0..width).contains(&x) && (0..height).contains(&y))
+                .then_some((x as usize, y as usize)),
+        };
+        for y in (first..height).step_by(2) {
+            for x in (first..width).step_by(2) {
+                let cells =
+                    [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)].map(|(cx, cy)| cell(cx, cy));
+                let block = cells.iter().enumerate().fold(0, |block, (bit, cell)| {
+                    let is_alive = cell.is_some_and(|(cx, cy)| self.life.is_alive(cx, cy));
+                    block | (is_alive as u8) << bit
+                });
+                let next = rule.next_block(block);
+                for (bit, cell) in cells.into_iter().enumerate() {
+                    if let Some((cx, cy)) = cell {
+                        self.life.set_cell(cx, cy, next >> bit & 1 == 1);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<L: Life> Steppable for MargolusLife<L> {
+    fn step(&mut self) {
+        MargolusLife::step(self)
+    }
+
+    fn generation(&self) -> u64 {
+        MargolusLife::generation(self)
+    }
+}
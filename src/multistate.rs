@@ -0,0 +1,106 @@
+// automata whose cells have more states than alive and dead, like the Generations rules where
+// dying cells fade through states of their own, state 0 always being the empty one
+use crate::neighborhood::Neighborhood;
+use crate::Life;
+
+// minimum behavior of a multi-state automaton, the counterpart of Life
+pub trait MultiStateLife {
+    /// width of the map
+    fn width(&self) -> usize;
+
+    /// height of the map
+    fn height(&self) -> usize;
+
+    /// number of states cells can be in, numbered from 0
+    fn states(&self) -> u8;
+
+    /// state of cell, 0 outside of the map
+    fn state(&self, x: usize, y: usize) -> u8;
+
+    /// set state of cell, cells outside of the map are ignored
+    fn set_state(&mut self, x: usize, y: usize, state: u8);
+
+    /// state of cell in the next generation
+    fn next_state(&self, x: usize, y: usize) -> u8;
+
+    /// whether coordinates past the edges wrap around to the opposite edge
+    fn wraps(&self) -> bool {
+        false
+    }
+
+    /// cells counted as neighbors of a cell, the 8 around it unless the automaton has another
+    fn neighborhood(&self) -> Neighborhood {
+        Neighborhood::Moore
+    }
+
+    /// number of neighbors of a cell in a state, across the edges on maps that wrap
+    fn neighbors_in_state(&self, x: usize, y: usize, state: u8) -> usize {
+        self.neighborhood()
+            .cells(x, y, self.width(), self.height(), self.wraps())
+            .filter(|&(nx, ny)| self.state(nx, ny) == state)
+            .count()
+    }
+
+    /// calculate the next generation of the map
+    fn next_generation(&self, other: &mut impl MultiStateLife) {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                other.set_state(x, y, self.next_state(x, y));
+            }
+        }
+    }
+}
+
+// states of the cells of a board of runtime size, row by row, for automata to wrap
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StateGrid {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl StateGrid {
+    /// create an empty grid of the given size
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height],
+        }
+    }
+
+    /// grid with the live cells of a board in a state and the others empty
+    pub fn from_life(life: &impl Life, state: u8) -> Self {
+        let mut grid = Self::new(life.width(), life.height());
+        crate::stats::live_cells(life).for_each(|(x, y)| grid.set(x, y, state));
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// state of cell, 0 outside of the grid
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        match x < self.width && y < self.height {
+            true => self.cells[y * self.width + x],
+            false => 0,
+        }
+    }
+
+    /// set state of cell, cells outside of the grid are ignored
+    pub fn set(&mut self, x: usize, y: usize, state: u8) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = state;
+        }
+    }
+
+    /// number of cells in a state
+    pub fn count(&self, state: u8) -> usize {
+        self.cells.iter().filter(|&&cell| cell == state).count()
+    }
+}
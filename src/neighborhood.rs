@@ -0,0 +1,215 @@
+// shapes of the cells counted as neighbors, consulted by the generic stepping through
+// Life::neighborhood, so that automata over other neighborhoods than the 8 cells around
+// need only a wrapper like NeighborhoodLife rather than a counting loop of their own
+//
+// backends with a stepping loop of their own, like VecGrid::par_next_generation or BitGrid,
+// always count the Moore neighborhood
+use crate::rule::Rule;
+use crate::Life;
+use std::fmt;
+use std::str::FromStr;
+
+// cells around a cell counted as its neighbors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Neighborhood {
+    /// the 8 cells around, Conway's
+    #[default]
+    Moore,
+    /// the 4 cells left, right, above and below
+    VonNeumann,
+    /// every cell up to a distance in both directions, the (2r+1)^2 - 1 cells of Larger
+    /// than Life, radius 1 being Moore
+    ExtendedMoore(usize),
+    /// the 6 cells of hexagonal Life laid out on a square grid, see hex
+    Hexagonal,
+}
+
+impl Neighborhood {
+    /// greatest distance of a neighbor in either direction
+    pub fn radius(self) -> usize {
+        match self {
+            Neighborhood::ExtendedMoore(radius) => radius,
+            _ => 1,
+        }
+    }
+
+    /// whether the cell at an offset from a cell is one of its neighbors
+    pub fn contains(self, dx: isize, dy: isize) -> bool {
+        let radius = self.radius() as isize;
+        if (dx, dy) == (0, 0) || dx.abs() > radius || dy.abs() > radius {
+            return false;
+        }
+        match self {
+            Neighborhood::Moore | Neighborhood::ExtendedMoore(_) => true,
+            Neighborhood::VonNeumann => dx.abs() + dy.abs() == 1,
+            Neighborhood::Hexagonal => crate::hex::NEIGHBORS.contains(&(dx, dy)),
+        }
+    }
+
+    /// number of neighbors of a cell
+    pub fn size(self) -> usize {
+        let radius = self.radius() as isize;
+        (-radius..=radius)
+            .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| self.contains(dx, dy))
+            .count()
+    }
+
+    /// coordinates of the neighbors of a cell on a map of a size, across the edges on maps
+    /// that wrap, where on maps smaller than the neighborhood the same cell can come up more
+    /// than once, and left out past the edges on the others
+    pub fn cells(
+        self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        wraps: bool,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let radius = self.radius() as isize;
+        let (columns, rows) = (width as isize, height as isize);
+        let empty = width == 0 || height == 0;
+        (-radius..=radius)
+            .flat_map(move |dy| (-radius..=radius).map(move |dx| (dx, dy)))
+            .filter(move |&(dx, dy)| !empty && self.contains(dx, dy))
+            .filter_map(move |(dx, dy)| match wraps {
+                true => Some((
+                    (x as isize + dx).rem_euclid(columns) as usize,
+                    (y as isize + dy).rem_euclid(rows) as usize,
+                )),
+                false => x.checked_add_signed(dx).zip(y.checked_add_signed(dy)),
+            })
+    }
+
+    /// number of live neighbors of a cell
+    pub fn count<L: Life + ?Sized>(self, life: &L, x: usize, y: usize) -> usize {
+        if self == Neighborhood::Moore && !life.wraps() {
+            // the common case stepping bounded grids, in plain loops for speed
+            let mut count = 0;
+            for ry in y.saturating_sub(1)..=y.saturating_add(1) {
+                for rx in x.saturating_sub(1)..=x.saturating_add(1) {
+                    if (rx, ry) != (x, y) && life.is_alive(rx, ry) {
+                        count += 1;
+                    }
+                }
+            }
+            return count;
+        }
+        self.cells(x, y, life.width(), life.height(), life.wraps())
+            .filter(|&(nx, ny)| life.is_alive(nx, ny))
+            .count()
+    }
+}
+
+impl fmt::Display for Neighborhood {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Neighborhood::Moore => write!(f, "moore"),
+            Neighborhood::VonNeumann => write!(f, "vonneumann"),
+            Neighborhood::ExtendedMoore(radius) => write!(f, "moore:{}", radius),
+            Neighborhood::Hexagonal => write!(f, "hex"),
+        }
+    }
+}
+
+// reason a neighborhood name couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNeighborhoodError(String);
+
+impl fmt::Display for ParseNeighborhoodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid neighborhood '{}', expected moore, vonneumann, moore:RADIUS or hex",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseNeighborhoodError {}
+
+impl FromStr for Neighborhood {
+    type Err = ParseNeighborhoodError;
+
+    /// parse a name as written by Display, case insensitive, von-neumann and moore:1 included
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseNeighborhoodError(s.to_string());
+        match s.trim().to_lowercase().as_str() {
+            "moore" => Ok(Neighborhood::Moore),
+            "vonneumann" | "von-neumann" => Ok(Neighborhood::VonNeumann),
+            "hex" | "hexagonal" => Ok(Neighborhood::Hexagonal),
+            name => match name.strip_prefix("moore:").map(str::parse) {
+                Some(Ok(1)) => Ok(Neighborhood::Moore),
+                Some(Ok(radius)) if radius > 1 => Ok(Neighborhood::ExtendedMoore(radius)),
+                _ => Err(error()),
+            },
+        }
+    }
+}
+
+// board stepped with the neighbors of a neighborhood of its own and a rule, using the
+// stepping loop of the Life trait, rules counting up to 8 neighbors so that births and
+// survivals past 8 never happen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NeighborhoodLife<L: Life> {
+    pub life: L,
+    pub neighborhood: Neighborhood,
+    pub rule: Rule,
+}
+
+impl<L: Life> NeighborhoodLife<L> {
+    /// step a board under a rule counting the neighbors of a neighborhood
+    pub fn new(life: L, neighborhood: Neighborhood, rule: Rule) -> Self {
+        Self {
+            life,
+            neighborhood,
+            rule,
+        }
+    }
+}
+
+impl<L: Life> Life for NeighborhoodLife<L> {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        self.life.set_cell(x, y, is_alive)
+    }
+
+    fn width(&self) -> usize {
+        self.life.width()
+    }
+
+    fn height(&self) -> usize {
+        self.life.height()
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.life.is_alive(x, y)
+    }
+
+    fn population_in(&self, x: usize, y: usize, width: usize, height: usize) -> usize {
+        self.life.population_in(x, y, width, height)
+    }
+
+    fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    fn number_of_neighbors(&self, x: usize, y: usize) -> usize {
+        self.neighborhood.count(&self.life, x, y)
+    }
+
+    fn set_packed(&mut self, x: usize, y: usize, word: u64) {
+        self.life.set_packed(x, y, word)
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() - std::mem::size_of::<L>() + self.life.memory_usage()
+    }
+
+    fn wraps(&self) -> bool {
+        self.life.wraps()
+    }
+
+    fn rule(&self) -> Rule {
+        self.rule
+    }
+}
@@ -0,0 +1,280 @@
+// walls: cells that always stay dead, for arenas and mazes, drawn from vector shapes
+// written one per line like
+//
+//     rect 0 0 64 36
+//     line 10 5 10 30
+//     filled circle 32 18 4
+//     polygon 40 5 50 5 45 12
+//
+// coordinates may lie off the board, parts of shapes off it are dropped, and blank lines and
+// lines starting with # are ignored
+use crate::{stats, Life};
+use std::fmt;
+
+// outline of a shape, or the whole of it when filled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    /// straight line between two cells
+    Line(i64, i64, i64, i64),
+    /// width by height rectangle with its top left corner at (x, y)
+    Rect {
+        x: i64,
+        y: i64,
+        width: i64,
+        height: i64,
+        filled: bool,
+    },
+    /// circle around a center with a radius
+    Circle {
+        x: i64,
+        y: i64,
+        radius: i64,
+        filled: bool,
+    },
+    /// closed polygon through at least three corners
+    Polygon {
+        corners: Vec<(i64, i64)>,
+        filled: bool,
+    },
+}
+
+impl Shape {
+    /// parse a line of a shape file, see the top of the module
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid shape '{}'", line);
+        let (filled, rest) = match line.trim().strip_prefix("filled ") {
+            Some(rest) => (true, rest),
+            None => (false, line.trim()),
+        };
+        let mut words = rest.split_whitespace();
+        let kind = words.next().ok_or_else(invalid)?;
+        let numbers = words
+            .map(str::parse)
+            .collect::<Result<Vec<i64>, _>>()
+            .map_err(|_| invalid())?;
+        match (kind, numbers.as_slice(), filled) {
+            ("line", &[x0, y0, x1, y1], false) => Ok(Shape::Line(x0, y0, x1, y1)),
+            ("rect", &[x, y, width, height], _) => Ok(Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                filled,
+            }),
+            ("circle", &[x, y, radius], _) => Ok(Shape::Circle {
+                x,
+                y,
+                radius,
+                filled,
+            }),
+            ("polygon", _, _) if numbers.len() >= 6 && numbers.len() % 2 == 0 => {
+                Ok(Shape::Polygon {
+                    corners: numbers.chunks(2).map(|c| (c[0], c[1])).collect(),
+                    filled,
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// parse every line of a shape file
+    pub fn parse_all(contents: &str) -> Result<Vec<Self>, String> {
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim().starts_with('#'))
+            .map(|(i, line)| Self::parse(line).map_err(|err| format!("line {}: {}", i + 1, err)))
+            .collect()
+    }
+
+    /// cells covered by the shape
+    pub fn cells(&self) -> Vec<(i64, i64)> {
+        let mut cells = Vec::new();
+        match *self {
+            Shape::Line(x0, y0, x1, y1) => line(&mut cells, (x0, y0), (x1, y1)),
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                filled,
+            } => {
+                if width <= 0 || height <= 0 {
+                    return cells;
+                }
+                let (right, bottom) = (x + width - 1, y + height - 1);
+                for cy in y..=bottom {
+                    for cx in x..=right {
+                        if filled || cx == x || cx == right || cy == y || cy == bottom {
+                            cells.push((cx, cy));
+                        }
+                    }
+                }
+            }
+            Shape::Circle {
+                x,
+                y,
+                radius,
+                filled,
+            } => {
+                // cells whose centers lie within half a cell of the circle, or inside it
+                let outer = (radius * 2 + 1) * (radius * 2 + 1);
+                let inner = (radius * 2 - 1).max(0) * (radius * 2 - 1).max(0);
+                for dy in -radius..=radius {
+                    for dx in -radius..=radius {
+                        let distance = 4 * (dx * dx + dy * dy);
+                        if distance < outer && (filled || distance >= inner) {
+                            cells.push((x + dx, y + dy));
+                        }
+                    }
+                }
+            }
+            Shape::Polygon {
+                ref corners,
+                filled,
+            } => {
+                for (i, &corner) in corners.iter().enumerate() {
+                    line(&mut cells, corner, corners[(i + 1) % corners.len()]);
+                }
+                if filled {
+                    fill_polygon(&mut cells, corners);
+                }
+            }
+        }
+        cells
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let filled = |filled: bool| if filled { "filled " } else { "" };
+        match self {
+            Shape::Line(x0, y0, x1, y1) => write!(f, "line {} {} {} {}", x0, y0, x1, y1),
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                filled: fill,
+            } => write!(f, "{}rect {} {} {} {}", filled(*fill), x, y, width, height),
+            Shape::Circle {
+                x,
+                y,
+                radius,
+                filled: fill,
+            } => write!(f, "{}circle {} {} {}", filled(*fill), x, y, radius),
+            Shape::Polygon {
+                corners,
+                filled: fill,
+            } => {
+                write!(f, "{}polygon", filled(*fill))?;
+                corners
+                    .iter()
+                    .try_for_each(|(x, y)| write!(f, " {} {}", x, y))
+            }
+        }
+    }
+}
+
+// cells of a line with Bresenham's algorithm
+fn line(cells: &mut Vec<(i64, i64)>, (x0, y0): (i64, i64), (x1, y1): (i64, i64)) {
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+    let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+    loop {
+        cells.push((x, y));
+        if (x, y) == (x1, y1) {
+            return;
+        }
+        if 2 * error >= dy {
+            error += dy;
+            x += sx;
+        }
+        if 2 * error <= dx {
+            error += dx;
+            y += sy;
+        }
+    }
+}
+
+// cells whose centers lie inside a polygon, by the even-odd rule along each row
+fn fill_polygon(cells: &mut Vec<(i64, i64)>, corners: &[(i64, i64)]) {
+    let top = corners.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let bottom = corners.iter().map(|&(_, y)| y).max().unwrap_or(0);
+    for y in top..=bottom {
+        let row = y as f64 + 0.5;
+        let mut crossings: Vec<f64> = (0..corners.len())
+            .filter_map(|i| {
+                let (x0, y0) = corners[i];
+                let (x1, y1) = corners[(i + 1) % corners.len()];
+                let (y0, y1, x0, x1) = (y0 as f64, y1 as f64, x0 as f64, x1 as f64);
+                ((y0 <= row) != (y1 <= row)).then(|| x0 + (row - y0) / (y1 - y0) * (x1 - x0))
+            })
+            .collect();
+        crossings.sort_by(f64::total_cmp);
+        for pair in crossings.chunks_exact(2) {
+            let (start, end) = (
+                (pair[0] - 0.5).ceil() as i64,
+                (pair[1] - 0.5).floor() as i64,
+            );
+            cells.extend((start..=end).map(|x| (x, y)));
+        }
+    }
+}
+
+// cells of a board that always stay dead
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Obstacles {
+    width: usize,
+    height: usize,
+    walls: Vec<bool>,
+}
+
+impl Obstacles {
+    /// board of the given size without walls
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            walls: vec![false; width * height],
+        }
+    }
+
+    /// walls of a board drawn from the shapes of a shape file
+    pub fn parse(contents: &str, width: usize, height: usize) -> Result<Self, String> {
+        let mut obstacles = Self::new(width, height);
+        Shape::parse_all(contents)?
+            .iter()
+            .for_each(|shape| obstacles.draw(shape));
+        Ok(obstacles)
+    }
+
+    /// turn the cells covered by a shape into walls
+    pub fn draw(&mut self, shape: &Shape) {
+        for (x, y) in shape.cells() {
+            if (0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y) {
+                self.walls[y as usize * self.width + x as usize] = true;
+            }
+        }
+    }
+
+    /// whether a cell is a wall, cells off the board aren't
+    pub fn is_wall(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.walls[y * self.width + x]
+    }
+
+    /// number of walls
+    pub fn count(&self) -> usize {
+        self.walls.iter().filter(|&&wall| wall).count()
+    }
+
+    /// kill the live cells on walls
+    pub fn apply(&self, life: &mut impl Life) {
+        let walled: Vec<(usize, usize)> = stats::live_cells(life)
+            .filter(|&(x, y)| self.is_wall(x, y))
+            .collect();
+        walled
+            .into_iter()
+            .for_each(|(x, y)| life.set_cell(x, y, false));
+    }
+}
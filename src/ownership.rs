@@ -0,0 +1,92 @@
+// which player placed each live cell on a shared board, cells born later inherit the owner
+// most of their parents have
+use crate::Life;
+
+/// owner of cells nobody placed, like those of the starting pattern
+pub const NO_OWNER: u8 = 0;
+
+// owner of every cell of a board, row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ownership {
+    width: usize,
+    height: usize,
+    owners: Vec<u8>,
+}
+
+impl Ownership {
+    /// ownership of a width by height board where nobody owns anything
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            owners: vec![NO_OWNER; width * height],
+        }
+    }
+
+    /// owner of a cell, NO_OWNER outside of the board
+    pub fn owner(&self, x: usize, y: usize) -> u8 {
+        match x < self.width && y < self.height {
+            true => self.owners[y * self.width + x],
+            false => NO_OWNER,
+        }
+    }
+
+    /// give a cell to an owner, cells outside of the board are ignored
+    pub fn set(&mut self, x: usize, y: usize, owner: u8) {
+        if x < self.width && y < self.height {
+            self.owners[y * self.width + x] = owner;
+        }
+    }
+
+    /// owners of every cell, row by row
+    pub fn owners(&self) -> &[u8] {
+        &self.owners
+    }
+
+    /// follow the cells from one generation to the next, survivors keep their owner and
+    /// births take the most common owner among their live neighbors, the lowest on ties
+    pub fn advance(&mut self, previous: &impl Life, next: &impl Life) {
+        let owners = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .map(
+                |(x, y)| match (previous.is_alive(x, y), next.is_alive(x, y)) {
+                    (_, false) => NO_OWNER,
+                    (true, true) => self.owner(x, y),
+                    (false, true) => self.inherited(previous, x, y),
+                },
+            )
+            .collect();
+        self.owners = owners;
+    }
+
+    /// live cells of each owner, for owners having any, in order of owner
+    pub fn populations(&self, life: &impl Life) -> Vec<(u8, usize)> {
+        let mut counts = [0; 256];
+        for (i, &owner) in self.owners.iter().enumerate() {
+            if owner != NO_OWNER && life.is_alive(i % self.width, i / self.width) {
+                counts[owner as usize] += 1;
+            }
+        }
+        (1..=u8::MAX)
+            .filter(|&owner| counts[owner as usize] > 0)
+            .map(|owner| (owner, counts[owner as usize]))
+            .collect()
+    }
+
+    // owner passed on to a cell born at (x, y) from the live neighbors it had
+    fn inherited(&self, previous: &impl Life, x: usize, y: usize) -> u8 {
+        let mut counts = [0u8; 256];
+        for ny in y.saturating_sub(1)..=y + 1 {
+            for nx in x.saturating_sub(1)..=x + 1 {
+                if (nx, ny) != (x, y) && previous.is_alive(nx, ny) {
+                    counts[self.owner(nx, ny) as usize] += 1;
+                }
+            }
+        }
+        // unowned parents only count when no parent is owned
+        (1..=u8::MAX)
+            .max_by_key(|&owner| (counts[owner as usize], std::cmp::Reverse(owner)))
+            .filter(|&owner| counts[owner as usize] > 0)
+            .unwrap_or(NO_OWNER)
+    }
+}
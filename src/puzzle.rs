@@ -0,0 +1,366 @@
+// puzzles: a starting board, a region of it the player may edit, a budget of cells to change
+// and a goal to reach within some generations, stored as RLE files with the rest of the
+// puzzle in comment lines like
+//
+//     #N first-light
+//     #C puzzle description send something across the board to light the far cell
+//     #C puzzle region 0 0 6 6
+//     #C puzzle budget 5
+//     #C puzzle goal light 16 16 by 60
+use crate::formats::{rle, Error};
+use crate::{stats, Life, Simulation, VecGrid};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+
+/// boards kept for each number of changed cells by Puzzle::solve unless told otherwise
+pub const DEFAULT_BEAM: usize = 64;
+
+// start of the comment lines holding the puzzle
+const PREFIX: &str = "#C puzzle ";
+
+// puzzles shipped with rlife, as puzzle files
+const BUILTIN: &[&str] = &[
+    "#N first-light
+#C puzzle description send something across the board to light the far cell
+#C puzzle region 0 0 6 6
+#C puzzle budget 5
+#C puzzle goal light 16 16 by 60
+x = 20, y = 20, rule = B3/S23
+!
+",
+    "#N clean-up
+#C puzzle description clear the board of the block with a single cell
+#C puzzle region 2 4 3 4
+#C puzzle budget 1
+#C puzzle goal extinct by 10
+x = 12, y = 12, rule = B3/S23
+5$6b2o$6b2o!
+",
+    "#N crowd
+#C puzzle description grow a crowd of at least 100 cells from five
+#C puzzle region 14 14 3 3
+#C puzzle budget 5
+#C puzzle goal population 100 at 100
+x = 32, y = 32, rule = B3/S23
+!
+",
+];
+
+// condition a solution has to bring about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// the cell is alive at some generation up to the limit
+    Light { x: usize, y: usize, by: u64 },
+    /// no cell is alive at some generation up to the limit
+    Extinct { by: u64 },
+    /// at least min cells are alive at the generation
+    Population { min: usize, at: u64 },
+}
+
+impl Goal {
+    /// last generation the goal can be met at
+    pub fn limit(self) -> u64 {
+        match self {
+            Goal::Light { by, .. } | Goal::Extinct { by } => by,
+            Goal::Population { at, .. } => at,
+        }
+    }
+
+    /// whether a board at a generation meets the goal
+    pub fn is_met(self, life: &impl Life, generation: u64) -> bool {
+        match self {
+            Goal::Light { x, y, by } => generation <= by && life.is_alive(x, y),
+            Goal::Extinct { by } => generation <= by && stats::population(life) == 0,
+            Goal::Population { min, at } => generation == at && stats::population(life) >= min,
+        }
+    }
+
+    // parse the words after "goal"
+    fn parse(words: &[&str]) -> Option<Self> {
+        Some(match words {
+            ["light", x, y, "by", by] => Goal::Light {
+                x: x.parse().ok()?,
+                y: y.parse().ok()?,
+                by: by.parse().ok()?,
+            },
+            ["extinct", "by", by] => Goal::Extinct {
+                by: by.parse().ok()?,
+            },
+            ["population", min, "at", at] => Goal::Population {
+                min: min.parse().ok()?,
+                at: at.parse().ok()?,
+            },
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Goal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Goal::Light { x, y, by } => write!(f, "light {} {} by {}", x, y, by),
+            Goal::Extinct { by } => write!(f, "extinct by {}", by),
+            Goal::Population { min, at } => write!(f, "population {} at {}", min, at),
+        }
+    }
+}
+
+// rectangle of cells the player may change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Region {
+    /// whether a cell is inside of the region
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+}
+
+// reason a board isn't a valid attempt at a puzzle, whether it reaches the goal or not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidSolution {
+    /// the board isn't the size of the puzzle
+    WrongSize { width: usize, height: usize },
+    /// a cell outside of the editable region was changed
+    OutsideRegion { x: usize, y: usize },
+    /// more cells were changed than the budget allows
+    OverBudget { changed: usize, budget: usize },
+}
+
+impl fmt::Display for InvalidSolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidSolution::WrongSize { width, height } => {
+                write!(f, "the puzzle is played on a {}x{} board", width, height)
+            }
+            InvalidSolution::OutsideRegion { x, y } => {
+                write!(f, "cell {},{} is outside of the editable region", x, y)
+            }
+            InvalidSolution::OverBudget { changed, budget } => write!(
+                f,
+                "{} cells were changed but the budget is {}",
+                changed, budget
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidSolution {}
+
+// puzzle read from a puzzle file
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub name: String,
+    pub description: String,
+    pub grid: VecGrid,
+    pub region: Region,
+    pub budget: usize,
+    pub goal: Goal,
+}
+
+impl Puzzle {
+    /// parse the contents of a puzzle file, every puzzle needs a region, budget and goal
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let (mut name, mut description) = (String::new(), String::new());
+        let (mut region, mut budget, mut goal) = (None, None, None);
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("#N") {
+                name = rest.trim().to_string();
+            }
+            let words: Vec<&str> = match line.strip_prefix(PREFIX) {
+                Some(rest) => rest.split_whitespace().collect(),
+                None => continue,
+            };
+            let error = || Error::Parse {
+                line: i + 1,
+                message: format!("invalid puzzle line '{}'", line),
+            };
+            let number = |word: &str| word.parse::<usize>().map_err(|_| error());
+            match words.as_slice() {
+                ["description", text @ ..] => description = text.join(" "),
+                ["region", x, y, width, height] => {
+                    region = Some(Region {
+                        x: number(x)?,
+                        y: number(y)?,
+                        width: number(width)?,
+                        height: number(height)?,
+                    })
+                }
+                ["budget", n] => budget = Some(number(n)?),
+                ["goal", rest @ ..] => goal = Some(Goal::parse(rest).ok_or_else(error)?),
+                _ => return Err(error()),
+            }
+        }
+        let missing = |what: &str| Error::Parse {
+            line: 1,
+            message: format!("puzzle has no {}", what),
+        };
+        Ok(Self {
+            name,
+            description,
+            grid: rle::parse(input)?.grid,
+            region: region.ok_or_else(|| missing("region"))?,
+            budget: budget.ok_or_else(|| missing("budget"))?,
+            goal: goal.ok_or_else(|| missing("goal"))?,
+        })
+    }
+
+    /// check a board made from the starting one, the generation the goal is met at, None if
+    /// it isn't met within the limit of the goal
+    pub fn check(&self, board: &impl Life) -> Result<Option<u64>, InvalidSolution> {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        if (board.width(), board.height()) != (width, height) {
+            return Err(InvalidSolution::WrongSize { width, height });
+        }
+        let mut changed = 0;
+        for (x, y) in (0..height).flat_map(|y| (0..width).map(move |x| (x, y))) {
+            if board.is_alive(x, y) != self.grid.is_alive(x, y) {
+                if !self.region.contains(x, y) {
+                    return Err(InvalidSolution::OutsideRegion { x, y });
+                }
+                changed += 1;
+            }
+        }
+        if changed > self.budget {
+            return Err(InvalidSolution::OverBudget {
+                changed,
+                budget: self.budget,
+            });
+        }
+
+        let mut grid = VecGrid::new(width, height);
+        for (x, y) in stats::live_cells(board) {
+            grid.set_cell(x, y, true);
+        }
+        let mut simulation = Simulation::from_life(grid);
+        loop {
+            let generation = simulation.generation();
+            if self.goal.is_met(simulation.life(), generation) {
+                return Ok(Some(generation));
+            }
+            if generation >= self.goal.limit() {
+                return Ok(None);
+            }
+            simulation.step();
+        }
+    }
+
+    /// cells of the region to change, within the budget, for the starting board to reach the
+    /// goal, most helpful first, None if none were found
+    ///
+    /// boards with one more cell changed are tried from the beam boards coming closest to the
+    /// goal for each number of cells, so a wider beam searches longer and finds more
+    pub fn solve(&self, beam: usize) -> Option<Vec<(usize, usize)>> {
+        let region = self.region;
+        let cells: Vec<_> = (region.y..region.y + region.height)
+            .flat_map(|y| (region.x..region.x + region.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| x < self.grid.width() && y < self.grid.height())
+            .collect();
+        if self.attempt(&[]).is_none() {
+            return Some(Vec::new());
+        }
+        let mut frontier = vec![Vec::new()];
+        let mut seen = HashSet::new();
+        for _ in 0..self.budget.min(cells.len()) {
+            let mut next = Vec::new();
+            for changes in &frontier {
+                for &cell in &cells {
+                    if changes.contains(&cell) {
+                        continue;
+                    }
+                    let mut changes = changes.clone();
+                    changes.push(cell);
+                    let mut key = changes.clone();
+                    key.sort_unstable();
+                    if !seen.insert(key) {
+                        continue;
+                    }
+                    match self.attempt(&changes) {
+                        None => return Some(changes),
+                        Some(distance) => next.push((distance, changes)),
+                    }
+                }
+            }
+            next.sort_by_key(|(distance, _)| *distance);
+            frontier = next
+                .into_iter()
+                .take(beam)
+                .map(|(_, changes)| changes)
+                .collect();
+        }
+        None
+    }
+
+    // run the starting board with some cells changed up to the limit of the goal, None if it
+    // meets the goal, otherwise how far from it the board came, in cells away from the cell
+    // to light, cells left alive or cells short of the population, and the live cells over
+    // every generation, ties going to the boards with most life in them
+    fn attempt(&self, changes: &[(usize, usize)]) -> Option<(usize, Reverse<usize>)> {
+        let mut grid = self.grid.clone();
+        for &(x, y) in changes {
+            grid.set_cell(x, y, !grid.is_alive(x, y));
+        }
+        let mut simulation = Simulation::from_life(grid);
+        let (mut closest, mut lived) = (usize::MAX, 0);
+        loop {
+            let (life, generation) = (simulation.life(), simulation.generation());
+            if self.goal.is_met(life, generation) {
+                return None;
+            }
+            let distance = match self.goal {
+                Goal::Light { x, y, .. } => stats::live_cells(life)
+                    .map(|(cx, cy)| cx.abs_diff(x).max(cy.abs_diff(y)))
+                    .min()
+                    .unwrap_or(usize::MAX),
+                Goal::Extinct { .. } => stats::population(life),
+                Goal::Population { min, at } if generation == at => {
+                    min.saturating_sub(stats::population(life))
+                }
+                Goal::Population { .. } => usize::MAX,
+            };
+            closest = closest.min(distance);
+            lived += stats::population(life);
+            if generation >= self.goal.limit() {
+                return Some((closest, Reverse(lived)));
+            }
+            simulation.step();
+        }
+    }
+}
+
+impl fmt::Display for Puzzle {
+    /// write the puzzle file parse reads
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let region = self.region;
+        writeln!(f, "#N {}", self.name)?;
+        writeln!(f, "{}description {}", PREFIX, self.description)?;
+        writeln!(
+            f,
+            "{}region {} {} {} {}",
+            PREFIX, region.x, region.y, region.width, region.height
+        )?;
+        writeln!(f, "{}budget {}", PREFIX, self.budget)?;
+        writeln!(f, "{}goal {}", PREFIX, self.goal)?;
+        write!(f, "{}", rle::write(&self.grid, crate::DEFAULT_RULE))
+    }
+}
+
+/// the puzzles shipped with rlife, in order of difficulty
+pub fn builtin() -> Vec<Puzzle> {
+    BUILTIN
+        .iter()
+        .map(|source| Puzzle::parse(source).expect("Invalid built-in puzzle"))
+        .collect()
+}
+
+/// built-in puzzle of a name
+pub fn find_builtin(name: &str) -> Option<Puzzle> {
+    builtin().into_iter().find(|puzzle| puzzle.name == name)
+}
@@ -0,0 +1,44 @@
+// seedable pseudo random numbers for generating soups
+use crate::Life;
+
+// xorshift64* generator, small and fast but not suitable for cryptography
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// create a generator, the same seed always produces the same sequence
+    pub fn new(seed: u64) -> Self {
+        // xorshift must never be seeded with zero, so scramble the seed first
+        Self {
+            state: seed ^ 0x9e37_79b9_7f4a_7c15 | 1,
+        }
+    }
+
+    /// create a generator seeded from the system clock
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Self::new(nanos)
+    }
+
+    /// next number of the sequence
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// next number of the sequence as a float in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// set every cell alive with probability density, like Life::fill_random
+pub fn fill_random(life: &mut impl Life, density: f64, rng: &mut Rng) {
+    life.fill_random(density, rng);
+}
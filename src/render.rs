@@ -0,0 +1,348 @@
+// drawing boards into 32-bit 0RGB frame buffers, shared by every frontend drawing pixels,
+// like the window, a wasm canvas or an embedded display
+use crate::multistate::MultiStateLife;
+use crate::{stats, Life};
+use std::fmt;
+use std::str::FromStr;
+
+// colors of live and dead cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub alive: u32,
+    pub dead: u32,
+}
+
+impl Default for Palette {
+    /// white cells on black
+    fn default() -> Self {
+        Self {
+            alive: 0xffffff,
+            dead: 0,
+        }
+    }
+}
+
+// colors of the states of multi-state automata, indexed by state, states past the last color
+// drawn in the last one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatePalette {
+    pub colors: Vec<u32>,
+}
+
+impl StatePalette {
+    /// palette of a color for every state from 0
+    pub fn new(colors: Vec<u32>) -> Self {
+        Self { colors }
+    }
+
+    /// color a state is drawn in, black without colors
+    pub fn color(&self, state: u8) -> u32 {
+        let last = self.colors.len().saturating_sub(1);
+        self.colors
+            .get((state as usize).min(last))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl From<Palette> for StatePalette {
+    /// dead cells as state 0 and live ones as every other state
+    fn from(palette: Palette) -> Self {
+        Self::new(vec![palette.dead, palette.alive])
+    }
+}
+
+// rectangle of the board drawn, one cell per pixel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// column of the cell at the top left pixel
+    pub x: usize,
+    /// row of the cell at the top left pixel
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    /// viewport showing the whole board
+    pub fn whole(life: &impl Life) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: life.width(),
+            height: life.height(),
+        }
+    }
+
+    /// viewport showing the whole map of a multi-state automaton
+    pub fn whole_states(life: &impl MultiStateLife) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: life.width(),
+            height: life.height(),
+        }
+    }
+
+    /// viewport of a part of a board zoomed in by a factor, as close to centered on a cell as
+    /// it gets while staying on the board
+    pub fn zoomed(width: usize, height: usize, zoom: usize, center: (usize, usize)) -> Self {
+        let zoom = zoom.max(1);
+        let (view_width, view_height) = ((width / zoom).max(1), (height / zoom).max(1));
+        Self {
+            x: center
+                .0
+                .saturating_sub(view_width / 2)
+                .min(width - view_width),
+            y: center
+                .1
+                .saturating_sub(view_height / 2)
+                .min(height - view_height),
+            width: view_width,
+            height: view_height,
+        }
+    }
+
+    /// factor a frame of a width scales the viewport up by
+    pub fn zoom(&self, width: usize) -> usize {
+        (width / self.width.max(1)).max(1)
+    }
+
+    /// cell at the middle of the viewport
+    pub fn center(&self) -> (usize, usize) {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// viewport moved by cells, stopping at the edges of a board of a size
+    pub fn moved(self, dx: isize, dy: isize, width: usize, height: usize) -> Self {
+        let clamp = |at: usize, by: isize, max: usize| at.saturating_add_signed(by).min(max);
+        Self {
+            x: clamp(self.x, dx, width.saturating_sub(self.width)),
+            y: clamp(self.y, dy, height.saturating_sub(self.height)),
+            ..self
+        }
+    }
+
+    /// cell under a pixel of a frame of a size the viewport is scaled up to fill
+    pub fn cell(&self, pixel: (usize, usize), width: usize, height: usize) -> (usize, usize) {
+        (
+            self.x + pixel.0 * self.width / width.max(1),
+            self.y + pixel.1 * self.height / height.max(1),
+        )
+    }
+}
+
+// what a frontend does when the simulation calculates generations faster than it draws them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameDrop {
+    /// draw the latest generation, skipping as many as were calculated since the last frame
+    #[default]
+    Latest,
+    /// skip at most this many generations in a row, holding the simulation until the next
+    /// frame is drawn, 0 drawing every generation
+    Bounded(u64),
+}
+
+impl FrameDrop {
+    /// whether the simulation may calculate the generation after the current one, given the
+    /// generation last drawn
+    pub fn may_step(self, generation: u64, drawn: u64) -> bool {
+        match self {
+            FrameDrop::Latest => true,
+            FrameDrop::Bounded(skipped) => generation <= drawn.saturating_add(skipped),
+        }
+    }
+}
+
+impl fmt::Display for FrameDrop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameDrop::Latest => write!(f, "latest"),
+            FrameDrop::Bounded(0) => write!(f, "never"),
+            FrameDrop::Bounded(skipped) => write!(f, "{}", skipped),
+        }
+    }
+}
+
+// reason a frame drop policy couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFrameDropError(String);
+
+impl fmt::Display for ParseFrameDropError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid frame drop policy '{}', expected latest, never or a number of generations",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseFrameDropError {}
+
+impl FromStr for FrameDrop {
+    type Err = ParseFrameDropError;
+
+    /// parse a policy as written by Display
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "latest" => Ok(FrameDrop::Latest),
+            "never" => Ok(FrameDrop::Bounded(0)),
+            skipped => skipped
+                .parse()
+                .map(FrameDrop::Bounded)
+                .map_err(|_| ParseFrameDropError(s.to_string())),
+        }
+    }
+}
+
+/// draw the cells of a viewport into a frame buffer of its width row by row, cells beyond the
+/// edges of the board are drawn as the board reports them, dead for bounded ones
+///
+/// cells are read 64 at a time into a word and then turned into pixels without branching,
+/// a loop compilers turn into vector instructions
+pub fn render_to_buffer(
+    life: &impl Life,
+    buffer: &mut [u32],
+    palette: &Palette,
+    viewport: Viewport,
+) {
+    assert_eq!(
+        buffer.len(),
+        viewport.width * viewport.height,
+        "buffer must match the viewport"
+    );
+    if viewport.width == 0 {
+        return;
+    }
+    let difference = palette.alive ^ palette.dead;
+    for (row, pixels) in buffer.chunks_exact_mut(viewport.width).enumerate() {
+        let y = viewport.y + row;
+        for (chunk, pixels) in pixels.chunks_mut(64).enumerate() {
+            let x = viewport.x + chunk * 64;
+            let word = packed(life, x, y, pixels.len());
+            for (i, pixel) in pixels.iter_mut().enumerate() {
+                let mask = 0u32.wrapping_sub((word >> i) as u32 & 1);
+                *pixel = palette.dead ^ (difference & mask);
+            }
+        }
+    }
+}
+
+/// draw only the cells of a viewport that differ between the board last drawn into the
+/// buffer and the current one, for large boards where few cells change between frames
+pub fn render_changes(
+    previous: &impl Life,
+    life: &impl Life,
+    buffer: &mut [u32],
+    palette: &Palette,
+    viewport: Viewport,
+) {
+    assert_eq!(
+        buffer.len(),
+        viewport.width * viewport.height,
+        "buffer must match the viewport"
+    );
+    if viewport.width == 0 {
+        return;
+    }
+    for (row, pixels) in buffer.chunks_exact_mut(viewport.width).enumerate() {
+        let y = viewport.y + row;
+        for (chunk, pixels) in pixels.chunks_mut(64).enumerate() {
+            let x = viewport.x + chunk * 64;
+            let alive = packed(life, x, y, pixels.len());
+            let mut changed = alive ^ packed(previous, x, y, pixels.len());
+            while changed != 0 {
+                let i = changed.trailing_zeros() as usize;
+                pixels[i] = match (alive >> i) & 1 {
+                    1 => palette.alive,
+                    _ => palette.dead,
+                };
+                changed &= changed - 1;
+            }
+        }
+    }
+}
+
+/// scale the part of a frame of a whole board a viewport shows up to fill a frame of the same
+/// size, each cell drawn as a square of pixels
+pub fn zoom_frame(frame: &[u32], width: usize, viewport: Viewport, zoomed: &mut [u32]) {
+    assert_eq!(frame.len(), zoomed.len(), "frames must have the same size");
+    if width == 0 {
+        return;
+    }
+    let height = frame.len() / width;
+    for (row, pixels) in zoomed.chunks_exact_mut(width).enumerate() {
+        let (_, y) = viewport.cell((0, row), width, height);
+        let cells = &frame[y * width..][..width];
+        for (column, pixel) in pixels.iter_mut().enumerate() {
+            let (x, _) = viewport.cell((column, row), width, height);
+            *pixel = cells[x];
+        }
+    }
+}
+
+/// draw the states of the cells of a viewport of a multi-state automaton into a frame buffer
+/// of its width row by row, in the colors of a palette, cells beyond the edges in state 0
+pub fn render_states_to_buffer(
+    life: &impl MultiStateLife,
+    buffer: &mut [u32],
+    palette: &StatePalette,
+    viewport: Viewport,
+) {
+    assert_eq!(
+        buffer.len(),
+        viewport.width * viewport.height,
+        "buffer must match the viewport"
+    );
+    if viewport.width == 0 {
+        return;
+    }
+    for (row, pixels) in buffer.chunks_exact_mut(viewport.width).enumerate() {
+        let y = viewport.y + row;
+        for (column, pixel) in pixels.iter_mut().enumerate() {
+            *pixel = palette.color(life.state(viewport.x + column, y));
+        }
+    }
+}
+
+/// size in pixels of the frame render_hex_to_buffer draws of a board
+pub fn hex_frame_size(life: &impl Life) -> (usize, usize) {
+    match (life.width(), life.height()) {
+        (0, _) | (_, 0) => (0, 0),
+        (width, height) => (2 * width + height - 1, height),
+    }
+}
+
+/// draw a board of hexagonal cells, as stepped by hex::HexLife, into a frame buffer of
+/// hex_frame_size, each cell 2 pixels wide and each row shifted a pixel right of the one
+/// below it, so that every cell touches its six neighbors, the corners left are drawn dead
+pub fn render_hex_to_buffer(life: &impl Life, buffer: &mut [u32], palette: &Palette) {
+    let (width, height) = hex_frame_size(life);
+    assert_eq!(
+        buffer.len(),
+        width * height,
+        "buffer must match the frame size"
+    );
+    buffer.fill(palette.dead);
+    for (x, y) in stats::live_cells(life) {
+        let start = y * width + 2 * x + (height - 1 - y);
+        buffer[start..start + 2].fill(palette.alive);
+    }
+}
+
+// up to 64 cells from (x, y) rightwards packed into a word, the leftmost in the lowest bit
+fn packed(life: &impl Life, x: usize, y: usize, len: usize) -> u64 {
+    (0..len).fold(0, |word, i| word | (life.is_alive(x + i, y) as u64) << i)
+}
+
+/// FNV-1a hash of the pixels of a frame, the same on every platform and build, so that
+/// frames can be compared against hashes recorded from a known good renderer
+pub fn frame_hash(buffer: &[u32]) -> u64 {
+    buffer
+        .iter()
+        .flat_map(|pixel| pixel.to_le_bytes())
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
@@ -0,0 +1,148 @@
+// tabular results that can be written as CSV or JSON
+use std::fmt::Write;
+
+// single field of a table
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl From<usize> for Value {
+    fn from(v: usize) -> Self {
+        Value::Int(v as i64)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(v: i64) -> Self {
+        Value::Int(v)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        Value::Float(v)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::Text(v.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::Text(v)
+    }
+}
+
+// rows of values under named columns
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    /// create an empty table with the given column names
+    pub fn new(columns: &[&str]) -> Self {
+        Self {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// append a row, which must have one value per column
+    pub fn push(&mut self, row: Vec<Value>) {
+        assert_eq!(
+            row.len(),
+            self.columns.len(),
+            "row length must match columns"
+        );
+        self.rows.push(row);
+    }
+
+    /// rows added so far
+    pub fn rows(&self) -> &[Vec<Value>] {
+        &self.rows
+    }
+
+    /// render as CSV with a header line
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        let line = |fields: Vec<String>| fields.join(",") + "\n";
+        out.push_str(&line(self.columns.iter().map(|c| csv_field(c)).collect()));
+        for row in &self.rows {
+            out.push_str(&line(
+                row.iter()
+                    .map(|value| match value {
+                        Value::Int(v) => v.to_string(),
+                        Value::Float(v) => v.to_string(),
+                        Value::Text(v) => csv_field(v),
+                    })
+                    .collect(),
+            ));
+        }
+        out
+    }
+
+    /// render as a JSON array with one object per row
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+        for (i, row) in self.rows.iter().enumerate() {
+            out.push_str(if i == 0 { "\n  {" } else { ",\n  {" });
+            for (j, (column, value)) in self.columns.iter().zip(row).enumerate() {
+                if j > 0 {
+                    out.push_str(", ");
+                }
+                let _ = write!(out, "{}: {}", json_string(column), json_value(value));
+            }
+            out.push('}');
+        }
+        out.push_str("\n]\n");
+        out
+    }
+}
+
+/// quote a string as a JSON string literal
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// render a value as JSON, non-finite floats become null
+pub fn json_value(value: &Value) -> String {
+    match value {
+        Value::Int(v) => v.to_string(),
+        Value::Float(v) if v.is_finite() => v.to_string(),
+        Value::Float(_) => "null".to_string(),
+        Value::Text(v) => json_string(v),
+    }
+}
+
+// quote a CSV field when it contains separators, quotes or line breaks
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
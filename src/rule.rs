@@ -0,0 +1,194 @@
+// outer-totalistic rules in B/S notation, like B3/S23 for Conway's Game of Life, B36/S23
+// for HighLife or B3678/S34678 for Day & Night, serialized in that notation with the serde
+// feature
+use crate::neighborhood::Neighborhood;
+use crate::Life;
+use std::fmt;
+use std::str::FromStr;
+
+// neighbor counts causing birth and survival, as bit masks over 0..=8
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+impl Rule {
+    /// number of distinct outer-totalistic rules
+    pub const COUNT: u32 = 1 << 18;
+
+    /// rule from bit masks, bit n set meaning n neighbors cause birth or survival
+    pub fn new(birth: u16, survival: u16) -> Self {
+        Self {
+            birth: birth & 0x1ff,
+            survival: survival & 0x1ff,
+        }
+    }
+
+    /// rule at a position below COUNT of the enumeration of every rule, with the birth
+    /// mask in the low nine bits and the survival mask in the high nine
+    pub fn from_index(index: u32) -> Self {
+        Self::new(index as u16, (index >> 9) as u16)
+    }
+
+    /// position of the rule in the enumeration of from_index
+    pub fn index(self) -> u32 {
+        self.birth as u32 | (self.survival as u32) << 9
+    }
+
+    /// whether a dead cell with the given number of live neighbors comes alive
+    pub fn is_born(self, neighbors: usize) -> bool {
+        neighbors <= 8 && self.birth & 1 << neighbors != 0
+    }
+
+    /// whether a live cell with the given number of live neighbors stays alive
+    pub fn survives(self, neighbors: usize) -> bool {
+        neighbors <= 8 && self.survival & 1 << neighbors != 0
+    }
+
+    /// state of a cell in the next generation
+    pub fn next_state(self, is_alive: bool, neighbors: usize) -> bool {
+        match is_alive {
+            true => self.survives(neighbors),
+            false => self.is_born(neighbors),
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        // B3/S23
+        Self::new(1 << 3, 1 << 2 | 1 << 3)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |mask: u16| -> String {
+            (0..=8)
+                .filter(|n| mask & 1 << n != 0)
+                .map(|n| char::from(b'0' + n as u8))
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+// reason a rulestring couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRuleError(String);
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid rule '{}', expected B/S notation like B3/S23",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseRuleError {}
+
+impl FromStr for Rule {
+    type Err = ParseRuleError;
+
+    /// parse B/S notation, case insensitive with either part optional, e.g. B36/S23 or b2
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseRuleError(s.to_string());
+        let (mut birth, mut survival) = (None, None);
+        for part in s.trim().split('/') {
+            let mut chars = part.chars();
+            let target = match chars.next() {
+                Some('B' | 'b') if birth.is_none() => &mut birth,
+                Some('S' | 's') if survival.is_none() => &mut survival,
+                _ => return Err(error()),
+            };
+            let mut mask = 0u16;
+            for c in chars {
+                match c.to_digit(10) {
+                    Some(n) if n <= 8 => mask |= 1 << n,
+                    _ => return Err(error()),
+                }
+            }
+            *target = Some(mask);
+        }
+        Ok(Self::new(birth.unwrap_or(0), survival.unwrap_or(0)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Rule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Rule {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// board stepped under a rule of its own instead of Conway's, using the stepping loop of the
+// board it wraps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleLife<L: Life> {
+    pub life: L,
+    pub rule: Rule,
+}
+
+impl<L: Life> RuleLife<L> {
+    /// step a board under a rule
+    pub fn new(life: L, rule: Rule) -> Self {
+        Self { life, rule }
+    }
+}
+
+impl<L: Life> Life for RuleLife<L> {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        self.life.set_cell(x, y, is_alive)
+    }
+
+    fn width(&self) -> usize {
+        self.life.width()
+    }
+
+    fn height(&self) -> usize {
+        self.life.height()
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.life.is_alive(x, y)
+    }
+
+    fn population_in(&self, x: usize, y: usize, width: usize, height: usize) -> usize {
+        self.life.population_in(x, y, width, height)
+    }
+
+    fn neighborhood(&self) -> Neighborhood {
+        self.life.neighborhood()
+    }
+
+    fn number_of_neighbors(&self, x: usize, y: usize) -> usize {
+        self.life.number_of_neighbors(x, y)
+    }
+
+    fn set_packed(&mut self, x: usize, y: usize, word: u64) {
+        self.life.set_packed(x, y, word)
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() - std::mem::size_of::<L>() + self.life.memory_usage()
+    }
+
+    fn wraps(&self) -> bool {
+        self.life.wraps()
+    }
+
+    fn rule(&self) -> Rule {
+        self.rule
+    }
+}
@@ -0,0 +1,171 @@
+// scans of parameter space: ensembles of soups run at every point of a grid of one or two
+// parameters, like density against rule, for heatmaps of how soups fare across them
+use crate::rule::Rule;
+use std::fmt;
+use std::str::FromStr;
+
+// setting of the soups a scan can vary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Parameter {
+    Density,
+    Rule,
+    Size,
+}
+
+impl Parameter {
+    /// name of the parameter, as given to --vary and written as a column
+    pub fn name(self) -> &'static str {
+        match self {
+            Parameter::Density => "density",
+            Parameter::Rule => "rule",
+            Parameter::Size => "size",
+        }
+    }
+}
+
+// reason an axis or a value of it couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAxisError(String);
+
+impl fmt::Display for ParseAxisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseAxisError {}
+
+impl FromStr for Parameter {
+    type Err = ParseAxisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "density" => Ok(Parameter::Density),
+            "rule" => Ok(Parameter::Rule),
+            "size" => Ok(Parameter::Size),
+            _ => Err(ParseAxisError(format!(
+                "unknown parameter '{}', expected density, rule or size",
+                s
+            ))),
+        }
+    }
+}
+
+// settings of the soups of an ensemble
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub width: usize,
+    pub height: usize,
+    pub density: f64,
+    pub rule: Rule,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            width: 64,
+            height: 64,
+            density: 0.35,
+            rule: Rule::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// set a parameter from its value written as in an axis, a size being WxH or a single
+    /// number for a square board
+    pub fn set(&mut self, parameter: Parameter, value: &str) -> Result<(), ParseAxisError> {
+        let error = || ParseAxisError(format!("invalid {} '{}'", parameter.name(), value));
+        match parameter {
+            Parameter::Density => {
+                self.density = value
+                    .parse()
+                    .ok()
+                    .filter(|density| (0.0..=1.0).contains(density))
+                    .ok_or_else(error)?
+            }
+            Parameter::Rule => self.rule = value.parse().map_err(|_| error())?,
+            Parameter::Size => {
+                let (width, height) = value.split_once('x').unwrap_or((value, value));
+                (self.width, self.height) = width
+                    .parse()
+                    .ok()
+                    .zip(height.parse().ok())
+                    .filter(|&(width, height)| width > 0 && height > 0)
+                    .ok_or_else(error)?
+            }
+        }
+        Ok(())
+    }
+}
+
+// values a parameter takes in a scan, written like density=0.1:0.9:0.1 for a range from
+// start to end by step, or like rule=B3/S23,B36/S23 for a list, size ranges being of
+// square boards
+#[derive(Debug, Clone, PartialEq)]
+pub struct Axis {
+    pub parameter: Parameter,
+    pub values: Vec<String>,
+}
+
+impl FromStr for Axis {
+    type Err = ParseAxisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (parameter, values) = s.split_once('=').ok_or_else(|| {
+            ParseAxisError(format!("invalid axis '{}', expected PARAMETER=VALUES", s))
+        })?;
+        let parameter: Parameter = parameter.trim().parse()?;
+        let range: Vec<&str> = values.split(':').collect();
+        let values: Vec<String> = match range[..] {
+            [start, end, step] if parameter != Parameter::Rule => {
+                let error = || ParseAxisError(format!("invalid range '{}'", values));
+                let (start, end, step): (f64, f64, f64) = start
+                    .parse()
+                    .ok()
+                    .zip(end.parse().ok())
+                    .zip(step.parse().ok())
+                    .map(|((start, end), step)| (start, end, step))
+                    .filter(|&(start, end, step)| step > 0.0 && start <= end)
+                    .ok_or_else(error)?;
+                // counted rather than accumulated so that steps like 0.1 don't drift
+                let count = ((end - start) / step + 1e-9).floor() as usize + 1;
+                (0..count)
+                    .map(|i| ((start + i as f64 * step) * 1e9).round() / 1e9)
+                    .map(|value| value.to_string())
+                    .collect()
+            }
+            _ => values
+                .split(',')
+                .map(|value| value.trim().to_string())
+                .collect(),
+        };
+        // check every value up front rather than partway through the scan
+        for value in &values {
+            Settings::default().set(parameter, value)?;
+        }
+        Ok(Self { parameter, values })
+    }
+}
+
+/// every point of the grid of the axes with the settings there, the base settings with the
+/// parameters of the axes set, the first axis varying slowest, each with the values of the
+/// axes at the point
+pub fn points(axes: &[Axis], base: Settings) -> Vec<(Vec<String>, Settings)> {
+    let mut points = vec![(Vec::new(), base)];
+    for axis in axes {
+        points = points
+            .into_iter()
+            .flat_map(|(values, settings)| {
+                axis.values.iter().map(move |value| {
+                    let mut settings = settings;
+                    settings
+                        .set(axis.parameter, value)
+                        .expect("values are checked when parsed");
+                    ([values.clone(), vec![value.clone()]].concat(), settings)
+                })
+            })
+            .collect();
+    }
+    points
+}
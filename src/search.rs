@@ -0,0 +1,394 @@
+// search for oscillators and ships in the manner of lifesrc: the cells of every generation
+// in a box are unknowns, chosen one at a time depth first, while the rule between each
+// generation and the next deduces as many others as it can, backtracking on contradictions
+//
+// the generation after the last is the first moved by the displacement searched for, and
+// cells outside of the box are dead in every generation, so what is found stays in the box
+// throughout its period
+//
+// the tree of choices splits into subtrees below prefixes of the first choices, which can be
+// searched independently, on threads of their own or over several runs
+use crate::analysis;
+use crate::rule::Rule;
+use crate::{Life, SparseLife, VecGrid};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// patterns to search for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search {
+    pub width: usize,
+    pub height: usize,
+    /// generations until the pattern returns
+    pub period: usize,
+    /// displacement of the pattern after a period, (0, 0) for oscillators and still lifes
+    pub dx: isize,
+    pub dy: isize,
+    /// rule the pattern follows, rules where cells with no live neighbors come alive (B0)
+    /// aren't supported
+    pub rule: Rule,
+}
+
+impl Search {
+    /// search for oscillators of a period, still lifes for period 1, fitting in a box under
+    /// Conway's rule
+    pub fn new(width: usize, height: usize, period: usize) -> Self {
+        Self {
+            width,
+            height,
+            period,
+            dx: 0,
+            dy: 0,
+            rule: Rule::default(),
+        }
+    }
+
+    /// branches taken by the first depth choices of every subtree, whose searches together
+    /// cover the whole tree
+    pub fn prefixes(depth: usize) -> Vec<Vec<bool>> {
+        (0..1usize << depth)
+            .map(|bits| {
+                (0..depth)
+                    .map(|i| (bits >> (depth - 1 - i)) & 1 == 1)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// search the subtree below a prefix of choices, giving the first generation of every
+    /// pattern found with exactly the period and displacement searched for, the same pattern
+    /// is found once for every phase and position of it in the box
+    pub fn run(&self, prefix: &[bool], mut found: impl FnMut(VecGrid)) {
+        assert!(self.period > 0, "the period must be at least 1");
+        assert!(
+            !self.rule.is_born(0),
+            "B0 rules can't be searched, the plane around the box would come alive"
+        );
+        let mut state = State::new(self);
+        if state.propagate().is_err() {
+            return;
+        }
+        // cell chosen, its state and the length of the trail before it was chosen
+        let mut choices: Vec<(usize, bool, usize)> = Vec::new();
+        loop {
+            let consistent = match state.unknown() {
+                Some(var) => {
+                    let value = prefix.get(choices.len()).copied().unwrap_or(false);
+                    choices.push((var, value, state.trail.len()));
+                    state.assign(var, value);
+                    state.propagate().is_ok()
+                }
+                None => {
+                    // a prefix longer than the branch reaching a solution counts it only
+                    // when it goes on with dead cells, so that it's found once
+                    let rest = &prefix[choices.len().min(prefix.len())..];
+                    if rest.iter().all(|&branch| !branch) {
+                        if let Some(pattern) = state.solution() {
+                            found(pattern);
+                        }
+                    }
+                    false
+                }
+            };
+            if consistent {
+                continue;
+            }
+            // go back to the last choice outside the prefix with a branch left to try
+            loop {
+                let Some((var, value, trail)) = choices.pop() else {
+                    return;
+                };
+                if choices.len() < prefix.len() {
+                    return;
+                }
+                state.undo(trail);
+                if !value {
+                    choices.push((var, true, trail));
+                    state.assign(var, true);
+                    if state.propagate().is_ok() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// search the subtrees below the given prefixes on a number of threads, calling done with
+    /// each prefix and what was found below it as soon as its subtree has been searched
+    pub fn par_run(
+        &self,
+        prefixes: &[Vec<bool>],
+        threads: usize,
+        done: impl Fn(&[bool], Vec<VecGrid>) + Sync,
+    ) {
+        let next = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..threads.clamp(1, prefixes.len().max(1)) {
+                scope.spawn(|| {
+                    while let Some(prefix) = prefixes.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let mut patterns = Vec::new();
+                        self.run(prefix, |pattern| patterns.push(pattern));
+                        done(prefix, patterns);
+                    }
+                });
+            }
+        });
+    }
+}
+
+/// form shared by every phase, translation, rotation and reflection of a pattern returning
+/// after a period, the least canonical form of its phases
+pub fn representative(pattern: &impl Life, rule: Rule, period: usize) -> VecGrid {
+    let mut sparse = SparseLife::with_rule(rule);
+    crate::stats::live_cells(pattern).for_each(|(x, y)| sparse.set_cell(x as i64, y as i64, true));
+    let mut phases = Vec::with_capacity(period);
+    for _ in 0..period.max(1) {
+        if let Some((grid, _)) = sparse.to_grid() {
+            phases.push(analysis::canonical(&grid));
+        }
+        sparse.step();
+    }
+    phases
+        .into_iter()
+        .min()
+        .unwrap_or_else(|| VecGrid::new(0, 0))
+}
+
+// cell of some generation, dead outside of the box or one of the unknowns
+#[derive(Debug, Clone, Copy)]
+enum Cell {
+    Dead,
+    Var(usize),
+}
+
+// partial assignment of the unknowns, with the constraints left to check
+struct State<'a> {
+    search: &'a Search,
+    cells: Vec<Option<bool>>,
+    // unknowns in the order they were assigned
+    trail: Vec<usize>,
+    // constraints waiting to be checked, each once
+    queue: Vec<usize>,
+    queued: Vec<bool>,
+    // margins of the constraints around the box, wide enough for every constraint whose
+    // next cell lands in the box once moved by the displacement
+    margin: (isize, isize),
+}
+
+impl<'a> State<'a> {
+    fn new(search: &'a Search) -> Self {
+        let margin = (1 + search.dx.abs(), 1 + search.dy.abs());
+        let constraints = (search.width + 2 * margin.0 as usize)
+            * (search.height + 2 * margin.1 as usize)
+            * search.period;
+        Self {
+            search,
+            cells: vec![None; search.width * search.height * search.period],
+            trail: Vec::new(),
+            queue: (0..constraints).collect(),
+            queued: vec![true; constraints],
+            margin,
+        }
+    }
+
+    // cell at a position of a generation, the generation after the last being the first moved
+    fn cell(&self, x: isize, y: isize, t: usize) -> Cell {
+        let search = self.search;
+        let (x, y, t) = match t == search.period {
+            true => (x - search.dx, y - search.dy, 0),
+            false => (x, y, t),
+        };
+        let inside =
+            (0..search.width as isize).contains(&x) && (0..search.height as isize).contains(&y);
+        match inside {
+            true => Cell::Var((t * search.height + y as usize) * search.width + x as usize),
+            false => Cell::Dead,
+        }
+    }
+
+    fn value(&self, cell: Cell) -> Option<bool> {
+        match cell {
+            Cell::Dead => Some(false),
+            Cell::Var(var) => self.cells[var],
+        }
+    }
+
+    // position and generation of an unknown
+    fn position(&self, var: usize) -> (isize, isize, usize) {
+        let (width, height) = (self.search.width, self.search.height);
+        (
+            (var % width) as isize,
+            (var / width % height) as isize,
+            var / (width * height),
+        )
+    }
+
+    // constraint between the cell at a position of a generation and the same cell next
+    // generation, None outside of the margins
+    fn constraint(&self, x: isize, y: isize, t: usize) -> Option<usize> {
+        let (mx, my) = self.margin;
+        let (cw, ch) = (
+            self.search.width as isize + 2 * mx,
+            self.search.height as isize + 2 * my,
+        );
+        let (cx, cy) = (x + mx, y + my);
+        ((0..cw).contains(&cx) && (0..ch).contains(&cy))
+            .then(|| ((t as isize * ch + cy) * cw + cx) as usize)
+    }
+
+    fn assign(&mut self, var: usize, value: bool) {
+        self.cells[var] = Some(value);
+        self.trail.push(var);
+        let (x, y, t) = self.position(var);
+        // constraints reading the cell, and the one deciding it
+        let mut affected = Vec::with_capacity(10);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                affected.push(self.constraint(x + dx, y + dy, t));
+            }
+        }
+        affected.push(match t {
+            0 => self.constraint(
+                x + self.search.dx,
+                y + self.search.dy,
+                self.search.period - 1,
+            ),
+            t => self.constraint(x, y, t - 1),
+        });
+        for constraint in affected.into_iter().flatten() {
+            if !self.queued[constraint] {
+                self.queued[constraint] = true;
+                self.queue.push(constraint);
+            }
+        }
+    }
+
+    // forget the assignments made after the trail had the given length
+    fn undo(&mut self, length: usize) {
+        for var in self.trail.drain(length..) {
+            self.cells[var] = None;
+        }
+    }
+
+    // check queued constraints until none are left, assigning what they decide
+    fn propagate(&mut self) -> Result<(), ()> {
+        while let Some(constraint) = self.queue.pop() {
+            self.queued[constraint] = false;
+            if self.check(constraint).is_err() {
+                for constraint in self.queue.drain(..) {
+                    self.queued[constraint] = false;
+                }
+                return Err(());
+            }
+        }
+        Ok(())
+    }
+
+    // deduce what a constraint can about its cell, the cell's neighbors and its next state
+    fn check(&mut self, constraint: usize) -> Result<(), ()> {
+        let (mx, my) = self.margin;
+        let cw = self.search.width + 2 * mx as usize;
+        let ch = self.search.height + 2 * my as usize;
+        let x = (constraint % cw) as isize - mx;
+        let y = (constraint / cw % ch) as isize - my;
+        let t = constraint / (cw * ch);
+
+        let (center, next) = (self.cell(x, y, t), self.cell(x, y, t + 1));
+        let (mut alive, mut unknown, mut unknowns) = (0, [Cell::Dead; 8], 0);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if (dx, dy) == (0, 0) {
+                    continue;
+                }
+                let neighbor = self.cell(x + dx, y + dy, t);
+                match self.value(neighbor) {
+                    Some(true) => alive += 1,
+                    Some(false) => (),
+                    None => {
+                        unknown[unknowns] = neighbor;
+                        unknowns += 1;
+                    }
+                }
+            }
+        }
+        let states: &[bool] = match self.value(center) {
+            Some(true) => &[true],
+            Some(false) => &[false],
+            None => &[false, true],
+        };
+        let next_value = self.value(next);
+        // which next states, cell states and neighbor counts some filling in allows
+        let (mut nexts, mut centers) = ([false; 2], [false; 2]);
+        let (mut fewest, mut most) = (usize::MAX, 0);
+        for &is_alive in states {
+            for neighbors in alive..=alive + unknowns {
+                let state = self.search.rule.next_state(is_alive, neighbors);
+                if next_value.is_none_or(|next| next == state) {
+                    nexts[state as usize] = true;
+                    centers[is_alive as usize] = true;
+                    fewest = fewest.min(neighbors);
+                    most = most.max(neighbors);
+                }
+            }
+        }
+        if !(nexts[0] || nexts[1]) {
+            return Err(());
+        }
+        self.decide(next, nexts);
+        self.decide(center, centers);
+        if unknowns > 0 && (fewest == alive + unknowns || most == alive) {
+            let is_alive = most > alive;
+            for &neighbor in &unknown[..unknowns] {
+                self.decide(neighbor, [!is_alive, is_alive]);
+            }
+        }
+        Ok(())
+    }
+
+    // assign an unknown cell when only one of its states is possible
+    fn decide(&mut self, cell: Cell, possible: [bool; 2]) {
+        if let (Cell::Var(var), None) = (cell, self.value(cell)) {
+            if possible[0] != possible[1] {
+                self.assign(var, possible[1]);
+            }
+        }
+    }
+
+    // unknown to choose next, by row and column so that rows fill in from the top
+    fn unknown(&self) -> Option<usize> {
+        self.cells.iter().position(|cell| cell.is_none())
+    }
+
+    // first generation of a complete assignment when it has live cells and doesn't return
+    // any sooner than the period
+    fn solution(&self) -> Option<VecGrid> {
+        let search = self.search;
+        let generation = |t: usize| -> Vec<(isize, isize)> {
+            (0..search.height as isize)
+                .flat_map(|y| (0..search.width as isize).map(move |x| (x, y)))
+                .filter(|&(x, y)| self.value(self.cell(x, y, t)) == Some(true))
+                .collect()
+        };
+        let first = generation(0);
+        if first.is_empty() {
+            return None;
+        }
+        // returning after q moved by s repeats every q generations, which only moves the
+        // pattern by the displacement searched for after a period if s is its share of it
+        let (period, dx, dy) = (search.period as isize, search.dx, search.dy);
+        for q in (1..period).filter(|q| period % q == 0) {
+            if (dx * q) % period != 0 || (dy * q) % period != 0 {
+                continue;
+            }
+            let (sx, sy) = (dx * q / period, dy * q / period);
+            let moved: Vec<_> = first.iter().map(|&(x, y)| (x + sx, y + sy)).collect();
+            if generation(q as usize) == moved {
+                return None;
+            }
+        }
+        let mut grid = VecGrid::new(search.width, search.height);
+        for (x, y) in first {
+            grid.set_cell(x as usize, y as usize, true);
+        }
+        Some(grid)
+    }
+}
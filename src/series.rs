@@ -0,0 +1,105 @@
+// memory bounded time series of population and activity, downsampling old data as it grows
+
+// averages over a span of consecutive generations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// first generation of the span
+    pub generation: u64,
+    /// number of generations in the span
+    pub generations: u64,
+    /// mean, least and greatest population over the span
+    pub population: f64,
+    pub min_population: usize,
+    pub max_population: usize,
+    /// mean number of cells changing state per generation
+    pub activity: f64,
+}
+
+impl Sample {
+    // span covering two consecutive spans
+    fn merge(self, next: Sample) -> Sample {
+        let generations = self.generations + next.generations;
+        let mean = |a: f64, b: f64| {
+            (a * self.generations as f64 + b * next.generations as f64) / generations as f64
+        };
+        Sample {
+            generation: self.generation,
+            generations,
+            population: mean(self.population, next.population),
+            min_population: self.min_population.min(next.min_population),
+            max_population: self.max_population.max(next.max_population),
+            activity: mean(self.activity, next.activity),
+        }
+    }
+}
+
+// series holding at most a fixed number of samples, each spanning stride generations,
+// the stride doubles whenever the series fills up by merging neighboring samples
+#[derive(Debug, Clone)]
+pub struct TimeSeries {
+    capacity: usize,
+    stride: u64,
+    samples: Vec<Sample>,
+    pending: Option<Sample>,
+}
+
+impl TimeSeries {
+    /// create an empty series keeping at most capacity samples, at least two
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(2);
+        Self {
+            capacity,
+            stride: 1,
+            samples: Vec::with_capacity(capacity),
+            pending: None,
+        }
+    }
+
+    /// record a generation, generations must be pushed in order
+    pub fn push(&mut self, generation: u64, population: usize, activity: usize) {
+        let sample = Sample {
+            generation,
+            generations: 1,
+            population: population as f64,
+            min_population: population,
+            max_population: population,
+            activity: activity as f64,
+        };
+        let pending = match self.pending.take() {
+            Some(pending) => pending.merge(sample),
+            None => sample,
+        };
+        if pending.generations < self.stride {
+            self.pending = Some(pending);
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.downsample();
+        }
+        self.samples.push(pending);
+    }
+
+    /// number of generations each sample spans, except possibly the last
+    pub fn stride(&self) -> u64 {
+        self.stride
+    }
+
+    /// samples in order, including the generations not yet filling a whole sample
+    pub fn samples(&self) -> impl Iterator<Item = &Sample> + '_ {
+        self.samples.iter().chain(self.pending.as_ref())
+    }
+
+    // halve the number of samples by merging each pair
+    fn downsample(&mut self) {
+        self.samples = self
+            .samples
+            .chunks(2)
+            .map(|pair| match *pair {
+                [a, b] => a.merge(b),
+                [a] => a,
+                _ => unreachable!("chunks are never empty"),
+            })
+            .collect();
+        self.stride *= 2;
+    }
+}
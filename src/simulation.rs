@@ -0,0 +1,219 @@
+// simulation of successive generations of a Life implementation
+use crate::explain::{Explanation, Phase};
+use crate::rule::Rule;
+use crate::{stats, Emitter, Life, LifeGrid, Steppable, VecGrid};
+
+/// transform applied to every new generation after the rule, along with its generation number
+pub type Hook<L> = Box<dyn FnMut(&mut L, u64) + Send>;
+
+// current generation along with the scratch grid the next one is calculated into
+pub struct Simulation<L: Life> {
+    current: L,
+    scratch: L,
+    generation: u64,
+    emitters: Vec<Emitter>,
+    hooks: Vec<Hook<L>>,
+    explanation: Option<Explanation>,
+}
+
+impl<L: Life> Simulation<L> {
+    /// start a simulation from a grid, scratch must have the same dimensions
+    pub fn new(current: L, scratch: L) -> Self {
+        assert_eq!(
+            (current.width(), current.height()),
+            (scratch.width(), scratch.height()),
+            "scratch grid must match dimensions"
+        );
+        Self {
+            current,
+            scratch,
+            generation: 0,
+            emitters: Vec::new(),
+            hooks: Vec::new(),
+            explanation: None,
+        }
+    }
+
+    /// calculate the next generation, swapping it with the scratch grid instead of
+    /// allocating a new one
+    pub fn step(&mut self) {
+        self.current.next_generation(&mut self.scratch);
+        self.commit();
+    }
+
+    /// calculate the next generation with another engine or rule, given the current
+    /// generation and the grid to write the next one into
+    pub fn step_with(&mut self, next_generation: impl FnOnce(&L, &mut L)) {
+        next_generation(&self.current, &mut self.scratch);
+        self.commit();
+    }
+
+    /// calculate the given number of generations
+    pub fn step_by(&mut self, generations: u64) {
+        (0..generations).for_each(|_| self.step());
+    }
+
+    /// advance the generation by a single phase, counting neighbors, deciding births and
+    /// deaths and then committing them like step, for showing the rule at work in slow motion
+    pub fn step_phase(&mut self) -> &Explanation {
+        let explanation = match self.explanation.take() {
+            None => Explanation::new(&self.current),
+            Some(mut explanation) if explanation.phase() == Phase::Decided => {
+                self.step();
+                explanation.committed();
+                explanation
+            }
+            Some(mut explanation) => {
+                explanation.advance(&mut self.current);
+                explanation
+            }
+        };
+        self.explanation.insert(explanation)
+    }
+
+    /// add a transform run on every new generation, e.g. to kill random cells or apply a mask,
+    /// hooks run in the order they were added
+    pub fn add_hook(&mut self, hook: impl FnMut(&mut L, u64) + Send + 'static) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    /// add an emitter, stamped after the rule and before the hooks whenever it is due
+    pub fn add_emitter(&mut self, emitter: Emitter) {
+        self.emitters.push(emitter);
+    }
+
+    /// emitters added so far
+    pub fn emitters(&self) -> &[Emitter] {
+        &self.emitters
+    }
+
+    /// emitters added so far, for reconfiguring them while the simulation runs
+    pub fn emitters_mut(&mut self) -> &mut Vec<Emitter> {
+        &mut self.emitters
+    }
+
+    // make the generation calculated into scratch the current one, then run emitters and hooks
+    fn commit(&mut self) {
+        std::mem::swap(&mut self.current, &mut self.scratch);
+        self.explanation = None;
+        self.generation += 1;
+        let generation = self.generation;
+        self.emitters
+            .iter()
+            .filter(|emitter| emitter.is_due(generation))
+            .for_each(|emitter| emitter.stamp(&mut self.current));
+        self.hooks
+            .iter_mut()
+            .for_each(|hook| hook(&mut self.current, generation));
+    }
+
+    /// number of generations calculated so far
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// current generation
+    pub fn life(&self) -> &L {
+        &self.current
+    }
+
+    /// generation before the current one as the rule left it, before emitters and hooks ran,
+    /// None until the first step
+    pub fn previous(&self) -> Option<&L> {
+        (self.generation > 0).then_some(&self.scratch)
+    }
+
+    /// live cells of the current generation
+    pub fn population(&self) -> usize {
+        stats::population(&self.current)
+    }
+
+    /// bytes taken by the current generation and the scratch grid
+    pub fn memory_usage(&self) -> usize {
+        self.current.memory_usage() + self.scratch.memory_usage()
+    }
+
+    /// cells that came alive in the last generation, counting those set by emitters, hooks and
+    /// edits since, 0 until the first step
+    pub fn births(&self) -> usize {
+        self.changes().0
+    }
+
+    /// cells that died in the last generation, counting those cleared by emitters, hooks and
+    /// edits since, 0 until the first step
+    pub fn deaths(&self) -> usize {
+        self.changes().1
+    }
+
+    // births and deaths between the previous generation and the current one, a word of
+    // cells at a time
+    fn changes(&self) -> (usize, usize) {
+        let Some(previous) = self.previous() else {
+            return (0, 0);
+        };
+        let rows = previous
+            .iter_rows_packed()
+            .zip(self.current.iter_rows_packed());
+        rows.flat_map(|(before, after)| before.into_iter().zip(after))
+            .fold((0, 0), |(births, deaths), (before, after)| {
+                (
+                    births + (after & !before).count_ones() as usize,
+                    deaths + (before & !after).count_ones() as usize,
+                )
+            })
+    }
+
+    /// current generation, for editing
+    pub fn life_mut(&mut self) -> &mut L {
+        self.explanation = None;
+        &mut self.current
+    }
+
+    /// stop the simulation, returning the current generation
+    pub fn into_life(self) -> L {
+        self.current
+    }
+}
+
+impl<L: Life> Steppable for Simulation<L> {
+    fn step(&mut self) {
+        Simulation::step(self)
+    }
+
+    fn generation(&self) -> u64 {
+        Simulation::generation(self)
+    }
+}
+
+impl<L: Life + Clone> Simulation<L> {
+    /// start a simulation from a grid, using a copy of it as scratch
+    pub fn from_life(life: L) -> Self {
+        Self::new(life.clone(), life)
+    }
+}
+
+impl Simulation<VecGrid> {
+    /// calculate the next generation splitting the work over the given number of threads,
+    /// with the same result as step
+    pub fn par_step(&mut self, threads: usize) {
+        self.current.par_next_generation(&mut self.scratch, threads);
+        self.commit();
+    }
+
+    /// calculate the next generation under a rule splitting the work over the given number
+    /// of threads, for boards whose rule isn't Conway's
+    pub fn par_step_with(&mut self, rule: Rule, threads: usize) {
+        self.current
+            .par_next_generation_with(rule, &mut self.scratch, threads);
+        self.commit();
+    }
+}
+
+impl<const W: usize, const H: usize> Simulation<LifeGrid<W, H>> {
+    /// calculate the next generation splitting the work over the given number of threads,
+    /// with the same result as step
+    pub fn par_step(&mut self, threads: usize) {
+        self.current.par_next_generation(&mut self.scratch, threads);
+        self.commit();
+    }
+}
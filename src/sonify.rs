@@ -0,0 +1,185 @@
+// sonification of board activity: every generation becomes a chord of up to three notes, the
+// population setting the pitch of a steady tone and births and deaths those of a high and a
+// low one, written as standard MIDI files or synthesized into WAV audio
+use crate::{stats, Life};
+
+// notes of the major pentatonic scale as semitones above the root, so that any chord sounds
+// consonant
+const PENTATONIC: [u8; 5] = [0, 2, 4, 7, 9];
+
+// octaves each voice spans
+const OCTAVES: usize = 2;
+
+// MIDI notes the population, births and deaths voices start at
+const POPULATION_ROOT: u8 = 48;
+const BIRTHS_ROOT: u8 = 72;
+const DEATHS_ROOT: u8 = 36;
+
+// MIDI ticks per quarter note, at the default tempo of half a second per quarter note a tick
+// lasts a millisecond
+const TICKS_PER_QUARTER: u16 = 500;
+
+// time notes take to fade in and out when synthesized, so that chords don't click
+const FADE_SECONDS: f64 = 0.01;
+
+// note of a chord, as in MIDI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Note {
+    pub pitch: u8,
+    pub velocity: u8,
+}
+
+// chords of consecutive generations, each lasting the same time
+#[derive(Debug, Clone)]
+pub struct Score {
+    millis: u32,
+    chords: Vec<Vec<Note>>,
+}
+
+impl Score {
+    /// empty score where each generation lasts the given milliseconds
+    pub fn new(millis: u32) -> Self {
+        Self {
+            millis,
+            chords: Vec::new(),
+        }
+    }
+
+    /// add the chord of a generation, births and deaths are counted against the previous
+    /// generation, if any
+    pub fn record(&mut self, previous: Option<&impl Life>, next: &impl Life) {
+        let cells = (next.width() * next.height()).max(1);
+        let population = stats::population(next);
+        let (births, deaths) = previous.map_or((0, 0), |previous| {
+            let births = stats::live_cells(next)
+                .filter(|&(x, y)| !previous.is_alive(x, y))
+                .count();
+            let deaths = stats::live_cells(previous)
+                .filter(|&(x, y)| !next.is_alive(x, y))
+                .count();
+            (births, deaths)
+        });
+        let chord = [
+            (POPULATION_ROOT, population),
+            (BIRTHS_ROOT, births),
+            (DEATHS_ROOT, deaths),
+        ]
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .map(|(root, count)| {
+            let level = level(count, cells);
+            Note {
+                pitch: pitch(root, level),
+                velocity: 40 + (level * 87.0) as u8,
+            }
+        })
+        .collect();
+        self.chords.push(chord);
+    }
+
+    /// chords recorded so far, one per generation
+    pub fn chords(&self) -> &[Vec<Note>] {
+        &self.chords
+    }
+
+    /// the score as a standard MIDI file of one track
+    pub fn to_midi(&self) -> Vec<u8> {
+        let mut track = Vec::new();
+        let mut delta = 0;
+        for chord in &self.chords {
+            for (i, note) in chord.iter().enumerate() {
+                write_delta(&mut track, if i == 0 { delta } else { 0 });
+                track.extend([0x90, note.pitch, note.velocity]);
+            }
+            if chord.is_empty() {
+                delta += self.millis;
+                continue;
+            }
+            for (i, note) in chord.iter().enumerate() {
+                write_delta(&mut track, if i == 0 { self.millis } else { 0 });
+                track.extend([0x80, note.pitch, 0]);
+            }
+            delta = 0;
+        }
+        // end of track
+        write_delta(&mut track, delta);
+        track.extend([0xff, 0x2f, 0x00]);
+
+        let mut midi = b"MThd".to_vec();
+        midi.extend(6u32.to_be_bytes());
+        // format 0, one track
+        midi.extend(0u16.to_be_bytes());
+        midi.extend(1u16.to_be_bytes());
+        midi.extend(TICKS_PER_QUARTER.to_be_bytes());
+        midi.extend(b"MTrk");
+        midi.extend((track.len() as u32).to_be_bytes());
+        midi.extend(track);
+        midi
+    }
+
+    /// the score synthesized as sine tones into a mono 16 bit WAV file
+    pub fn to_wav(&self, sample_rate: u32) -> Vec<u8> {
+        let per_chord = (sample_rate as u64 * self.millis as u64 / 1000) as usize;
+        let fade = (FADE_SECONDS * sample_rate as f64).min(per_chord as f64 / 2.0);
+        let mut samples = Vec::with_capacity(self.chords.len() * per_chord * 2);
+        for chord in &self.chords {
+            for i in 0..per_chord {
+                let t = i as f64 / sample_rate as f64;
+                let envelope = (i as f64 / fade)
+                    .min((per_chord - i) as f64 / fade)
+                    .min(1.0);
+                let value: f64 = chord
+                    .iter()
+                    .map(|note| {
+                        let frequency = 440.0 * 2f64.powf((note.pitch as f64 - 69.0) / 12.0);
+                        let amplitude = note.velocity as f64 / 127.0 / 3.0;
+                        amplitude * (2.0 * std::f64::consts::PI * frequency * t).sin()
+                    })
+                    .sum();
+                let sample = (value * envelope * i16::MAX as f64) as i16;
+                samples.extend(sample.to_le_bytes());
+            }
+        }
+
+        let mut wav = b"RIFF".to_vec();
+        wav.extend((36 + samples.len() as u32).to_le_bytes());
+        wav.extend(b"WAVEfmt ");
+        wav.extend(16u32.to_le_bytes());
+        // PCM, mono
+        wav.extend(1u16.to_le_bytes());
+        wav.extend(1u16.to_le_bytes());
+        wav.extend(sample_rate.to_le_bytes());
+        wav.extend((sample_rate * 2).to_le_bytes());
+        wav.extend(2u16.to_le_bytes());
+        wav.extend(16u16.to_le_bytes());
+        wav.extend(b"data");
+        wav.extend((samples.len() as u32).to_le_bytes());
+        wav.extend(samples);
+        wav
+    }
+}
+
+// loudness of a count of cells out of a board, between 0 and 1, rising quickly at first
+// since only a small fraction of the board is ever alive or changing
+fn level(count: usize, cells: usize) -> f64 {
+    (count as f64 / cells as f64 * 4.0).sqrt().min(1.0)
+}
+
+// note of the pentatonic scale from a root at a level between 0 and 1
+fn pitch(root: u8, level: f64) -> u8 {
+    let steps = OCTAVES * PENTATONIC.len();
+    let step = (level * (steps - 1) as f64).round() as usize;
+    root + 12 * (step / PENTATONIC.len()) as u8 + PENTATONIC[step % PENTATONIC.len()]
+}
+
+// MIDI variable length quantity, seven bits per byte with the highest bit set on all but
+// the last
+fn write_delta(track: &mut Vec<u8>, mut ticks: u32) {
+    let mut bytes = vec![(ticks & 0x7f) as u8];
+    ticks >>= 7;
+    while ticks > 0 {
+        bytes.push((ticks & 0x7f) as u8 | 0x80);
+        ticks >>= 7;
+    }
+    track.extend(bytes.into_iter().rev());
+}
@@ -0,0 +1,192 @@
+// unbounded universe keeping only its live cells, so that gliders and guns can spread
+// without ever reaching an edge, stepping visits live cells and their neighbors only
+use crate::rule::Rule;
+use crate::stats;
+use crate::{Life, Steppable, VecGrid};
+use std::collections::{HashMap, HashSet};
+
+// the set of live cells is shrunk once it has room for this many times its cells, and more
+// than SHRINK_CAPACITY, as hash sets keep their room when cells are removed
+const SHRINK_FACTOR: usize = 4;
+const SHRINK_CAPACITY: usize = 1024;
+
+// live cells on the signed plane along with the rule they follow
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseLife {
+    cells: HashSet<(i64, i64)>,
+    rule: Rule,
+    generation: u64,
+}
+
+impl SparseLife {
+    /// empty universe following Conway's rule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// empty universe following another rule, rules where dead cells with no live neighbors
+    /// come alive (B0) would fill the plane and aren't supported
+    ///
+    /// panics on B0 rules, check rule.is_born(0) first for rules read from users
+    pub fn with_rule(rule: Rule) -> Self {
+        assert!(
+            !rule.is_born(0),
+            "B0 rules can't be simulated on an unbounded plane"
+        );
+        Self {
+            rule,
+            ..Self::default()
+        }
+    }
+
+    /// universe holding the live cells of a board with its top left corner at (x, y),
+    /// following the board's rule, which like with_rule mustn't be a B0 rule
+    pub fn from_life(life: &impl Life, x: i64, y: i64) -> Self {
+        let mut sparse = Self::with_rule(life.rule());
+        sparse.cells = stats::live_cells(life)
+            .map(|(cx, cy)| (x + cx as i64, y + cy as i64))
+            .collect();
+        sparse
+    }
+
+    /// rule the universe follows
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// set state of cell
+    pub fn set_cell(&mut self, x: i64, y: i64, is_alive: bool) {
+        match is_alive {
+            true => self.cells.insert((x, y)),
+            false => self.cells.remove(&(x, y)),
+        };
+        if !is_alive
+            && self.cells.capacity() > SHRINK_CAPACITY.max(self.cells.len() * SHRINK_FACTOR)
+        {
+            self.compact();
+        }
+    }
+
+    /// state of cell
+    pub fn is_alive(&self, x: i64, y: i64) -> bool {
+        self.cells.contains(&(x, y))
+    }
+
+    /// live cells in no particular order
+    pub fn cells(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /// number of live cells
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// number of generations calculated so far
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// bytes taken by the universe, estimated from the capacity of its set of live cells
+    /// with a control byte per slot as in the standard hash set
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.cells.capacity() * (std::mem::size_of::<(i64, i64)>() + 1)
+    }
+
+    /// give back the room the set of live cells keeps for cells since removed, done on its
+    /// own once it holds a fraction of its room
+    pub fn compact(&mut self) {
+        self.cells.shrink_to_fit();
+    }
+
+    /// move every live cell so that the middle of their bounds is at (0, 0), keeping the
+    /// coordinates of patterns that travel far small, returning the offset added to the
+    /// coordinates of every cell
+    pub fn recenter(&mut self) -> (i64, i64) {
+        let Some((x, y, width, height)) = self.bounds() else {
+            return (0, 0);
+        };
+        let offset = (
+            0i64.wrapping_sub(x.wrapping_add_unsigned(width / 2)),
+            0i64.wrapping_sub(y.wrapping_add_unsigned(height / 2)),
+        );
+        self.cells = self
+            .cells()
+            .map(|(cx, cy)| (cx.wrapping_add(offset.0), cy.wrapping_add(offset.1)))
+            .collect();
+        offset
+    }
+
+    /// top left corner, width and height of the smallest rectangle holding every live cell,
+    /// None when there are none
+    pub fn bounds(&self) -> Option<(i64, i64, u64, u64)> {
+        let mut cells = self.cells();
+        let (x, y) = cells.next()?;
+        let (left, top, right, bottom) = cells.fold((x, y, x, y), |(l, t, r, b), (x, y)| {
+            (l.min(x), t.min(y), r.max(x), b.max(y))
+        });
+        Some((
+            left,
+            top,
+            right.abs_diff(left) + 1,
+            bottom.abs_diff(top) + 1,
+        ))
+    }
+
+    /// live cells copied onto a grid just holding them, along with the position of its top
+    /// left corner, None without live cells
+    pub fn to_grid(&self) -> Option<(VecGrid, (i64, i64))> {
+        let (x, y, width, height) = self.bounds()?;
+        let mut grid = VecGrid::new(width as usize, height as usize);
+        self.cells()
+            .for_each(|(cx, cy)| grid.set_cell((cx - x) as usize, (cy - y) as usize, true));
+        Some((grid, (x, y)))
+    }
+
+    /// calculate the next generation, counting neighbors around live cells only
+    pub fn step(&mut self) {
+        let mut counts: HashMap<(i64, i64), u8> = HashMap::with_capacity(self.cells.len() * 8);
+        for &(x, y) in &self.cells {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if (dx, dy) != (0, 0) {
+                        *counts.entry((x + dx, y + dy)).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        // live cells without live neighbors aren't counted, they survive only under S0
+        let lonely = self
+            .cells
+            .iter()
+            .filter(|&cell| self.rule.survives(0) && !counts.contains_key(cell))
+            .copied();
+        let next = counts
+            .iter()
+            .filter(|&(cell, &count)| {
+                self.rule
+                    .next_state(self.cells.contains(cell), count as usize)
+            })
+            .map(|(&cell, _)| cell)
+            .chain(lonely)
+            .collect();
+        self.cells = next;
+        self.generation += 1;
+    }
+
+    /// calculate the given number of generations
+    pub fn step_by(&mut self, generations: u64) {
+        (0..generations).for_each(|_| self.step());
+    }
+}
+
+impl Steppable for SparseLife {
+    fn step(&mut self) {
+        SparseLife::step(self)
+    }
+
+    fn generation(&self) -> u64 {
+        SparseLife::generation(self)
+    }
+}
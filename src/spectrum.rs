@@ -0,0 +1,164 @@
+// spatial structure of a board: the power spectrum of the 2D Fourier transform of its
+// density field, the fraction of live cells in each block with the mean taken out, whose
+// peaks show the scales patterns arrange themselves at, like the domains of Day & Night
+//
+// the transform runs along rows and then columns, with a radix-2 FFT for lengths that are
+// powers of two and a plain DFT for the others
+use crate::{stats, Life};
+use std::f64::consts::PI;
+
+// power of every frequency of a density field
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    /// size of the density field, the board's size in blocks
+    pub width: usize,
+    pub height: usize,
+    /// side of the blocks, in cells
+    pub block: usize,
+    // squared magnitudes row by row, frequency 0 first
+    power: Vec<f64>,
+}
+
+impl Spectrum {
+    /// spectrum of the densities of the block by block squares of a board, of single cells for
+    /// blocks of 1
+    pub fn new(life: &impl Life, block: usize) -> Self {
+        let block = block.max(1);
+        let (width, height) = (life.width().div_ceil(block), life.height().div_ceil(block));
+        let densities = stats::block_densities(life, block);
+        let mean = densities.iter().sum::<f64>() / densities.len().max(1) as f64;
+        let mut field: Vec<(f64, f64)> = densities.iter().map(|d| (d - mean, 0.0)).collect();
+        for row in field.chunks_exact_mut(width.max(1)) {
+            transform(row);
+        }
+        let mut column = vec![(0.0, 0.0); height];
+        for x in 0..width {
+            for y in 0..height {
+                column[y] = field[y * width + x];
+            }
+            transform(&mut column);
+            for y in 0..height {
+                field[y * width + x] = column[y];
+            }
+        }
+        let power = field.iter().map(|(re, im)| re * re + im * im).collect();
+        Self {
+            width,
+            height,
+            block,
+            power,
+        }
+    }
+
+    /// power of the frequency of fx cycles across the width and fy down the height, taken
+    /// modulo the size so that negative frequencies count from the end
+    pub fn power(&self, fx: isize, fy: isize) -> f64 {
+        let x = fx.rem_euclid(self.width.max(1) as isize) as usize;
+        let y = fy.rem_euclid(self.height.max(1) as isize) as usize;
+        self.power.get(y * self.width + x).copied().unwrap_or(0.0)
+    }
+
+    /// mean power at each integer wavenumber k, the number of cycles across the shorter side
+    /// of the field, from 0 up to half of that side, a peak at k meaning structure repeating
+    /// every wavelength(k) cells
+    pub fn radial(&self) -> Vec<f64> {
+        let side = self.width.min(self.height);
+        let mut sums = vec![(0.0, 0usize); side / 2 + 1];
+        for (fx, fy) in self.frequencies() {
+            let k = side as f64
+                * ((fx as f64 / self.width as f64).powi(2)
+                    + (fy as f64 / self.height as f64).powi(2))
+                .sqrt();
+            if let Some(bin) = sums.get_mut(k.round() as usize) {
+                bin.0 += self.power(fx, fy);
+                bin.1 += 1;
+            }
+        }
+        sums.iter()
+            .map(|&(sum, count)| match count {
+                0 => 0.0,
+                count => sum / count as f64,
+            })
+            .collect()
+    }
+
+    /// cells a structure of wavenumber k repeats after, infinite for k = 0
+    pub fn wavelength(&self, k: usize) -> f64 {
+        (self.width.min(self.height) * self.block) as f64 / k as f64
+    }
+
+    /// gray level of every frequency row by row, with frequency 0 in the middle and the
+    /// logarithm of the power scaled to the strongest one, for drawing the spectrum
+    pub fn levels(&self) -> Vec<u8> {
+        let log = |power: f64| (1.0 + power).ln();
+        let strongest = self.power.iter().copied().map(log).fold(0.0, f64::max);
+        let (half_x, half_y) = ((self.width / 2) as isize, (self.height / 2) as isize);
+        (0..self.height as isize)
+            .flat_map(|y| (0..self.width as isize).map(move |x| (x - half_x, y - half_y)))
+            .map(|(fx, fy)| match strongest {
+                s if s > 0.0 => (255.0 * log(self.power(fx, fy)) / s) as u8,
+                _ => 0,
+            })
+            .collect()
+    }
+
+    // every frequency, negative ones past half of each side
+    fn frequencies(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        let signed = |f: usize, n: usize| match f > n / 2 {
+            true => f as isize - n as isize,
+            false => f as isize,
+        };
+        (0..self.height).flat_map(move |y| {
+            (0..self.width).map(move |x| (signed(x, self.width), signed(y, self.height)))
+        })
+    }
+}
+
+// discrete Fourier transform of complex values in place
+fn transform(values: &mut [(f64, f64)]) {
+    let n = values.len();
+    if n.is_power_of_two() {
+        fft(values);
+    } else if n > 1 {
+        let input = values.to_vec();
+        for (k, value) in values.iter_mut().enumerate() {
+            *value = input
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(re, im), (j, &x)| {
+                    let angle = -2.0 * PI * ((j * k) % n) as f64 / n as f64;
+                    let w = (angle.cos(), angle.sin());
+                    (re + x.0 * w.0 - x.1 * w.1, im + x.0 * w.1 + x.1 * w.0)
+                });
+        }
+    }
+}
+
+// iterative radix-2 Cooley-Tukey FFT of a power of two values
+fn fft(values: &mut [(f64, f64)]) {
+    let n = values.len();
+    if n < 2 {
+        return;
+    }
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    let mut length = 2;
+    while length <= n {
+        let angle = -2.0 * PI / length as f64;
+        for start in (0..n).step_by(length) {
+            for i in 0..length / 2 {
+                let w = ((angle * i as f64).cos(), (angle * i as f64).sin());
+                let (a, b) = (values[start + i], values[start + i + length / 2]);
+                let t = (b.0 * w.0 - b.1 * w.1, b.0 * w.1 + b.1 * w.0);
+                values[start + i] = (a.0 + t.0, a.1 + t.1);
+                values[start + i + length / 2] = (a.0 - t.0, a.1 - t.1);
+            }
+        }
+        length *= 2;
+    }
+}
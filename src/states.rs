@@ -0,0 +1,279 @@
+// the whole state space of a tiny board: every assignment of its cells, numbered as bitsets
+// with cell n of the rows from the top at bit n, and the state each one steps to, exported as
+// a graph for teaching how finite automata fall into attractors
+//
+// graphs of the largest boards have tens of millions of states, so they're written to a
+// writer as they go rather than built into strings
+use crate::rule::Rule;
+use crate::{Life, VecGrid};
+use std::io::{self, Write};
+
+/// most cells a board can have for its state space to be explored
+pub const MAX_STATE_CELLS: usize = 25;
+
+// cycle that states fall into and the states falling into it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attractor {
+    /// states of the cycle in the order they step to each other, from the lowest
+    pub cycle: Vec<u32>,
+    /// number of states ending up in the cycle, the cycle included
+    pub basin: usize,
+    /// most generations a state of the basin takes to reach the cycle
+    pub transient: usize,
+}
+
+impl Attractor {
+    /// generations the cycle takes to repeat, 1 for fixed points
+    pub fn period(&self) -> usize {
+        self.cycle.len()
+    }
+}
+
+// states of a board and the successor of each
+#[derive(Debug, Clone)]
+pub struct StateGraph {
+    pub width: usize,
+    pub height: usize,
+    pub rule: Rule,
+    /// whether the board wraps around its edges like TorusGrid, otherwise cells past them
+    /// are dead like on VecGrid
+    pub wraps: bool,
+    successors: Vec<u32>,
+}
+
+impl StateGraph {
+    /// step every state of a board, which can have at most MAX_STATE_CELLS cells
+    pub fn new(width: usize, height: usize, rule: Rule, wraps: bool) -> Self {
+        let cells = width * height;
+        assert!(
+            cells <= MAX_STATE_CELLS,
+            "boards can have at most {} cells",
+            MAX_STATE_CELLS
+        );
+        // cells neighboring each one, repeated when a wrapped board is narrower than 3 cells
+        // like on TorusGrid
+        let neighbors: Vec<Vec<u32>> = (0..cells)
+            .map(|n| {
+                let (x, y) = ((n % width) as isize, (n / width) as isize);
+                let mut around = Vec::with_capacity(8);
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        let (nx, ny) = (x + dx, y + dy);
+                        let (w, h) = (width as isize, height as isize);
+                        if (dx, dy) == (0, 0) {
+                            continue;
+                        } else if wraps {
+                            around.push((ny.rem_euclid(h) * w + nx.rem_euclid(w)) as u32);
+                        } else if (0..w).contains(&nx) && (0..h).contains(&ny) {
+                            around.push((ny * w + nx) as u32);
+                        }
+                    }
+                }
+                around
+            })
+            .collect();
+        let next: Vec<[bool; 9]> = [false, true]
+            .iter()
+            .map(|&alive| std::array::from_fn(|count| rule.next_state(alive, count)))
+            .collect();
+        let successors = (0..1u32 << cells)
+            .map(|state| {
+                neighbors
+                    .iter()
+                    .enumerate()
+                    .fold(0, |successor, (n, around)| {
+                        let count = around.iter().filter(|&&m| (state >> m) & 1 == 1).count();
+                        let alive = (state >> n) & 1 == 1;
+                        successor | ((next[alive as usize][count] as u32) << n)
+                    })
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            rule,
+            wraps,
+            successors,
+        }
+    }
+
+    /// number of states, 2 to the number of cells
+    pub fn len(&self) -> usize {
+        self.successors.len()
+    }
+
+    /// whether there are no states, never true as even a board without cells has one
+    pub fn is_empty(&self) -> bool {
+        self.successors.is_empty()
+    }
+
+    /// state a state steps to
+    pub fn successor(&self, state: u32) -> u32 {
+        self.successors[state as usize]
+    }
+
+    /// state of a board of the size of the graph
+    pub fn state(&self, life: &impl Life) -> u32 {
+        (0..self.width * self.height)
+            .filter(|n| life.is_alive(n % self.width, n / self.width))
+            .fold(0, |state, n| state | (1 << n))
+    }
+
+    /// board of a state
+    pub fn board(&self, state: u32) -> VecGrid {
+        let mut grid = VecGrid::new(self.width, self.height);
+        for n in (0..self.width * self.height).filter(|n| (state >> n) & 1 == 1) {
+            grid.set_cell(n % self.width, n / self.width, true);
+        }
+        grid
+    }
+
+    /// every attractor of the board, by the lowest state of its cycle
+    pub fn attractors(&self) -> Vec<Attractor> {
+        const UNSEEN: u32 = u32::MAX;
+        // attractor of every state once known, and its distance from the cycle
+        let mut attractor = vec![UNSEEN; self.len()];
+        let mut distance = vec![0u32; self.len()];
+        let mut attractors: Vec<Attractor> = Vec::new();
+        let mut path = Vec::new();
+        for start in 0..self.len() as u32 {
+            if attractor[start as usize] != UNSEEN {
+                continue;
+            }
+            // follow successors until reaching a known state or one already on the path,
+            // marking the path with an attractor number that can't be a real one yet
+            let on_path = u32::MAX - 1;
+            let mut state = start;
+            while attractor[state as usize] == UNSEEN {
+                attractor[state as usize] = on_path;
+                path.push(state);
+                state = self.successor(state);
+            }
+            let (id, mut depth) = match attractor[state as usize] {
+                id if id == on_path => {
+                    // the path closed a new cycle, starting where it first met state
+                    let first = path.iter().position(|&s| s == state).unwrap();
+                    let mut cycle = path.split_off(first);
+                    let lowest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+                    cycle.rotate_left(lowest);
+                    let id = attractors.len() as u32;
+                    for &s in &cycle {
+                        attractor[s as usize] = id;
+                    }
+                    attractors.push(Attractor {
+                        basin: cycle.len(),
+                        cycle,
+                        transient: 0,
+                    });
+                    (id, 0)
+                }
+                id => (id, distance[state as usize]),
+            };
+            for &s in path.iter().rev() {
+                depth += 1;
+                attractor[s as usize] = id;
+                distance[s as usize] = depth;
+            }
+            let found = &mut attractors[id as usize];
+            found.basin += path.len();
+            found.transient = found.transient.max(depth as usize);
+            path.clear();
+        }
+        attractors.sort_by_key(|attractor| attractor.cycle[0]);
+        attractors
+    }
+
+    /// cells of a state as rows of plaintext from the top separated by /, with . for dead
+    /// cells and O for live ones
+    pub fn cells(&self, state: u32) -> String {
+        self.rows(state).join("/")
+    }
+
+    /// write the graph in Graphviz DOT, a node per state labeled with its cells
+    pub fn write_dot(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "digraph states {{")?;
+        writeln!(out, "    // {}", self.description())?;
+        writeln!(out, "    node [shape=box, fontname=monospace];")?;
+        for state in 0..self.len() as u32 {
+            // rows ended by \l, left justified
+            let label: String = self
+                .rows(state)
+                .iter()
+                .map(|row| row.clone() + "\\l")
+                .collect();
+            writeln!(out, "    s{} [label=\"{}\"];", state, label)?;
+        }
+        for state in 0..self.len() as u32 {
+            writeln!(out, "    s{} -> s{};", state, self.successor(state))?;
+        }
+        writeln!(out, "}}")
+    }
+
+    /// write the graph in GraphML, with the cells and population of every state as data
+    pub fn write_graphml(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+        writeln!(out, "  <!-- {} -->", self.description())?;
+        writeln!(
+            out,
+            r#"  <key id="cells" for="node" attr.name="cells" attr.type="string"/>"#
+        )?;
+        writeln!(
+            out,
+            r#"  <key id="population" for="node" attr.name="population" attr.type="int"/>"#
+        )?;
+        writeln!(out, r#"  <graph id="states" edgedefault="directed">"#)?;
+        for state in 0..self.len() as u32 {
+            writeln!(
+                out,
+                r#"    <node id="s{}"><data key="cells">{}</data><data key="population">{}</data></node>"#,
+                state,
+                self.cells(state),
+                state.count_ones()
+            )?;
+        }
+        for state in 0..self.len() as u32 {
+            writeln!(
+                out,
+                r#"    <edge source="s{}" target="s{}"/>"#,
+                state,
+                self.successor(state)
+            )?;
+        }
+        writeln!(out, "  </graph>")?;
+        writeln!(out, "</graphml>")
+    }
+
+    // size, rule and edges of the board, for the comments of exported graphs
+    fn description(&self) -> String {
+        let edges = match self.wraps {
+            true => "wrapping",
+            false => "bounded",
+        };
+        format!(
+            "{}x{} {} board under {}, {} states",
+            self.width,
+            self.height,
+            edges,
+            self.rule,
+            self.len()
+        )
+    }
+
+    // rows of a state from the top, in plaintext with . for dead cells and O for live ones
+    fn rows(&self, state: u32) -> Vec<String> {
+        (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| match (state >> (y * self.width + x)) & 1 {
+                        1 => 'O',
+                        _ => '.',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
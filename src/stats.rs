@@ -0,0 +1,309 @@
+// basic statistics of a grid
+use crate::Life;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+// smallest rectangle containing every live cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// number of live cells
+pub fn population(life: &impl Life) -> usize {
+    live_cells(life).count()
+}
+
+/// smallest rectangle containing every live cell, None if there are none
+pub fn bounding_box(life: &impl Life) -> Option<BoundingBox> {
+    let mut cells = live_cells(life);
+    let (x, y) = cells.next()?;
+    let (min_x, min_y, max_x, max_y) = cells.fold((x, y, x, y), |(x0, y0, x1, y1), (x, y)| {
+        (x0.min(x), y0.min(y), x1.max(x), y1.max(y))
+    });
+    Some(BoundingBox {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x + 1,
+        height: max_y - min_y + 1,
+    })
+}
+
+/// coordinates of live cells, row by row
+pub fn live_cells(life: &impl Life) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..life.height())
+        .flat_map(move |y| (0..life.width()).map(move |x| (x, y)))
+        .filter(move |&(x, y)| life.is_alive(x, y))
+}
+
+/// mean position of the live cells, None if there are none
+pub fn centroid(life: &impl Life) -> Option<(f64, f64)> {
+    let (count, sum_x, sum_y) = live_cells(life).fold((0, 0.0, 0.0), |(n, sx, sy), (x, y)| {
+        (n + 1, sx + x as f64, sy + y as f64)
+    });
+    (count > 0).then(|| (sum_x / count as f64, sum_y / count as f64))
+}
+
+/// hash of the live cells, equal grids always have equal fingerprints
+pub fn fingerprint(life: &impl Life) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (life.width(), life.height()).hash(&mut hasher);
+    live_cells(life).for_each(|cell| cell.hash(&mut hasher));
+    hasher.finish()
+}
+
+/// fraction of live cells in each block by block square, row by row, for drawing boards
+/// too big to show cell by cell, blocks along the right and bottom edges may be cut short
+pub fn block_densities(life: &impl Life, block: usize) -> Vec<f64> {
+    let block = block.max(1);
+    let (width, height) = (life.width(), life.height());
+    (0..height.div_ceil(block))
+        .flat_map(|row| (0..width.div_ceil(block)).map(move |col| (col * block, row * block)))
+        .map(|(x, y)| {
+            let area = (width - x).min(block) * (height - y).min(block);
+            life.population_in(x, y, block, block) as f64 / area as f64
+        })
+        .collect()
+}
+
+/// number of cells where two boards differ, cells past the edges of the smaller board
+/// counting as dead
+pub fn hamming_distance(a: &impl Life, b: &impl Life) -> usize {
+    let (width, height) = (a.width().max(b.width()), a.height().max(b.height()));
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| cell(a, x, y) != cell(b, x, y))
+        .count()
+}
+
+// state of a cell, dead past the edges
+fn cell(life: &impl Life, x: usize, y: usize) -> bool {
+    x < life.width() && y < life.height() && life.is_alive(x, y)
+}
+
+// how many cells have each number of live neighbors, and the changes they lead to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NeighborHistogram {
+    /// cells with 0 to 8 live neighbors, indexed by count
+    pub counts: [usize; 9],
+    /// dead cells coming alive in the next generation
+    pub births: usize,
+    /// live cells dying in the next generation
+    pub deaths: usize,
+}
+
+/// histogram of live neighbor counts, with births and deaths expected under the rule of the
+/// board
+pub fn neighbor_histogram(life: &impl Life) -> NeighborHistogram {
+    let width = life.width();
+    let rule = life.rule();
+    let mut histogram = NeighborHistogram::default();
+    for (i, &count) in neighbor_counts(life).iter().enumerate() {
+        histogram.counts[count as usize] += 1;
+        let is_alive = life.is_alive(i % width, i / width);
+        match (is_alive, rule.next_state(is_alive, count as usize)) {
+            (false, true) => histogram.births += 1,
+            (true, false) => histogram.deaths += 1,
+            _ => (),
+        }
+    }
+    histogram
+}
+
+/// live neighbors of every cell row by row, counted on the bounded plane like the default
+/// Life::number_of_neighbors but in one pass over the live cells
+pub fn neighbor_counts(life: &impl Life) -> Vec<u8> {
+    let (width, height) = (life.width(), life.height());
+    let mut counts = vec![0; width * height];
+    for (x, y) in live_cells(life) {
+        for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+            for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                if (nx, ny) != (x, y) {
+                    counts[ny * width + nx] += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// a number of bytes written with a binary unit, like 1.5 MiB
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", value, UNITS[unit]),
+    }
+}
+
+/// length in bytes of the packed rows of a board compressed as an LZ4 block, a rough measure
+/// of its complexity, random soups hardly compressing while empty and ordered boards shrink
+/// to almost nothing
+pub fn compressed_size(life: &impl Life) -> usize {
+    let row_bytes = life.width().div_ceil(8);
+    let bytes: Vec<u8> = life
+        .iter_rows_packed()
+        .flat_map(|row| {
+            let bytes: Vec<u8> = row.iter().flat_map(|word| word.to_le_bytes()).collect();
+            bytes.into_iter().take(row_bytes)
+        })
+        .collect();
+    lz4_block_size(&bytes)
+}
+
+// length of the LZ4 block made of data by a greedy compressor matching 4 byte sequences
+// through a hash table, like the fast mode of the reference compressor, following the rules
+// of the format that the last 5 bytes are literals and no match starts in the last 12
+fn lz4_block_size(data: &[u8]) -> usize {
+    const MIN_MATCH: usize = 4;
+    const LAST_LITERALS: usize = 5;
+    const MATCH_FIND_LIMIT: usize = 12;
+    const MAX_OFFSET: usize = 65535;
+    // bytes extending a length of 15 or more in a token
+    let extension = |length: usize| match length {
+        0..=14 => 0,
+        _ => (length - 15) / 255 + 1,
+    };
+    let (mut size, mut anchor, mut i) = (0, 0, 0);
+    let mut table = vec![usize::MAX; 1 << 12];
+    let hash = |i: usize| {
+        let word = u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        (word.wrapping_mul(2654435761) >> 20) as usize
+    };
+    while i + MATCH_FIND_LIMIT < data.len() {
+        let h = hash(i);
+        let candidate = std::mem::replace(&mut table[h], i);
+        if candidate == usize::MAX
+            || i - candidate > MAX_OFFSET
+            || data[candidate..candidate + MIN_MATCH] != data[i..i + MIN_MATCH]
+        {
+            i += 1;
+            continue;
+        }
+        let mut length = MIN_MATCH;
+        while i + length < data.len() - LAST_LITERALS
+            && data[candidate + length] == data[i + length]
+        {
+            length += 1;
+        }
+        // token, literals and their length, offset and the length of the match
+        let literals = i - anchor;
+        size += 1 + extension(literals) + literals + 2 + extension(length - MIN_MATCH);
+        i += length;
+        anchor = i;
+    }
+    let literals = data.len() - anchor;
+    size + 1 + extension(literals) + literals
+}
+
+/// Shannon entropy in bits of the arrangements of cells in the block by block squares tiling
+/// the board, 0 when every block looks the same and up to block squared bits for noise,
+/// squares cut short by the right and bottom edges are left out, blocks are at most 8 wide
+pub fn block_entropy(life: &impl Life, block: usize) -> f64 {
+    let block = block.clamp(1, 8);
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for y in (0..life.height() / block).map(|row| row * block) {
+        for x in (0..life.width() / block).map(|col| col * block) {
+            let arrangement = (0..block * block)
+                .filter(|&i| life.is_alive(x + i % block, y + i / block))
+                .fold(0u64, |bits, i| bits | (1 << i));
+            *counts.entry(arrangement).or_default() += 1;
+        }
+    }
+    let total = counts.values().sum::<usize>() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+// sizes of the clusters of a board, groups of live cells joined through their 8 neighbors
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Clusters {
+    /// number of clusters of each size, smallest size first
+    pub histogram: Vec<(usize, usize)>,
+    /// cells of the largest cluster, 0 on empty boards
+    pub largest: usize,
+    /// fraction of the live cells in the largest cluster, 0 on empty boards, near 1 once a
+    /// cluster percolates through the board
+    pub largest_fraction: f64,
+}
+
+impl Clusters {
+    /// number of clusters
+    pub fn count(&self) -> usize {
+        self.histogram.iter().map(|&(_, count)| count).sum()
+    }
+
+    /// mean size of the clusters, 0 on empty boards
+    pub fn mean_size(&self) -> f64 {
+        let cells: usize = self
+            .histogram
+            .iter()
+            .map(|&(size, count)| size * count)
+            .sum();
+        match self.count() {
+            0 => 0.0,
+            count => cells as f64 / count as f64,
+        }
+    }
+}
+
+/// size distribution of the clusters of live cells, joined across the edges on maps that wrap
+pub fn clusters(life: &impl Life) -> Clusters {
+    let (width, height, wraps) = (life.width(), life.height(), life.wraps());
+    let mut seen = vec![false; width * height];
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    let mut stack = Vec::new();
+    let (mut population, mut largest) = (0, 0);
+    for (x, y) in live_cells(life) {
+        if std::mem::replace(&mut seen[y * width + x], true) {
+            continue;
+        }
+        // flood the cluster from its first cell
+        let mut size = 0;
+        stack.push((x, y));
+        while let Some((x, y)) = stack.pop() {
+            size += 1;
+            for (dx, dy) in (0..9).map(|i| (i % 3, i / 3)).filter(|&d| d != (1, 1)) {
+                let (nx, ny) = match wraps {
+                    true => ((x + width + dx - 1) % width, (y + height + dy - 1) % height),
+                    false => match ((x + dx).checked_sub(1), (y + dy).checked_sub(1)) {
+                        (Some(nx), Some(ny)) if nx < width && ny < height => (nx, ny),
+                        _ => continue,
+                    },
+                };
+                if life.is_alive(nx, ny) && !std::mem::replace(&mut seen[ny * width + nx], true) {
+                    stack.push((nx, ny));
+                }
+            }
+        }
+        *sizes.entry(size).or_default() += 1;
+        population += size;
+        largest = largest.max(size);
+    }
+    let mut histogram: Vec<(usize, usize)> = sizes.into_iter().collect();
+    histogram.sort_unstable();
+    Clusters {
+        histogram,
+        largest,
+        largest_fraction: match population {
+            0 => 0.0,
+            population => largest as f64 / population as f64,
+        },
+    }
+}
@@ -0,0 +1,118 @@
+// symmetries of the square and other operations producing new grids from existing ones
+use crate::stats;
+use crate::{Life, VecGrid};
+use std::fmt;
+
+// rotation or reflection of a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    Identity,
+    /// quarter turn clockwise
+    Rotate90,
+    Rotate180,
+    /// quarter turn counterclockwise
+    Rotate270,
+    /// mirror left to right
+    FlipHorizontal,
+    /// mirror top to bottom
+    FlipVertical,
+    /// mirror across the top-left to bottom-right diagonal
+    FlipDiagonal,
+    /// mirror across the top-right to bottom-left diagonal
+    FlipAntiDiagonal,
+}
+
+impl Transform {
+    /// every symmetry of the square
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::FlipDiagonal,
+        Transform::FlipAntiDiagonal,
+    ];
+
+    /// whether width and height are exchanged by the transform
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::FlipDiagonal
+                | Transform::FlipAntiDiagonal
+        )
+    }
+
+    /// dimensions of a width by height rectangle after the transform
+    pub fn dimensions(self, width: usize, height: usize) -> (usize, usize) {
+        if self.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// position of cell (x, y) of a width by height rectangle after the transform
+    pub fn apply(self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        let (max_x, max_y) = (width - 1, height - 1);
+        match self {
+            Transform::Identity => (x, y),
+            Transform::Rotate90 => (max_y - y, x),
+            Transform::Rotate180 => (max_x - x, max_y - y),
+            Transform::Rotate270 => (y, max_x - x),
+            Transform::FlipHorizontal => (max_x - x, y),
+            Transform::FlipVertical => (x, max_y - y),
+            Transform::FlipDiagonal => (y, x),
+            Transform::FlipAntiDiagonal => (max_y - y, max_x - x),
+        }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Transform::Identity => "identity",
+            Transform::Rotate90 => "rotation by 90 degrees",
+            Transform::Rotate180 => "rotation by 180 degrees",
+            Transform::Rotate270 => "rotation by 270 degrees",
+            Transform::FlipHorizontal => "horizontal flip",
+            Transform::FlipVertical => "vertical flip",
+            Transform::FlipDiagonal => "diagonal flip",
+            Transform::FlipAntiDiagonal => "anti-diagonal flip",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// copy of a grid with a transform applied
+pub fn transformed(life: &impl Life, transform: Transform) -> VecGrid {
+    let (width, height) = (life.width(), life.height());
+    let (new_width, new_height) = transform.dimensions(width, height);
+    let mut grid = VecGrid::new(new_width, new_height);
+    stats::live_cells(life).for_each(|(x, y)| {
+        let (tx, ty) = transform.apply(x, y, width, height);
+        grid.set_cell(tx, ty, true);
+    });
+    grid
+}
+
+/// copy of the width by height region of a grid starting at (x, y), cells outside the grid are dead
+pub fn cropped(life: &impl Life, x: usize, y: usize, width: usize, height: usize) -> VecGrid {
+    let mut grid = VecGrid::new(width, height);
+    (0..height)
+        .flat_map(|cy| (0..width).map(move |cx| (cx, cy)))
+        .filter(|&(cx, cy)| life.is_alive(x + cx, y + cy))
+        .for_each(|(cx, cy)| grid.set_cell(cx, cy, true));
+    grid
+}
+
+/// copy of a grid cropped to the bounding box of its live cells
+pub fn trimmed(life: &impl Life) -> VecGrid {
+    match stats::bounding_box(life) {
+        Some(bbox) => cropped(life, bbox.x, bbox.y, bbox.width, bbox.height),
+        None => VecGrid::new(0, 0),
+    }
+}
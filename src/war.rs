@@ -0,0 +1,202 @@
+// two player Life war: players take turns placing a budget of cells in their half of the
+// board, then the board runs for a number of generations and each player scores the live
+// cells they own, owners being passed on to births like on shared boards
+use crate::ownership::Ownership;
+use crate::{Life, Simulation, VecGrid};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// players, numbered like owners
+pub const PLAYERS: [u8; 2] = [1, 2];
+
+// what the game is waiting for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    /// a player placing cells
+    Placing(u8),
+    /// generations left to run before the round is scored
+    Running(u64),
+    /// every round has been played
+    Over,
+}
+
+// rejected placement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceError {
+    /// no player is placing cells
+    NotPlacing,
+    /// the cell is in the other player's half or off the board
+    OutsideTerritory,
+    /// the cell is alive already
+    Occupied,
+}
+
+impl fmt::Display for PlaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            PlaceError::NotPlacing => "no player is placing cells",
+            PlaceError::OutsideTerritory => "the cell is outside of the player's territory",
+            PlaceError::Occupied => "the cell is alive already",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for PlaceError {}
+
+// state of a game
+pub struct War {
+    simulation: Simulation<VecGrid>,
+    ownership: Ownership,
+    budget: usize,
+    generations: u64,
+    rounds: u32,
+    round: u32,
+    turn: Turn,
+    placed: usize,
+    scores: [usize; 2],
+}
+
+impl War {
+    /// start a game on an empty width by height board, every round each player places
+    /// budget cells before generations are run
+    pub fn new(width: usize, height: usize, budget: usize, generations: u64, rounds: u32) -> Self {
+        Self {
+            simulation: Simulation::from_life(VecGrid::new(width, height)),
+            ownership: Ownership::new(width, height),
+            budget,
+            generations,
+            rounds,
+            round: 1,
+            turn: if rounds == 0 {
+                Turn::Over
+            } else {
+                Turn::Placing(PLAYERS[0])
+            },
+            placed: 0,
+            scores: [0; 2],
+        }
+    }
+
+    /// the board
+    pub fn life(&self) -> &VecGrid {
+        self.simulation.life()
+    }
+
+    /// owner of a cell, NO_OWNER if it isn't alive
+    pub fn owner(&self, x: usize, y: usize) -> u8 {
+        self.ownership.owner(x, y)
+    }
+
+    /// player whose half of the board a column is in, the first player has the left half
+    pub fn territory(&self, x: usize) -> u8 {
+        PLAYERS[(x >= self.life().width() / 2) as usize]
+    }
+
+    /// what the game is waiting for
+    pub fn turn(&self) -> Turn {
+        self.turn
+    }
+
+    /// round being played, from 1
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// rounds in the game
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    /// cells the placing player can still place this turn
+    pub fn remaining_budget(&self) -> usize {
+        self.budget - self.placed
+    }
+
+    /// scores of both players over the rounds played so far
+    pub fn scores(&self) -> [usize; 2] {
+        self.scores
+    }
+
+    /// live cells of both players right now
+    pub fn populations(&self) -> [usize; 2] {
+        let mut populations = [0; 2];
+        for (owner, population) in self.ownership.populations(self.life()) {
+            if let Some(i) = PLAYERS.iter().position(|&player| player == owner) {
+                populations[i] = population;
+            }
+        }
+        populations
+    }
+
+    /// place a cell for the player whose turn it is, the turn ends once the budget is spent
+    pub fn place(&mut self, x: usize, y: usize) -> Result<(), PlaceError> {
+        let player = match self.turn {
+            Turn::Placing(player) => player,
+            _ => return Err(PlaceError::NotPlacing),
+        };
+        if x >= self.life().width() || y >= self.life().height() || self.territory(x) != player {
+            return Err(PlaceError::OutsideTerritory);
+        }
+        if self.life().is_alive(x, y) {
+            return Err(PlaceError::Occupied);
+        }
+        self.simulation.life_mut().set_cell(x, y, true);
+        self.ownership.set(x, y, player);
+        self.placed += 1;
+        if self.placed == self.budget {
+            self.end_turn();
+        }
+        Ok(())
+    }
+
+    /// end the turn of the placing player, leaving the rest of the budget unused, after the
+    /// last player the generations start running
+    pub fn end_turn(&mut self) {
+        if let Turn::Placing(player) = self.turn {
+            self.placed = 0;
+            self.turn = match PLAYERS.iter().position(|&p| p == player) {
+                Some(i) if i + 1 < PLAYERS.len() => Turn::Placing(PLAYERS[i + 1]),
+                _ => Turn::Running(self.generations),
+            };
+        }
+    }
+
+    /// run one generation of the round, scoring it after the last one, returns whether a
+    /// generation was run
+    pub fn step(&mut self) -> bool {
+        let remaining = match self.turn {
+            Turn::Running(remaining) => remaining,
+            _ => return false,
+        };
+        if remaining > 0 {
+            self.simulation.step();
+            if let Some(previous) = self.simulation.previous() {
+                self.ownership.advance(previous, self.simulation.life());
+            }
+        }
+        self.turn = match remaining {
+            0 | 1 => {
+                let populations = self.populations();
+                self.scores[0] += populations[0];
+                self.scores[1] += populations[1];
+                self.round += 1;
+                match self.round > self.rounds {
+                    true => Turn::Over,
+                    false => Turn::Placing(PLAYERS[0]),
+                }
+            }
+            n => Turn::Running(n - 1),
+        };
+        remaining > 0
+    }
+
+    /// player with the higher score, None on a tie or before the game is over
+    pub fn winner(&self) -> Option<u8> {
+        match (self.turn, self.scores[0].cmp(&self.scores[1])) {
+            (Turn::Over, Ordering::Greater) => Some(PLAYERS[0]),
+            (Turn::Over, Ordering::Less) => Some(PLAYERS[1]),
+            _ => None,
+        }
+    }
+}
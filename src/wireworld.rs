@@ -0,0 +1,136 @@
+// WireWorld, Brian Silverman's automaton of electrons running along wires, for simulating
+// circuits: an electron head becomes a tail, a tail becomes wire again and wire becomes a
+// head when 1 or 2 of the 8 cells around it are heads, empty cells never change
+//
+// states are numbered as in Golly, so that its WireWorld patterns keep their meaning, and
+// written as text a character per cell, . for empty cells, @ for heads, ~ for tails and #
+// for wire
+use crate::multistate::{MultiStateLife, StateGrid};
+use crate::render::StatePalette;
+use std::fmt;
+use std::str::FromStr;
+
+pub const EMPTY: u8 = 0;
+pub const HEAD: u8 = 1;
+pub const TAIL: u8 = 2;
+pub const CONDUCTOR: u8 = 3;
+
+/// characters of the states in the text form, in the order of the states
+pub const CHARS: [char; 4] = ['.', '@', '~', '#'];
+
+/// state of a cell in the next generation given the electron heads around it
+pub fn next_state(state: u8, heads: usize) -> u8 {
+    match state {
+        HEAD => TAIL,
+        TAIL => CONDUCTOR,
+        CONDUCTOR if (1..=2).contains(&heads) => HEAD,
+        state => state,
+    }
+}
+
+// map of wires and electrons stepped under the WireWorld rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireWorld {
+    pub grid: StateGrid,
+}
+
+impl WireWorld {
+    /// step a grid of the states of WireWorld
+    pub fn new(grid: StateGrid) -> Self {
+        Self { grid }
+    }
+
+    /// number of electron heads, the signals running along the wires
+    pub fn heads(&self) -> usize {
+        self.grid.count(HEAD)
+    }
+
+    /// colors of the states, empty cells black, heads blue, tails red and wire orange
+    pub fn palette() -> StatePalette {
+        StatePalette::new(vec![0x000000, 0x4080ff, 0xff4020, 0xffb000])
+    }
+}
+
+impl MultiStateLife for WireWorld {
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn states(&self) -> u8 {
+        CHARS.len() as u8
+    }
+
+    fn state(&self, x: usize, y: usize) -> u8 {
+        self.grid.get(x, y)
+    }
+
+    fn set_state(&mut self, x: usize, y: usize, state: u8) {
+        self.grid.set(x, y, state.min(CONDUCTOR))
+    }
+
+    fn next_state(&self, x: usize, y: usize) -> u8 {
+        match self.state(x, y) {
+            // only wire needs its neighbors counted
+            CONDUCTOR => next_state(CONDUCTOR, self.neighbors_in_state(x, y, HEAD)),
+            state => next_state(state, 0),
+        }
+    }
+}
+
+impl fmt::Display for WireWorld {
+    /// the text form, a line per row
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height() {
+            let row: String = (0..self.width())
+                .map(|x| CHARS[self.state(x, y) as usize])
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+// reason a WireWorld map couldn't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWireWorldError(String);
+
+impl fmt::Display for ParseWireWorldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid WireWorld map: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseWireWorldError {}
+
+impl FromStr for WireWorld {
+    type Err = ParseWireWorldError;
+
+    /// read the text form, lines starting with ! being comments, spaces standing for empty
+    /// cells too and rows shorter than the longest padded with empty cells
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<(usize, &str)> = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.starts_with('!'))
+            .map(|(i, line)| (i + 1, line.trim_end()))
+            .collect();
+        let width = rows.iter().map(|(_, row)| row.chars().count()).max();
+        let mut grid = StateGrid::new(width.unwrap_or(0), rows.len());
+        for (y, &(line, row)) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let state = match c {
+                    ' ' => EMPTY,
+                    c => CHARS.iter().position(|&state| state == c).ok_or_else(|| {
+                        ParseWireWorldError(format!("line {}: unexpected '{}'", line, c))
+                    })? as u8,
+                };
+                grid.set(x, y, state);
+            }
+        }
+        Ok(Self::new(grid))
+    }
+}
@@ -0,0 +1,87 @@
+// rules that differ from one region of the board to another, like a Seeds zone feeding a
+// Conway zone, neighbors are counted across zone borders so that zones interact
+use crate::rule::Rule;
+use crate::{stats, Life};
+
+// rule of every cell of a board, zones added later covering earlier ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zones {
+    width: usize,
+    height: usize,
+    /// distinct rules, the first one outside of every zone
+    rules: Vec<Rule>,
+    /// index into rules of every cell row by row
+    cells: Vec<u32>,
+}
+
+impl Zones {
+    /// board of the given size where every cell follows one rule until zones are added
+    pub fn new(width: usize, height: usize, rule: Rule) -> Self {
+        Self {
+            width,
+            height,
+            rules: vec![rule],
+            cells: vec![0; width * height],
+        }
+    }
+
+    /// make the width by height rectangle with its top left corner at (x, y) follow a rule,
+    /// parts off the board are dropped
+    pub fn add_rect(&mut self, x: usize, y: usize, width: usize, height: usize, rule: Rule) {
+        let index = self.index(rule);
+        let (right, bottom) = (
+            x.saturating_add(width).min(self.width),
+            y.saturating_add(height).min(self.height),
+        );
+        for cy in y..bottom {
+            for cx in x..right {
+                self.cells[cy * self.width + cx] = index;
+            }
+        }
+    }
+
+    /// make the cells that are alive in a mask follow a rule, the mask lying over the board
+    /// from its top left corner
+    pub fn add_mask(&mut self, mask: &impl Life, rule: Rule) {
+        let index = self.index(rule);
+        for (x, y) in stats::live_cells(mask) {
+            if x < self.width && y < self.height {
+                self.cells[y * self.width + x] = index;
+            }
+        }
+    }
+
+    /// rule a cell follows, the one outside of every zone for cells off the board
+    pub fn rule_at(&self, x: usize, y: usize) -> Rule {
+        match (x < self.width, y < self.height) {
+            (true, true) => self.rules[self.cells[y * self.width + x] as usize],
+            _ => self.rules[0],
+        }
+    }
+
+    /// calculate the next generation of a board of the same size with every cell following
+    /// the rule of its zone
+    pub fn next_generation(&self, current: &impl Life, next: &mut impl Life) {
+        let width = current.width();
+        for (i, &count) in stats::neighbor_counts(current).iter().enumerate() {
+            let (x, y) = (i % width, i / width);
+            let rule = self.rule_at(x, y);
+            next.set_cell(
+                x,
+                y,
+                rule.next_state(current.is_alive(x, y), count as usize),
+            );
+        }
+    }
+
+    // index of a rule in rules, adding it if it's new
+    fn index(&mut self, rule: Rule) -> u32 {
+        match self.rules.iter().position(|&r| r == rule) {
+            Some(index) => index as u32,
+            None => {
+                self.rules.push(rule);
+                (self.rules.len() - 1) as u32
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// stepping dense backends must not allocate, counted by an allocator wrapping the system one
+use rlife::{BitGrid, Life, LifeGrid, Rule, RuleLife, Simulation, TorusGrid, VecGrid};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// allocations are only counted on the thread measuring them, tests run side by side
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn count() {
+    // try_with since the allocator also runs while thread locals are torn down
+    let _ = COUNTING.try_with(|counting| {
+        if counting.get() {
+            let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        }
+    });
+}
+
+// allocations made by f on this thread
+fn allocations(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|allocations| allocations.set(0));
+    COUNTING.with(|counting| counting.set(true));
+    f();
+    COUNTING.with(|counting| counting.set(false));
+    ALLOCATIONS.with(Cell::get)
+}
+
+// deterministic soup from a xorshift generator, about a third of the cells alive
+fn fill(life: &mut impl Life, seed: u64) {
+    let mut state = seed.max(1);
+    for y in 0..life.height() {
+        for x in 0..life.width() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            life.set_cell(x, y, state.is_multiple_of(3));
+        }
+    }
+}
+
+#[test]
+fn next_generation_does_not_allocate() {
+    let mut grid = VecGrid::new(64, 48);
+    fill(&mut grid, 7);
+    let mut next = VecGrid::new(64, 48);
+    assert_eq!(allocations(|| grid.next_generation(&mut next)), 0);
+
+    let mut fixed = Box::<LifeGrid<64, 36>>::default();
+    fill(fixed.as_mut(), 11);
+    let mut next = Box::<LifeGrid<64, 36>>::default();
+    assert_eq!(allocations(|| fixed.next_generation(next.as_mut())), 0);
+
+    let mut torus = Box::<TorusGrid<64, 36>>::default();
+    fill(torus.as_mut(), 13);
+    let mut next = Box::<TorusGrid<64, 36>>::default();
+    assert_eq!(allocations(|| torus.next_generation(next.as_mut())), 0);
+
+    let mut bits = BitGrid::new(100, 48);
+    fill(&mut bits, 17);
+    let mut next = BitGrid::new(100, 48);
+    assert_eq!(allocations(|| bits.next_generation(&mut next)), 0);
+}
+
+#[test]
+fn simulation_steps_do_not_allocate() {
+    let mut grid = VecGrid::new(64, 48);
+    fill(&mut grid, 2024);
+    let mut simulation = Simulation::from_life(grid);
+    assert_eq!(allocations(|| simulation.step_by(50)), 0);
+
+    let highlife: Rule = "B36/S23".parse().unwrap();
+    let mut grid = VecGrid::new(64, 48);
+    fill(&mut grid, 99);
+    let mut simulation = Simulation::from_life(RuleLife::new(grid, highlife));
+    assert_eq!(allocations(|| simulation.step_by(50)), 0);
+}
@@ -0,0 +1,74 @@
+// ants turn, recolor and step forward as their rules say
+use rlife::ant::{AntRule, AntWorld, Heading, Turn};
+use rlife::multistate::MultiStateLife;
+
+#[test]
+fn langtons_ant_walks_a_square_and_turns_back() {
+    let mut world = AntWorld::new(16, 16, AntRule::LANGTON.parse().unwrap());
+    world.add_ant(8, 8, Heading::North);
+    world.step_by(4);
+    // four right turns on white cells blacken a 2x2 square and bring the ant home
+    assert_eq!(world.grid.count(1), 4);
+    assert_eq!((world.ants[0].x, world.ants[0].y), (8, 8));
+    assert_eq!(world.ants[0].heading, Heading::North);
+    // on black it turns left instead, whitening the cell
+    world.step();
+    assert_eq!(world.state(8, 8), 0);
+    assert_eq!((world.ants[0].x, world.ants[0].heading), (7, Heading::West));
+    assert_eq!(world.generation(), 5);
+}
+
+#[test]
+fn langtons_ant_builds_its_highway() {
+    let mut world = AntWorld::new(128, 128, AntRule::default());
+    world.add_ant(64, 64, Heading::North);
+    world.step_by(11_000);
+    // the highway repeats every 104 steps, moving the ant 2 cells diagonally
+    let (x, y) = (world.ants[0].x, world.ants[0].y);
+    let black = world.grid.count(1);
+    world.step_by(104);
+    assert_eq!(x.abs_diff(world.ants[0].x), 2);
+    assert_eq!(y.abs_diff(world.ants[0].y), 2);
+    assert_eq!(world.grid.count(1), black + 12);
+}
+
+#[test]
+fn ants_cycle_cells_through_the_colors_of_their_rule() {
+    let rule: AntRule = "llrr".parse().unwrap();
+    assert_eq!(rule.to_string(), "LLRR");
+    assert_eq!(
+        (rule.colors(), rule.turn(1), rule.turn(2)),
+        (4, Turn::Left, Turn::Right)
+    );
+    assert_eq!("RLNU".parse::<AntRule>().unwrap().turn(3), Turn::Back);
+    for invalid in ["", "R", "RX"] {
+        assert!(invalid.parse::<AntRule>().is_err(), "{}", invalid);
+    }
+
+    // an ant on a single row turning left off it
+    let mut world = AntWorld::new(4, 1, rule);
+    world.add_ant(0, 0, Heading::East);
+    assert_eq!(world.next_state(0, 0), 1);
+    world.step();
+    assert_eq!(world.state(0, 0), 1);
+    assert_eq!(world.ants[0].heading, Heading::North);
+    // and wrapping around the edges back onto its cell
+    assert_eq!((world.ants[0].x, world.ants[0].y), (0, 0));
+}
+
+#[test]
+fn several_ants_walk_in_turn() {
+    let mut one = AntWorld::new(64, 64, AntRule::default());
+    one.add_ant(16, 16, Heading::North);
+    let mut two = one.clone();
+    two.add_ant(48, 48, Heading::North);
+    one.step_by(200);
+    two.step_by(200);
+    // far enough apart, each makes the same marks
+    assert_eq!(two.grid.count(1), 2 * one.grid.count(1));
+    assert_eq!(two.ants[0], one.ants[0]);
+    assert_eq!(
+        (two.ants[1].x - 32, two.ants[1].y - 32),
+        (one.ants[0].x, one.ants[0].y)
+    );
+}
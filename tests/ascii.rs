@@ -0,0 +1,54 @@
+// pictures of boards parse back into the same boards
+use rlife::ascii::{self, Ascii, ParseError};
+use rlife::{BitGrid, Life, LifeGrid};
+
+const GLIDER: &str = ".#.\n..#\n###\n";
+
+#[test]
+fn pictures_round_trip() {
+    let glider = ascii::from_ascii(GLIDER).unwrap();
+    assert_eq!((glider.width(), glider.height()), (3, 3));
+    assert!(glider.is_alive(1, 0) && glider.is_alive(2, 1) && !glider.is_alive(0, 1));
+    assert_eq!(ascii::to_ascii(&glider), GLIDER);
+    assert_eq!(glider.to_string(), GLIDER);
+
+    let custom = Ascii::new('O', ' ');
+    let drawn = custom.draw(&glider);
+    assert_eq!(drawn, " O \n  O\nOOO\n");
+    assert!(custom.parse(&drawn).unwrap() == glider);
+}
+
+#[test]
+fn short_lines_are_dead_past_their_end() {
+    let grid = ascii::from_ascii("#\n\n..#").unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 3));
+    assert_eq!(grid.to_string(), "#..\n...\n..#\n");
+    assert_eq!(ascii::from_ascii("").unwrap().width(), 0);
+}
+
+#[test]
+fn parses_onto_any_board() {
+    let mut bits = BitGrid::new(4, 2);
+    bits.set_cell(3, 1, true);
+    ascii::Ascii::default()
+        .parse_into(GLIDER, &mut bits)
+        .unwrap();
+    assert_eq!(bits.to_string(), ".#..\n..#.\n");
+    let mut fixed = LifeGrid::<3, 3>::default();
+    Ascii::default().parse_into(GLIDER, &mut fixed).unwrap();
+    assert_eq!(fixed.to_string(), GLIDER);
+}
+
+#[test]
+fn unknown_characters_are_reported() {
+    let err = ascii::from_ascii("..\n.x").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError {
+            line: 2,
+            column: 2,
+            found: 'x'
+        }
+    );
+    assert!(Ascii::new('O', '.').parse("#").is_err());
+}
@@ -0,0 +1,79 @@
+// sessions autosaved every so often and offered back after a crash
+use rlife::autosave::{self, Autosave};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+// directory of its own for the files of a test
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rlife-autosave-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// make a file look like it was last written some time ago
+fn age(path: &Path, seconds: u64) {
+    let file = File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(seconds))
+        .unwrap();
+}
+
+#[test]
+fn autosaves_are_due_a_period_after_the_last() {
+    let dir = scratch("due");
+    let start = Instant::now();
+    let every = Duration::from_secs(60);
+    // the state directory is made on the first save
+    let path = dir.join("state").join("rlife").join("autosave.rle");
+    let mut autosave = Autosave::new(path.clone(), every, start);
+    assert_eq!(autosave.path(), path);
+    assert!(!autosave.is_due(start + Duration::from_secs(59)));
+    assert!(autosave.is_due(start + every));
+    autosave.save("x = 1, y = 1\no!\n", start + every).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "x = 1, y = 1\no!\n"
+    );
+    assert!(!autosave.is_due(start + every + Duration::from_secs(1)));
+    assert!(autosave.is_due(start + 2 * every));
+
+    // failures still put the next autosave off
+    let mut blocked = Autosave::new(path.join("inside a file"), every, start);
+    assert!(blocked.save("", start + every).is_err());
+    assert!(!blocked.is_due(start + every));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn sessions_autosave_next_to_their_file() {
+    assert_eq!(
+        autosave::path(Some(Path::new("patterns/garden.rle"))),
+        Path::new("patterns/garden.rle.autosave")
+    );
+    assert!(autosave::path(None).ends_with("rlife/autosave.rle"));
+}
+
+#[test]
+fn autosaves_newer_than_the_session_are_recovered() {
+    let dir = scratch("recover");
+    let (path, session) = (dir.join("life.rle.autosave"), dir.join("life.rle"));
+    // nothing to recover before the first autosave
+    assert_eq!(autosave::recover(&path, Some(&session)).unwrap(), None);
+
+    // a run that crashed left an autosave behind, with no session saved or one saved before
+    autosave::write(&path, "x = 2, y = 1\n2o!\n").unwrap();
+    age(&path, 180);
+    let recovered = autosave::recover(&path, None).unwrap().unwrap();
+    assert_eq!(recovered.contents, "x = 2, y = 1\n2o!\n");
+    assert!(recovered.age >= Duration::from_secs(180));
+    assert!(recovered.age < Duration::from_secs(240));
+    std::fs::write(&session, "x = 1, y = 1\no!\n").unwrap();
+    age(&session, 600);
+    assert!(autosave::recover(&path, Some(&session)).unwrap().is_some());
+
+    // a run that saved its session on exit has nothing to offer back
+    age(&session, 60);
+    assert_eq!(autosave::recover(&path, Some(&session)).unwrap(), None);
+    std::fs::remove_dir_all(dir).unwrap();
+}
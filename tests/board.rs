@@ -0,0 +1,46 @@
+// boards loaded from pattern files and stepped under their rule
+use rlife::formats::{self, rle, Pattern};
+use rlife::transform::Transform;
+use rlife::{Board, Life, VecGrid};
+
+#[test]
+fn boards_step_under_the_rule_of_their_pattern() {
+    let replicator = rle::parse("x = 5, y = 5, rule = B36/S23\n2b3o$bo2bo$o3bo$o2bo$3o!").unwrap();
+    let mut grid = VecGrid::new(40, 40);
+    grid.blit(&replicator.grid, 18, 18, Transform::Identity);
+    let mut board = Board::from_pattern(Pattern {
+        grid,
+        rule: replicator.rule,
+    })
+    .unwrap();
+    assert_eq!(board.rule().to_string(), "B36/S23");
+    board.step_by(12);
+    // HighLife's replicator leaves two copies of itself along its diagonal
+    let mut expected = VecGrid::new(40, 40);
+    expected.blit(&replicator.grid, 16, 16, Transform::Identity);
+    expected.blit(&replicator.grid, 20, 20, Transform::Identity);
+    assert_eq!(board.grid(), &expected);
+    assert_eq!(board.to_pattern().rule.as_deref(), Some("B36/S23"));
+}
+
+#[test]
+fn patterns_without_a_rule_step_under_conway() {
+    let blinker = rle::parse("x = 3, y = 3\n$3o!").unwrap();
+    let mut board = Board::from_pattern(blinker).unwrap();
+    assert_eq!(board.rule().to_string(), "B3/S23");
+    board.step();
+    assert!((0..3).all(|y| board.is_alive(1, y)));
+    assert_eq!(board.population(), 3);
+}
+
+#[test]
+fn patterns_with_an_invalid_rule_are_refused() {
+    let pattern = Pattern {
+        grid: VecGrid::new(4, 4),
+        rule: Some("B9/S23".to_string()),
+    };
+    assert!(matches!(
+        Board::from_pattern(pattern),
+        Err(formats::Error::Rule(_))
+    ));
+}
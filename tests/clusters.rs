@@ -0,0 +1,33 @@
+// cluster size distributions join live cells through their 8 neighbors
+use rlife::formats::rle;
+use rlife::{stats, Life, TorusGrid, VecGrid};
+
+#[test]
+fn separate_patterns_are_separate_clusters() {
+    // a glider and a block, cells touching only at corners still joined
+    let grid = rle::parse("x = 6, y = 7\nbo$2bo$3o3$4b2o$4b2o!")
+        .unwrap()
+        .grid;
+    let clusters = stats::clusters(&grid);
+    assert_eq!(clusters.histogram, vec![(4, 1), (5, 1)]);
+    assert_eq!(clusters.count(), 2);
+    assert_eq!(clusters.largest, 5);
+    assert!((clusters.largest_fraction - 5.0 / 9.0).abs() < 1e-9);
+    assert!((clusters.mean_size() - 4.5).abs() < 1e-9);
+
+    let empty = stats::clusters(&VecGrid::new(8, 8));
+    assert_eq!((empty.count(), empty.largest), (0, 0));
+    assert_eq!(empty.largest_fraction, 0.0);
+}
+
+#[test]
+fn clusters_join_across_edges_that_wrap() {
+    let mut torus = TorusGrid::<8, 8>::default();
+    let mut plane = VecGrid::new(8, 8);
+    for (x, y) in [(0, 0), (7, 7), (3, 4)] {
+        torus.set_cell(x, y, true);
+        plane.set_cell(x, y, true);
+    }
+    assert_eq!(stats::clusters(&torus).histogram, vec![(1, 1), (2, 1)]);
+    assert_eq!(stats::clusters(&plane).histogram, vec![(1, 3)]);
+}
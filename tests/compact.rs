@@ -0,0 +1,47 @@
+// boards kept as compact strings come back with the same size and cells
+use rlife::compact;
+use rlife::{BitGrid, Life, LifeGrid, VecGrid};
+
+#[test]
+fn glider_is_a_few_characters() {
+    let mut glider = VecGrid::new(3, 3);
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        glider.set_cell(x, y, true);
+    }
+    assert_eq!(compact::encode(&glider), "3x3:4gE");
+    assert_eq!(compact::decode("3x3:4gE").unwrap(), glider);
+}
+
+#[test]
+fn boards_of_every_kind_round_trip() {
+    // sizes leaving each number of bytes over in the last base64 chunk
+    for (width, height) in [(0, 0), (1, 1), (5, 3), (8, 2), (70, 9)] {
+        let mut grid = VecGrid::new(width, height);
+        for i in (0..width * height).filter(|i| i % 3 == 0 || i % 7 == 1) {
+            grid.set_cell(i % width, i / width, true);
+        }
+        let encoded = compact::encode(&grid);
+        assert_eq!(compact::decode(&encoded).unwrap(), grid, "{}", encoded);
+
+        let mut bits = BitGrid::new(width, height);
+        compact::decode_into(&encoded, &mut bits).unwrap();
+        assert_eq!(compact::encode(&bits), encoded);
+    }
+    let mut fixed = LifeGrid::<4, 4>::default();
+    compact::decode_into("4x4:8A8", &mut fixed).unwrap();
+    assert_eq!(compact::encode(&fixed), "4x4:8A8");
+    assert!(compact::decode_into("3x3:4gE", &mut fixed).is_err());
+}
+
+#[test]
+fn malformed_boards_are_rejected() {
+    for s in [
+        "4gE",
+        "3x:4gE",
+        "3x3:4g",
+        "3x3:4g!",
+        "99999999999x99999999999:",
+    ] {
+        assert!(compact::decode(s).is_err(), "{}", s);
+    }
+}
@@ -0,0 +1,53 @@
+// compressed size and block entropy tell ordered boards from noise
+use rlife::{stats, Life, VecGrid};
+
+fn noise(width: usize, height: usize, seed: u64) -> VecGrid {
+    let mut state = seed.max(1);
+    let mut grid = VecGrid::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            grid.set_cell(x, y, state & 1 == 1);
+        }
+    }
+    grid
+}
+
+#[test]
+fn noise_compresses_worse_than_order() {
+    let empty = VecGrid::new(128, 128);
+    let mut stripes = VecGrid::new(128, 128);
+    for y in (0..128).step_by(2) {
+        (0..128).for_each(|x| stripes.set_cell(x, y, true));
+    }
+    let noise = noise(128, 128, 99);
+    // 2048 bytes of packed rows
+    let (empty, stripes, noise) = (
+        stats::compressed_size(&empty),
+        stats::compressed_size(&stripes),
+        stats::compressed_size(&noise),
+    );
+    assert!(empty < 20, "{}", empty);
+    assert!(stripes < 40, "{}", stripes);
+    assert!(noise > 2048, "{}", noise);
+    // a board too small to hold a match is all literals behind a token
+    assert_eq!(stats::compressed_size(&VecGrid::new(8, 8)), 9);
+    assert_eq!(stats::compressed_size(&VecGrid::new(0, 0)), 1);
+}
+
+#[test]
+fn block_entropy_counts_bits_of_surprise_per_block() {
+    assert_eq!(stats::block_entropy(&VecGrid::new(12, 12), 3), 0.0);
+    // two arrangements, equally often
+    let mut halves = VecGrid::new(8, 4);
+    for y in 0..4 {
+        (0..4).for_each(|x| halves.set_cell(x, y, true));
+    }
+    assert!((stats::block_entropy(&halves, 2) - 1.0).abs() < 1e-9);
+    // the 3x3 arrangements of noise are about equally likely, nearly a bit per cell
+    let noise = noise(300, 300, 7);
+    let entropy = stats::block_entropy(&noise, 3);
+    assert!(entropy > 8.5 && entropy <= 9.0, "{}", entropy);
+}
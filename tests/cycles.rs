@@ -0,0 +1,60 @@
+// settling into still lifes and oscillators is noticed with the period and start of the cycle
+use rlife::cycle::{Cycle, CycleDetector};
+use rlife::formats::rle;
+use rlife::{stats, Life, Simulation, VecGrid};
+
+fn simulation(text: &str, width: usize, height: usize, x: usize, y: usize) -> Simulation<VecGrid> {
+    let pattern = rle::parse(text).unwrap().grid;
+    let mut grid = VecGrid::new(width, height);
+    stats::live_cells(&pattern).for_each(|(px, py)| grid.set_cell(x + px, y + py, true));
+    Simulation::from_life(grid)
+}
+
+#[test]
+fn oscillators_and_still_lifes_close_cycles_where_they_start() {
+    let mut blinker = simulation("x = 3, y = 1\n3o!", 5, 5, 1, 2);
+    let cycle = CycleDetector::default().run(&mut blinker, 10);
+    assert_eq!(
+        cycle,
+        Some(Cycle {
+            start: 0,
+            period: 2
+        })
+    );
+    assert_eq!(blinker.generation(), 2);
+
+    // the T tetromino settles into the traffic light, four blinkers
+    let mut t = simulation("x = 3, y = 2\n3o$bo!", 15, 15, 6, 6);
+    let cycle = CycleDetector::default().run(&mut t, 100).unwrap();
+    assert_eq!(cycle.period, 2);
+    // the cycle is entered at its start and not a generation earlier
+    let mut replay = simulation("x = 3, y = 2\n3o$bo!", 15, 15, 6, 6);
+    replay.step_by(cycle.start);
+    let entered = replay.life().clone();
+    replay.step_by(2);
+    assert_eq!(*replay.life(), entered);
+    let mut before = simulation("x = 3, y = 2\n3o$bo!", 15, 15, 6, 6);
+    before.step_by(cycle.start - 1);
+    let earlier = before.life().clone();
+    before.step_by(2);
+    assert_ne!(*before.life(), earlier);
+
+    let mut block = simulation("x = 2, y = 2\n2o$2o!", 4, 4, 1, 1);
+    let cycle = CycleDetector::default().run(&mut block, 10).unwrap();
+    assert!(cycle.is_still_life());
+    assert_eq!(cycle.start, 0);
+}
+
+#[test]
+fn periods_longer_than_the_window_go_unnoticed() {
+    // remembering a single generation, the blinker's repeats are never seen
+    let mut blinker = simulation("x = 3, y = 1\n3o!", 5, 5, 1, 2);
+    assert_eq!(CycleDetector::new(1).run(&mut blinker, 10), None);
+    assert_eq!(blinker.generation(), 10);
+
+    let mut detector = CycleDetector::new(4);
+    let mut blinker = simulation("x = 3, y = 1\n3o!", 5, 5, 1, 2);
+    assert!(detector.run(&mut blinker, 10).is_some());
+    detector.clear();
+    assert_eq!(detector.observe(blinker.life(), blinker.generation()), None);
+}
@@ -0,0 +1,55 @@
+// damage spreading compares a board with a copy of it stepped alongside
+use rlife::damage::{self, DamageCurve, Lockstep};
+use rlife::random::Rng;
+use rlife::{stats, Life, Rule, RuleLife, VecGrid};
+
+#[test]
+fn flipping_a_dead_cell_far_away_heals_at_once() {
+    let mut grid = VecGrid::new(16, 16);
+    (4..7).for_each(|x| grid.set_cell(x, 5, true));
+    // a lone cell dies in one generation and the blinker never notices
+    assert_eq!(damage::spread(&grid, 12, 12, 4), vec![1, 0, 0, 0, 0]);
+    // breaking the blinker leaves nothing of it, so that damage never heals
+    let distances = damage::spread(&grid, 5, 5, 3);
+    assert_eq!(distances[0], 1);
+    assert!(distances[1..].iter().all(|&distance| distance > 0));
+
+    let mut lockstep = Lockstep::new(grid.clone(), 0, 0);
+    lockstep.step();
+    assert_eq!(lockstep.original.generation(), 1);
+    assert_eq!(lockstep.distance(), 0);
+    assert_eq!(stats::hamming_distance(&grid, &VecGrid::new(16, 16)), 3);
+    assert_eq!(stats::hamming_distance(&grid, &VecGrid::new(3, 3)), 3);
+}
+
+#[test]
+fn chaotic_rules_spread_damage_further_than_life() {
+    let mean = |rule: Rule| {
+        let trials: Vec<Vec<usize>> = (0..8)
+            .map(|seed| {
+                let mut rng = Rng::new(seed);
+                let mut soup = VecGrid::new(48, 48);
+                soup.fill_random(0.35, &mut rng);
+                damage::spread_random(&RuleLife::new(soup, rule), 60, &mut rng)
+            })
+            .collect();
+        DamageCurve::new(&trials)
+    };
+    // Seeds explodes from any difference, while most damage in Life stays local
+    let seeds = mean("B2/S".parse().unwrap());
+    let life = mean(Rule::default());
+    assert_eq!(seeds.mean.len(), 61);
+    assert_eq!(seeds.mean[0], 1.0);
+    assert_eq!(seeds.variance[0], 0.0);
+    assert!(seeds.mean[60] > life.mean[60]);
+    assert_eq!(seeds.surviving[60], 1.0);
+}
+
+#[test]
+fn curves_average_trials_of_any_length() {
+    let curve = DamageCurve::new(&[vec![1, 2, 0], vec![1, 4]]);
+    assert_eq!(curve.mean, vec![1.0, 3.0, 0.0]);
+    assert_eq!(curve.variance, vec![0.0, 1.0, 0.0]);
+    assert_eq!(curve.surviving, vec![1.0, 1.0, 0.0]);
+    assert_eq!(DamageCurve::new(&[]), DamageCurve::default());
+}
@@ -0,0 +1,68 @@
+// painting with the mouse paints the opposite state of the cell a stroke started on
+use rlife::edit::Brush;
+use rlife::{Life, VecGrid};
+
+// feed the brush a frame and apply its edit like the window does
+fn frame(brush: &mut Brush, grid: &mut VecGrid, pressed: bool, cell: Option<(usize, usize)>) {
+    if let Some((x, y, is_alive)) = brush.update(pressed, cell, grid) {
+        grid.set_cell(x, y, is_alive);
+    }
+}
+
+#[test]
+fn click_toggles_and_double_click_toggles_back() {
+    let mut grid = VecGrid::new(8, 8);
+    let mut brush = Brush::default();
+    frame(&mut brush, &mut grid, true, Some((3, 4)));
+    // holding the button on the cell doesn't flip it every frame
+    frame(&mut brush, &mut grid, true, Some((3, 4)));
+    frame(&mut brush, &mut grid, false, Some((3, 4)));
+    assert!(grid.is_alive(3, 4));
+
+    frame(&mut brush, &mut grid, true, Some((3, 4)));
+    frame(&mut brush, &mut grid, false, Some((3, 4)));
+    assert!(!grid.is_alive(3, 4));
+    assert_eq!(brush, Brush::Up);
+}
+
+#[test]
+fn drag_paints_the_state_sampled_on_press() {
+    let mut grid = VecGrid::new(8, 8);
+    grid.set_cell(2, 0, true);
+    let mut brush = Brush::default();
+    // starting on a dead cell paints live cells, live ones crossed stay alive
+    for x in 0..5 {
+        frame(&mut brush, &mut grid, true, Some((x, 0)));
+    }
+    frame(&mut brush, &mut grid, false, None);
+    assert!((0..5).all(|x| grid.is_alive(x, 0)));
+
+    // starting on a live cell erases, going back over erased cells and leaving the board
+    // on the way
+    for cell in [Some((4, 0)), Some((3, 0)), None, Some((3, 0)), Some((1, 0))] {
+        frame(&mut brush, &mut grid, true, cell);
+    }
+    frame(&mut brush, &mut grid, false, None);
+    assert_eq!(
+        (0..5).map(|x| grid.is_alive(x, 0)).collect::<Vec<_>>(),
+        [true, false, true, false, false]
+    );
+}
+
+#[test]
+fn edits_are_only_given_for_cells_changing_state() {
+    let mut grid = VecGrid::new(8, 8);
+    grid.set_cell(1, 1, true);
+    let mut brush = Brush::default();
+    assert_eq!(brush.update(true, Some((0, 1)), &grid), Some((0, 1, true)));
+    grid.set_cell(0, 1, true);
+    assert_eq!(brush.update(true, Some((1, 1)), &grid), None);
+    assert_eq!(
+        brush,
+        Brush::Painting {
+            is_alive: true,
+            last: (1, 1)
+        }
+    );
+    assert_eq!(brush.update(false, Some((1, 1)), &grid), None);
+}
@@ -0,0 +1,49 @@
+// ensembles give every run its own seed, whatever the number of threads
+use rlife::ensemble::{self, Curve, Ensemble};
+use rlife::{Life, VecGrid};
+
+fn soups(threads: usize) -> Vec<Vec<usize>> {
+    let ensemble = Ensemble {
+        runs: 12,
+        generations: 30,
+        seed: 5,
+        threads,
+    };
+    ensemble.populations(|rng| {
+        let mut soup = VecGrid::new(24, 24);
+        soup.fill_random(0.35, rng);
+        soup
+    })
+}
+
+#[test]
+fn runs_are_the_same_on_any_number_of_threads() {
+    let serial = soups(1);
+    assert_eq!(serial.len(), 12);
+    assert!(serial.iter().all(|run| run.len() == 31));
+    // different seeds make different soups
+    assert!(serial[0] != serial[1]);
+    assert_eq!(soups(4), serial);
+    assert_eq!(soups(64), serial);
+}
+
+#[test]
+fn curves_count_the_runs_still_alive() {
+    let curve = Curve::new(&[vec![4, 3, 0], vec![4, 5, 2]]);
+    assert_eq!(curve.mean, vec![4.0, 4.0, 1.0]);
+    assert_eq!(curve.variance, vec![0.0, 1.0, 1.0]);
+    assert_eq!(curve.surviving, vec![1.0, 1.0, 0.5]);
+    let none = Ensemble {
+        runs: 0,
+        generations: 10,
+        seed: 1,
+        threads: 8,
+    };
+    assert!(none.populations(|_| VecGrid::new(4, 4)).is_empty());
+}
+
+#[test]
+fn lifespans_end_at_extinction() {
+    let runs = [vec![4, 3, 0, 0], vec![4, 5, 2, 1], vec![0], vec![]];
+    assert_eq!(ensemble::lifespans(&runs), vec![2, 3, 0, 0]);
+}
@@ -0,0 +1,62 @@
+// every small pattern is enumerated once up to symmetry and classified
+use rlife::analysis::{self, Behavior};
+use rlife::formats::rle;
+use rlife::{stats, Rule};
+
+#[test]
+fn patterns_are_counted_once_up_to_symmetry() {
+    // a cell, a pair side by side or diagonal, three cells and the block
+    assert_eq!(analysis::enumerate(2, 2).len(), 5);
+    let patterns = analysis::enumerate(3, 3);
+    assert_eq!(patterns.len(), 85);
+    assert!(patterns.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(patterns
+        .iter()
+        .all(|pattern| stats::population(pattern) > 0));
+    assert!(patterns
+        .iter()
+        .all(|pattern| analysis::canonical(pattern) == *pattern));
+}
+
+#[test]
+fn enumerated_three_by_three_patterns_hold_the_glider() {
+    let classes: Vec<_> = analysis::enumerate(3, 3)
+        .iter()
+        .map(|pattern| analysis::classify(pattern, Rule::default(), 8))
+        .collect();
+    let count = |f: fn(&Behavior) -> bool| classes.iter().filter(|b| f(b)).count();
+    // the block, boat, ship and tub
+    assert_eq!(count(|b| *b == Behavior::StillLife), 4);
+    assert_eq!(
+        count(|b| matches!(b, Behavior::Oscillator { period: 2 })),
+        1
+    );
+    // the glider in its two phases of distinct shapes
+    assert_eq!(count(|b| matches!(b, Behavior::Ship { period: 4, .. })), 2);
+
+    let glider = analysis::canonical(&rle::parse("x = 3, y = 3\nbo$2bo$3o!").unwrap().grid);
+    assert!(analysis::enumerate(3, 3).contains(&glider));
+}
+
+#[test]
+fn patterns_are_classified_under_their_rule() {
+    let blinker = rle::parse("x = 3, y = 1\n3o!").unwrap().grid;
+    let conway = Rule::default();
+    assert_eq!(
+        analysis::classify(&blinker, conway, 8),
+        Behavior::Oscillator { period: 2 }
+    );
+    // no cell dies under this rule, so the blinker grows instead
+    let immortal: Rule = "B3/S012345678".parse().unwrap();
+    assert_eq!(analysis::classify(&blinker, immortal, 8), Behavior::Unknown);
+    // nor does a lone cell, which dies right away under Conway's rule
+    let cell = rle::parse("x = 1, y = 1\no!").unwrap().grid;
+    assert_eq!(
+        analysis::classify(&cell, conway, 8),
+        Behavior::Dies { generation: 1 }
+    );
+    assert_eq!(analysis::classify(&cell, immortal, 8), Behavior::StillLife);
+    // B0 rules fill the plane and can't be classified
+    let b0: Rule = "B0/S8".parse().unwrap();
+    assert_eq!(analysis::classify(&blinker, b0, 8), Behavior::Unknown);
+}
@@ -0,0 +1,76 @@
+// rules written as expressions over a cell
+use rlife::expression::RuleExpression;
+use rlife::rule::Rule;
+
+// outer-totalistic rule an expression amounts to
+fn rule(expression: &str) -> Option<Rule> {
+    expression.parse::<RuleExpression>().unwrap().rule()
+}
+
+#[test]
+fn expressions_of_counts_compile_to_rules() {
+    let conway = "alive = n == 3 || (alive && n == 2)";
+    assert_eq!(rule(conway), Some(Rule::default()));
+    assert_eq!(rule("n == 3 || alive && n == 2"), Some(Rule::default()));
+    assert_eq!(
+        rule("n == 3 || n == 6 || alive && n == 2"),
+        "B36/S236".parse().ok()
+    );
+    assert_eq!(rule("false"), "B/S".parse().ok());
+    assert_eq!(rule("true"), "B012345678/S012345678".parse().ok());
+    assert_eq!(
+        conway.parse::<RuleExpression>().unwrap().to_string(),
+        conway
+    );
+}
+
+#[test]
+fn operators_bind_like_c() {
+    // products before sums before comparisons before && before ||
+    assert_eq!(rule("n == 1 + 2 * 1"), "B3/S3".parse().ok());
+    assert_eq!(rule("(1 + 2) * 1 == n"), "B3/S3".parse().ok());
+    assert_eq!(rule("n == 2 || n == 3 && alive"), "B2/S23".parse().ok());
+    assert_eq!(rule("(n == 2 || n == 3) && alive"), "B/S23".parse().ok());
+    // operators of a level are left associative and unary ones bind tightest
+    assert_eq!(rule("n - 1 - 1 == 1"), "B3/S3".parse().ok());
+    assert_eq!(rule("12 / n / 2 == 2"), "B3/S3".parse().ok());
+    assert_eq!(rule("-n + 5 == 2"), "B3/S3".parse().ok());
+    assert_eq!(rule("!alive && n == 1"), "B1/S".parse().ok());
+    assert_eq!(rule("!!alive"), "B/S012345678".parse().ok());
+    // division and remainder by 0 give 0
+    assert_eq!(
+        rule("n / 0 == 0 && n % 0 == 0 && n % 4 == 2"),
+        "B26/S26".parse().ok()
+    );
+}
+
+#[test]
+fn positions_are_evaluated_for_every_cell() {
+    let stripes: RuleExpression = "x % 2 == 0 && n == 3 || y > 5 && alive".parse().unwrap();
+    assert_eq!(stripes.rule(), None);
+    assert!(stripes.next_state(false, 3, 4, 0));
+    assert!(!stripes.next_state(false, 3, 5, 0));
+    assert!(stripes.next_state(true, 0, 5, 6));
+    assert!(!stripes.next_state(true, 0, 5, 5));
+}
+
+#[test]
+fn malformed_expressions_are_refused() {
+    for invalid in [
+        "",
+        "alive =",
+        "n ==",
+        "(n == 3",
+        "n == 3)",
+        "m == 3",
+        "n # 3",
+        "n == 3 3",
+        "n = 3",
+        "n == 99999999999999999999",
+    ] {
+        let err = invalid.parse::<RuleExpression>().unwrap_err();
+        assert!(err.to_string().contains(invalid.trim()), "{}", err);
+    }
+    let err = "n == 3 && m".parse::<RuleExpression>().unwrap_err();
+    assert!(err.to_string().ends_with("unknown variable 'm'"), "{}", err);
+}
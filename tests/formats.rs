@@ -0,0 +1,140 @@
+// patterns read from RLE, the formats of older collections and plaintext, and written back
+use rlife::formats::{cells, lif, rle, Error};
+use rlife::random::Rng;
+use rlife::{stats, Life, VecGrid};
+
+#[test]
+fn life_106_coordinates_may_be_negative() {
+    let pattern = lif::parse("#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n").unwrap();
+    assert_eq!((pattern.grid.width(), pattern.grid.height()), (3, 3));
+    let cells: Vec<_> = stats::live_cells(&pattern.grid).collect();
+    assert_eq!(cells, [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    assert_eq!(pattern.rule, None);
+    assert_eq!(
+        lif::parse(&lif::write_106(&pattern.grid)).unwrap().grid,
+        pattern.grid
+    );
+}
+
+#[test]
+fn life_105_blocks_are_placed_by_their_offsets() {
+    let input = "#Life 1.05\n#D two blocks\n#R 23/36\n#P -1 -1\n.*\n..*\n***\n#P 4 0\n**\n";
+    let pattern = lif::parse(input).unwrap();
+    assert_eq!(pattern.rule.as_deref(), Some("B36/S23"));
+    let cells: Vec<_> = stats::live_cells(&pattern.grid).collect();
+    assert_eq!(
+        cells,
+        [(1, 0), (2, 1), (5, 1), (6, 1), (0, 2), (1, 2), (2, 2)]
+    );
+}
+
+#[test]
+fn life_105_round_trips_wide_patterns_and_rules() {
+    let mut grid = VecGrid::new(200, 3);
+    for x in (0..200).step_by(3) {
+        grid.set_cell(x, x % 3, true);
+        grid.set_cell(x, 2, true);
+    }
+    let written = lif::write_105(&grid, "B36/S23");
+    assert!(written.lines().all(|line| line.len() <= 80));
+    assert!(written.contains("#R 23/36"));
+    let pattern = lif::parse(&written).unwrap();
+    assert_eq!(pattern.rule.as_deref(), Some("B36/S23"));
+    let cells = |life: &VecGrid| stats::live_cells(life).collect::<Vec<_>>();
+    assert_eq!(cells(&pattern.grid), cells(&grid));
+}
+
+#[test]
+fn life_files_load_onto_any_board() {
+    let mut grid = VecGrid::new(5, 5);
+    let rule = lif::parse_into("#Life 1.05\n#N\n#P 10 10\n**\n**\n", &mut grid, 3, 3).unwrap();
+    assert_eq!(rule.as_deref(), Some(rlife::DEFAULT_RULE));
+    assert_eq!(
+        stats::live_cells(&grid).collect::<Vec<_>>(),
+        [(3, 3), (4, 3), (3, 4), (4, 4)]
+    );
+    assert!(lif::parse("x = 1, y = 1\no!").is_err());
+}
+
+#[test]
+fn plaintext_cells_round_trip_with_their_size() {
+    let glider = cells::parse("!Name: Glider\n!\n.O\n..O\nOOO\n\n").unwrap();
+    assert_eq!((glider.grid.width(), glider.grid.height()), (3, 3));
+    assert_eq!(
+        stats::live_cells(&glider.grid).collect::<Vec<_>>(),
+        [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+    );
+
+    // empty rows and columns around the cells are kept
+    let mut grid = VecGrid::new(6, 4);
+    grid.set_cell(2, 1, true);
+    let written = cells::write(&grid, "dot");
+    assert_eq!(written, "!Name: dot\n......\n..O...\n......\n......\n");
+    assert_eq!(cells::parse(&written).unwrap().grid, grid);
+    assert!(cells::parse(".O\nxO\n").is_err());
+}
+
+#[test]
+fn rle_headers_and_runs_are_read() {
+    let glider = rle::parse(
+        "#N Glider\n#C comments and blank lines are skipped\n\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!",
+    )
+    .unwrap();
+    assert_eq!(glider.rule.as_deref(), Some("B3/S23"));
+    assert_eq!(
+        stats::live_cells(&glider.grid).collect::<Vec<_>>(),
+        [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+    );
+
+    // counts before $ skip rows, runs go on across lines and anything after ! is ignored
+    let spread = rle::parse("x = 12, y = 5\n3b2o$\n10bo3$12o!\n3o$this isn't read").unwrap();
+    assert_eq!(spread.rule, None);
+    assert_eq!((spread.grid.width(), spread.grid.height()), (12, 5));
+    assert_eq!(stats::population(&spread.grid), 15);
+    assert!(spread.grid.is_alive(3, 0) && spread.grid.is_alive(4, 0));
+    assert!(spread.grid.is_alive(10, 1));
+    assert!((0..12).all(|x| spread.grid.is_alive(x, 4)));
+}
+
+#[test]
+fn rle_round_trips_soups_and_rules() {
+    let mut soup = VecGrid::new(150, 40);
+    soup.fill_random(0.4, &mut Rng::new(7));
+    let written = rle::write(&soup, "B36/S23");
+    assert!(written.starts_with("x = 150, y = 40, rule = B36/S23\n"));
+    assert!(written.trim_end().ends_with('!'));
+    assert!(written.lines().all(|line| line.len() <= 70));
+    let read = rle::parse(&written).unwrap();
+    assert_eq!(read.grid, soup);
+    assert_eq!(read.rule.as_deref(), Some("B36/S23"));
+
+    // empty rows collapse into a count and trailing dead cells aren't written
+    let mut sparse = VecGrid::new(8, 6);
+    sparse.set_cell(1, 0, true);
+    sparse.set_cell(2, 4, true);
+    assert_eq!(
+        rle::write(&sparse, "B3/S23"),
+        "x = 8, y = 6, rule = B3/S23\nbo4$2bo!\n"
+    );
+    assert_eq!(
+        rle::parse(&rle::write(&sparse, "B3/S23")).unwrap().grid,
+        sparse
+    );
+}
+
+#[test]
+fn malformed_rle_is_refused_with_its_line() {
+    let line = |input: &str| match rle::parse(input) {
+        Err(Error::Parse { line, .. }) => line,
+        other => panic!("{:?} parsed as {:?}", input, other.map(|p| p.grid)),
+    };
+    assert_eq!(line(""), 1);
+    assert_eq!(line("#C only comments\n"), 1);
+    assert_eq!(line("x = 3\no!"), 1);
+    assert_eq!(line("x = three, y = 3\no!"), 1);
+    assert_eq!(line("x 3, y = 3\no!"), 1);
+    // cells past the declared size, or characters that aren't runs
+    assert_eq!(line("x = 2, y = 2\n3o!"), 2);
+    assert_eq!(line("x = 2, y = 2\no$o$o!"), 2);
+    assert_eq!(line("#N bad\nx = 2, y = 2\nbo$\no?!"), 4);
+}
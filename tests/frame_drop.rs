@@ -0,0 +1,34 @@
+// frame drop policies hold the simulation back from running too far ahead of the frames drawn
+use rlife::render::FrameDrop;
+
+#[test]
+fn policies_bound_the_generations_skipped() {
+    assert!(FrameDrop::Latest.may_step(1000, 0));
+    let never = FrameDrop::Bounded(0);
+    assert!(never.may_step(5, 5));
+    assert!(!never.may_step(6, 5));
+    let some = FrameDrop::Bounded(3);
+    assert!(some.may_step(8, 5));
+    assert!(!some.may_step(9, 5));
+    // stepping back leaves the simulation behind the last frame
+    assert!(never.may_step(2, 5));
+    assert!(FrameDrop::Bounded(u64::MAX).may_step(u64::MAX, 1));
+}
+
+#[test]
+fn policies_are_parsed_as_written() {
+    for policy in [
+        FrameDrop::Latest,
+        FrameDrop::Bounded(0),
+        FrameDrop::Bounded(10),
+    ] {
+        assert_eq!(policy.to_string().parse::<FrameDrop>().unwrap(), policy);
+    }
+    assert_eq!("never".parse::<FrameDrop>().unwrap(), FrameDrop::Bounded(0));
+    assert_eq!(FrameDrop::default(), FrameDrop::Latest);
+    let error = "sometimes".parse::<FrameDrop>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid frame drop policy 'sometimes', expected latest, never or a number of generations"
+    );
+}
@@ -0,0 +1,46 @@
+// frames of a glider hash to the values recorded from rlife frames, so changes to the renderer
+// that alter a single pixel fail here
+use rlife::render::{self, Palette, Viewport};
+use rlife::{Life, Simulation, VecGrid};
+
+// hashes printed by rlife frames for the glider below in generations 0 to 3
+const GOLDEN: [u64; 4] = [
+    0xe429970af2ebf9bc,
+    0xc7dd473318ae065c,
+    0x5c4e2e1aeb6642ec,
+    0x65af34130ed07e0c,
+];
+
+#[test]
+fn glider_frames_match_golden_hashes() {
+    let mut grid = VecGrid::new(20, 12);
+    for (x, y) in [(1, 1), (2, 2), (0, 3), (1, 3), (2, 3)] {
+        grid.set_cell(x, y, true);
+    }
+    let mut simulation = Simulation::from_life(grid);
+    let viewport = Viewport::whole(simulation.life());
+    let mut frame = vec![0; viewport.width * viewport.height];
+    let mut changes = frame.clone();
+    render::render_to_buffer(
+        simulation.life(),
+        &mut changes,
+        &Palette::default(),
+        viewport,
+    );
+    for golden in GOLDEN {
+        let previous = simulation.life().clone();
+        render::render_to_buffer(simulation.life(), &mut frame, &Palette::default(), viewport);
+        assert_eq!(render::frame_hash(&frame), golden);
+        simulation.step();
+        render::render_changes(
+            &previous,
+            simulation.life(),
+            &mut changes,
+            &Palette::default(),
+            viewport,
+        );
+    }
+    // the partial redraws kept up with the board
+    render::render_to_buffer(simulation.life(), &mut frame, &Palette::default(), viewport);
+    assert_eq!(changes, frame);
+}
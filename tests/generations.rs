@@ -0,0 +1,84 @@
+// Generations rules fade dying cells through their own states
+use rlife::generations::{GenerationsLife, GenerationsRule, ALIVE};
+use rlife::multistate::{MultiStateLife, StateGrid};
+use rlife::render::{self, Viewport};
+use rlife::{Life, Rule, VecGrid};
+
+#[test]
+fn rules_parse_in_both_notations() {
+    let brain: GenerationsRule = GenerationsRule::BRIANS_BRAIN.parse().unwrap();
+    assert_eq!(brain.rule, "B2/S".parse::<Rule>().unwrap());
+    assert_eq!(brain.states(), 3);
+    assert_eq!(brain.to_string(), "B2/S/C3");
+    assert_eq!("/2/3".parse::<GenerationsRule>().unwrap(), brain);
+    let star_wars: GenerationsRule = "345/2/4".parse().unwrap();
+    assert_eq!(star_wars.to_string(), "B2/S345/C4");
+    assert_eq!("b2/s345/g4".parse::<GenerationsRule>().unwrap(), star_wars);
+    for invalid in ["B3/S23", "B2/S/C1", "B9/S/C3", "x/2/3", ""] {
+        assert!(invalid.parse::<GenerationsRule>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn cells_fade_through_every_state() {
+    let rule = GenerationsRule::new("B2/S".parse().unwrap(), 4);
+    assert_eq!(rule.next_state(0, 2), ALIVE);
+    assert_eq!(rule.next_state(0, 3), 0);
+    assert_eq!(rule.next_state(ALIVE, 2), 2);
+    assert_eq!(rule.next_state(2, 2), 3);
+    assert_eq!(rule.next_state(3, 2), 0);
+    // with 2 states it is the B/S rule itself
+    let life = GenerationsRule::new(Rule::default(), 2);
+    assert_eq!(life.next_state(ALIVE, 2), ALIVE);
+    assert_eq!(life.next_state(ALIVE, 4), 0);
+}
+
+#[test]
+fn brians_brain_moves_its_oscillators_along() {
+    // two live cells side by side with a dying pair behind them travel as a spaceship
+    let mut grid = StateGrid::new(12, 8);
+    for (x, y, state) in [(5, 3, 1), (5, 4, 1), (4, 3, 2), (4, 4, 2)] {
+        grid.set(x, y, state);
+    }
+    let life = GenerationsLife::new(grid, GenerationsRule::BRIANS_BRAIN.parse().unwrap());
+    let mut next = life.clone();
+    life.next_generation(&mut next);
+    assert_eq!(next.population(), 2);
+    assert_eq!((next.state(6, 3), next.state(6, 4)), (ALIVE, ALIVE));
+    assert_eq!((next.state(5, 3), next.state(4, 3)), (2, 0));
+
+    let mut board = VecGrid::new(3, 3);
+    board.set_cell(1, 1, true);
+    let grid = StateGrid::from_life(&board, ALIVE);
+    assert_eq!((grid.get(1, 1), grid.count(0)), (ALIVE, 8));
+    assert_eq!(grid.get(5, 5), 0);
+}
+
+#[test]
+fn dying_states_fade_in_the_palette() {
+    let brain: GenerationsRule = GenerationsRule::BRIANS_BRAIN.parse().unwrap();
+    assert_eq!(brain.palette().colors, [0x000000, 0xffffff, 0x4080ff]);
+    let star_wars = GenerationsRule::new(Rule::default(), 4).palette();
+    assert_eq!(star_wars.colors, [0x000000, 0xffffff, 0x4080ff, 0x20407f]);
+    assert_eq!(
+        GenerationsRule::new(Rule::default(), 2)
+            .palette()
+            .colors
+            .len(),
+        2
+    );
+
+    // a firing pair moving right drawn in three colors
+    let mut grid = StateGrid::new(4, 1);
+    grid.set(1, 0, 2);
+    grid.set(2, 0, ALIVE);
+    let life = GenerationsLife::new(grid, brain);
+    let mut buffer = vec![0; 4];
+    render::render_states_to_buffer(
+        &life,
+        &mut buffer,
+        &brain.palette(),
+        Viewport::whole_states(&life),
+    );
+    assert_eq!(buffer, [0x000000, 0x4080ff, 0xffffff, 0x000000]);
+}
@@ -0,0 +1,83 @@
+// hashlife jumps agree with the engines stepping a generation at a time
+use rlife::transform::Transform;
+use rlife::{stats, HashLifeUniverse, Life, SparseLife, VecGrid};
+
+// glider heading down and right with its top left corner at (x, y), by row from the top
+fn glider(x: i64, y: i64) -> Vec<(i64, i64)> {
+    [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+        .map(|(cx, cy)| (x + cx, y + cy))
+        .to_vec()
+}
+
+// R-pentomino on a board of its own
+fn r_pentomino() -> VecGrid {
+    let mut grid = VecGrid::new(3, 3);
+    for (x, y) in [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)] {
+        grid.set_cell(x, y, true);
+    }
+    grid
+}
+
+// live cells of a sparse universe by row from the top, the order hashlife gives them in
+fn sorted(sparse: &SparseLife) -> Vec<(i64, i64)> {
+    let mut cells: Vec<_> = sparse.cells().collect();
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+    cells
+}
+
+#[test]
+fn gliders_travel_a_cell_diagonally_every_four_generations() {
+    let mut universe = HashLifeUniverse::new();
+    glider(0, 0)
+        .into_iter()
+        .for_each(|(x, y)| universe.set_cell(x, y, true));
+    for generations in [4, 36, 1000] {
+        universe.advance(generations);
+        universe.check_invariants().unwrap();
+    }
+    assert_eq!(universe.generation(), 1040);
+    assert_eq!(universe.cells(), glider(260, 260));
+}
+
+#[test]
+fn jumps_match_sparse_steps() {
+    let mut universe = HashLifeUniverse::from_life(&r_pentomino(), -1, -1);
+    let mut sparse = SparseLife::from_life(&r_pentomino(), -1, -1);
+    // jumps of every size along with odd ones made of several powers of two
+    for generations in [1, 2, 3, 7, 64, 100, 926] {
+        universe.advance(generations);
+        sparse.step_by(generations);
+        universe.check_invariants().unwrap();
+        assert_eq!(universe.generation(), sparse.generation());
+        assert_eq!(universe.cells(), sorted(&sparse));
+    }
+    // the R-pentomino settles at generation 1103 with 116 cells, six of them gliders
+    assert_eq!(universe.generation(), 1103);
+    assert_eq!(universe.population(), 116);
+    universe.advance(1);
+    sparse.step();
+    assert_eq!(universe.cells(), sorted(&sparse));
+    assert_eq!(universe.population(), 116);
+}
+
+#[test]
+fn jumps_match_grids_away_from_their_edges() {
+    let mut grid = VecGrid::new(160, 160);
+    grid.blit(&r_pentomino(), 80, 80, Transform::Identity);
+    let mut universe = HashLifeUniverse::from_life(&grid, 0, 0);
+    for _ in 0..150 {
+        let mut next = VecGrid::new(160, 160);
+        grid.next_generation(&mut next);
+        grid = next;
+    }
+    universe.advance(150);
+    universe.check_invariants().unwrap();
+    let mut cells: Vec<_> = stats::live_cells(&grid)
+        .map(|(x, y)| (x as i64, y as i64))
+        .collect();
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+    assert_eq!(universe.cells(), cells);
+    assert!(universe.bounds().is_some_and(|(x, y, w, h)| {
+        x > 0 && y > 0 && x as u64 + w < 160 && y as u64 + h < 160
+    }));
+}
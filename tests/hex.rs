@@ -0,0 +1,74 @@
+// hexagonal Life counts six neighbors and draws rows shifted half a cell
+use rlife::hex::{self, HexLife, HexRule};
+use rlife::render::{self, Palette};
+use rlife::{Life, Rule, Simulation, TorusGrid, VecGrid};
+
+#[test]
+fn hex_rules_parse_with_counts_up_to_6() {
+    let rule: HexRule = "B2/S34H".parse().unwrap();
+    assert_eq!(rule.0, "B2/S34".parse::<Rule>().unwrap());
+    assert_eq!(rule.to_string(), "B2/S34H");
+    assert_eq!("b2/s34h".parse::<HexRule>().unwrap(), rule);
+    for invalid in ["B3/S23", "B7/S2H", "B2/S38H", "H", ""] {
+        assert!(invalid.parse::<HexRule>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn only_six_neighbors_count() {
+    let mut grid = VecGrid::new(5, 5);
+    for y in 1..4 {
+        for x in 1..4 {
+            grid.set_cell(x, y, true);
+        }
+    }
+    grid.set_cell(2, 2, false);
+    // up right and down left are not neighbors
+    assert_eq!(hex::number_of_neighbors(&grid, 2, 2), 6);
+    grid.set_cell(3, 1, false);
+    grid.set_cell(1, 3, false);
+    assert_eq!(hex::number_of_neighbors(&grid, 2, 2), 6);
+    grid.set_cell(1, 1, false);
+    assert_eq!(hex::number_of_neighbors(&grid, 2, 2), 5);
+    assert_eq!(hex::number_of_neighbors(&grid, 0, 0), 0);
+}
+
+#[test]
+fn neighbors_wrap_on_a_torus() {
+    let mut grid = TorusGrid::<8, 8>::default();
+    grid.set_cell(7, 7, true);
+    grid.set_cell(1, 0, true);
+    // up right across the top edge isn't a neighbor
+    grid.set_cell(1, 7, true);
+    assert_eq!(hex::number_of_neighbors(&grid, 0, 0), 2);
+}
+
+#[test]
+fn hex_life_steps_under_its_rule() {
+    // under B2/S34H a pair of neighbors gives birth to the two cells next to both
+    let mut grid = VecGrid::new(6, 6);
+    grid.set_cell(2, 2, true);
+    grid.set_cell(3, 2, true);
+    let mut simulation = Simulation::from_life(HexLife::new(grid, "B2/S34H".parse().unwrap()));
+    simulation.step();
+    let life = simulation.life();
+    assert!(life.is_alive(2, 1) && life.is_alive(3, 3));
+    assert!(!life.is_alive(3, 1) && !life.is_alive(2, 3));
+    assert!(!life.is_alive(2, 2) && !life.is_alive(3, 2));
+    assert_eq!(simulation.population(), 2);
+}
+
+#[test]
+fn rows_are_drawn_shifted_half_a_cell() {
+    let mut grid = VecGrid::new(3, 2);
+    grid.set_cell(0, 0, true);
+    grid.set_cell(0, 1, true);
+    assert_eq!(render::hex_frame_size(&grid), (7, 2));
+    assert_eq!(render::hex_frame_size(&VecGrid::new(0, 4)), (0, 0));
+    let palette = Palette::default();
+    let mut frame = vec![0; 14];
+    render::render_hex_to_buffer(&grid, &mut frame, &palette);
+    let alive: Vec<usize> = (0..14).filter(|&i| frame[i] == palette.alive).collect();
+    // the top row shifted a pixel right of the bottom one
+    assert_eq!(alive, [1, 2, 7, 8]);
+}
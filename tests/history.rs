@@ -0,0 +1,91 @@
+// histories step back and forth through the generations they keep
+use rlife::ascii;
+use rlife::{History, Life, Simulation, VecGrid};
+
+fn glider() -> VecGrid {
+    let mut grid = VecGrid::new(70, 12);
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        grid.set_cell(x, y, true);
+    }
+    grid
+}
+
+#[test]
+fn undo_and_redo_retrace_the_simulation() {
+    let mut history = History::from_life(glider(), 16);
+    let mut simulation = Simulation::from_life(glider());
+    let mut boards = vec![glider()];
+    for _ in 0..10 {
+        history.step();
+        simulation.step();
+        boards.push(simulation.life().clone());
+        assert!(history.life() == simulation.life());
+    }
+    assert_eq!(history.generation(), 10);
+    assert!(history.undo());
+    assert_eq!(history.generation(), 9);
+    assert!(*history.life() == boards[9]);
+    assert!(history.rewind_to(2));
+    assert!(
+        *history.life() == boards[2],
+        "{}",
+        ascii::to_ascii(history.life())
+    );
+    assert_eq!(history.newest(), 10);
+    assert!(history.redo());
+    assert!(*history.life() == boards[3]);
+    // stepping replays undone generations, reaching the same boards
+    history.step();
+    assert!(*history.life() == boards[4]);
+    assert!(history.rewind_to(10));
+    assert!(*history.life() == boards[10]);
+    assert!(!history.redo());
+}
+
+#[test]
+fn only_the_last_generations_are_kept() {
+    let mut history = History::from_life(glider(), 4);
+    (0..10).for_each(|_| history.step());
+    assert_eq!(history.oldest(), 6);
+    assert!(!history.rewind_to(5));
+    assert_eq!(history.generation(), 10);
+    assert!(history.rewind_to(6));
+    assert!(!history.undo());
+
+    let mut none = History::from_life(glider(), 0);
+    none.step();
+    assert!(!none.undo());
+}
+
+#[test]
+fn edits_forget_the_undone_future() {
+    let mut history = History::from_life(glider(), 8);
+    (0..3).for_each(|_| history.step());
+    history.undo();
+    history.life_mut().set_cell(60, 10, true);
+    assert!(!history.redo());
+    assert_eq!(history.newest(), 2);
+    history.reset(40);
+    assert_eq!((history.generation(), history.oldest()), (40, 40));
+    assert!(!history.undo());
+    assert!(history.into_life().is_alive(60, 10));
+}
+
+#[test]
+fn unchanged_parts_of_the_board_are_shared_between_generations() {
+    let mut board = VecGrid::new(512, 256);
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        board.set_cell(x, y, true);
+    }
+    let mut history = History::from_life(board, 100);
+    let start = history.memory_usage();
+    (0..100).for_each(|_| history.step());
+    // full copies would take 100 times 16 KiB
+    let kept = history.memory_usage() - start;
+    assert!(kept < 100 * 512 * 256 / 8 / 8, "{} bytes", kept);
+    assert!(history.rewind_to(0));
+    assert_eq!(rlife::stats::live_cells(history.life()).count(), 5);
+    assert!(history.life().is_alive(1, 0));
+    assert!(history.rewind_to(100));
+    assert!(history.life().is_alive(26, 25));
+}
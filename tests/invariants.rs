@@ -0,0 +1,91 @@
+// the --paranoid checks pass on the engines as they are and catch broken ones
+use rlife::invariants;
+use rlife::{BitGrid, HashLifeUniverse, Life, LifeGrid, VecGrid};
+
+// grid caching its population in a counter that goes stale when cells are cleared
+struct StaleCounter {
+    grid: VecGrid,
+    population: usize,
+}
+
+impl Life for StaleCounter {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        self.population += is_alive as usize;
+        self.grid.set_cell(x, y, is_alive);
+    }
+
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.grid.is_alive(x, y)
+    }
+
+    fn population_in(&self, x: usize, y: usize, width: usize, height: usize) -> usize {
+        match (x, y, width, height) == (0, 0, self.width(), self.height()) {
+            true => self.population,
+            false => self.grid.population_in(x, y, width, height),
+        }
+    }
+}
+
+// R-pentomino on a board
+fn r_pentomino(life: &mut impl Life) {
+    for (x, y) in [(31, 30), (32, 30), (30, 31), (31, 31), (31, 32)] {
+        life.set_cell(x, y, true);
+    }
+}
+
+#[test]
+fn engines_keep_their_invariants() {
+    let mut grid = VecGrid::new(70, 64);
+    r_pentomino(&mut grid);
+    let mut bits = BitGrid::new(70, 64);
+    r_pentomino(&mut bits);
+    let mut fixed = LifeGrid::<70, 64>::default();
+    r_pentomino(&mut fixed);
+    for _ in 0..50 {
+        let mut next = VecGrid::new(70, 64);
+        grid.next_generation(&mut next);
+        invariants::check_step(&grid, &next).unwrap();
+        grid = next;
+        let mut next = BitGrid::new(70, 64);
+        bits.next_generation(&mut next);
+        bits = next;
+        invariants::check_life(&grid).unwrap();
+        invariants::check_life(&bits).unwrap();
+        invariants::check_same("bit grid", &grid, &bits).unwrap();
+    }
+    invariants::check_life(&fixed).unwrap();
+
+    let mut universe = HashLifeUniverse::from_life(&grid, 0, 0);
+    universe.advance(1000);
+    universe.check_invariants().unwrap();
+    universe.compact();
+    universe.check_invariants().unwrap();
+}
+
+#[test]
+fn stale_counters_and_wrong_steps_are_caught() {
+    let mut stale = StaleCounter {
+        grid: VecGrid::new(8, 8),
+        population: 0,
+    };
+    stale.set_cell(1, 1, true);
+    invariants::check_life(&stale).unwrap();
+    stale.set_cell(1, 1, false);
+    assert!(invariants::check_life(&stale).is_err());
+
+    let mut grid = VecGrid::new(64, 64);
+    r_pentomino(&mut grid);
+    let mut next = VecGrid::new(64, 64);
+    grid.next_generation(&mut next);
+    next.set_cell(0, 0, true);
+    let violation = invariants::check_step(&grid, &next).unwrap_err();
+    assert!(violation.to_string().contains("(0, 0)"), "{}", violation);
+}
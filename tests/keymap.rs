@@ -0,0 +1,194 @@
+// keys turned into the actions of the window
+use rlife::input::{Action, ActionGuard, Guard, InputEvent, Key, Keymap, Verdict, CONFIRM_TIME};
+use std::time::{Duration, Instant};
+
+#[test]
+fn shift_picks_files_in_dialogs() {
+    let keymap = Keymap::default();
+    let press = |c, shift| InputEvent {
+        shift,
+        ..InputEvent::pressed(Key::Char(c))
+    };
+    assert_eq!(keymap.action(&press('o', false)), Some(Action::SetOrigin));
+    assert_eq!(keymap.action(&press('o', true)), Some(Action::OpenPattern));
+    assert_eq!(keymap.action(&press('s', false)), Some(Action::SaveSession));
+    assert_eq!(
+        keymap.action(&press('s', true)),
+        Some(Action::SaveSessionAs)
+    );
+}
+
+#[test]
+fn dialog_actions_are_rebound_by_name() {
+    let keymap = Keymap::parse("f open\nshift+w save-as").unwrap();
+    let f = InputEvent::pressed(Key::Char('f'));
+    assert_eq!(keymap.action(&f), Some(Action::OpenPattern));
+    let w = InputEvent {
+        shift: true,
+        ..InputEvent::pressed(Key::Char('w'))
+    };
+    assert_eq!(keymap.action(&w), Some(Action::SaveSessionAs));
+    assert_eq!(Action::OpenPattern.to_string(), "open");
+}
+
+#[test]
+fn arrows_pan_the_view_while_held() {
+    let keymap = Keymap::parse("shift+up zoom-in").unwrap();
+    let held = |key| InputEvent {
+        repeat: true,
+        ..InputEvent::pressed(key)
+    };
+    assert_eq!(keymap.action(&held(Key::Left)), Some(Action::PanLeft));
+    assert_eq!(keymap.action(&held(Key::Down)), Some(Action::PanDown));
+    let zoom = InputEvent {
+        shift: true,
+        ..InputEvent::pressed(Key::Up)
+    };
+    assert_eq!(keymap.action(&zoom), Some(Action::ZoomIn));
+    // zooming doesn't repeat, as a held key would zoom all the way in
+    assert_eq!(keymap.action(&held(Key::Char(']'))), None);
+    assert_eq!(Key::Right.to_string(), "right");
+    assert_eq!("down".parse(), Ok(Key::Down));
+}
+
+#[test]
+fn destructive_actions_wait_for_the_guard() {
+    let now = Instant::now();
+    let mut guard = ActionGuard::new(Guard::Confirm);
+    assert_eq!(guard.check(Action::Quit, false, now), Verdict::Confirm);
+    assert_eq!(guard.check(Action::Quit, false, now), Verdict::Allowed);
+    // other actions in between and waiting too long both cancel the confirmation
+    assert_eq!(
+        guard.check(Action::FillRandom, false, now),
+        Verdict::Confirm
+    );
+    assert_eq!(
+        guard.check(Action::TogglePause, false, now),
+        Verdict::Allowed
+    );
+    assert_eq!(
+        guard.check(Action::FillRandom, false, now),
+        Verdict::Confirm
+    );
+    let later = now + CONFIRM_TIME + Duration::from_millis(1);
+    assert_eq!(
+        guard.check(Action::FillRandom, false, later),
+        Verdict::Confirm
+    );
+
+    let mut guard = ActionGuard::new("shift".parse().unwrap());
+    assert_eq!(guard.check(Action::Quit, false, now), Verdict::NeedsShift);
+    assert_eq!(guard.check(Action::Quit, true, now), Verdict::Allowed);
+    let mut guard = ActionGuard::new(Guard::None);
+    assert_eq!(guard.check(Action::Quit, false, now), Verdict::Allowed);
+    assert!("twice".parse::<Guard>().is_err());
+}
+
+#[test]
+fn keys_are_unbound_with_none() {
+    let keymap = Keymap::parse("escape none\nshift+q quit").unwrap();
+    let escape = InputEvent::pressed(Key::Escape);
+    assert_eq!(keymap.action(&escape), None);
+    let q = InputEvent {
+        shift: true,
+        ..InputEvent::pressed(Key::Char('q'))
+    };
+    assert_eq!(keymap.action(&q), Some(Action::Quit));
+}
+
+#[test]
+fn every_action_throwing_the_board_away_is_guarded() {
+    let destructive = ["fill-random", "open", "quit", "jump-1", "jump-9"];
+    let safe = [
+        "pause",
+        "step-back",
+        "explain",
+        "time-lapse",
+        "fewer-frames",
+        "more-frames",
+        "art",
+        "neighbors",
+        "reseed",
+        "construction",
+        "origin",
+        "snap",
+        "label",
+        "rectangle",
+        "delete-annotations",
+        "record-macro",
+        "next-macro",
+        "turn-replay",
+        "replay-macro",
+        "symmetry",
+        "save",
+        "save-as",
+        "continue",
+        "bookmark-1",
+        "bookmark-9",
+        "zoom-in",
+        "zoom-out",
+        "pan-left",
+        "pan-down",
+    ];
+    let now = Instant::now();
+    for name in destructive {
+        let action: Action = name.parse().unwrap();
+        assert!(action.is_destructive(), "{}", name);
+        let mut guard = ActionGuard::new(Guard::Confirm);
+        assert_eq!(
+            guard.check(action, false, now),
+            Verdict::Confirm,
+            "{}",
+            name
+        );
+        assert_eq!(
+            guard.check(action, false, now),
+            Verdict::Allowed,
+            "{}",
+            name
+        );
+        let mut guard = ActionGuard::new(Guard::None);
+        assert_eq!(
+            guard.check(action, false, now),
+            Verdict::Allowed,
+            "{}",
+            name
+        );
+    }
+    for name in safe {
+        let action: Action = name.parse().unwrap();
+        assert!(!action.is_destructive(), "{}", name);
+        let mut guard = ActionGuard::new(Guard::Confirm);
+        assert_eq!(
+            guard.check(action, false, now),
+            Verdict::Allowed,
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn jumps_are_confirmed_when_shift_bookmarks() {
+    let now = Instant::now();
+    let mut guard = ActionGuard::new(Guard::Shift);
+    let jump = Action::JumpToBookmark(3);
+    assert_eq!(guard.check(jump, false, now), Verdict::Confirm);
+    assert_eq!(guard.check(jump, false, now), Verdict::Allowed);
+    // a jump to another slot asks again rather than confirming the first
+    assert_eq!(guard.check(jump, false, now), Verdict::Confirm);
+    assert_eq!(
+        guard.check(Action::JumpToBookmark(4), false, now),
+        Verdict::Confirm
+    );
+    assert_eq!(
+        guard.check(Action::FillRandom, false, now),
+        Verdict::NeedsShift
+    );
+    let keymap = Keymap::default();
+    let shifted = InputEvent {
+        shift: true,
+        ..InputEvent::pressed(Key::Char('3'))
+    };
+    assert_eq!(keymap.action(&shifted), Some(Action::Bookmark(3)));
+}
@@ -0,0 +1,71 @@
+// Larger than Life rules count ranges of neighbors up to a radius
+use rlife::formats::rle;
+use rlife::ltl::{LtlLife, LtlRule};
+use rlife::neighborhood::Neighborhood;
+use rlife::random::Rng;
+use rlife::{Life, Simulation, VecGrid};
+
+#[test]
+fn rules_parse_in_golly_notation() {
+    let bosco: LtlRule = LtlRule::BOSCO.parse().unwrap();
+    assert_eq!(bosco.radius, 5);
+    assert!(bosco.middle);
+    assert_eq!(
+        (bosco.survival.clone(), bosco.birth.clone()),
+        (34..=58, 34..=45)
+    );
+    assert_eq!(bosco.neighborhood(), Neighborhood::ExtendedMoore(5));
+    assert_eq!(bosco.to_string(), LtlRule::BOSCO);
+    let short: LtlRule = "r2,m0,s5,b3..4".parse().unwrap();
+    assert_eq!(short.to_string(), "R2,C0,M0,S5..5,B3..4,NM");
+    for invalid in [
+        "B3/S23",
+        "R0,C0,M0,S2..3,B3..3,NM",
+        "R501,C0,M0,S2..3,B3..3,NM",
+        "R2,C3,M0,S2..3,B3..3,NM",
+        "R2,C0,M0,S2..3,B3..3,NN",
+        "R2,C0,M0,B3..3",
+        "R2,S2..x,B3",
+    ] {
+        assert!(invalid.parse::<LtlRule>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn radius_1_rules_step_like_conway() {
+    let mut soup = VecGrid::new(32, 24);
+    soup.fill_random(0.4, &mut Rng::new(3));
+    let mut conway = Simulation::from_life(soup.clone());
+    // survival counting the cell itself is shifted by one
+    let mut without = Simulation::from_life(LtlLife::new(
+        soup.clone(),
+        "R1,C0,M0,S2..3,B3,NM".parse().unwrap(),
+    ));
+    let mut with =
+        Simulation::from_life(LtlLife::new(soup, "R1,C0,M1,S3..4,B3,NM".parse().unwrap()));
+    for _ in 0..20 {
+        conway.step();
+        without.step();
+        with.step();
+        assert_eq!(&without.life().life, conway.life());
+        assert_eq!(&with.life().life, conway.life());
+    }
+}
+
+#[test]
+fn neighbors_are_counted_up_to_the_radius() {
+    let mut grid = VecGrid::new(11, 11);
+    for (x, y) in [(0, 0), (10, 10), (5, 0), (3, 4)] {
+        grid.set_cell(x, y, true);
+    }
+    let life = LtlLife::new(grid, LtlRule::BOSCO.parse().unwrap());
+    assert_eq!(life.number_of_neighbors(5, 5), 4);
+    assert_eq!(life.number_of_neighbors(3, 4), 2);
+    assert_eq!(life.number_of_neighbors(10, 0), 1);
+}
+
+#[test]
+fn rle_headers_keep_rules_with_commas() {
+    let pattern = rle::parse("x = 2, y = 1, rule = R5,C0,M1,S34..58,B34..45,NM\n2o!").unwrap();
+    assert_eq!(pattern.rule.as_deref(), Some(LtlRule::BOSCO));
+}
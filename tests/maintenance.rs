@@ -0,0 +1,76 @@
+// sparse backends give back memory and recenter patterns that travel far
+use rlife::{HashLifeUniverse, SparseLife};
+
+// glider heading down and right with its top left corner at (x, y), by row from the top
+fn glider(x: i64, y: i64) -> Vec<(i64, i64)> {
+    [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+        .map(|(cx, cy)| (x + cx, y + cy))
+        .to_vec()
+}
+
+#[test]
+fn sparse_sets_shrink_once_cells_are_removed() {
+    let mut sparse = SparseLife::new();
+    for i in 0..10_000 {
+        sparse.set_cell(i * 3, 0, true);
+    }
+    let full = sparse.memory_usage();
+    for i in 0..9_990 {
+        sparse.set_cell(i * 3, 0, false);
+    }
+    assert_eq!(sparse.population(), 10);
+    assert!(sparse.memory_usage() < full / 4);
+    sparse.compact();
+    assert!(sparse.memory_usage() < full / 100);
+}
+
+#[test]
+fn sparse_universes_recenter_their_cells() {
+    let mut sparse = SparseLife::new();
+    glider(1_000_000, -50)
+        .into_iter()
+        .for_each(|(x, y)| sparse.set_cell(x, y, true));
+    sparse.step_by(40);
+    let (x, y, width, height) = sparse.bounds().unwrap();
+    let (dx, dy) = sparse.recenter();
+    assert_eq!(sparse.bounds(), Some((x + dx, y + dy, width, height)));
+    assert_eq!((x + dx, y + dy), (-1, -1));
+    sparse.step_by(4);
+    assert_eq!(sparse.bounds(), Some((0, 0, 3, 3)));
+    assert_eq!(SparseLife::new().recenter(), (0, 0));
+}
+
+#[test]
+fn compacting_hashlife_drops_the_empty_border() {
+    let mut universe = HashLifeUniverse::new();
+    universe.set_cell(1 << 30, 1 << 30, true);
+    universe.set_cell(1 << 30, 1 << 30, false);
+    glider(0, 0)
+        .into_iter()
+        .for_each(|(x, y)| universe.set_cell(x, y, true));
+    let levels = universe.node_stats().levels;
+    universe.compact();
+    let stats = universe.node_stats();
+    assert!(stats.levels < levels - 20);
+    assert!(universe.memory_usage() < 10_000);
+    universe.check_invariants().unwrap();
+    assert_eq!(universe.cells(), glider(0, 0));
+}
+
+#[test]
+fn hashlife_universes_recenter_their_cells() {
+    let mut universe = HashLifeUniverse::new();
+    glider(0, 0)
+        .into_iter()
+        .for_each(|(x, y)| universe.set_cell(x, y, true));
+    universe.advance(1 << 20);
+    let (x, y, width, height) = universe.bounds().unwrap();
+    assert_eq!((x, y), (1 << 18, 1 << 18));
+    let (dx, dy) = universe.recenter();
+    assert_eq!(universe.bounds(), Some((-1, -1, width, height)));
+    assert_eq!((dx, dy), (-1 - x, -1 - y));
+    universe.check_invariants().unwrap();
+    // the recentred glider steps on as before
+    universe.advance(4);
+    assert_eq!(universe.bounds(), Some((0, 0, 3, 3)));
+}
@@ -0,0 +1,102 @@
+// block automata replace 2x2 blocks at once, stepped like every other universe
+use rlife::ant::{AntRule, AntWorld};
+use rlife::margolus::{MargolusLife, MargolusRule};
+use rlife::{HashLifeUniverse, Life, Simulation, SparseLife, Steppable, TorusGrid, VecGrid};
+
+#[test]
+fn rules_are_parsed_as_written_by_mcell() {
+    let billiards: MargolusRule = MargolusRule::BILLIARD_BALLS.parse().unwrap();
+    assert_eq!(billiards.to_string(), MargolusRule::BILLIARD_BALLS);
+    assert_eq!("bbm".parse::<MargolusRule>().unwrap(), billiards);
+    assert_eq!(
+        " ms,d0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15".parse::<MargolusRule>(),
+        Ok(billiards)
+    );
+    let critters: MargolusRule = "Critters".parse().unwrap();
+    // blocks of two cells stay, others are inverted and those of three turned around too
+    assert_eq!(critters.next_block(0b0110), 0b0110);
+    assert_eq!(critters.next_block(0b0000), 0b1111);
+    assert_eq!(critters.next_block(0b0111), 0b0001);
+    assert!(critters.is_reversible() && billiards.is_reversible());
+    for invalid in [
+        "",
+        "B3/S23",
+        "MS,D0;1",
+        "MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;16",
+    ] {
+        assert!(invalid.parse::<MargolusRule>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn billiard_balls_travel_diagonally() {
+    let mut grid = VecGrid::new(32, 32);
+    grid.set_cell(4, 4, true);
+    let mut margolus = MargolusLife::new(grid, MargolusRule::BILLIARD_BALLS.parse().unwrap());
+    margolus.step_by(10);
+    assert!(margolus.life.is_alive(14, 14));
+    assert_eq!(rlife::stats::population(&margolus.life), 1);
+    assert_eq!((margolus.generation(), margolus.phase()), (10, 0));
+
+    // on a torus the ball comes back around
+    let mut torus = TorusGrid::<8, 8>::default();
+    torus.set_cell(2, 2, true);
+    let mut margolus = MargolusLife::new(torus, MargolusRule::BILLIARD_BALLS.parse().unwrap());
+    margolus.step_by(8);
+    assert!(margolus.life.is_alive(2, 2));
+    assert_eq!(rlife::stats::population(&margolus.life), 1);
+}
+
+#[test]
+fn critters_run_backwards_to_where_they_started() {
+    let critters: MargolusRule = MargolusRule::CRITTERS.parse().unwrap();
+    let inverse = critters.inverse().unwrap();
+    assert_eq!(inverse.inverse(), Some(critters));
+    let soup = || {
+        let mut soup = TorusGrid::<16, 16>::default();
+        soup.fill_random(0.3, &mut rlife::random::Rng::new(7));
+        soup
+    };
+    let cells = |life: &TorusGrid<16, 16>| rlife::stats::live_cells(life).collect::<Vec<_>>();
+    let mut margolus = MargolusLife::new(soup(), critters);
+    margolus.step_by(21);
+    assert_ne!(cells(&margolus.life), cells(&soup()));
+    while margolus.step_back() {}
+    assert_eq!(margolus.generation(), 0);
+    assert_eq!(cells(&margolus.life), cells(&soup()));
+
+    let mut tron = MargolusLife::new(VecGrid::new(4, 4), MargolusRule::TRON.parse().unwrap());
+    assert!(!tron.step_back());
+    let blank: MargolusRule = "MS,D0;0;0;0;0;0;0;0;0;0;0;0;0;0;0;0".parse().unwrap();
+    assert_eq!(blank.inverse(), None);
+    tron.rule = blank;
+    tron.step();
+    assert!(!tron.step_back());
+}
+
+// step any universe to a generation
+fn run(universe: &mut impl Steppable, generations: u64) -> u64 {
+    universe.step_by(generations);
+    universe.generation()
+}
+
+#[test]
+fn every_universe_steps_alike() {
+    let mut glider = VecGrid::new(16, 16);
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        glider.set_cell(x, y, true);
+    }
+    let mut simulation = Simulation::from_life(glider.clone());
+    let mut sparse = SparseLife::from_life(&glider, 0, 0);
+    let mut hashlife = HashLifeUniverse::from_life(&glider, 0, 0);
+    assert_eq!(run(&mut simulation, 8), 8);
+    assert_eq!(run(&mut sparse, 8), 8);
+    assert_eq!(run(&mut hashlife, 8), 8);
+    assert_eq!(sparse.bounds(), Some((2, 2, 3, 3)));
+    assert_eq!(hashlife.bounds(), Some((2, 2, 3, 3)));
+    assert!(simulation.life().is_alive(4, 4));
+    let mut ants = AntWorld::new(8, 8, AntRule::default());
+    assert_eq!(run(&mut ants, 3), 3);
+    let mut margolus = MargolusLife::new(glider, MargolusRule::CRITTERS.parse().unwrap());
+    assert_eq!(run(&mut margolus, 3), 3);
+}
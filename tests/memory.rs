@@ -0,0 +1,63 @@
+// backends report the memory their boards take
+use rlife::random::Rng;
+use rlife::transform::Transform;
+use rlife::{
+    stats, BitGrid, HashLifeUniverse, History, Life, RuleLife, Simulation, SparseLife, VecGrid,
+};
+
+#[test]
+fn grids_take_memory_for_every_cell() {
+    let mut dense = VecGrid::new(256, 128);
+    dense.fill_random(0.3, &mut Rng::new(2));
+    assert!(dense.memory_usage() >= 256 * 128);
+    let mut packed = BitGrid::new(256, 128);
+    packed.blit(&dense, 0, 0, Transform::Identity);
+    assert!(packed.memory_usage() >= 256 * 128 / 8);
+    assert!(packed.memory_usage() < dense.memory_usage() / 4);
+    // wrappers add their own fields to the board they wrap
+    let ruled = RuleLife::new(dense.clone(), "B36/S23".parse().unwrap());
+    assert!(ruled.memory_usage() > dense.memory_usage());
+    let simulation = Simulation::from_life(dense.clone());
+    assert_eq!(simulation.memory_usage(), 2 * dense.memory_usage());
+}
+
+#[test]
+fn unbounded_universes_grow_with_their_patterns() {
+    let mut small = VecGrid::new(64, 64);
+    small.set_cell(1, 1, true);
+    let mut soup = VecGrid::new(64, 64);
+    soup.fill_random(0.5, &mut Rng::new(4));
+    let (empty, full) = (
+        SparseLife::from_life(&small, 0, 0),
+        SparseLife::from_life(&soup, 0, 0),
+    );
+    assert!(full.memory_usage() > empty.memory_usage());
+    let (empty, full) = (
+        HashLifeUniverse::from_life(&small, 0, 0),
+        HashLifeUniverse::from_life(&soup, 0, 0),
+    );
+    assert!(full.memory_usage() > empty.memory_usage());
+    let node_stats = full.node_stats();
+    assert_eq!(node_stats.nodes, full.node_count());
+    assert_eq!(node_stats.memory, full.memory_usage());
+    assert!(node_stats.levels >= 6);
+}
+
+#[test]
+fn histories_count_their_kept_generations() {
+    let mut soup = VecGrid::new(128, 64);
+    soup.fill_random(0.35, &mut Rng::new(9));
+    let mut history = History::from_life(soup, 16);
+    let start = history.memory_usage();
+    history.step();
+    history.step();
+    assert!(history.memory_usage() >= start + 2 * 128 * 64 / 8);
+}
+
+#[test]
+fn bytes_are_written_in_binary_units() {
+    assert_eq!(stats::format_bytes(0), "0 B");
+    assert_eq!(stats::format_bytes(1023), "1023 B");
+    assert_eq!(stats::format_bytes(1536), "1.5 KiB");
+    assert_eq!(stats::format_bytes(3 << 20), "3.0 MiB");
+}
@@ -0,0 +1,90 @@
+// neighborhoods decide which cells the generic stepping counts as neighbors
+use rlife::neighborhood::{Neighborhood, NeighborhoodLife};
+use rlife::{Life, Simulation, TorusGrid, VecGrid};
+
+fn full(width: usize, height: usize) -> VecGrid {
+    let mut grid = VecGrid::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            grid.set_cell(x, y, true);
+        }
+    }
+    grid
+}
+
+#[test]
+fn neighborhoods_have_their_sizes() {
+    assert_eq!(Neighborhood::Moore.size(), 8);
+    assert_eq!(Neighborhood::VonNeumann.size(), 4);
+    assert_eq!(Neighborhood::ExtendedMoore(2).size(), 24);
+    assert_eq!(Neighborhood::Hexagonal.size(), 6);
+    assert!(Neighborhood::VonNeumann.contains(0, -1));
+    assert!(!Neighborhood::VonNeumann.contains(1, 1));
+    assert!(!Neighborhood::Moore.contains(0, 0));
+    let grid = full(7, 7);
+    for neighborhood in [
+        Neighborhood::Moore,
+        Neighborhood::VonNeumann,
+        Neighborhood::ExtendedMoore(3),
+        Neighborhood::Hexagonal,
+    ] {
+        assert_eq!(neighborhood.count(&grid, 3, 3), neighborhood.size());
+    }
+    // cells past the edges of bounded maps are left out
+    assert_eq!(Neighborhood::VonNeumann.count(&grid, 0, 0), 2);
+    assert_eq!(Neighborhood::ExtendedMoore(2).count(&grid, 0, 0), 8);
+}
+
+#[test]
+fn names_round_trip() {
+    for neighborhood in [
+        Neighborhood::Moore,
+        Neighborhood::VonNeumann,
+        Neighborhood::ExtendedMoore(5),
+        Neighborhood::Hexagonal,
+    ] {
+        assert_eq!(
+            neighborhood.to_string().parse::<Neighborhood>(),
+            Ok(neighborhood)
+        );
+    }
+    assert_eq!("Von-Neumann".parse(), Ok(Neighborhood::VonNeumann));
+    assert_eq!("moore:1".parse(), Ok(Neighborhood::Moore));
+    for invalid in ["moore:0", "moore:x", "square", ""] {
+        assert!(invalid.parse::<Neighborhood>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn neighbors_wrap_on_a_torus() {
+    let mut grid = TorusGrid::<6, 6>::default();
+    grid.set_cell(5, 0, true);
+    grid.set_cell(0, 5, true);
+    grid.set_cell(5, 5, true);
+    assert_eq!(grid.number_of_neighbors(0, 0), 3);
+    assert_eq!(Neighborhood::VonNeumann.count(&grid, 0, 0), 2);
+}
+
+#[test]
+fn von_neumann_rules_step_through_the_generic_loop() {
+    // under B1/S with von Neumann neighbors a single cell grows into a diamond outline
+    let mut grid = VecGrid::new(9, 9);
+    grid.set_cell(4, 4, true);
+    let life = NeighborhoodLife::new(grid, Neighborhood::VonNeumann, "B1/S".parse().unwrap());
+    assert_eq!(life.neighborhood(), Neighborhood::VonNeumann);
+    let mut simulation = Simulation::from_life(life);
+    simulation.step();
+    let life = simulation.life();
+    assert_eq!(simulation.population(), 4);
+    assert!(life.is_alive(4, 3) && life.is_alive(3, 4) && !life.is_alive(3, 3));
+    // the same board under Moore neighbors grows into a square outline
+    let mut grid = VecGrid::new(9, 9);
+    grid.set_cell(4, 4, true);
+    let mut moore = Simulation::from_life(NeighborhoodLife::new(
+        grid,
+        Neighborhood::Moore,
+        "B1/S".parse().unwrap(),
+    ));
+    moore.step();
+    assert_eq!(moore.population(), 8);
+}
@@ -0,0 +1,93 @@
+// rows packed into words agree between the default methods and the backends overriding them
+use rlife::random::Rng;
+use rlife::{BitGrid, Life, LifeGrid, VecGrid};
+
+// board implementing only what Life requires, so that it packs cells with the defaults
+struct Plain(VecGrid);
+
+impl Life for Plain {
+    fn set_cell(&mut self, x: usize, y: usize, is_alive: bool) {
+        self.0.set_cell(x, y, is_alive)
+    }
+
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.0.is_alive(x, y)
+    }
+}
+
+// copy every cell of a board onto another
+fn copy(from: &impl Life, to: &mut impl Life) {
+    for y in 0..from.height() {
+        for x in 0..from.width() {
+            to.set_cell(x, y, from.is_alive(x, y));
+        }
+    }
+}
+
+// rows of a board as packed by it
+fn packed(life: &impl Life) -> Vec<Vec<u64>> {
+    life.iter_rows_packed().collect()
+}
+
+#[test]
+fn packed_rows_agree_at_any_width() {
+    for width in [1, 5, 63, 64, 65, 127, 128, 130] {
+        let mut grid = VecGrid::new(width, 3);
+        grid.fill_random(0.5, &mut Rng::new(width as u64));
+        let mut plain = Plain(VecGrid::new(width, 3));
+        copy(&grid, &mut plain);
+        let mut bits = BitGrid::new(width, 3);
+        copy(&grid, &mut bits);
+        let rows = packed(&plain);
+        assert!(rows.iter().all(|row| row.len() == width.div_ceil(64)));
+        // bits past the right edge are clear
+        if width % 64 != 0 {
+            assert!(rows.iter().all(|row| row[width / 64] >> (width % 64) == 0));
+        }
+        assert_eq!(packed(&grid), rows, "width {}", width);
+        assert_eq!(packed(&bits), rows, "width {}", width);
+    }
+    let mut fixed = LifeGrid::<130, 3>::default();
+    let mut plain = Plain(VecGrid::new(130, 3));
+    for (x, y) in [(0, 0), (63, 0), (64, 1), (127, 1), (128, 2), (129, 2)] {
+        fixed.set_cell(x, y, true);
+        plain.set_cell(x, y, true);
+    }
+    assert_eq!(packed(&fixed), packed(&plain));
+}
+
+#[test]
+fn packed_words_are_set_at_any_offset() {
+    let word = 0xdead_beef_f00d_cafe;
+    for width in [5, 64, 70, 130] {
+        for x in [0, 3, 60, 64, 66] {
+            let mut plain = Plain(VecGrid::new(width, 2));
+            let mut bits = BitGrid::new(width, 2);
+            let mut grid = VecGrid::new(width, 2);
+            plain.set_packed(x, 1, word);
+            bits.set_packed(x, 1, word);
+            grid.set_packed(x, 1, word);
+            // cells past the right edge are left out
+            for cx in 0..width {
+                let expected = cx >= x && cx < x + 64 && (word >> (cx - x)) & 1 == 1;
+                assert_eq!(plain.is_alive(cx, 1), expected, "{} at {}", cx, x);
+                assert!(!plain.is_alive(cx, 0));
+            }
+            assert_eq!(packed(&bits), packed(&plain), "width {} at {}", width, x);
+            assert_eq!(packed(&grid), packed(&plain), "width {} at {}", width, x);
+        }
+    }
+    let mut fixed = LifeGrid::<70, 2>::default();
+    let mut plain = Plain(VecGrid::new(70, 2));
+    fixed.set_packed(60, 0, word);
+    plain.set_packed(60, 0, word);
+    assert_eq!(packed(&fixed), packed(&plain));
+}
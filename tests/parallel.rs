@@ -0,0 +1,76 @@
+// parallel stepping must match the serial engine bit for bit
+use rlife::{stats, Life, LifeGrid, Simulation, VecGrid};
+
+// deterministic soup from a xorshift generator, about a third of the cells alive
+fn soup(width: usize, height: usize, seed: u64) -> VecGrid {
+    let mut state = seed.max(1);
+    let mut grid = VecGrid::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            grid.set_cell(x, y, state.is_multiple_of(3));
+        }
+    }
+    grid
+}
+
+#[test]
+fn par_next_generation_matches_serial_for_any_thread_count() {
+    // heights that divide evenly, unevenly and into fewer rows than threads
+    for &(width, height) in &[(1, 1), (7, 3), (32, 32), (50, 17), (3, 64)] {
+        let grid = soup(width, height, (width * 31 + height) as u64);
+        let mut expected = VecGrid::new(width, height);
+        grid.next_generation(&mut expected);
+        for threads in 0..=9 {
+            let mut actual = VecGrid::new(width, height);
+            grid.par_next_generation(&mut actual, threads);
+            assert_eq!(
+                actual, expected,
+                "{}x{} with {} threads",
+                width, height, threads
+            );
+        }
+    }
+}
+
+#[test]
+fn par_step_matches_step_over_many_generations() {
+    let grid = soup(64, 48, 2024);
+    let mut serial = Simulation::from_life(grid.clone());
+    let mut parallels: Vec<_> = [2, 3, 8]
+        .iter()
+        .map(|&threads| (threads, Simulation::from_life(grid.clone())))
+        .collect();
+    for _ in 0..200 {
+        serial.step();
+        for (threads, simulation) in parallels.iter_mut() {
+            simulation.par_step(*threads);
+            assert_eq!(simulation.life(), serial.life(), "{} threads", threads);
+        }
+    }
+    assert_eq!(serial.generation(), 200);
+}
+
+#[test]
+fn fixed_size_grids_step_in_parallel_like_serially() {
+    let grid = soup(50, 17, 7);
+    let fill = || {
+        let mut fixed = Box::<LifeGrid<50, 17>>::default();
+        stats::live_cells(&grid).for_each(|(x, y)| fixed.set_cell(x, y, true));
+        Simulation::new(*fixed, LifeGrid::default())
+    };
+    let mut serial = fill();
+    let mut parallels: Vec<_> = [1, 4, 17, 30]
+        .iter()
+        .map(|&threads| (threads, fill()))
+        .collect();
+    for _ in 0..100 {
+        serial.step();
+        for (threads, simulation) in parallels.iter_mut() {
+            simulation.par_step(*threads);
+            assert_eq!(**simulation.life(), **serial.life(), "{} threads", threads);
+        }
+    }
+}
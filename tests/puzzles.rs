@@ -0,0 +1,31 @@
+// hints solve the built-in puzzles within their regions and budgets
+use rlife::puzzle::{self, Puzzle};
+use rlife::Life;
+
+fn solved(puzzle: &Puzzle) -> Option<u64> {
+    let changes = puzzle.solve(puzzle::DEFAULT_BEAM)?;
+    let mut board = puzzle.grid.clone();
+    for &(x, y) in &changes {
+        board.set_cell(x, y, !board.is_alive(x, y));
+    }
+    puzzle.check(&board).unwrap()
+}
+
+#[test]
+fn built_in_puzzles_are_solved() {
+    for puzzle in puzzle::builtin() {
+        assert!(solved(&puzzle).is_some(), "{} wasn't solved", puzzle.name);
+    }
+}
+
+#[test]
+fn puzzles_without_solutions_are_given_up() {
+    // a single cell can't light a cell out of reach
+    let mut puzzle = puzzle::find_builtin("first-light").unwrap();
+    puzzle.budget = 1;
+    assert_eq!(puzzle.solve(puzzle::DEFAULT_BEAM), None);
+    // nothing needs changing when the board already meets the goal
+    let mut puzzle = puzzle::find_builtin("clean-up").unwrap();
+    puzzle.grid = rlife::VecGrid::new(12, 12);
+    assert_eq!(puzzle.solve(puzzle::DEFAULT_BEAM), Some(Vec::new()));
+}
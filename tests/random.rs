@@ -0,0 +1,34 @@
+// random soups are reproducible from their seed on every backend
+use rlife::random::Rng;
+use rlife::{stats, BitGrid, Life, TorusGrid, VecGrid};
+
+#[test]
+fn same_seed_fills_same_soup() {
+    let mut grid = VecGrid::new(100, 100);
+    grid.fill_random(0.35, &mut Rng::new(7));
+    let mut again = VecGrid::new(100, 100);
+    again.fill_random(0.35, &mut Rng::new(7));
+    assert!(grid == again);
+    let mut other = VecGrid::new(100, 100);
+    other.fill_random(0.35, &mut Rng::new(8));
+    assert!(grid != other);
+    // about the density asked for
+    let population = stats::population(&grid);
+    assert!((3000..4000).contains(&population), "{}", population);
+
+    let mut bits = BitGrid::new(100, 100);
+    bits.fill_random(0.35, &mut Rng::new(7));
+    let mut torus = TorusGrid::<100, 100>::default();
+    torus.fill_random(0.35, &mut Rng::new(7));
+    assert_eq!(stats::fingerprint(&bits), stats::fingerprint(&grid));
+    assert_eq!(stats::fingerprint(&torus), stats::fingerprint(&grid));
+}
+
+#[test]
+fn refilling_clears_the_old_soup() {
+    let mut grid = VecGrid::new(20, 20);
+    grid.fill_random(1.0, &mut Rng::new(1));
+    assert_eq!(stats::population(&grid), 400);
+    grid.fill_random(0.0, &mut Rng::new(1));
+    assert_eq!(stats::population(&grid), 0);
+}
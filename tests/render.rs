@@ -0,0 +1,147 @@
+// boards drawn into frame buffers
+use rlife::random::Rng;
+use rlife::render::{self, Palette, Viewport};
+use rlife::{Life, TorusGrid, VecGrid};
+
+const PALETTE: Palette = Palette {
+    alive: 0x00ff8800,
+    dead: 0x00102030,
+};
+
+// board with live cells on the diagonal, wider than a word
+fn diagonal(width: usize, height: usize) -> VecGrid {
+    let mut grid = VecGrid::new(width, height);
+    (0..width.min(height)).for_each(|i| grid.set_cell(i, i, true));
+    grid
+}
+
+#[test]
+fn cells_are_drawn_in_the_colors_of_the_palette() {
+    let grid = diagonal(100, 70);
+    let viewport = Viewport::whole(&grid);
+    let mut buffer = vec![0; 100 * 70];
+    render::render_to_buffer(&grid, &mut buffer, &PALETTE, viewport);
+    for (i, &pixel) in buffer.iter().enumerate() {
+        let (x, y) = (i % 100, i / 100);
+        let expected = if x == y { PALETTE.alive } else { PALETTE.dead };
+        assert_eq!(pixel, expected, "pixel {},{}", x, y);
+    }
+}
+
+#[test]
+fn viewports_draw_part_of_the_board() {
+    let grid = diagonal(100, 70);
+    // a rectangle straddling the second word of every row, running past the bottom edge
+    let viewport = Viewport {
+        x: 60,
+        y: 60,
+        width: 30,
+        height: 20,
+    };
+    let mut buffer = vec![0; 30 * 20];
+    render::render_to_buffer(&grid, &mut buffer, &PALETTE, viewport);
+    for (i, &pixel) in buffer.iter().enumerate() {
+        let (x, y) = (60 + i % 30, 60 + i / 30);
+        let expected = if x == y && y < 70 {
+            PALETTE.alive
+        } else {
+            PALETTE.dead
+        };
+        assert_eq!(pixel, expected, "cell {},{}", x, y);
+    }
+    // cells past the edges of boards that wrap come from the opposite edge
+    let mut torus = TorusGrid::<8, 8>::default();
+    torus.set_cell(0, 0, true);
+    let mut buffer = vec![0; 4];
+    let viewport = Viewport {
+        x: 7,
+        y: 7,
+        width: 2,
+        height: 2,
+    };
+    render::render_to_buffer(&torus, &mut buffer, &PALETTE, viewport);
+    assert_eq!(
+        buffer,
+        [PALETTE.dead, PALETTE.dead, PALETTE.dead, PALETTE.alive]
+    );
+}
+
+#[test]
+#[should_panic(expected = "buffer must match the viewport")]
+fn buffers_must_match_the_viewport() {
+    let grid = diagonal(10, 10);
+    let mut buffer = vec![0; 99];
+    render::render_to_buffer(&grid, &mut buffer, &PALETTE, Viewport::whole(&grid));
+}
+
+#[test]
+fn only_changed_cells_are_redrawn() {
+    let previous = diagonal(100, 70);
+    let mut current = previous.clone();
+    current.set_cell(5, 5, false);
+    current.set_cell(70, 3, true);
+    current.set_cell(99, 69, true);
+    // pixels not written keep a color no cell is drawn in
+    let untouched = 0x00abcdef;
+    let mut buffer = vec![untouched; 100 * 70];
+    let viewport = Viewport::whole(&current);
+    render::render_changes(&previous, &current, &mut buffer, &PALETTE, viewport);
+    for (i, &pixel) in buffer.iter().enumerate() {
+        let expected = match (i % 100, i / 100) {
+            (5, 5) => PALETTE.dead,
+            (70, 3) | (99, 69) => PALETTE.alive,
+            _ => untouched,
+        };
+        assert_eq!(pixel, expected, "pixel {},{}", i % 100, i / 100);
+    }
+}
+
+#[test]
+fn drawing_changes_gives_the_whole_frame() {
+    let mut previous = VecGrid::new(150, 40);
+    previous.fill_random(0.3, &mut Rng::new(5));
+    let mut current = VecGrid::new(150, 40);
+    previous.next_generation(&mut current);
+    let viewport = Viewport {
+        x: 10,
+        y: 5,
+        width: 130,
+        height: 30,
+    };
+    let mut buffer = vec![0; 130 * 30];
+    render::render_to_buffer(&previous, &mut buffer, &PALETTE, viewport);
+    render::render_changes(&previous, &current, &mut buffer, &PALETTE, viewport);
+    let mut whole = vec![0; 130 * 30];
+    render::render_to_buffer(&current, &mut whole, &PALETTE, viewport);
+    assert_eq!(buffer, whole);
+}
+
+#[test]
+fn zoomed_viewports_stay_on_the_board() {
+    let view = Viewport::zoomed(64, 36, 2, (60, 1));
+    assert_eq!((view.x, view.y, view.width, view.height), (32, 0, 32, 18));
+    assert_eq!(view.zoom(64), 2);
+    assert_eq!(view.cell((63, 35), 64, 36), (63, 17));
+    assert_eq!(view.cell((0, 2), 64, 36), (32, 1));
+    let view = view.moved(-40, 3, 64, 36);
+    assert_eq!((view.x, view.y), (0, 3));
+    assert_eq!(view.moved(100, 100, 64, 36).center(), (48, 27));
+    let whole = Viewport::zoomed(64, 36, 1, (10, 10));
+    assert_eq!(whole, Viewport::whole(&VecGrid::new(64, 36)));
+}
+
+#[test]
+fn zoomed_frames_draw_cells_as_squares() {
+    let grid = diagonal(8, 6);
+    let mut frame = vec![0; 8 * 6];
+    render::render_to_buffer(&grid, &mut frame, &PALETTE, Viewport::whole(&grid));
+    let view = Viewport::zoomed(8, 6, 2, (3, 3));
+    let mut zoomed = vec![0; 8 * 6];
+    render::zoom_frame(&frame, 8, view, &mut zoomed);
+    for (i, &pixel) in zoomed.iter().enumerate() {
+        let (x, y) = view.cell((i % 8, i / 8), 8, 6);
+        assert_eq!(pixel, frame[y * 8 + x], "pixel {},{}", i % 8, i / 8);
+        let expected = if x == y { PALETTE.alive } else { PALETTE.dead };
+        assert_eq!(pixel, expected, "pixel {},{}", i % 8, i / 8);
+    }
+}
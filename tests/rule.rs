@@ -0,0 +1,57 @@
+// rules in B/S notation read and written back
+use rlife::rule::Rule;
+
+// rule from the neighbor counts of birth and survival
+fn rule(birth: &[u16], survival: &[u16]) -> Rule {
+    let mask = |counts: &[u16]| counts.iter().fold(0, |mask, n| mask | 1 << n);
+    Rule::new(mask(birth), mask(survival))
+}
+
+#[test]
+fn rules_are_read_in_any_case_with_either_part_first() {
+    assert_eq!("B3/S23".parse(), Ok(Rule::default()));
+    assert_eq!("b36/s23".parse(), Ok(rule(&[3, 6], &[2, 3])));
+    assert_eq!(" S23/B36 ".parse(), Ok(rule(&[3, 6], &[2, 3])));
+    assert_eq!(
+        "B3678/S34678".parse(),
+        Ok(rule(&[3, 6, 7, 8], &[3, 4, 6, 7, 8]))
+    );
+    // either part may be left empty or out
+    assert_eq!("B2/S".parse(), Ok(rule(&[2], &[])));
+    assert_eq!("b2".parse(), Ok(rule(&[2], &[])));
+    assert_eq!(
+        "S012345678".parse(),
+        Ok(rule(&[], &[0, 1, 2, 3, 4, 5, 6, 7, 8]))
+    );
+}
+
+#[test]
+fn malformed_rules_are_refused() {
+    // survival/birth notation without letters isn't accepted
+    for invalid in [
+        "23/3",
+        "",
+        "B3/S23/B3",
+        "B3/B6",
+        "B9/S23",
+        "B3/S2x",
+        "X3/S23",
+        "B3,S23",
+    ] {
+        let err = invalid.parse::<Rule>().unwrap_err();
+        assert!(err.to_string().contains(invalid), "{}", err);
+    }
+}
+
+#[test]
+fn rules_are_written_in_b_s_notation() {
+    assert_eq!(Rule::default().to_string(), "B3/S23");
+    assert_eq!(rule(&[2], &[]).to_string(), "B2/S");
+    assert_eq!("s32/B63".parse::<Rule>().unwrap().to_string(), "B36/S23");
+    // every rule reads back as itself
+    for index in (0..Rule::COUNT).step_by(97) {
+        let rule = Rule::from_index(index);
+        assert_eq!(rule.to_string().parse(), Ok(rule));
+        assert_eq!(rule.index(), index);
+    }
+}
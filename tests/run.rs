@@ -0,0 +1,343 @@
+// headless runs of the rlife binary
+use rlife::formats::{self, Pattern};
+use rlife::{stats, Life, RuleLife, VecGrid};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// directory of its own for the files of a test
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rlife-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// run the binary with arguments, failing the test when it fails
+fn rlife(args: &[&str]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_rlife"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "rlife {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// run the binary with arguments expected to be refused, giving the error it reports
+fn refused(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rlife"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(!output.status.success(), "rlife {:?} succeeded", args);
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn runs_step_under_the_rule_of_the_pattern() {
+    let dir = scratch("rule");
+    let (input, output) = (dir.join("cell.rle"), dir.join("out.rle"));
+    let mut cell = VecGrid::new(1, 1);
+    cell.set_cell(0, 0, true);
+    let pattern = Pattern {
+        grid: cell,
+        rule: Some("B1/S012345678".to_string()),
+    };
+    formats::save(&input, &pattern).unwrap();
+    rlife(&[
+        "run",
+        input.to_str().unwrap(),
+        "--size",
+        "32x32",
+        "--generations",
+        "4",
+        "--threads",
+        "3",
+        "--verify",
+        "--paranoid",
+        "--quiet",
+        "--output",
+        output.to_str().unwrap(),
+    ]);
+
+    let mut expected = VecGrid::new(32, 32);
+    expected.set_cell(16, 16, true);
+    let mut expected = RuleLife::new(expected, "B1/S012345678".parse().unwrap());
+    for _ in 0..4 {
+        let mut next = expected.clone();
+        expected.next_generation(&mut next);
+        expected = next;
+    }
+    let result = formats::load(&output).unwrap();
+    // a lone cell dies under Conway's rule but grows under this one
+    assert!(stats::population(&result.grid) > 1);
+    assert_eq!(
+        stats::population(&result.grid),
+        stats::population(&expected)
+    );
+    assert_eq!(result.rule.as_deref(), Some("B1/S012345678"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn manifests_repeat_their_run() {
+    let dir = scratch("manifest");
+    let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+    rlife(&[
+        "run",
+        "--seed",
+        "42",
+        "--density",
+        "0.3",
+        "--size",
+        "40x30",
+        "--generations",
+        "25",
+        "--threads",
+        "2",
+        "--zone",
+        "0,0,20,30,B36/S23",
+        "--quiet",
+        "--output",
+        &path("first.rle"),
+    ]);
+    let manifest = std::fs::read_to_string(path("first.rle.manifest")).unwrap();
+    for line in [
+        "seed = 42",
+        "density = 0.3",
+        "size = 40x30",
+        "generations = 25",
+        "zone = 0,0,20,30,B36/S23",
+    ] {
+        assert!(manifest.lines().any(|l| l == line), "{}", manifest);
+    }
+
+    // the manifest written by the repeat reads back as the one it was made from
+    rlife(&[
+        "run",
+        "--from-manifest",
+        &path("first.rle.manifest"),
+        "--quiet",
+        "--output",
+        &path("second.rle"),
+    ]);
+    assert_eq!(
+        std::fs::read_to_string(path("second.rle.manifest")).unwrap(),
+        manifest
+    );
+    let (first, second) = (
+        formats::load(path("first.rle")).unwrap(),
+        formats::load(path("second.rle")).unwrap(),
+    );
+    assert_eq!(first.grid, second.grid);
+    assert!(stats::population(&first.grid) > 0);
+
+    // a different seed gives a different soup
+    rlife(&[
+        "run",
+        "--from-manifest",
+        &path("first.rle.manifest"),
+        "--seed",
+        "1042",
+        "--quiet",
+        "--output",
+        &path("third.rle"),
+    ]);
+    assert_ne!(formats::load(path("third.rle")).unwrap().grid, first.grid);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn runs_answer_commands_from_stdin() {
+    let dir = scratch("control");
+    let (input, output) = (dir.join("block.rle"), dir.join("out.rle"));
+    let mut block = VecGrid::new(8, 8);
+    for (x, y) in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+        block.set_cell(x, y, true);
+    }
+    let pattern = Pattern {
+        grid: block,
+        rule: None,
+    };
+    formats::save(&input, &pattern).unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rlife"))
+        .args(["run", input.to_str().unwrap(), "--stdin", "--quiet"])
+        .args([
+            "--generations",
+            "1000000000",
+            "--output",
+            output.to_str().unwrap(),
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let commands = "pause\nstatus\nbogus\nstep x\nset 1\nset a b\nset 9 9\nset 6 6\n\
+                    status\nquit now\nquit\n";
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(commands.as_bytes())
+        .unwrap();
+    let result = child.wait_with_output().unwrap();
+    assert!(result.status.success());
+    let stderr = String::from_utf8(result.stderr).unwrap();
+    let replied = |reply: &str| stderr.lines().any(|line| line.contains(reply));
+    // the block stays put while paused and the cell set joins it
+    assert!(replied(": population 4, paused"), "{}", stderr);
+    assert!(replied(": population 5, paused"), "{}", stderr);
+    assert!(replied("cell 6,6 set by client 0"), "{}", stderr);
+    assert!(replied("quitting at generation"), "{}", stderr);
+    // unknown commands and malformed arguments are reported without stopping the run
+    assert!(replied("unknown command 'bogus'"), "{}", stderr);
+    assert!(replied("invalid generation count 'x'"), "{}", stderr);
+    assert!(replied("unknown command 'set 1'"), "{}", stderr);
+    assert!(replied("invalid cell 'a b'"), "{}", stderr);
+    assert!(replied("cell 9,9 is outside of the board"), "{}", stderr);
+    assert!(replied("unexpected 'now' after command"), "{}", stderr);
+    let saved = formats::load(&output).unwrap();
+    assert_eq!(stats::population(&saved.grid), 5);
+    assert!(saved.grid.is_alive(6, 6));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn verified_runs_refuse_emitters() {
+    let dir = scratch("verify-emit");
+    let glider = dir.join("g.rle");
+    let mut grid = VecGrid::new(3, 3);
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        grid.set_cell(x, y, true);
+    }
+    let pattern = Pattern { grid, rule: None };
+    formats::save(&glider, &pattern).unwrap();
+    let emit = format!("5,5,10,{}", glider.display());
+    let error = refused(&[
+        "run", "--size", "32x32", "--verify", "--emit", &emit, "--quiet",
+    ]);
+    assert!(
+        error.contains("--verify can't be combined with --emit"),
+        "{}",
+        error
+    );
+    // the same run goes ahead without --verify
+    rlife(&[
+        "run",
+        "--size",
+        "32x32",
+        "--generations",
+        "20",
+        "--emit",
+        &emit,
+        "--quiet",
+    ]);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn verified_runs_refuse_obstacles() {
+    let dir = scratch("verify-walls");
+    let walls = dir.join("walls.txt");
+    std::fs::write(&walls, "rect 0 0 32 2\n").unwrap();
+    let walls = walls.to_str().unwrap();
+    let args = [
+        "run",
+        "--size",
+        "32x32",
+        "--seed",
+        "3",
+        "--obstacles",
+        walls,
+        "--quiet",
+    ];
+    let error = refused(&[&args[..], &["--verify"]].concat());
+    assert!(
+        error.contains("--verify can't be combined with --obstacles"),
+        "{}",
+        error
+    );
+    rlife(&[&args[..], &["--generations", "5"]].concat());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn info_describes_patterns_under_their_rule() {
+    let dir = scratch("info");
+    let path = dir.join("blinker.rle");
+    let mut grid = VecGrid::new(3, 1);
+    (0..3).for_each(|x| grid.set_cell(x, 0, true));
+    let pattern = Pattern {
+        grid,
+        rule: Some("B3/S012345678".to_string()),
+    };
+    formats::save(&path, &pattern).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rlife"))
+        .args(["info", path.to_str().unwrap(), "--max-period", "8"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // none of the cells die, where two would under Conway's rule, and it's no oscillator
+    assert!(stdout.contains("rule: B3/S012345678"), "{}", stdout);
+    assert!(
+        stdout.contains("next generation: 0 births, 0 deaths"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("behavior: unknown"), "{}", stdout);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn batches_classify_patterns_under_their_rule() {
+    let dir = scratch("batch");
+    let patterns = dir.join("patterns");
+    std::fs::create_dir_all(&patterns).unwrap();
+    let mut grid = VecGrid::new(3, 1);
+    (0..3).for_each(|x| grid.set_cell(x, 0, true));
+    for (name, rule) in [
+        ("conway.rle", None),
+        ("immortal.rle", Some("B3/S012345678")),
+    ] {
+        let pattern = Pattern {
+            grid: grid.clone(),
+            rule: rule.map(str::to_string),
+        };
+        formats::save(patterns.join(name), &pattern).unwrap();
+    }
+    let patterns = patterns.to_str().unwrap();
+    let output = dir.join("out.csv");
+    let output = output.to_str().unwrap();
+    rlife(&["batch", patterns, "--op", "info", "--output", output]);
+    let info = std::fs::read_to_string(output).unwrap();
+    let row = |name: &str| {
+        info.lines()
+            .find(|line| line.contains(name))
+            .unwrap()
+            .to_string()
+    };
+    assert!(
+        row("conway.rle").contains("oscillator with period 2"),
+        "{}",
+        info
+    );
+    assert!(
+        row("immortal.rle").contains("B3/S012345678,unknown"),
+        "{}",
+        info
+    );
+    rlife(&["batch", patterns, "--op", "census", "--output", output]);
+    let census = std::fs::read_to_string(output).unwrap();
+    assert!(
+        census.lines().any(|line| line == "oscillator p2,1"),
+        "{}",
+        census
+    );
+    assert!(census.lines().any(|line| line == "unknown,1"), "{}", census);
+    std::fs::remove_dir_all(dir).unwrap();
+}
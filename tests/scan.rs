@@ -0,0 +1,41 @@
+// scans cover every point of a grid of one or two parameters
+use rlife::scan::{self, Axis, Parameter, Settings};
+
+#[test]
+fn axes_parse_ranges_and_lists() {
+    let density: Axis = "density=0.1:0.5:0.1".parse().unwrap();
+    assert_eq!(density.parameter, Parameter::Density);
+    assert_eq!(density.values, ["0.1", "0.2", "0.3", "0.4", "0.5"]);
+    let rules: Axis = "rule=B3/S23,B36/S23".parse().unwrap();
+    assert_eq!(rules.values, ["B3/S23", "B36/S23"]);
+    let sizes: Axis = "size=16:48:16".parse().unwrap();
+    assert_eq!(sizes.values, ["16", "32", "48"]);
+    for invalid in [
+        "density",
+        "speed=1,2",
+        "density=0.5:0.1:0.1",
+        "density=0:1:0",
+        "density=1.5",
+        "rule=B3/S23,nope",
+        "size=0x4",
+    ] {
+        assert!(invalid.parse::<Axis>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn points_vary_the_first_axis_slowest() {
+    let axes: Vec<Axis> = ["size=8,16x4", "rule=B3/S23,B2/S"]
+        .iter()
+        .map(|axis| axis.parse().unwrap())
+        .collect();
+    let points = scan::points(&axes, Settings::default());
+    let values: Vec<String> = points.iter().map(|(values, _)| values.join(" ")).collect();
+    assert_eq!(values, ["8 B3/S23", "8 B2/S", "16x4 B3/S23", "16x4 B2/S"]);
+    let (_, settings) = points[3];
+    assert_eq!((settings.width, settings.height), (16, 4));
+    assert_eq!(settings.rule, "B2/S".parse().unwrap());
+    // parameters not varied keep their base value
+    assert_eq!(settings.density, Settings::default().density);
+    assert_eq!(points[0].1.width, 8);
+}
@@ -0,0 +1,106 @@
+// searches for oscillators and ships find the known small ones and nothing else
+use rlife::analysis;
+use rlife::formats::rle;
+use rlife::search::{self, Search};
+use rlife::{Rule, VecGrid};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+fn distinct(search: &Search, prefix: &[bool]) -> BTreeSet<VecGrid> {
+    let mut found = BTreeSet::new();
+    search.run(prefix, |pattern| {
+        found.insert(search::representative(&pattern, search.rule, search.period));
+    });
+    found
+}
+
+fn pattern(text: &str) -> VecGrid {
+    analysis::canonical(&rle::parse(text).unwrap().grid)
+}
+
+#[test]
+fn blinker_is_the_only_period_two_oscillator_of_three_by_three() {
+    let found = distinct(&Search::new(3, 3, 2), &[]);
+    assert_eq!(
+        found.into_iter().collect::<Vec<_>>(),
+        [pattern("x = 3, y = 1\n3o!")]
+    );
+    // nothing returns after exactly three generations in a box that small
+    assert!(distinct(&Search::new(4, 4, 3), &[]).is_empty());
+}
+
+#[test]
+fn still_lifes_match_the_enumeration() {
+    let found = distinct(&Search::new(4, 4, 1), &[]);
+    let enumerated: BTreeSet<_> = analysis::enumerate(4, 4)
+        .into_iter()
+        .filter(|pattern| {
+            analysis::classify(pattern, Rule::default(), 8) == analysis::Behavior::StillLife
+        })
+        .collect();
+    assert_eq!(found, enumerated);
+}
+
+#[test]
+fn glider_is_found_moving_diagonally() {
+    let search = Search {
+        dx: 1,
+        dy: 1,
+        ..Search::new(4, 4, 4)
+    };
+    let found = distinct(&search, &[]);
+    assert_eq!(found.len(), 1);
+    let glider = pattern("x = 3, y = 3\nbo$2bo$3o!");
+    assert_eq!(
+        search::representative(&glider, Rule::default(), 4),
+        *found.first().unwrap()
+    );
+}
+
+#[test]
+fn subtrees_cover_the_whole_search() {
+    let search = Search::new(4, 4, 2);
+    let whole = distinct(&search, &[]);
+    assert_eq!(whole.len(), 4);
+    for depth in [1, 3, 12] {
+        let mut split = BTreeSet::new();
+        let mut count = 0;
+        for prefix in Search::prefixes(depth) {
+            let mut found = 0;
+            search.run(&prefix, |_| found += 1);
+            count += found;
+            split.append(&mut distinct(&search, &prefix));
+        }
+        // every solution lies below exactly one prefix
+        let mut total = 0;
+        search.run(&[], |_| total += 1);
+        assert_eq!(count, total, "depth {}", depth);
+        assert_eq!(split, whole);
+    }
+
+    let parallel = Mutex::new(BTreeSet::new());
+    let prefixes = Search::prefixes(4);
+    search.par_run(&prefixes, 3, |_, patterns| {
+        let mut parallel = parallel.lock().unwrap();
+        for pattern in patterns {
+            parallel.insert(search::representative(&pattern, search.rule, 2));
+        }
+    });
+    assert_eq!(parallel.into_inner().unwrap(), whole);
+}
+
+#[test]
+fn other_rules_are_searched() {
+    // under HighLife the three-cell line still blinks
+    let search = Search {
+        rule: "B36/S23".parse().unwrap(),
+        ..Search::new(3, 3, 2)
+    };
+    assert_eq!(distinct(&search, &[]).len(), 1);
+    // no cell survives, so nothing is still
+    let search = Search {
+        rule: "B3/S".parse().unwrap(),
+        ..Search::new(3, 3, 1)
+    };
+    assert!(distinct(&search, &[]).is_empty());
+}
@@ -0,0 +1,179 @@
+// boards and rules kept in JSON come back as they were
+use rlife::report::json_string;
+use rlife::{Life, LifeGrid, Rule, VecGrid};
+use serde::de::value::{Error, StrDeserializer};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::ser::{Impossible, Serialize, Serializer};
+
+// JSON of a value serialized as a string, the only kind boards and rules serialize as
+struct Json;
+
+macro_rules! not_a_string {
+    ($($method:ident($($ty:ty),*)),* $(,)?) => {
+        $(fn $method(self, $(_: $ty),*) -> Result<String, Error> {
+            Err(Error::custom(concat!("unexpected ", stringify!($method))))
+        })*
+    };
+}
+
+impl Serializer for Json {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(json_string(v))
+    }
+
+    not_a_string! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<String, Error> {
+        Err(Error::custom("unexpected option"))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, Error> {
+        Err(Error::custom("unexpected newtype"))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, Error> {
+        Err(Error::custom("unexpected variant"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Error::custom("unexpected sequence"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error::custom("unexpected tuple"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error::custom("unexpected tuple struct"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::custom("unexpected variant"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::custom("unexpected map"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        Err(Error::custom("unexpected struct"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::custom("unexpected variant"))
+    }
+}
+
+// value of a JSON string literal
+fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, Error> {
+    let quoted = json
+        .strip_prefix('"')
+        .and_then(|json| json.strip_suffix('"'))
+        .ok_or_else(|| Error::custom("expected a string"))?;
+    let mut s = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        s.push(match c {
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some(c @ ('"' | '\\' | '/')) => c,
+                _ => return Err(Error::custom("invalid escape")),
+            },
+            c => c,
+        });
+    }
+    T::deserialize(StrDeserializer::<Error>::new(&s))
+}
+
+#[test]
+fn grids_serialize_as_compact_strings() {
+    let mut grid = VecGrid::new(3, 3);
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        grid.set_cell(x, y, true);
+    }
+    let json = grid.serialize(Json).unwrap();
+    assert_eq!(json, "\"3x3:4gE\"");
+    assert_eq!(from_json::<VecGrid>(&json).unwrap(), grid);
+    assert!(from_json::<VecGrid>("\"3x3:4g\"").is_err());
+
+    let mut life = LifeGrid::<20, 10>::default();
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2), (19, 9)] {
+        life.set_cell(x, y, true);
+    }
+    let json = life.serialize(Json).unwrap();
+    let back: LifeGrid<20, 10> = from_json(&json).unwrap();
+    assert_eq!(back.as_slice(), life.as_slice());
+    // a board of another size doesn't fit
+    assert!(from_json::<LifeGrid<10, 20>>(&json).is_err());
+}
+
+#[test]
+fn rules_serialize_in_bs_notation() {
+    for name in ["B3/S23", "B36/S23", "B3678/S34678", "B2/S"] {
+        let rule: Rule = name.parse().unwrap();
+        let json = rule.serialize(Json).unwrap();
+        assert_eq!(json, format!("\"{}\"", rule));
+        assert_eq!(from_json::<Rule>(&json).unwrap(), rule);
+    }
+    assert_eq!(
+        from_json::<Rule>("\"b36/s23\"").unwrap().to_string(),
+        "B36/S23"
+    );
+    assert!(from_json::<Rule>("\"23/3\"").is_err());
+}
@@ -0,0 +1,52 @@
+// rows stepped with vector instructions come out as when stepped a cell at a time
+use rlife::random::Rng;
+use rlife::rule::Rule;
+use rlife::{simd, stats, Life, LifeGrid, VecGrid};
+
+#[test]
+fn rows_of_every_width_match_the_rule() {
+    let mut rng = Rng::new(7);
+    let rule: Rule = "B36/S23".parse().unwrap();
+    // widths around the 16 cells done at once, with the cells at either end
+    for width in 0..60 {
+        let mut random_row = || -> Vec<bool> { (0..width).map(|_| rng.next_f64() < 0.4).collect() };
+        let (above, row, below) = (random_row(), random_row(), random_row());
+        let mut next = vec![false; width];
+        simd::next_row(rule, &above, &row, &below, &mut next);
+        for x in 0..width {
+            let neighbors = (x.saturating_sub(1)..(x + 2).min(width))
+                .map(|nx| above[nx] as usize + below[nx] as usize + (nx != x && row[nx]) as usize)
+                .sum();
+            assert_eq!(
+                next[x],
+                rule.next_state(row[x], neighbors),
+                "width {} x {}",
+                width,
+                x
+            );
+        }
+    }
+}
+
+#[test]
+fn grids_step_as_dense_boards_do() {
+    let mut grid = Box::new(LifeGrid::<83, 41>::default());
+    grid.fill_random(0.35, &mut Rng::new(3));
+    let mut board = VecGrid::new(83, 41);
+    stats::live_cells(grid.as_ref()).for_each(|(x, y)| board.set_cell(x, y, true));
+    let (mut next_grid, mut next_board) = (Box::new(LifeGrid::default()), board.clone());
+    for _ in 0..20 {
+        grid.simd_next_generation(&mut next_grid);
+        board.next_generation(&mut next_board);
+        std::mem::swap(&mut grid, &mut next_grid);
+        std::mem::swap(&mut board, &mut next_board);
+        let cells: Vec<_> = stats::live_cells(grid.as_ref()).collect();
+        assert_eq!(cells, stats::live_cells(&board).collect::<Vec<_>>());
+        // stepping through the Life trait goes the same way
+        let mut stepped = VecGrid::new(83, 41);
+        grid.next_generation(&mut stepped);
+        let mut expected = VecGrid::new(83, 41);
+        board.next_generation(&mut expected);
+        assert_eq!(stepped, expected);
+    }
+}
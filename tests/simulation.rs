@@ -0,0 +1,49 @@
+// simulations count generations, live cells, births and deaths as they go
+use rlife::formats::rle;
+use rlife::{stats, Life, Simulation, VecGrid};
+
+fn board(text: &str, width: usize, height: usize) -> VecGrid {
+    let pattern = rle::parse(text).unwrap().grid;
+    let mut grid = VecGrid::new(width, height);
+    stats::live_cells(&pattern).for_each(|(x, y)| grid.set_cell(x + 1, y + 1, true));
+    grid
+}
+
+#[test]
+fn blinker_turns_two_cells_over_every_step() {
+    let mut simulation = Simulation::from_life(board("x = 3, y = 1\n3o!", 5, 5));
+    assert_eq!(simulation.generation(), 0);
+    assert_eq!(simulation.population(), 3);
+    assert_eq!((simulation.births(), simulation.deaths()), (0, 0));
+    for generation in 1..=4 {
+        simulation.step();
+        assert_eq!(simulation.generation(), generation);
+        assert_eq!(simulation.population(), 3);
+        assert_eq!((simulation.births(), simulation.deaths()), (2, 2));
+    }
+}
+
+#[test]
+fn births_and_deaths_account_for_the_change_in_population() {
+    // the R-pentomino on a board wider than 64 cells, so rows span several words
+    let mut simulation = Simulation::from_life(board("x = 3, y = 3\nb2o$2o$bo!", 100, 40));
+    for _ in 0..80 {
+        let before = simulation.population();
+        simulation.step();
+        let previous = simulation.previous().unwrap();
+        let born = stats::live_cells(simulation.life())
+            .filter(|&(x, y)| !previous.is_alive(x, y))
+            .count();
+        assert_eq!(simulation.births(), born);
+        assert_eq!(
+            simulation.population(),
+            before + simulation.births() - simulation.deaths()
+        );
+    }
+    // edits after a step count as changes of that step
+    simulation.life_mut().set_cell(0, 0, true);
+    simulation.life_mut().set_cell(99, 39, true);
+    let births = simulation.births();
+    simulation.life_mut().set_cell(0, 0, false);
+    assert_eq!(simulation.births(), births - 1);
+}
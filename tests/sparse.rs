@@ -0,0 +1,76 @@
+// sparse universes step live cells on an unbounded plane
+use rlife::{Life, RuleLife, SparseLife, VecGrid};
+
+// glider heading down and right with its top left corner at (x, y)
+fn glider(x: i64, y: i64) -> Vec<(i64, i64)> {
+    let mut cells: Vec<_> = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+        .map(|(cx, cy)| (x + cx, y + cy))
+        .to_vec();
+    cells.sort_unstable();
+    cells
+}
+
+// live cells of a universe in a fixed order
+fn cells(sparse: &SparseLife) -> Vec<(i64, i64)> {
+    let mut cells: Vec<_> = sparse.cells().collect();
+    cells.sort_unstable();
+    cells
+}
+
+#[test]
+fn gliders_travel_past_the_origin() {
+    let mut sparse = SparseLife::new();
+    glider(-10, -10)
+        .into_iter()
+        .for_each(|(x, y)| sparse.set_cell(x, y, true));
+    sparse.step_by(4);
+    assert_eq!(cells(&sparse), glider(-9, -9));
+    sparse.step_by(80);
+    assert_eq!(sparse.generation(), 84);
+    assert_eq!(cells(&sparse), glider(11, 11));
+    assert_eq!(sparse.bounds(), Some((11, 11, 3, 3)));
+}
+
+#[test]
+fn lonely_cells_survive_only_under_s0() {
+    let mut lonely = SparseLife::with_rule("B3/S0".parse().unwrap());
+    lonely.set_cell(5, -5, true);
+    lonely.set_cell(-40, 7, true);
+    lonely.step_by(3);
+    assert_eq!(cells(&lonely), vec![(-40, 7), (5, -5)]);
+    let mut conway = SparseLife::new();
+    conway.set_cell(5, -5, true);
+    conway.step();
+    assert_eq!(conway.population(), 0);
+}
+
+#[test]
+fn recentering_reports_the_offset_added_to_every_cell() {
+    let mut sparse = SparseLife::new();
+    for (x, y) in [(100, 20), (103, 20), (100, 26)] {
+        sparse.set_cell(x, y, true);
+    }
+    // bounds of 4 by 7 cells from (100, 20), their middle at (102, 23)
+    assert_eq!(sparse.recenter(), (-102, -23));
+    assert_eq!(cells(&sparse), vec![(-2, -3), (-2, 3), (1, -3)]);
+    assert_eq!(sparse.recenter(), (0, 0));
+}
+
+#[test]
+fn universes_follow_the_rule_of_the_board_they_come_from() {
+    let mut grid = VecGrid::new(2, 1);
+    grid.set_cell(0, 0, true);
+    let seeds = RuleLife::new(grid, "B2/S".parse().unwrap());
+    let mut sparse = SparseLife::from_life(&seeds, 0, 0);
+    assert_eq!(sparse.rule(), "B2/S".parse().unwrap());
+    sparse.set_cell(1, 0, true);
+    sparse.step();
+    assert_eq!(cells(&sparse), vec![(0, -1), (0, 1), (1, -1), (1, 1)]);
+}
+
+#[test]
+#[should_panic(expected = "B0 rules")]
+fn b0_rules_are_refused() {
+    let grid = RuleLife::new(VecGrid::new(2, 2), "B01/S23".parse().unwrap());
+    SparseLife::from_life(&grid, 0, 0);
+}
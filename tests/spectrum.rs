@@ -0,0 +1,60 @@
+// power spectra of boards peak at the scales their structures repeat at
+use rlife::spectrum::Spectrum;
+use rlife::{Life, VecGrid};
+
+// columns of live cells, 4 wide, every period cells
+fn stripes(width: usize, height: usize, period: usize) -> VecGrid {
+    let mut grid = VecGrid::new(width, height);
+    for y in 0..height {
+        (0..width)
+            .filter(|x| x % period < 4)
+            .for_each(|x| grid.set_cell(x, y, true));
+    }
+    grid
+}
+
+#[test]
+fn stripes_peak_at_their_wavenumber() {
+    let spectrum = Spectrum::new(&stripes(64, 64, 8), 1);
+    // the mean is taken out, so nothing is left at frequency 0
+    assert!(spectrum.power(0, 0) < 1e-9);
+    assert!(spectrum.power(8, 0) > 1.0);
+    assert!((spectrum.power(8, 0) - spectrum.power(-8, 0)).abs() < 1e-6);
+    assert!(spectrum.power(0, 8) < 1e-9);
+    let radial = spectrum.radial();
+    assert_eq!(radial.len(), 33);
+    let peak = (0..radial.len())
+        .max_by(|&a, &b| radial[a].total_cmp(&radial[b]))
+        .unwrap();
+    assert_eq!(peak, 8);
+    assert_eq!(spectrum.wavelength(peak), 8.0);
+}
+
+#[test]
+fn sizes_other_than_powers_of_two_transform_alike() {
+    // 8 periods of 6 cells, through the plain DFT, and in blocks of 2
+    let spectrum = Spectrum::new(&stripes(48, 48, 6), 1);
+    let radial = spectrum.radial();
+    let peak = (0..radial.len())
+        .max_by(|&a, &b| radial[a].total_cmp(&radial[b]))
+        .unwrap();
+    assert_eq!(peak, 8);
+    let blocks = Spectrum::new(&stripes(128, 32, 16), 2);
+    assert_eq!((blocks.width, blocks.height, blocks.block), (64, 16, 2));
+    assert_eq!(blocks.wavelength(2), 16.0);
+    assert!(blocks.power(8, 0) > blocks.power(4, 0));
+}
+
+#[test]
+fn levels_center_frequency_zero() {
+    let spectrum = Spectrum::new(&stripes(16, 16, 8), 1);
+    let levels = spectrum.levels();
+    assert_eq!(levels.len(), 256);
+    // strongest 2 cycles either side of the middle, nothing at frequency 0 in the middle
+    assert!(levels[8 * 16 + 10] >= 254 && levels[8 * 16 + 6] >= 254);
+    assert_eq!(levels[8 * 16 + 8], 0);
+    assert!(Spectrum::new(&VecGrid::new(16, 16), 1)
+        .levels()
+        .iter()
+        .all(|&level| level == 0));
+}
@@ -0,0 +1,56 @@
+// patterns are stamped at an offset and orientation, clipped or wrapped at the edges
+use rlife::formats::rle;
+use rlife::prelude::*;
+use rlife::stats;
+
+fn glider() -> VecGrid {
+    rle::parse("x = 3, y = 3\nbo$2bo$3o!").unwrap().grid
+}
+
+fn cells(life: &impl Life) -> Vec<(usize, usize)> {
+    stats::live_cells(life).collect()
+}
+
+#[test]
+fn stamps_transformed_patterns_at_an_offset() {
+    let mut grid = VecGrid::new(8, 8);
+    grid.blit(&glider(), 2, 3, Transform::Identity);
+    assert_eq!(cells(&grid), [(3, 3), (4, 4), (2, 5), (3, 5), (4, 5)]);
+
+    // a glider heading down and right turned a quarter heads down and left
+    let mut grid = VecGrid::new(8, 8);
+    grid.blit(&glider(), 0, 0, Transform::Rotate90);
+    assert_eq!(cells(&grid), [(0, 0), (0, 1), (2, 1), (0, 2), (1, 2)]);
+    for transform in Transform::ALL {
+        let mut grid = VecGrid::new(8, 8);
+        grid.blit(&glider(), 1, 1, transform);
+        let expected = rlife::transform::transformed(&glider(), transform);
+        assert_eq!(rlife::transform::cropped(&grid, 1, 1, 3, 3), expected);
+    }
+}
+
+#[test]
+fn stamping_adds_to_the_cells_already_alive() {
+    let mut grid = VecGrid::new(6, 6);
+    grid.set_cell(5, 5, true);
+    grid.blit(&glider(), 0, 0, Transform::FlipHorizontal);
+    assert_eq!(stats::population(&grid), 6);
+    assert!(grid.is_alive(5, 5));
+}
+
+#[test]
+fn edges_clip_or_wrap() {
+    let mut grid = VecGrid::new(4, 4);
+    grid.blit(&glider(), 2, 2, Transform::Identity);
+    assert_eq!(cells(&grid), [(3, 2)]);
+
+    let wrapped = [(0, 0), (2, 0), (3, 0), (3, 2), (0, 3)];
+    let mut torus = TorusGrid::<4, 4>::default();
+    assert!(torus.wraps());
+    torus.blit(&glider(), 2, 2, Transform::Identity);
+    assert_eq!(cells(&torus), wrapped);
+    // offsets past the edges wrap too, and rule wrappers keep the boundary of their map
+    let mut torus = RuleLife::new(TorusGrid::<4, 4>::default(), "B36/S23".parse().unwrap());
+    torus.blit(&glider(), 6, 10, Transform::Identity);
+    assert_eq!(cells(&torus), wrapped);
+}
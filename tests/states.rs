@@ -0,0 +1,98 @@
+// state graphs of tiny boards step every state the way the grids do
+use rlife::states::{Attractor, StateGraph};
+use rlife::{Life, Rule, TorusGrid, VecGrid};
+
+#[test]
+fn successors_match_stepping_the_grids() {
+    let graph = StateGraph::new(3, 3, Rule::default(), false);
+    assert_eq!(graph.len(), 512);
+    for state in 0..512 {
+        let board = graph.board(state);
+        assert_eq!(graph.state(&board), state);
+        let mut next = VecGrid::new(3, 3);
+        board.next_generation(&mut next);
+        assert_eq!(graph.successor(state), graph.state(&next));
+    }
+
+    // on a torus narrower than 3 cells neighbors are counted twice, as by TorusGrid
+    let graph = StateGraph::new(2, 3, "B36/S23".parse().unwrap(), true);
+    for state in 0..graph.len() as u32 {
+        let mut torus = rlife::RuleLife::new(TorusGrid::<2, 3>::default(), graph.rule);
+        for (x, y) in rlife::stats::live_cells(&graph.board(state)) {
+            torus.set_cell(x, y, true);
+        }
+        let mut next = rlife::RuleLife::new(TorusGrid::<2, 3>::default(), graph.rule);
+        torus.next_generation(&mut next);
+        assert_eq!(graph.successor(state), graph.state(&next));
+    }
+}
+
+#[test]
+fn graphs_are_exported_with_a_node_and_edge_per_state() {
+    let graph = StateGraph::new(2, 2, Rule::default(), false);
+    let mut dot = Vec::new();
+    graph.write_dot(&mut dot).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(dot.starts_with("digraph states {"));
+    assert_eq!(dot.matches(" -> ").count(), 16);
+    // the block stays and a lone cell dies
+    assert!(dot.contains("s15 [label=\"OO\\lOO\\l\"];"));
+    assert!(dot.contains("s15 -> s15;"));
+    assert!(dot.contains("s1 -> s0;"));
+
+    let mut graphml = Vec::new();
+    graph.write_graphml(&mut graphml).unwrap();
+    let graphml = String::from_utf8(graphml).unwrap();
+    assert_eq!(graphml.matches("<node ").count(), 16);
+    assert_eq!(graphml.matches("<edge ").count(), 16);
+    assert!(graphml.contains(r#"<edge source="s7" target="s15"/>"#));
+    assert!(graphml.trim_end().ends_with("</graphml>"));
+}
+
+// lowest state of the cycle a state ends in, and the generations it takes to get there
+fn settle(graph: &StateGraph, state: u32) -> (u32, usize) {
+    let mut seen = vec![state];
+    let mut current = state;
+    loop {
+        current = graph.successor(current);
+        if let Some(first) = seen.iter().position(|&s| s == current) {
+            return (*seen[first..].iter().min().unwrap(), first);
+        }
+        seen.push(current);
+    }
+}
+
+#[test]
+fn attractors_hold_every_state_once() {
+    for (width, height, wraps) in [(1, 1, false), (3, 3, false), (4, 3, true), (2, 2, true)] {
+        let graph = StateGraph::new(width, height, Rule::default(), wraps);
+        let attractors = graph.attractors();
+        assert_eq!(
+            attractors.iter().map(|a| a.basin).sum::<usize>(),
+            graph.len()
+        );
+        for attractor in &attractors {
+            let cycle = &attractor.cycle;
+            assert_eq!(*cycle.iter().min().unwrap(), cycle[0]);
+            for (i, &state) in cycle.iter().enumerate() {
+                assert_eq!(graph.successor(state), cycle[(i + 1) % cycle.len()]);
+            }
+            let basin: Vec<_> = (0..graph.len() as u32)
+                .map(|state| settle(&graph, state))
+                .filter(|&(lowest, _)| lowest == cycle[0])
+                .collect();
+            assert_eq!(basin.len(), attractor.basin);
+            let transient = basin.iter().map(|&(_, steps)| steps).max().unwrap();
+            assert_eq!(transient, attractor.transient);
+        }
+    }
+
+    // a lone cell dies, so the empty board is all there is to fall into
+    let graph = StateGraph::new(1, 1, Rule::default(), false);
+    let only = Attractor {
+        cycle: vec![0],
+        basin: 2,
+        transient: 1,
+    };
+    assert_eq!(graph.attractors(), [only]);
+}
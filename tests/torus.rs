@@ -0,0 +1,27 @@
+// boards whose opposite edges are joined
+use rlife::{stats, Life, Simulation, TorusGrid};
+
+#[test]
+fn gliders_cross_the_seams_and_come_back() {
+    let mut torus = TorusGrid::<10, 10>::default();
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        torus.set_cell(x + 6, y + 6, true);
+    }
+    assert!(torus.wraps());
+    let start: Vec<_> = stats::live_cells(&torus).collect();
+    let mut simulation = Simulation::new(torus, TorusGrid::default());
+    let mut crossed = false;
+    for _ in 0..4 * 10 {
+        simulation.step();
+        assert_eq!(simulation.population(), 5);
+        // cells on both sides of the seams at once
+        let cells: Vec<_> = stats::live_cells(simulation.life()).collect();
+        crossed |= cells.iter().any(|&(x, _)| x == 0) && cells.iter().any(|&(x, _)| x == 9);
+        crossed |= cells.iter().any(|&(_, y)| y == 0) && cells.iter().any(|&(_, y)| y == 9);
+    }
+    assert!(crossed);
+    assert_eq!(
+        stats::live_cells(simulation.life()).collect::<Vec<_>>(),
+        start
+    );
+}
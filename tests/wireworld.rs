@@ -0,0 +1,88 @@
+// WireWorld carries electrons along wires and is drawn in the colors of its states
+use rlife::multistate::MultiStateLife;
+use rlife::render::{self, Palette, StatePalette, Viewport};
+use rlife::wireworld::{self, WireWorld, CONDUCTOR, EMPTY, HEAD, TAIL};
+
+// step a map a number of generations
+fn run(world: &WireWorld, generations: usize) -> WireWorld {
+    let mut world = world.clone();
+    let mut next = world.clone();
+    for _ in 0..generations {
+        world.next_generation(&mut next);
+        std::mem::swap(&mut world, &mut next);
+    }
+    world
+}
+
+#[test]
+fn states_follow_the_rules() {
+    assert_eq!(wireworld::next_state(HEAD, 0), TAIL);
+    assert_eq!(wireworld::next_state(TAIL, 3), CONDUCTOR);
+    assert_eq!(wireworld::next_state(EMPTY, 2), EMPTY);
+    let wire: Vec<u8> = (0..4)
+        .map(|heads| wireworld::next_state(CONDUCTOR, heads))
+        .collect();
+    assert_eq!(wire, [CONDUCTOR, HEAD, HEAD, CONDUCTOR]);
+}
+
+#[test]
+fn maps_are_read_and_written_as_text() {
+    let world: WireWorld = "! a wire\n~@##\n.  #\n".parse().unwrap();
+    assert_eq!((world.width(), world.height()), (4, 2));
+    assert_eq!((world.state(0, 0), world.state(1, 0)), (TAIL, HEAD));
+    assert_eq!((world.state(1, 1), world.state(3, 1)), (EMPTY, CONDUCTOR));
+    assert_eq!(world.to_string(), "~@##\n...#\n");
+    assert_eq!(world.to_string().parse::<WireWorld>().unwrap(), world);
+    let error = "##\n#x\n".parse::<WireWorld>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid WireWorld map: line 2: unexpected 'x'"
+    );
+}
+
+#[test]
+fn electrons_run_along_wires() {
+    let world: WireWorld = "~@#######".parse().unwrap();
+    let later = run(&world, 5);
+    assert_eq!(later.to_string(), "#####~@##\n");
+    assert_eq!(later.heads(), 1);
+    // past the end of the wire the electron is gone
+    assert_eq!(run(&world, 9).heads(), 0);
+}
+
+#[test]
+fn loops_clock_electrons_and_forks_split_them() {
+    let clock: WireWorld = ".~@##.\n#....#\n.####.".parse().unwrap();
+    assert!((0..10).all(|generations| run(&clock, generations).heads() == 1));
+    assert_ne!(run(&clock, 5), clock);
+    assert_eq!(run(&clock, 10), clock);
+
+    let fork: WireWorld = ".....###\n~@###...\n.....###".parse().unwrap();
+    let split = run(&fork, 5);
+    assert_eq!(split.heads(), 2);
+    assert_eq!((split.state(6, 0), split.state(6, 2)), (HEAD, HEAD));
+}
+
+#[test]
+fn states_are_drawn_in_the_colors_of_the_palette() {
+    let world: WireWorld = "@~#.".parse().unwrap();
+    let palette = WireWorld::palette();
+    let mut buffer = vec![0; 4];
+    render::render_states_to_buffer(
+        &world,
+        &mut buffer,
+        &palette,
+        Viewport::whole_states(&world),
+    );
+    assert_eq!(
+        buffer,
+        [HEAD, TAIL, CONDUCTOR, EMPTY].map(|state| palette.color(state))
+    );
+    assert_eq!(buffer[3], 0);
+    // states past the last color are drawn in it
+    let palette: StatePalette = Palette::default().into();
+    assert_eq!(
+        (palette.color(0), palette.color(1), palette.color(3)),
+        (0, 0xffffff, 0xffffff)
+    );
+}
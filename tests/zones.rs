@@ -0,0 +1,56 @@
+// regions of a board following rules of their own
+use rlife::random::Rng;
+use rlife::rule::Rule;
+use rlife::zones::Zones;
+use rlife::{stats, Life, RuleLife, VecGrid};
+
+#[test]
+fn cells_on_either_side_of_a_border_follow_their_own_zone() {
+    // Conway on the left half, Seeds from column 10
+    let seeds: Rule = "B2/S".parse().unwrap();
+    let mut zones = Zones::new(20, 10, Rule::default());
+    zones.add_rect(10, 0, 10, 10, seeds);
+    assert_eq!(zones.rule_at(9, 4), Rule::default());
+    assert_eq!(zones.rule_at(10, 4), seeds);
+    assert_eq!(zones.rule_at(25, 4), Rule::default());
+
+    // a block straddling the border, its neighbors counted across it
+    let mut current = VecGrid::new(20, 10);
+    for (x, y) in [(9, 4), (10, 4), (9, 5), (10, 5)] {
+        current.set_cell(x, y, true);
+    }
+    let mut next = VecGrid::new(20, 10);
+    zones.next_generation(&current, &mut next);
+    // the Conway half of the block survives and none of its cells are born with two
+    // neighbors, while the Seeds half dies and its cells with two neighbors are born
+    assert_eq!(
+        stats::live_cells(&next).collect::<Vec<_>>(),
+        [(10, 3), (9, 4), (11, 4), (9, 5), (11, 5), (10, 6)]
+    );
+}
+
+#[test]
+fn masks_cover_earlier_zones() {
+    let highlife: Rule = "B36/S23".parse().unwrap();
+    let mut zones = Zones::new(8, 8, Rule::default());
+    zones.add_rect(0, 0, 8, 4, "B2/S".parse().unwrap());
+    let mut mask = VecGrid::new(3, 3);
+    mask.set_cell(1, 1, true);
+    zones.add_mask(&mask, highlife);
+    assert_eq!(zones.rule_at(1, 1), highlife);
+    assert_eq!(zones.rule_at(0, 0), "B2/S".parse().unwrap());
+    assert_eq!(zones.rule_at(1, 4), Rule::default());
+}
+
+#[test]
+fn a_single_zone_steps_like_its_rule() {
+    let highlife: Rule = "B36/S23".parse().unwrap();
+    let zones = Zones::new(40, 30, highlife);
+    let mut current = VecGrid::new(40, 30);
+    current.fill_random(0.4, &mut Rng::new(3));
+    let mut next = VecGrid::new(40, 30);
+    zones.next_generation(&current, &mut next);
+    let mut expected = VecGrid::new(40, 30);
+    RuleLife::new(current, highlife).next_generation(&mut expected);
+    assert_eq!(next, expected);
+}